@@ -1,10 +1,12 @@
 //! Command line argument parsing.
-use std::path::PathBuf;
+use crate::{docs::DocsFormatArg, graph::GraphFormat, report::ReportFormat};
+use std::{collections::HashSet, path::PathBuf};
 
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 #[non_exhaustive]
 pub enum AvailableLanguage {
     Kotlin,
+    #[value(alias = "reasonml")]
     ReasonML,
     Scala,
     Swift,
@@ -15,7 +17,7 @@ pub enum AvailableLanguage {
     Python,
 }
 
-#[derive(clap::Parser)]
+#[derive(clap::Parser, Clone)]
 #[command(
     version,
     args_conflicts_with_subcommands = true,
@@ -26,10 +28,42 @@ pub struct Args {
     #[command(subcommand)]
     pub subcommand: Option<Command>,
 
-    /// Language of generated types
-    #[arg(short, long = "lang", required_unless_present = "generate_config")]
+    /// Language of generated types. Without it, the types of every language with
+    /// outputs in the configuration file are generated
+    #[arg(short, long = "lang")]
     pub language: Option<AvailableLanguage>,
 
+    /// Generate the types with this plugin of the configuration file, a language
+    /// backend outside of typeshare, instead of a language
+    #[arg(long, conflicts_with_all = ["language", "watch", "graph", "report", "generate_config"])]
+    pub plugin: Option<String>,
+
+    /// Generate the types with these templates of the configuration file instead
+    /// of a language
+    #[arg(long, conflicts_with_all = ["language", "plugin", "watch", "graph", "report", "generate_config"])]
+    pub template: Option<String>,
+
+    /// Instead of generating types, write the parsed types of every crate as
+    /// JSON, to the output file or stdout
+    #[arg(long, conflicts_with_all = ["language", "plugin", "template", "watch", "graph", "report", "generate_config"])]
+    pub ir: bool,
+
+    /// Instead of generating types, generate a test of the language which decodes
+    /// and encodes the samples of this JSON file, by the Rust names of their types,
+    /// with the generated types
+    #[arg(long, value_name = "SAMPLES", requires = "language", conflicts_with_all = ["plugin", "template", "ir", "watch", "graph", "report", "generate_config"])]
+    pub conformance: Option<PathBuf>,
+
+    /// Instead of generating types, write example JSON of every type, by its Rust
+    /// name, to the output file or stdout, like the samples of --conformance
+    #[arg(long, conflicts_with_all = ["language", "plugin", "template", "ir", "conformance", "watch", "graph", "report", "generate_config"])]
+    pub samples: bool,
+
+    /// Instead of generating types, write reference documentation of every type, with
+    /// their fields, variants and doc comments, to the output file or stdout
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["language", "plugin", "template", "ir", "conformance", "samples", "watch", "graph", "report", "generate_config"])]
+    pub docs: Option<DocsFormatArg>,
+
     /// Prefix for generated Swift types
     #[arg(short, long)]
     pub swift_prefix: Option<String>,
@@ -63,42 +97,259 @@ pub struct Args {
     #[arg(short, long)]
     pub config_file: Option<PathBuf>,
 
+    /// Override the configuration with the `[profile.<name>]` table of this
+    /// profile in the configuration file
+    #[arg(long)]
+    pub profile: Option<String>,
+
     #[command(flatten)]
     pub output: Output,
 
+    /// When generating into an output folder, write a file per Rust module
+    /// instead of per crate
+    #[arg(long)]
+    pub per_module: bool,
+
+    /// When generating into an output folder, write every type to its own file
+    /// named after the type
+    #[arg(long)]
+    pub file_per_type: bool,
+
+    /// Generate the types sorted by name instead of in the order of the Rust
+    /// files, with the types others depend on still before them
+    #[arg(long)]
+    pub sort_types: bool,
+
+    /// Only process the Rust files matching any of these globs, relative to the
+    /// first directory
+    #[arg(long, num_args = 1..)]
+    pub include: Option<Vec<String>>,
+
+    /// Skip the Rust files matching any of these globs, relative to the first
+    /// directory
+    #[arg(long, num_args = 1..)]
+    pub exclude: Option<Vec<String>>,
+
+    /// Only generate the types whose Rust names match any of these regular
+    /// expressions
+    #[arg(long = "only", num_args = 1..)]
+    pub only_types: Option<Vec<String>>,
+
+    /// Don't generate the types whose Rust names match any of these regular
+    /// expressions
+    #[arg(long = "exclude-type", num_args = 1..)]
+    pub exclude_types: Option<Vec<String>>,
+
+    /// Only generate these types, by their Rust names, and all the types they
+    /// reference directly or indirectly
+    #[arg(long = "root-type", num_args = 1..)]
+    pub root_types: Option<Vec<String>>,
+
+    /// Record the input files in this cache file, and only parse and generate
+    /// the crates whose input files changed since the last run
+    #[arg(long)]
+    pub cache_file: Option<PathBuf>,
+
+    /// Write a JSON manifest to this file, listing every generated file with
+    /// the SHA-256 hash of its contents, and the typeshare version and hash of
+    /// the configuration they were generated with
+    #[arg(long)]
+    pub manifest: Option<PathBuf>,
+
+    /// End every generated file with a comment of the checksum of its contents,
+    /// and don't overwrite generated files whose contents don't match it anymore
+    #[arg(long)]
+    pub checksum_markers: bool,
+
+    /// Overwrite generated files even if they were edited since they were
+    /// generated
+    #[arg(long)]
+    pub force: bool,
+
+    /// Fail on the typeshared items that can't be represented in the language,
+    /// instead of generating a fallback like a comment or an opaque type. The
+    /// same as denying the `unsupported_item` lint
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Fail on every warning of a lint that isn't allowed
+    #[arg(long)]
+    pub deny_warnings: bool,
+
     /// Follow symbolic links to directories instead of ignoring them.
     #[arg(short = 'L', long)]
     pub follow_links: bool,
 
-    /// Directories within which to recursively find and process rust files
-    #[arg(required=true, num_args = 1..)]
+    /// Directories within which to recursively find and process rust files,
+    /// or `-` to read Rust source from stdin. Without directories, the packages
+    /// of the Cargo workspace of the current directory are processed
+    #[arg(num_args = 1.., value_hint = clap::ValueHint::DirPath)]
     pub directories: Vec<PathBuf>,
 
+    /// Without directories, only process these packages of the Cargo workspace
+    #[arg(short, long = "package", num_args = 1.., conflicts_with = "directories")]
+    pub packages: Vec<String>,
+
+    /// Without directories, don't process these packages of the Cargo workspace
+    #[arg(long = "exclude-package", num_args = 1.., conflicts_with = "directories")]
+    pub exclude_packages: Vec<String>,
+
+    /// The Rust names of the types of the path dependencies of the packages of the
+    /// Cargo workspace, which are only generated when referenced from their types.
+    #[arg(skip)]
+    pub dependency_types: HashSet<String>,
+
     /// Optional restrict to target_os
     #[arg(short, long, num_args = 1..)]
     pub target_os: Option<Vec<String>>,
+
+    /// Keep running, and generate the types again whenever the input files
+    /// or the configuration file change
+    #[arg(short, long, conflicts_with = "generate_config")]
+    pub watch: bool,
+
+    /// Instead of writing the generated files, print a diff of the files that
+    /// are out of date and fail if there are any
+    #[arg(long, conflicts_with_all = ["generate_config", "watch"])]
+    pub check: bool,
+
+    /// Instead of writing the generated files, print which files would be
+    /// created or changed, and how many types would be generated
+    #[arg(long, conflicts_with_all = ["generate_config", "watch", "check"])]
+    pub dry_run: bool,
+
+    /// Instead of generating types, print the dependency graph of the types
+    /// that would be generated to stdout
+    #[arg(long, value_enum, requires = "language", conflicts_with_all = ["generate_config", "watch", "check", "dry_run"])]
+    pub graph: Option<GraphFormat>,
+
+    /// Log more about what typeshare does: -v logs which files are parsed and
+    /// written and why types are skipped, -vv logs the details of parsing and
+    /// -vvv everything
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only log errors
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print a summary of the generated files, skipped types, warnings and
+    /// errors to stdout when done
+    #[arg(long, value_enum, conflicts_with_all = ["generate_config", "watch", "check", "dry_run", "graph"])]
+    pub report: Option<ReportFormat>,
 }
 
-#[derive(Debug, Clone, Copy, clap::Subcommand)]
+#[derive(Debug, Clone, clap::Subcommand)]
 pub enum Command {
     /// Generate shell completions
     Completions {
         /// The shell to generate the completions for
         shell: clap_complete::Shell,
     },
+    /// Write a starter configuration file, with the command for generating the
+    /// types of the crates in the current directory
+    Init {
+        /// The configuration file to write, typeshare.toml by default
+        #[arg(short, long)]
+        config_file: Option<PathBuf>,
+
+        /// Also write an example typeshared type to the first crate
+        #[arg(long)]
+        example: bool,
+    },
+    /// Compare the typeshared types of two versions of Rust source, and print
+    /// the changes of the serialized types, classified as breaking or
+    /// non-breaking
+    Diff {
+        /// The Rust file or directory with the old types, or with --baseline the
+        /// one to compare with its version at the baseline revision
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        old: PathBuf,
+
+        /// The Rust file or directory with the new types
+        #[arg(
+            value_hint = clap::ValueHint::AnyPath,
+            required_unless_present = "baseline",
+            conflicts_with = "baseline"
+        )]
+        new: Option<PathBuf>,
+
+        /// Compare the types at this git revision, like `origin/main`, with the
+        /// current ones
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Fail if any of the changes is breaking
+        #[arg(long)]
+        deny_breaking: bool,
+    },
+    /// Print the JSON schema of the configuration file, for completion and
+    /// validation in editors
+    ConfigSchema,
+    /// Answer requests for the generated source of the type under the cursor, one
+    /// JSON object per line of stdin, for previews in editors. The types are
+    /// generated again when the input files change
+    Serve {
+        /// The directories of the Rust files, the packages of the Cargo workspace
+        /// by default
+        #[arg(value_hint = clap::ValueHint::DirPath)]
+        directories: Vec<PathBuf>,
+
+        /// Configuration file for typeshare
+        #[arg(short, long)]
+        config_file: Option<PathBuf>,
+
+        /// Override the configuration with the `[profile.<name>]` table of this
+        /// profile in the configuration file
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Write snapshots of the generated source of every type, in every language
+    /// with outputs in the configuration file, or verify them with --check
+    SnapshotTests {
+        /// The directories of the Rust files, the packages of the Cargo workspace
+        /// by default
+        #[arg(value_hint = clap::ValueHint::DirPath)]
+        directories: Vec<PathBuf>,
+
+        /// The folder the snapshots are written to, with a folder per language
+        #[arg(long, default_value = "typeshare-snapshots", value_hint = clap::ValueHint::DirPath)]
+        snapshots: PathBuf,
+
+        /// Language of the snapshots, instead of every language with outputs in
+        /// the configuration file
+        #[arg(short, long = "lang")]
+        language: Option<AvailableLanguage>,
+
+        /// Verify the snapshots instead of writing them, failing if any changed,
+        /// is missing or is of a type which isn't typeshared anymore
+        #[arg(long)]
+        check: bool,
+
+        /// Configuration file for typeshare
+        #[arg(short, long)]
+        config_file: Option<PathBuf>,
+
+        /// Override the configuration with the `[profile.<name>]` table of this
+        /// profile in the configuration file
+        #[arg(long)]
+        profile: Option<String>,
+    },
 }
 
-#[derive(clap::Args, Debug)]
-#[group(multiple = false, required = true)]
+// Where to write the generated types. Without a file or folder, they're written to stdout.
+// Not a doc comment, which clap would use as the about text of the whole command.
+#[derive(clap::Args, Debug, Clone)]
+#[group(multiple = false)]
 pub struct Output {
-    /// File to write output to. mtime will be preserved if the file contents
-    /// don't change
+    /// File to write output to, or `-` for stdout. mtime will be preserved if
+    /// the file contents don't change
     #[arg(short = 'o', long = "output-file")]
     pub file: Option<PathBuf>,
 
     /// Folder to write output to. mtime will be preserved if the file contents
     /// don't change
-    #[arg(short = 'd', long = "output-folder")]
+    #[arg(short = 'd', long = "output-folder", value_hint = clap::ValueHint::DirPath)]
     pub folder: Option<PathBuf>,
 
     // If given, we're going to output a new template configuration file
@@ -109,4 +360,18 @@ pub struct Output {
     /// path specified by the --config-file option.
     #[arg(short, long)]
     pub generate_config: bool,
+
+    /// The folder to generate into without an output file or folder, or outputs in the
+    /// configuration: the `typeshare` folder of the Cargo workspace's target directory.
+    #[arg(skip)]
+    pub workspace_folder: Option<PathBuf>,
+}
+
+impl Args {
+    /// Whether Rust source is read from stdin instead of directories.
+    pub fn reads_stdin(&self) -> bool {
+        self.directories
+            .iter()
+            .any(|directory| directory.as_os_str() == "-")
+    }
 }
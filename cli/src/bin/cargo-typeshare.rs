@@ -0,0 +1,25 @@
+//! `cargo typeshare`, which runs the `typeshare` installed next to it. Without
+//! directories, typeshare generates the types of the packages of the Cargo workspace.
+use std::{
+    env,
+    process::{self, Command},
+};
+
+fn main() {
+    let typeshare = env::current_exe()
+        .map(|exe| exe.with_file_name(format!("typeshare{}", env::consts::EXE_SUFFIX)))
+        .unwrap_or_else(|_| "typeshare".into());
+
+    // Cargo passes the name of the subcommand as the first argument
+    let mut args = env::args_os().skip(1).peekable();
+    args.next_if(|arg| arg == "typeshare");
+
+    let status = Command::new(&typeshare).args(args).status();
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(err) => {
+            eprintln!("Failed to run {typeshare:?}: {err}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Skipping unchanged input files between runs.
+use crate::parse::{file_crate_name, merge_by_crate, parallel_parse_files};
+use anyhow::Context;
+use ignore::WalkBuilder;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashSet},
+    env, fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use typeshare_core::{
+    context::ParseContext,
+    language::{CrateName, CrateTypes, Language, SupportedLanguage},
+    parser::ParsedData,
+    reconcile::RenamedTypes,
+};
+
+/// The input files of the last successful run, used to only parse and generate the
+/// crates whose input files have changed since.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Cache {
+    /// Hash of the typeshare version, command line and configuration file the types
+    /// were generated with. The cached files are only used if it didn't change.
+    settings: String,
+    /// The input files by their path.
+    files: BTreeMap<String, CachedFile>,
+}
+
+/// An input file of the last run.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+struct CachedFile {
+    /// Hash of the contents of the file.
+    hash: String,
+    /// The crate (or module) the file was generated into.
+    crate_name: String,
+    /// The names of the types the file contributes.
+    types: BTreeSet<String>,
+    /// The types of the file renamed with `serde(rename)`, by their original name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    renamed: BTreeMap<String, String>,
+}
+
+/// The input files that changed since the last run.
+pub struct Changes {
+    /// The parsed data of the crates with changed input files.
+    pub parsed_data: BTreeMap<CrateName, ParsedData>,
+    /// The types of the crates without changes, for imports.
+    pub unchanged_types: CrateTypes,
+    /// The types renamed with `serde(rename)` in the crates without changes.
+    pub unchanged_renamed: RenamedTypes,
+}
+
+impl Cache {
+    /// Load the cache file written by the last run. A missing or outdated cache
+    /// file results in an empty cache, so that everything is generated.
+    pub fn load(
+        path: &Path,
+        config_file: Option<&Path>,
+        profile: Option<&str>,
+        language: SupportedLanguage,
+    ) -> Self {
+        let settings = settings_hash(config_file, profile, language);
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str::<Cache>(&contents).ok())
+            .filter(|cache| cache.settings == settings)
+            .unwrap_or(Cache {
+                settings,
+                files: BTreeMap::new(),
+            })
+    }
+
+    /// Forget the input files of the last run, so that everything is generated.
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+
+    /// Write the cache file for the next run.
+    pub fn store(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = toml::to_string(self).context("Failed to serialize the cache")?;
+        fs::write(path, contents).with_context(|| format!("Failed to write cache file {path:?}"))
+    }
+
+    /// Parse the input files of the crates which have changed input files, and update
+    /// the cache with them. Returns `None` if no input file changed.
+    ///
+    /// When generating a single file, or when the types a crate contributes change,
+    /// every crate is parsed, as the output of the other crates may depend on them.
+    pub fn parse_changes(
+        &mut self,
+        parse_context: &ParseContext,
+        per_module: bool,
+        walker_builder: &WalkBuilder,
+        language_type: SupportedLanguage,
+        lang: &dyn Language,
+    ) -> anyhow::Result<Option<Changes>> {
+        let multi_file = parse_context.multi_file;
+        let first_run = self.files.is_empty();
+
+        let mut current = BTreeMap::new();
+        for entry in walker_builder.build() {
+            let entry = entry.context("Failed traversing")?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let contents = fs::read(entry.path())
+                .with_context(|| format!("Failed to read input: {:?}", entry.path()))?;
+            let Some(crate_name) = file_crate_name(multi_file, per_module, entry.path()) else {
+                continue;
+            };
+            current.insert(entry.into_path(), (hash(&contents), crate_name));
+        }
+
+        // Crates with new, changed or removed files.
+        let mut changed_crates = current
+            .iter()
+            .filter(|(path, (hash, _))| {
+                self.files
+                    .get(path.to_string_lossy().as_ref())
+                    .is_none_or(|file| file.hash != *hash)
+            })
+            .map(|(_, (_, crate_name))| crate_name.clone())
+            .collect::<HashSet<_>>();
+        changed_crates.extend(
+            self.files
+                .iter()
+                .filter(|(path, _)| !current.contains_key(Path::new(path)))
+                .map(|(_, file)| CrateName::from(file.crate_name.as_str())),
+        );
+        if changed_crates.is_empty() {
+            return Ok(None);
+        }
+
+        let changed_files = |crates: &HashSet<CrateName>| {
+            current
+                .iter()
+                .filter(|(_, (_, crate_name))| !multi_file || crates.contains(crate_name))
+                .map(|(path, _)| path.clone())
+                .collect::<HashSet<_>>()
+        };
+        let mut files = changed_files(&changed_crates);
+        let mut file_parsed_data = parse_files(
+            parse_context,
+            per_module,
+            walker_builder,
+            language_type,
+            lang,
+            &files,
+        )?;
+
+        // Files without typeshared types aren't parsed, and contribute no types.
+        let removed_types = self.files.iter().any(|(path, file)| {
+            !current.contains_key(Path::new(path))
+                && (!file.types.is_empty() || !file.renamed.is_empty())
+        });
+        let types_changed = removed_types
+            || files.iter().any(|path| {
+                let cached = self
+                    .files
+                    .get(path.to_string_lossy().as_ref())
+                    .map(|file| (file.types.clone(), file.renamed.clone()))
+                    .unwrap_or_default();
+                let parsed = file_parsed_data
+                    .get(path)
+                    .map(|data| (type_names(data), renamed_types(data)))
+                    .unwrap_or_default();
+                cached != parsed
+            });
+        if multi_file && types_changed {
+            if !first_run {
+                info!("typeshare found changed types, generating all crates");
+            }
+            changed_crates.extend(current.values().map(|(_, crate_name)| crate_name.clone()));
+            let remaining = changed_files(&changed_crates)
+                .difference(&files)
+                .cloned()
+                .collect::<HashSet<_>>();
+            file_parsed_data.extend(parse_files(
+                parse_context,
+                per_module,
+                walker_builder,
+                language_type,
+                lang,
+                &remaining,
+            )?);
+            files.extend(remaining);
+        }
+
+        self.files
+            .retain(|path, _| current.contains_key(Path::new(path)));
+        for path in files {
+            let (hash, crate_name) = &current[&path];
+            let parsed = file_parsed_data.get(&path);
+            self.files.insert(
+                path.to_string_lossy().into_owned(),
+                CachedFile {
+                    hash: hash.clone(),
+                    crate_name: crate_name.to_string(),
+                    types: parsed.map(type_names).unwrap_or_default(),
+                    renamed: parsed.map(renamed_types).unwrap_or_default(),
+                },
+            );
+        }
+
+        let mut unchanged_types = CrateTypes::new();
+        let mut unchanged_renamed = RenamedTypes::new();
+        for file in self.files.values() {
+            let crate_name = CrateName::from(file.crate_name.as_str());
+            if changed_crates.contains(&crate_name) {
+                continue;
+            }
+            unchanged_types
+                .entry(crate_name.clone())
+                .or_default()
+                .extend(file.types.iter().cloned());
+            for (original, renamed) in &file.renamed {
+                unchanged_renamed
+                    .entry(original.clone())
+                    .or_default()
+                    .insert(crate_name.clone(), renamed.clone());
+            }
+        }
+
+        Ok(Some(Changes {
+            parsed_data: merge_by_crate(file_parsed_data),
+            unchanged_types,
+            unchanged_renamed,
+        }))
+    }
+}
+
+/// Parse the given files of the input directories.
+fn parse_files(
+    parse_context: &ParseContext,
+    per_module: bool,
+    walker_builder: &WalkBuilder,
+    language_type: SupportedLanguage,
+    lang: &dyn Language,
+    files: &HashSet<PathBuf>,
+) -> anyhow::Result<BTreeMap<PathBuf, ParsedData>> {
+    if files.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let files = Arc::new(files.clone());
+    let mut walker_builder = walker_builder.clone();
+    walker_builder.filter_entry(move |entry| {
+        entry.file_type().is_some_and(|t| t.is_dir()) || files.contains(entry.path())
+    });
+    parallel_parse_files(
+        parse_context,
+        per_module,
+        walker_builder,
+        language_type,
+        lang,
+    )
+}
+
+fn type_names(parsed_data: &ParsedData) -> BTreeSet<String> {
+    parsed_data.type_names.iter().cloned().collect()
+}
+
+fn renamed_types(parsed_data: &ParsedData) -> BTreeMap<String, String> {
+    let structs = parsed_data.structs.iter().map(|s| &s.id);
+    let enums = parsed_data.enums.iter().map(|e| &e.shared().id);
+    let aliases = parsed_data.aliases.iter().map(|a| &a.id);
+    structs
+        .chain(enums)
+        .chain(aliases)
+        .filter(|id| id.serde_rename)
+        .map(|id| (id.original.clone(), id.renamed.clone()))
+        .collect()
+}
+
+/// Hash of everything besides the input files that affects the generated types.
+fn settings_hash(
+    config_file: Option<&Path>,
+    profile: Option<&str>,
+    language: SupportedLanguage,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    env::args().collect::<Vec<_>>().hash(&mut hasher);
+    // Without `--lang`, every configured language is generated with the same arguments.
+    format!("{language:?}").hash(&mut hasher);
+    // The configuration as it's loaded, so that changes of the files it extends or of the
+    // environment variables it references are noticed too.
+    crate::config::load_config_table(config_file, profile)
+        .ok()
+        .flatten()
+        .map(|table| table.to_string())
+        .hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn hash(contents: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{settings_hash, Cache};
+    use crate::tempdir::tempdir;
+    use ignore::WalkBuilder;
+    use std::{env, fs, path::Path};
+    use typeshare_core::{
+        context::ParseContext,
+        language::{CrateName, SupportedLanguage, TypeScript},
+    };
+
+    fn write_crate(root: &Path, name: &str, source: &str) {
+        let src = root.join(name).join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), source).unwrap();
+    }
+
+    #[test]
+    fn parses_changed_crates() {
+        let root = tempdir();
+        write_crate(
+            root.path(),
+            "foo",
+            "#[typeshare]\npub struct Foo { pub a: String }\n",
+        );
+        write_crate(
+            root.path(),
+            "bar",
+            "#[typeshare]\npub struct Bar { pub b: String }\n",
+        );
+
+        let parse_context = ParseContext {
+            multi_file: true,
+            ..Default::default()
+        };
+        let walker_builder = WalkBuilder::new(root.path());
+        let mut cache = Cache::default();
+        let parse_changes = |cache: &mut Cache| {
+            cache
+                .parse_changes(
+                    &parse_context,
+                    false,
+                    &walker_builder,
+                    SupportedLanguage::TypeScript,
+                    &TypeScript::default(),
+                )
+                .unwrap()
+        };
+
+        let changes = parse_changes(&mut cache).unwrap();
+        assert_eq!(changes.parsed_data.len(), 2);
+        assert!(parse_changes(&mut cache).is_none());
+
+        write_crate(
+            root.path(),
+            "bar",
+            "#[typeshare]\npub struct Bar { pub c: String }\n",
+        );
+        let changes = parse_changes(&mut cache).unwrap();
+        assert_eq!(
+            changes.parsed_data.keys().collect::<Vec<_>>(),
+            [&CrateName::from("bar")]
+        );
+        assert!(changes.unchanged_types[&CrateName::from("foo")].contains("Foo"));
+
+        // Changing the types of a crate can change the imports of the others
+        write_crate(
+            root.path(),
+            "bar",
+            "#[typeshare]\npub struct Baz { pub c: String }\n",
+        );
+        let changes = parse_changes(&mut cache).unwrap();
+        assert_eq!(changes.parsed_data.len(), 2);
+    }
+
+    #[test]
+    fn hashes_the_interpolated_configuration() {
+        let root = tempdir();
+        let config_file = root.path().join("typeshare.toml");
+        fs::write(
+            &config_file,
+            "[swift]\nprefix = \"${TYPESHARE_TEST_CACHE_PREFIX}\"\n",
+        )
+        .unwrap();
+
+        let settings = |prefix: &str| {
+            env::set_var("TYPESHARE_TEST_CACHE_PREFIX", prefix);
+            settings_hash(Some(&config_file), None, SupportedLanguage::Swift)
+        };
+        let first = settings("First");
+        let same = settings("First");
+        let second = settings("Second");
+        env::remove_var("TYPESHARE_TEST_CACHE_PREFIX");
+        assert_eq!(first, same);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,331 @@
+//! Discovering the packages of the Cargo workspace with `cargo metadata`.
+use anyhow::Context;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashSet},
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// The packages of a Cargo workspace.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Workspace {
+    /// The source directories of the libraries and binaries of the selected packages,
+    /// so their examples, tests, build scripts and the target directory aren't parsed.
+    pub directories: Vec<PathBuf>,
+    /// The source directories of the path dependencies of the selected packages, and
+    /// of theirs, which aren't selected, inside of the workspace or outside of it.
+    pub dependencies: Vec<PathBuf>,
+    /// The directory Cargo builds into, usually `target`.
+    pub target_directory: PathBuf,
+}
+
+/// The fields of the output of `cargo metadata` typeshare uses.
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+    target_directory: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+    targets: Vec<Target>,
+    #[serde(default)]
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dependency {
+    /// `dev` or `build` for those dependencies, `None` for those of the libraries and
+    /// binaries.
+    kind: Option<String>,
+    /// The directory of the dependency, for path dependencies.
+    path: Option<PathBuf>,
+}
+
+impl Package {
+    /// The directories of the path dependencies of the libraries and binaries.
+    fn path_dependencies(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.kind.is_none())
+            .filter_map(|dependency| dependency.path.clone())
+    }
+
+    /// The source directories of the libraries and binaries.
+    fn source_directories(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.targets
+            .iter()
+            .filter(|target| target.is_lib_or_bin())
+            .filter_map(|target| Some(target.src_path.parent()?.to_path_buf()))
+    }
+}
+
+/// The fields of the `Cargo.toml` of a path dependency outside of the workspace, which
+/// `cargo metadata --no-deps` doesn't describe.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Manifest {
+    lib: Option<Lib>,
+    dependencies: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Lib {
+    path: Option<PathBuf>,
+}
+
+impl Manifest {
+    fn read(directory: &Path) -> anyhow::Result<Self> {
+        let path = directory.join("Cargo.toml");
+        let manifest =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {path:?}"))?;
+        toml::from_str(&manifest).with_context(|| format!("Failed to parse {path:?}"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    kind: Vec<String>,
+    src_path: PathBuf,
+}
+
+impl Target {
+    /// Whether the target is a library or binary, rather than an example, test, bench
+    /// or build script.
+    fn is_lib_or_bin(&self) -> bool {
+        self.kind
+            .iter()
+            .all(|kind| !matches!(kind.as_str(), "example" | "test" | "bench" | "custom-build"))
+    }
+}
+
+/// Find the packages of the Cargo workspace of the current directory. This runs the
+/// `cargo` the process was run by, if any, like when running `cargo typeshare`.
+///
+/// Only the packages named in `packages` are selected, or all of them when it's empty,
+/// except for the packages named in `exclude_packages`.
+pub fn workspace(packages: &[String], exclude_packages: &[String]) -> anyhow::Result<Workspace> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = process::Command::new(&cargo)
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .stderr(process::Stdio::inherit())
+        .output()
+        .with_context(|| format!("Failed to run {cargo:?} metadata"))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "cargo metadata failed with {}, without directories typeshare needs a Cargo workspace",
+        output.status
+    );
+    let metadata = serde_json::from_slice(&output.stdout)
+        .context("Failed to parse the output of cargo metadata")?;
+    workspace_of(metadata, packages, exclude_packages)
+}
+
+fn workspace_of(
+    metadata: Metadata,
+    packages: &[String],
+    exclude_packages: &[String],
+) -> anyhow::Result<Workspace> {
+    let members = metadata
+        .packages
+        .into_iter()
+        .filter(|package| metadata.workspace_members.contains(&package.id))
+        .collect::<Vec<_>>();
+    for name in packages.iter().chain(exclude_packages) {
+        anyhow::ensure!(
+            members.iter().any(|package| &package.name == name),
+            "The Cargo workspace has no package named {name:?}"
+        );
+    }
+
+    let selected = members
+        .iter()
+        .filter(|package| packages.is_empty() || packages.contains(&package.name))
+        .filter(|package| !exclude_packages.contains(&package.name))
+        .collect::<Vec<_>>();
+    let mut directories = selected
+        .iter()
+        .flat_map(|package| package.source_directories())
+        .collect::<Vec<_>>();
+    sort_directories(&mut directories);
+    anyhow::ensure!(
+        !directories.is_empty(),
+        "No packages of the Cargo workspace are selected"
+    );
+
+    let mut unvisited = selected
+        .iter()
+        .flat_map(|package| package.path_dependencies())
+        .collect::<Vec<_>>();
+    let mut visited = HashSet::new();
+    let mut dependencies = Vec::new();
+    while let Some(dependency) = unvisited.pop() {
+        if !visited.insert(dependency.clone()) {
+            continue;
+        }
+        if let Some(member) = members
+            .iter()
+            .find(|package| package.manifest_path.parent() == Some(&dependency))
+        {
+            unvisited.extend(member.path_dependencies());
+            dependencies.extend(member.source_directories());
+        } else {
+            let manifest = Manifest::read(&dependency)?;
+            unvisited.extend(
+                manifest
+                    .dependencies
+                    .values()
+                    .filter_map(|dependency| dependency.get("path")?.as_str())
+                    .map(|path| dependency.join(path)),
+            );
+            let lib_path = manifest.lib.and_then(|lib| lib.path);
+            dependencies.push(match lib_path.as_deref().and_then(Path::parent) {
+                Some(parent) => dependency.join(parent),
+                None => dependency.join("src"),
+            });
+        }
+    }
+    sort_directories(&mut dependencies);
+    dependencies.retain(|dependency| {
+        !directories
+            .iter()
+            .any(|directory| dependency.starts_with(directory))
+    });
+
+    Ok(Workspace {
+        directories,
+        dependencies,
+        target_directory: metadata.target_directory,
+    })
+}
+
+/// Sort the directories, without those inside of another one.
+fn sort_directories(directories: &mut Vec<PathBuf>) {
+    // Directories sort right after their parent, like `src/bin` after `src`, which
+    // already contains them.
+    directories.sort();
+    directories.dedup_by(|directory, parent| directory.starts_with(parent));
+}
+
+#[cfg(test)]
+mod test {
+    use super::{workspace_of, Metadata, Workspace};
+    use std::path::{Path, PathBuf};
+
+    /// A crate outside of the workspace, `dep`, which is a path dependency of `core`.
+    fn dep() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../core")
+    }
+
+    fn metadata() -> Metadata {
+        let metadata = r#"{
+                "packages": [
+                    {
+                        "id": "path+file:///ws#root@0.1.0",
+                        "name": "root",
+                        "manifest_path": "/ws/Cargo.toml",
+                        "targets": [
+                            { "kind": ["bin"], "name": "root", "src_path": "/ws/src/main.rs" },
+                            { "kind": ["bin"], "name": "tool", "src_path": "/ws/src/bin/tool.rs" },
+                            { "kind": ["custom-build"], "name": "build-script-build", "src_path": "/ws/build.rs" }
+                        ],
+                        "dependencies": [
+                            { "name": "core", "kind": null, "path": "/ws/core" },
+                            { "name": "dep", "kind": "dev", "path": "/ws/vendor/dep" }
+                        ]
+                    },
+                    {
+                        "id": "path+file:///ws/core#core@0.1.0",
+                        "name": "core",
+                        "manifest_path": "/ws/core/Cargo.toml",
+                        "targets": [
+                            { "kind": ["lib"], "name": "core", "src_path": "/ws/core/src/lib.rs" },
+                            { "kind": ["example"], "name": "demo", "src_path": "/ws/core/examples/demo.rs" },
+                            { "kind": ["test"], "name": "it", "src_path": "/ws/core/tests/it.rs" }
+                        ],
+                        "dependencies": [
+                            { "name": "serde", "kind": null },
+                            { "name": "dep", "kind": null, "path": "/ws/vendor/dep" }
+                        ]
+                    },
+                    {
+                        "id": "path+file:///ws/api#0.1.0",
+                        "name": "api",
+                        "manifest_path": "/ws/api/Cargo.toml",
+                        "targets": [
+                            { "kind": ["cdylib", "rlib"], "name": "api", "src_path": "/ws/api/src/lib.rs" }
+                        ]
+                    },
+                    {
+                        "id": "path+file:///ws/vendor/dep#0.1.0",
+                        "name": "dep",
+                        "manifest_path": "/ws/vendor/dep/Cargo.toml",
+                        "targets": [
+                            { "kind": ["lib"], "name": "dep", "src_path": "/ws/vendor/dep/src/lib.rs" }
+                        ]
+                    }
+                ],
+                "workspace_members": [
+                    "path+file:///ws#root@0.1.0",
+                    "path+file:///ws/core#core@0.1.0",
+                    "path+file:///ws/api#0.1.0"
+                ],
+                "target_directory": "/ws/target",
+                "version": 1
+            }"#;
+        serde_json::from_str(&metadata.replace("/ws/vendor/dep", dep().to_str().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn finds_workspace_members() {
+        assert_eq!(
+            workspace_of(metadata(), &[], &[]).unwrap(),
+            Workspace {
+                directories: vec![
+                    "/ws/api/src".into(),
+                    "/ws/core/src".into(),
+                    "/ws/src".into()
+                ],
+                dependencies: vec![dep().join("src")],
+                target_directory: "/ws/target".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn follows_path_dependencies() {
+        let workspace = workspace_of(metadata(), &["root".into()], &[]).unwrap();
+        assert_eq!(workspace.directories, [PathBuf::from("/ws/src")]);
+        assert_eq!(
+            workspace.dependencies,
+            [dep().join("src"), PathBuf::from("/ws/core/src")]
+        );
+
+        let workspace = workspace_of(metadata(), &["api".into()], &[]).unwrap();
+        assert!(workspace.dependencies.is_empty());
+    }
+
+    #[test]
+    fn filters_packages() {
+        let workspace = workspace_of(metadata(), &["core".into(), "api".into()], &[]).unwrap();
+        assert_eq!(
+            workspace.directories,
+            [PathBuf::from("/ws/api/src"), "/ws/core/src".into()]
+        );
+
+        let workspace = workspace_of(metadata(), &[], &["root".into()]).unwrap();
+        assert_eq!(
+            workspace.directories,
+            [PathBuf::from("/ws/api/src"), "/ws/core/src".into()]
+        );
+
+        assert!(workspace_of(metadata(), &["dep".into()], &[]).is_err());
+        assert!(workspace_of(metadata(), &["core".into()], &["core".into()]).is_err());
+    }
+}
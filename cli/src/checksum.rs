@@ -0,0 +1,69 @@
+//! Checksum markers in generated files, to detect files which were edited since they
+//! were generated.
+use crate::sha256::sha256_hex;
+use typeshare_core::language::SupportedLanguage;
+
+/// What the marker comment starts with, followed by the SHA-256 hash of the contents
+/// before the marker.
+const MARKER: &str = "typeshare-checksum: ";
+
+/// The line comment of the language, which the marker is written as.
+pub fn line_comment(language: SupportedLanguage) -> &'static str {
+    match language {
+        SupportedLanguage::Python => "#",
+        SupportedLanguage::Go
+        | SupportedLanguage::Kotlin
+        | SupportedLanguage::ReasonML
+        | SupportedLanguage::Scala
+        | SupportedLanguage::Swift
+        | SupportedLanguage::TypeScript => "//",
+    }
+}
+
+/// Append a marker line with the checksum of the contents, as a comment starting with
+/// `comment`.
+pub fn add_marker(contents: &mut Vec<u8>, comment: &str) {
+    if !contents.is_empty() && !contents.ends_with(b"\n") {
+        contents.push(b'\n');
+    }
+    let marker = format!("{comment} {MARKER}{}\n", sha256_hex(contents));
+    contents.extend_from_slice(marker.as_bytes());
+}
+
+/// Whether the contents were changed since their checksum marker was added. Contents
+/// without a marker, like files typeshare wrote without markers, aren't.
+pub fn is_modified(contents: &[u8]) -> bool {
+    let without_newline = contents.strip_suffix(b"\n").unwrap_or(contents);
+    let start = without_newline
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let Ok(last_line) = std::str::from_utf8(&without_newline[start..]) else {
+        return false;
+    };
+    match last_line.split_once(MARKER) {
+        Some((_, checksum)) => checksum.trim() != sha256_hex(&contents[..start]),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_marker, is_modified};
+
+    #[test]
+    fn detects_modified_contents() {
+        let mut contents = b"export type Id = string;\n".to_vec();
+        add_marker(&mut contents, "//");
+        assert!(String::from_utf8_lossy(&contents)
+            .ends_with("\n// typeshare-checksum: f646c675a7880124194563cfed40327f757bdfe289379543091682caeb1af66f\n"));
+        assert!(!is_modified(&contents));
+
+        let edited = String::from_utf8(contents)
+            .unwrap()
+            .replace("string", "number");
+        assert!(is_modified(edited.as_bytes()));
+
+        assert!(!is_modified(b"export type Id = string;\n"));
+    }
+}
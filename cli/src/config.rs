@@ -1,47 +1,156 @@
+use crate::schema;
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     env,
     fs::{self, OpenOptions},
     io::{self, Write},
+    num::NonZeroUsize,
     path::{Path, PathBuf},
 };
+use typeshare_core::language::CrateName;
 
-const DEFAULT_CONFIG_FILE_NAME: &str = "typeshare.toml";
+pub(crate) const DEFAULT_CONFIG_FILE_NAME: &str = "typeshare.toml";
 
-#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(default)]
 #[cfg(feature = "python")]
 pub struct PythonParams {
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+    /// Identifiers which get a trailing underscore like Python keywords.
+    pub reserved_words: Vec<String>,
 }
 
-#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+/// A file or folder generated types are written to, like `{ file = "web/types.ts" }`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputDestination {
+    File(PathBuf),
+    Folder(PathBuf),
+}
+
+/// What levels of indentation are written with, like in `.editorconfig`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+/// The serialization framework generated Kotlin types are annotated for.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum KotlinSerializer {
+    #[default]
+    Kotlinx,
+    Moshi,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(default)]
 pub struct KotlinParams {
     pub package: String,
     pub module_name: String,
     pub prefix: String,
+    pub serializer: KotlinSerializer,
+    pub sealed_interfaces: bool,
+    pub serde_default_values: bool,
+    pub parcelize: bool,
+    /// Generate a file and package per Rust module instead of per crate.
+    pub package_per_module: bool,
+    pub newtype_value_classes: bool,
+    /// Generate common Kotlin Multiplatform source.
+    pub multiplatform: bool,
+    /// Generate a Kotest arbitrary for every type.
+    pub arbitraries: bool,
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+    /// Identifiers of properties which are escaped with backticks, like framework names.
+    pub reserved_words: Vec<String>,
 }
 
-#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(default)]
 pub struct ReasonMLParams {
+    /// Mappings from Rust type names to ReasonML types, which override the TypeScript
+    /// mappings ReasonML uses as a base.
     pub type_mappings: HashMap<String, String>,
+    /// Don't write the "Generated by typeshare" comment at the top of generated files.
+    pub no_version_header: bool,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+    /// Identifiers which are quoted like ReasonML keywords.
+    pub reserved_words: Vec<String>,
+    /// Write `decode<Type>` and `encode<Type>` functions converting every type from and
+    /// to `Js.Json.t`.
+    pub codecs: bool,
 }
 
-#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(default)]
 pub struct ScalaParams {
     pub package: String,
     pub module_name: String,
+    pub circe: bool,
+    pub scala3: bool,
+    pub opaque_newtypes: bool,
+    /// Generate Scala.js facades instead of case classes.
+    pub scala_js: bool,
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+    /// Identifiers which are escaped with backticks like Scala keywords.
+    pub reserved_words: Vec<String>,
+}
+
+/// How `CodingKeys` are generated for Swift structs.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SwiftCodingKeys {
+    #[default]
+    Default,
+    ConvertFromSnakeCase,
+    Explicit,
 }
 
-#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 #[serde(default)]
 pub struct SwiftParams {
     pub prefix: String,
@@ -49,30 +158,265 @@ pub struct SwiftParams {
     pub default_generic_constraints: Vec<String>,
     /// The constraints to apply to `CodableVoid`.
     pub codablevoid_constraints: Vec<String>,
+    /// Default optional initializer parameters to `nil`.
+    pub init_optional_defaults: bool,
+    /// Emit Swift default values for fields with `#[serde(default)]`.
+    pub serde_default_values: bool,
+    /// Generate a SwiftPM package with this name when writing to an output folder.
+    pub package_name: Option<String>,
+    pub coding_keys: SwiftCodingKeys,
+    /// Name of a fallback case added to enums for unknown values.
+    pub unknown_case: Option<String>,
+    pub mocks: bool,
+    /// Conform every struct and enum to SwiftCheck's `Arbitrary`.
+    pub arbitraries: bool,
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+    /// Identifiers which are escaped with backticks like Swift keywords.
+    pub reserved_words: Vec<String>,
 }
 
-#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(default)]
 pub struct TypeScriptParams {
+    pub module_format: TypeScriptModuleFormat,
+    /// Extension of the import specifiers between generated files.
+    pub import_extension: Option<String>,
+    pub field_naming: TypeScriptFieldNaming,
+    /// Generate a fast-check arbitrary for every type.
+    pub arbitraries: bool,
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+}
+
+/// How the properties of generated TypeScript types are named.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeScriptFieldNaming {
+    #[default]
+    Wire,
+    CamelCase,
+}
+
+/// The module system generated TypeScript files are consumed with.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeScriptModuleFormat {
+    #[default]
+    Bundler,
+    Esm,
+    #[serde(rename = "commonjs")]
+    CommonJs,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[cfg(feature = "go")]
+pub enum GoOptionStyle {
+    #[default]
+    Pointer,
+    Generic,
+    Null,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+#[cfg(feature = "go")]
+pub enum GoDateTimeFormat {
+    #[default]
+    Rfc3339,
+    Unix,
+    UnixMillis,
+    /// Any other value is a Go time layout.
+    #[serde(untagged)]
+    Layout(String),
 }
 
-#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Default, Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 #[serde(default)]
 #[cfg(feature = "go")]
 pub struct GoParams {
     pub package: String,
     pub uppercase_acronyms: Vec<String>,
     pub no_pointer_slice: bool,
+    pub no_omitempty: bool,
+    pub option_style: GoOptionStyle,
+    pub datetime_format: GoDateTimeFormat,
+    pub import_path: String,
+    pub package_names: HashMap<String, String>,
+    pub json_value_any: bool,
+    /// Generate `String`, `MarshalJSON` and `UnmarshalJSON` methods for unit enums.
+    pub enum_methods: bool,
+    /// Generate a package per Rust module instead of per crate.
+    pub package_per_module: bool,
     pub type_mappings: HashMap<String, String>,
+    /// Command run on every generated file, with the path of the file appended.
+    pub formatter: Vec<String>,
+    /// The files and folders to generate into when no output is given on the command line.
+    pub outputs: Vec<OutputDestination>,
+    /// Indent with tabs or spaces, instead of the indentation of the language.
+    pub indent_style: Option<IndentStyle>,
+    /// The number of spaces of every level of indentation.
+    pub indent_size: Option<NonZeroUsize>,
+    /// Wrap comments longer than this width.
+    pub max_line_width: Option<NonZeroUsize>,
+}
+
+/// Overrides of a typeshared type, for types whose Rust source can't be annotated.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(default)]
+pub struct TypeOverride {
+    /// The name of the generated type, instead of its Rust name.
+    pub rename: Option<String>,
+    /// The crate, or module with `per_module`, whose file the type is generated into
+    /// when generating multiple files.
+    pub file: Option<String>,
+    /// Swift protocols the type conforms to, like `#[typeshare(swift = "...")]`.
+    pub swift: Vec<String>,
+    /// Kotlin decorators of the type, like `#[typeshare(kotlin = "...")]`.
+    pub kotlin: Vec<String>,
+}
+
+/// A language backend outside of typeshare, which is run with `--plugin <name>`.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(default)]
+pub struct PluginParams {
+    /// The program of the plugin and its arguments.
+    pub command: Vec<String>,
+    /// The folder the generated files are written into, unless one is given on the
+    /// command line.
+    pub output_folder: Option<PathBuf>,
+    /// Options which are passed on to the plugin.
+    pub options: toml::Table,
+}
+
+/// A language whose types are rendered with templates, which is run with
+/// `--template <name>`.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(default)]
+pub struct TemplateParams {
+    /// The extension of the files generated into an output folder, like `elm`.
+    pub extension: String,
+    /// The template file written at the start of every file.
+    pub header: Option<PathBuf>,
+    /// The template file written for every struct.
+    #[serde(rename = "struct")]
+    pub struct_template: Option<PathBuf>,
+    /// The template file written for every enum.
+    #[serde(rename = "enum")]
+    pub enum_template: Option<PathBuf>,
+    /// The template file written for every type alias.
+    pub alias: Option<PathBuf>,
+    /// The template file written for every constant.
+    #[serde(rename = "const")]
+    pub const_template: Option<PathBuf>,
+    /// The template file written at the end of every file.
+    pub footer: Option<PathBuf>,
+    pub type_mappings: HashMap<String, String>,
+    pub outputs: Vec<OutputDestination>,
+}
+
+/// How the round-trip tests of `--conformance` refer to the generated types.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(default)]
+pub struct ConformanceParams {
+    /// The module the generated types are imported from: the Swift module, the
+    /// TypeScript import path or the ReasonML module.
+    pub module: Option<String>,
+}
+
+/// How fields of `Option` types are represented in every language.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionalFields {
+    Absent,
+    Null,
+    Both,
+}
+
+/// What to do with the warnings of a lint.
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum LintLevel {
+    /// Ignore the warnings.
+    Allow,
+    /// Log the warnings.
+    #[default]
+    Warn,
+    /// Fail on the warnings.
+    Deny,
 }
 
 /// The parameters that are used to configure the behaviour of typeshare
 /// from the configuration file `typeshare.toml`
-#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone)]
 #[serde(default)]
 pub(crate) struct Config {
+    /// In multi-file mode, generate a file per Rust module instead of per crate.
+    pub per_module: bool,
+    /// In multi-file mode, generate a file per type.
+    pub file_per_type: bool,
+    /// Generate the types sorted by name instead of in the order they're parsed in.
+    pub sort_types: bool,
+    /// Only process the Rust files matching any of these globs.
+    pub include: Vec<String>,
+    /// Skip the Rust files matching any of these globs.
+    pub exclude: Vec<String>,
+    /// Cache file recording the input files, to only generate the crates whose
+    /// input files changed since the last run.
+    pub cache_file: Option<PathBuf>,
+    /// Manifest file listing the generated files with the hashes of their contents.
+    pub manifest: Option<PathBuf>,
+    /// End every generated file with a marker of the checksum of its contents, to not
+    /// overwrite files which were edited since they were generated.
+    pub checksum_markers: bool,
+    /// Only generate the types whose Rust names match any of these regular expressions.
+    pub only_types: Vec<String>,
+    /// Don't generate the types whose Rust names match any of these regular expressions.
+    pub exclude_types: Vec<String>,
+    /// Only generate these types, by their Rust names, and the types they reference.
+    pub root_types: Vec<String>,
+    /// The level of each lint, by its name, like `lossy_mapping = "deny"`.
+    pub lints: BTreeMap<String, LintLevel>,
+    /// A header replacing the "Generated by typeshare" comment of every generated file,
+    /// with `{version}`, `{timestamp}` and `{crate}` placeholders.
+    pub header: Option<String>,
+    /// Leave the version of typeshare out of the "Generated by typeshare" comment, so
+    /// upgrading typeshare doesn't change every generated file.
+    pub no_header_version: bool,
+    /// Add the git revision of the sources to the "Generated by typeshare" comment.
+    pub header_revision: bool,
+    /// How fields of `Option` types are represented, instead of the default of each
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
+    /// Write a constant with the schema hash of every type after it, in every language.
+    pub schema_hashes: bool,
+    /// Overrides of typeshared types, by their Rust names.
+    pub types: BTreeMap<String, TypeOverride>,
+    /// Language backends outside of typeshare, by the name they're run with.
+    pub plugins: BTreeMap<String, PluginParams>,
+    /// Languages rendered with templates, by the name they're run with.
+    pub templates: BTreeMap<String, TemplateParams>,
+    /// How the round-trip tests of `--conformance` refer to the generated types.
+    pub conformance: ConformanceParams,
     pub swift: SwiftParams,
     pub typescript: TypeScriptParams,
     pub kotlin: KotlinParams,
@@ -84,6 +428,9 @@ pub(crate) struct Config {
     pub go: GoParams,
     #[serde(skip)]
     pub target_os: Vec<String>,
+    /// The git revision of the sources, if the header needs it.
+    #[serde(skip)]
+    pub revision: Option<String>,
 }
 
 pub(crate) fn store_config(config: &Config, file_path: Option<&Path>) -> anyhow::Result<()> {
@@ -101,21 +448,260 @@ pub(crate) fn store_config(config: &Config, file_path: Option<&Path>) -> anyhow:
     Ok(())
 }
 
-pub(crate) fn load_config(file_path: Option<&Path>) -> Result<Config, io::Error> {
+/// Load the configuration file, with the values of the `[profile.<name>]` table of the
+/// profile, if any, overriding the others.
+pub(crate) fn load_config(
+    file_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<Config, io::Error> {
     let file_path = file_path
         .map(Cow::Borrowed)
         .or_else(|| find_configuration_file().map(Cow::Owned));
 
-    if let Some(file_path) = file_path {
-        let config_string = fs::read_to_string(file_path)?;
-        toml::from_str(&config_string).map_err(io::Error::other)
-    } else {
-        Ok(Config::default())
+    let Some(file_path) = file_path else {
+        return match profile {
+            Some(profile) => Err(io::Error::other(format!(
+                "No configuration file found for the profile {profile:?}"
+            ))),
+            None => Ok(Config::default()),
+        };
+    };
+    let table = read_profile_table(&file_path, profile, true)?;
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(io::Error::other)
+}
+
+/// The table of the configuration file, given or found, as it's loaded: merged with the
+/// files it extends, with the environment variables interpolated and the profile applied.
+/// `None` without a configuration file.
+pub(crate) fn load_config_table(
+    file_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<Option<toml::Table>, io::Error> {
+    let Some(file_path) = file_path
+        .map(Path::to_path_buf)
+        .or_else(find_configuration_file)
+    else {
+        return Ok(None);
+    };
+    read_profile_table(&file_path, profile, true).map(Some)
+}
+
+/// The configurations of the crates of the input directories which have a configuration
+/// file of their own, next to their `src` folder, by crate name. The options of a
+/// crate's file, and of its table of the profile, override those of the configuration
+/// file, which is merged like a file it extends.
+pub(crate) fn load_crate_configs(
+    file_path: Option<&Path>,
+    profile: Option<&str>,
+    directories: &[PathBuf],
+) -> Result<BTreeMap<String, Config>, io::Error> {
+    let crate_files = crate_config_files(file_path, directories);
+    if crate_files.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let file_path = file_path
+        .map(Path::to_path_buf)
+        .or_else(find_configuration_file);
+    let table = match &file_path {
+        Some(file_path) => read_profile_table(file_path, profile, true)?,
+        None => toml::Table::new(),
+    };
+
+    let mut configs = BTreeMap::new();
+    for (crate_name, crate_file) in crate_files {
+        let mut crate_table = table.clone();
+        merge_tables(
+            &mut crate_table,
+            read_profile_table(&crate_file, profile, false)?,
+        );
+        let config = toml::Value::Table(crate_table)
+            .try_into()
+            .map_err(|err| io::Error::other(format!("Invalid {crate_file:?}: {err}")))?;
+        configs.insert(crate_name, config);
+    }
+    Ok(configs)
+}
+
+/// The configuration files of the crates of the input directories, by crate name,
+/// besides the given or found configuration file.
+pub(crate) fn crate_config_files(
+    file_path: Option<&Path>,
+    directories: &[PathBuf],
+) -> BTreeMap<String, PathBuf> {
+    let config_file = file_path
+        .map(Path::to_path_buf)
+        .or_else(find_configuration_file)
+        .and_then(|file_path| fs::canonicalize(file_path).ok());
+    directories
+        .iter()
+        .filter_map(|directory| {
+            // The source folders of workspace packages, or the crate folders
+            let crate_root = match directory.file_name() {
+                Some(name) if name == "src" => directory.parent()?,
+                _ => directory.as_path(),
+            };
+            let crate_root = fs::canonicalize(crate_root).ok()?;
+            let crate_file = crate_root.join(DEFAULT_CONFIG_FILE_NAME);
+            if !crate_file.is_file() || config_file.as_ref() == Some(&crate_file) {
+                return None;
+            }
+            let crate_name = CrateName::find_crate_name(&crate_root.join("src").join("lib.rs"))?;
+            Some((crate_name.to_string(), crate_file))
+        })
+        .collect()
+}
+
+/// Read the table of a configuration file, with the values of the `[profile.<name>]`
+/// table of the profile, if any, overriding the others. Without that table, it's an error
+/// if `required`.
+fn read_profile_table(
+    file_path: &Path,
+    profile: Option<&str>,
+    required: bool,
+) -> Result<toml::Table, io::Error> {
+    let mut table = read_table(file_path, &mut Vec::new())?;
+    let profiles = table.remove("profile");
+    let Some(profile) = profile else {
+        return Ok(table);
+    };
+    match profiles
+        .as_ref()
+        .and_then(|profiles| profiles.get(profile))
+        .and_then(toml::Value::as_table)
+    {
+        Some(overrides) => merge_tables(&mut table, overrides.clone()),
+        None if required => {
+            return Err(io::Error::other(format!(
+                "The configuration has no profile {profile:?}"
+            )))
+        }
+        None => {}
+    }
+    Ok(table)
+}
+
+/// The configuration file, given or found, and the files it extends.
+pub(crate) fn config_files(file_path: Option<&Path>) -> Vec<PathBuf> {
+    let Some(file_path) = file_path
+        .map(Path::to_path_buf)
+        .or_else(find_configuration_file)
+    else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    // An invalid configuration is reported when it's loaded
+    let _ = read_table(&file_path, &mut files);
+    files
+}
+
+/// Read the table of a configuration file, whose values override those of the file it
+/// `extends`, if any. That path is relative to the directory of the file. `files`
+/// collects the files read, to detect files extending themselves.
+fn read_table(file_path: &Path, files: &mut Vec<PathBuf>) -> Result<toml::Table, io::Error> {
+    let path = fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    if files.contains(&path) {
+        return Err(io::Error::other(format!(
+            "The configuration file {file_path:?} extends itself"
+        )));
+    }
+    files.push(path);
+
+    let config_string = fs::read_to_string(file_path).map_err(|err| {
+        io::Error::new(err.kind(), format!("Failed to read {file_path:?}: {err}"))
+    })?;
+    let mut table = toml::from_str::<toml::Table>(&config_string).map_err(io::Error::other)?;
+    let unknown_keys = schema::unknown_keys(&table);
+    if !unknown_keys.is_empty() {
+        return Err(io::Error::other(format!(
+            "Invalid configuration file {file_path:?}:\n  {}",
+            unknown_keys.join("\n  ")
+        )));
+    }
+    for (_, value) in table.iter_mut() {
+        interpolate_value(value)
+            .map_err(|err| io::Error::other(format!("{err} in {file_path:?}")))?;
+    }
+    let Some(extends) = table.remove("extends") else {
+        return Ok(table);
+    };
+    let extends = extends.as_str().ok_or_else(|| {
+        io::Error::other(format!(
+            "`extends` in {file_path:?} must be the path of a file"
+        ))
+    })?;
+    let base_path = file_path.parent().unwrap_or(Path::new("")).join(extends);
+    let mut base = read_table(&base_path, files)?;
+    merge_tables(&mut base, table);
+    Ok(base)
+}
+
+/// Replace the references to environment variables in the strings of a value, see
+/// [`interpolate`].
+fn interpolate_value(value: &mut toml::Value) -> Result<(), String> {
+    match value {
+        toml::Value::String(string) => *string = interpolate(string)?,
+        toml::Value::Array(values) => values.iter_mut().try_for_each(interpolate_value)?,
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .try_for_each(|(_, value)| interpolate_value(value))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace the `${VAR}` references to environment variables in the string with their
+/// values, or `${VAR:-default}` for a default when the variable isn't set. `$${` is a
+/// literal `${`.
+fn interpolate(string: &str) -> Result<String, String> {
+    let mut interpolated = String::new();
+    let mut rest = string;
+    while let Some(start) = rest.find("${") {
+        if let Some(before) = rest[..start].strip_suffix('$') {
+            interpolated.push_str(before);
+            interpolated.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        interpolated.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("Unclosed `${{` in {string:?}"))?;
+        let reference = &rest[start + 2..end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        match (env::var(name), default) {
+            (Ok(value), _) => interpolated.push_str(&value),
+            (Err(_), Some(default)) => interpolated.push_str(default),
+            (Err(_), None) => return Err(format!("The environment variable {name:?} isn't set")),
+        }
+        rest = &rest[end + 1..];
+    }
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+/// Merge the values of `overrides` into `table`. Tables are merged recursively, and any
+/// other value replaces the existing one.
+fn merge_tables(table: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (table.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(overrides)) => {
+                merge_tables(existing, overrides)
+            }
+            (_, value) => {
+                table.insert(key, value);
+            }
+        }
     }
 }
 
 /// Search each ancestor directory for configuration file
-fn find_configuration_file() -> Option<PathBuf> {
+pub(crate) fn find_configuration_file() -> Option<PathBuf> {
     let mut path = env::current_dir().ok()?;
     let file = Path::new(DEFAULT_CONFIG_FILE_NAME);
 
@@ -146,7 +732,7 @@ mod test {
     #[test]
     fn to_string_and_back() {
         let path = config_file_path("mappings_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         toml::from_str::<Config>(&toml::to_string_pretty(&config).unwrap()).unwrap();
     }
@@ -154,7 +740,7 @@ mod test {
     #[test]
     fn default_test() {
         let path = config_file_path("default_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config, Config::default());
     }
@@ -162,7 +748,7 @@ mod test {
     #[test]
     fn empty_test() {
         let path = config_file_path("empty_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config, Config::default());
     }
@@ -170,12 +756,13 @@ mod test {
     #[test]
     fn mappings_test() {
         let path = config_file_path("mappings_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config.swift.type_mappings["DateTime"], "Date");
         assert_eq!(config.kotlin.type_mappings["DateTime"], "String");
         assert_eq!(config.scala.type_mappings["DateTime"], "String");
         assert_eq!(config.typescript.type_mappings["DateTime"], "string");
+        assert_eq!(config.reasonml.type_mappings["DateTime"], "Js.Date.t");
         #[cfg(feature = "python")]
         {
             assert_eq!(config.python.type_mappings["Url"], "AnyUrl");
@@ -188,7 +775,7 @@ mod test {
     #[test]
     fn decorators_test() {
         let path = config_file_path("decorators_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config.swift.default_decorators.len(), 1);
         assert_eq!(config.swift.default_decorators[0], "Sendable");
@@ -197,7 +784,7 @@ mod test {
     #[test]
     fn constraints_test() {
         let path = config_file_path("constraints_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config.swift.default_generic_constraints.len(), 1);
         assert_eq!(config.swift.default_generic_constraints[0], "Sendable");
@@ -206,16 +793,238 @@ mod test {
     #[test]
     fn swift_prefix_test() {
         let path = config_file_path("swift_prefix_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config.swift.prefix, "test");
     }
+
+    #[test]
+    fn swift_coding_keys_test() {
+        let path = config_file_path("swift_coding_keys_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(
+            config.swift.coding_keys,
+            SwiftCodingKeys::ConvertFromSnakeCase
+        );
+    }
+
+    #[test]
+    fn profile_test() {
+        let path = config_file_path("profiles_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+        assert_eq!(config.exclude_types, [".*Internal"]);
+        assert_eq!(config.typescript.type_mappings["DateTime"], "string");
+
+        let config = load_config(Some(&path), Some("internal")).unwrap();
+        assert!(config.exclude_types.is_empty());
+        assert_eq!(config.typescript.type_mappings["DateTime"], "Date");
+        assert_eq!(config.typescript.type_mappings["Url"], "string");
+        assert_eq!(
+            config.typescript.outputs,
+            [OutputDestination::File("internal/types.ts".into())]
+        );
+
+        assert!(load_config(Some(&path), Some("missing")).is_err());
+    }
+
+    #[test]
+    fn extends_test() {
+        let path = config_file_path("extends_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+        assert_eq!(config.swift.prefix, "Base");
+        assert_eq!(config.typescript.type_mappings["DateTime"], "string");
+        assert_eq!(config.typescript.type_mappings["Url"], "URL");
+        assert_eq!(
+            config.typescript.outputs,
+            [OutputDestination::File("app/types.ts".into())]
+        );
+        assert_eq!(
+            config_files(Some(&path)),
+            [
+                fs::canonicalize(&path).unwrap(),
+                fs::canonicalize(config_file_path("extends_base_config.toml")).unwrap()
+            ]
+        );
+
+        let path = config_file_path("extends_self_config.toml");
+        assert!(load_config(Some(&path), None).is_err());
+    }
+
+    #[test]
+    fn header_test() {
+        let path = config_file_path("header_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert!(config.no_header_version);
+        assert!(config.header_revision);
+        assert_eq!(config.header, None);
+    }
+
+    #[test]
+    fn reserved_words_test() {
+        let path = config_file_path("reserved_words_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(config.swift.reserved_words, ["description", "hash"]);
+        assert!(config.kotlin.reserved_words.is_empty());
+    }
+
+    #[test]
+    fn plugin_test() {
+        let path = config_file_path("plugin_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        let elm = &config.plugins["elm"];
+        assert_eq!(elm.command, ["typeshare-elm", "--module", "Types"]);
+        assert_eq!(elm.output_folder, Some(PathBuf::from("frontend/src")));
+        assert_eq!(elm.options["decoders"], toml::Value::Boolean(true));
+    }
+
+    #[test]
+    fn template_test() {
+        let path = config_file_path("template_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        let elm = &config.templates["elm"];
+        assert_eq!(elm.extension, "elm");
+        assert_eq!(
+            elm.struct_template,
+            Some(PathBuf::from("templates/struct.elm"))
+        );
+        assert_eq!(elm.const_template, None);
+        assert_eq!(elm.type_mappings["String"], "String");
+        assert_eq!(
+            elm.outputs,
+            [OutputDestination::Folder(PathBuf::from("frontend/src"))]
+        );
+    }
+
+    #[test]
+    fn conformance_test() {
+        let path = config_file_path("conformance_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(config.conformance.module.as_deref(), Some("@app/types"));
+    }
+
+    #[test]
+    fn code_style_test() {
+        let path = config_file_path("code_style_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(config.swift.indent_size, NonZeroUsize::new(2));
+        assert_eq!(config.swift.max_line_width, NonZeroUsize::new(100));
+        assert_eq!(config.reasonml.indent_style, Some(IndentStyle::Tab));
+        assert_eq!(config.typescript.indent_style, None);
+    }
+
+    #[test]
+    fn unknown_keys_test() {
+        let path = config_file_path("unknown_keys_config.toml");
+        let error = load_config(Some(&path), None).unwrap_err().to_string();
+        let errors = error.lines().skip(1).map(str::trim).collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "unknown key `kotlin.pacakge`, did you mean `kotlin.package`?",
+                "unknown key `package`, did you mean `go.package` or `kotlin.package` or `scala.package`?",
+                "unknown key `profile.release.swift.prefx`, did you mean `profile.release.swift.prefix`?",
+                "unknown key `sort_type`, did you mean `sort_types`?",
+                "unknown key `typescript.sort_types`, did you mean the top-level `sort_types`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolates_environment_variables() {
+        // Set by cargo when running tests
+        assert_eq!(
+            interpolate("target/${CARGO_PKG_NAME}/types.ts").unwrap(),
+            "target/typeshare-cli/types.ts"
+        );
+        assert_eq!(
+            interpolate("${TYPESHARE_TEST_UNSET:-web}/${CARGO_PKG_NAME:-cli}").unwrap(),
+            "web/typeshare-cli"
+        );
+        assert_eq!(interpolate("cost: $${total}").unwrap(), "cost: ${total}");
+        assert!(interpolate("${TYPESHARE_TEST_UNSET}").is_err());
+        assert!(interpolate("${CARGO_PKG_NAME").is_err());
+    }
+
+    #[test]
+    fn outputs_test() {
+        let path = config_file_path("outputs_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(
+            config.typescript.outputs,
+            [
+                OutputDestination::File("web/src/types.ts".into()),
+                OutputDestination::Folder("mobile/src/types".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn crate_configs_test() {
+        let path = config_file_path("crate_configs/typeshare.toml");
+        let member = config_file_path("crate_configs/member/src");
+        let configs =
+            load_crate_configs(Some(&path), None, &[member, "missing/src".into()]).unwrap();
+
+        assert_eq!(configs.keys().collect::<Vec<_>>(), ["member"]);
+        let config = &configs["member"];
+        assert_eq!(config.typescript.type_mappings["DateTime"], "Date");
+        assert_eq!(config.typescript.type_mappings["Uuid"], "string");
+        assert_eq!(config.kotlin.package, "com.example.types");
+    }
+
+    #[test]
+    fn types_test() {
+        let path = config_file_path("types_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(
+            config.types,
+            BTreeMap::from([(
+                "User".to_string(),
+                TypeOverride {
+                    rename: Some("Account".into()),
+                    file: Some("shared".into()),
+                    swift: vec!["Hashable".into()],
+                    kotlin: Vec::new(),
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn optional_fields_test() {
+        let path = config_file_path("optional_fields_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(config.optional_fields, Some(OptionalFields::Null));
+    }
+
     #[test]
     #[cfg(feature = "go")]
     fn go_package_test() {
         let path = config_file_path("go_config.toml");
-        let config = load_config(Some(&path)).unwrap();
+        let config = load_config(Some(&path), None).unwrap();
 
         assert_eq!(config.go.package, "testPackage");
     }
+
+    #[test]
+    #[cfg(feature = "go")]
+    fn go_datetime_format_test() {
+        let path = config_file_path("go_datetime_format_config.toml");
+        let config = load_config(Some(&path), None).unwrap();
+
+        assert_eq!(
+            config.go.datetime_format,
+            GoDateTimeFormat::Layout("2006-01-02".into())
+        );
+    }
 }
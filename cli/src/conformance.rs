@@ -0,0 +1,81 @@
+//! Generating round-trip tests of the generated types, from samples serialized by serde.
+use crate::{
+    args::{Args, AvailableLanguage},
+    config,
+    ir::write_output,
+    language, override_configuration,
+    plugin::parse_crates,
+};
+use anyhow::Context;
+use std::{fs, path::Path};
+use typeshare_core::{
+    conformance::{self, Harness, Samples},
+    language::{CrateTypes, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
+};
+
+/// Generate a test of the language which decodes and encodes the samples of the file
+/// with the generated types, to the output file or stdout.
+pub fn generate_conformance(
+    config_file: Option<&Path>,
+    options: &Args,
+    samples: &Path,
+) -> anyhow::Result<()> {
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    anyhow::ensure!(
+        options.output.folder.is_none() && !options.reads_stdin(),
+        "The round-trip test of Rust files is written to a single file or stdout"
+    );
+    let samples: Samples = serde_json::from_str(
+        &fs::read_to_string(samples)
+            .with_context(|| format!("Failed to read the samples {samples:?}"))?,
+    )
+    .with_context(|| format!("The samples {samples:?} aren't arrays of samples by type"))?;
+
+    let module = config.conformance.module.clone();
+    let harness = match options.language.context("--conformance needs a language")? {
+        AvailableLanguage::Swift => Harness::Swift {
+            module,
+            prefix: config.swift.prefix.clone(),
+        },
+        AvailableLanguage::Kotlin => Harness::Kotlin {
+            package: config.kotlin.package.clone(),
+            prefix: config.kotlin.prefix.clone(),
+        },
+        AvailableLanguage::Typescript => Harness::TypeScript {
+            import: module.unwrap_or_else(|| "./types".to_owned()),
+            codecs: config.typescript.field_naming == config::TypeScriptFieldNaming::CamelCase,
+            reviver: has_reviver(&config, options)?,
+        },
+        AvailableLanguage::ReasonML => {
+            anyhow::ensure!(
+                config.reasonml.codecs,
+                "The ReasonML round-trip test decodes with the codecs of `reasonml.codecs = true`"
+            );
+            Harness::ReasonML {
+                module: module.unwrap_or_else(|| "Types".to_owned()),
+            }
+        }
+        #[allow(unreachable_patterns)]
+        _ => anyhow::bail!(
+            "Round-trip tests are generated for Swift, Kotlin, TypeScript and ReasonML"
+        ),
+    };
+
+    let mut crates = parse_crates(&config, options, false)?;
+    let parsed_data = crates.remove(&SINGLE_FILE_CRATE_NAME).unwrap_or_default();
+    let test = conformance::generate(&harness, &parsed_data, &samples)?;
+    write_output(options, test)
+}
+
+/// Whether the generated TypeScript file has a `ReviverFunc` and `ReplacerFunc`, which
+/// it has for type mappings like `Date`.
+fn has_reviver(config: &config::Config, options: &Args) -> anyhow::Result<bool> {
+    let mut crates = parse_crates(config, options, false)?;
+    let parsed_data = crates.remove(&SINGLE_FILE_CRATE_NAME).unwrap_or_default();
+    let mut typescript = language(SupportedLanguage::TypeScript, config.clone(), false);
+    let mut generated = Vec::new();
+    typescript.generate_types(&mut generated, &CrateTypes::default(), parsed_data)?;
+    Ok(String::from_utf8_lossy(&generated).contains("export const ReviverFunc"))
+}
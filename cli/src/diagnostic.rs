@@ -0,0 +1,87 @@
+//! Rendering errors with the Rust source they point at.
+use std::fs;
+use typeshare_core::error::SourceSpan;
+
+/// Lines of source shown before the line the span starts on.
+const CONTEXT_LINES: usize = 1;
+
+/// Render an error at a span of a Rust file, with the lines of the file it points
+/// at, like:
+///
+/// ```text
+///   × The serde flatten attribute is not currently supported
+///    ╭─[src/lib.rs:3:4]
+///  2 │ pub struct Foo {
+///  3 │     #[serde(flatten)]
+///    ·     ─────────────────
+///    ╰────
+/// ```
+///
+/// Without a span, or if the file can't be read, only the error and the file are shown.
+pub fn render(message: &str, file_name: &str, span: Option<SourceSpan>) -> String {
+    let source = span.and_then(|span| Some((span, fs::read_to_string(file_name).ok()?)));
+    let Some((span, source)) = source else {
+        return format!("  × {message}\n   ╰─[{file_name}]\n");
+    };
+    render_source(message, file_name, &source, span)
+}
+
+fn render_source(message: &str, file_name: &str, source: &str, span: SourceSpan) -> String {
+    let lines = source.lines().collect::<Vec<_>>();
+    let line = span.line.clamp(1, lines.len().max(1));
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let width = line.to_string().len();
+    let gutter = " ".repeat(width);
+
+    let mut output = format!(
+        "  × {message}\n {gutter} ╭─[{file_name}:{line}:{}]\n",
+        span.column
+    );
+    for number in first..=line {
+        let text = lines.get(number - 1).copied().unwrap_or_default();
+        output += &format!(" {number:>width$} │ {text}\n");
+    }
+    let text = lines.get(line - 1).copied().unwrap_or_default();
+    let length = text.chars().count();
+    let start = span.column.min(length);
+    // Spans over several lines are underlined to the end of their first line.
+    let end = if span.end_line == span.line {
+        span.end_column.clamp(start + 1, length.max(start + 1))
+    } else {
+        length.max(start + 1)
+    };
+    output += &format!(
+        " {gutter} · {}{}\n",
+        " ".repeat(start),
+        "─".repeat(end - start)
+    );
+    output += &format!(" {gutter} ╰────\n");
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::render_source;
+    use typeshare_core::error::SourceSpan;
+
+    #[test]
+    fn renders_span() {
+        let source = "#[typeshare]\npub struct Foo {\n    #[serde(flatten)]\n    pub a: A,\n}\n";
+        let span = SourceSpan {
+            line: 3,
+            column: 4,
+            end_line: 3,
+            end_column: 21,
+        };
+        assert_eq!(
+            render_source("flatten isn't supported", "src/lib.rs", source, span),
+            "  × flatten isn't supported
+   ╭─[src/lib.rs:3:4]
+ 2 │ pub struct Foo {
+ 3 │     #[serde(flatten)]
+   ·     ─────────────────
+   ╰────
+"
+        );
+    }
+}
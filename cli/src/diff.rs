@@ -0,0 +1,185 @@
+//! Unified diffs between existing and generated files.
+use std::fmt::Write;
+
+/// Lines of unchanged context around every change.
+const CONTEXT: usize = 3;
+
+/// Beyond this many cells in the table of longest common subsequences, the changed
+/// part of a file is shown as entirely removed and added, instead of line by line.
+const MAX_TABLE_SIZE: usize = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A unified diff from `old` to `new`, or `None` if they are the same.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let lines = diff_lines(&old, &new);
+
+    let mut diff = format!("--- {old_name}\n+++ {new_name}\n");
+    for hunk in hunks(&lines) {
+        let old_start = lines[..hunk.start]
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_start = lines[..hunk.start]
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        let old_len = lines[hunk.clone()]
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_len = lines[hunk.clone()]
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        // Empty ranges start at the line before them
+        let _ = writeln!(
+            diff,
+            "@@ -{},{old_len} +{},{new_len} @@",
+            old_start + usize::from(old_len > 0),
+            new_start + usize::from(new_len > 0)
+        );
+        for line in &lines[hunk] {
+            let _ = match line {
+                Line::Same(l) => writeln!(diff, " {l}"),
+                Line::Removed(l) => writeln!(diff, "-{l}"),
+                Line::Added(l) => writeln!(diff, "+{l}"),
+            };
+        }
+    }
+    Some(diff)
+}
+
+/// The lines of both files, marked as the same in both, removed or added.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut lines = old[..prefix]
+        .iter()
+        .map(|l| Line::Same(l))
+        .collect::<Vec<_>>();
+    if (old_middle.len() + 1) * (new_middle.len() + 1) > MAX_TABLE_SIZE {
+        lines.extend(old_middle.iter().map(|l| Line::Removed(l)));
+        lines.extend(new_middle.iter().map(|l| Line::Added(l)));
+    } else {
+        lines.extend(diff_middle(old_middle, new_middle));
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|l| Line::Same(l)));
+    lines
+}
+
+/// Diffs lines along their longest common subsequence.
+fn diff_middle<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // `table[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`
+    let width = new.len() + 1;
+    let mut table = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len() + new.len());
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len()
+            || (i < old.len() && table[(i + 1) * width + j] >= table[i * width + j + 1])
+        {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// The ranges of lines shown in each hunk: every change with its context,
+/// merged when their contexts overlap.
+fn hunks(lines: &[Line<'_>]) -> Vec<std::ops::Range<usize>> {
+    let mut hunks: Vec<std::ops::Range<usize>> = Vec::new();
+    for (index, _) in lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, Line::Same(_)))
+    {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if last.end >= start => last.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod test {
+    use super::unified_diff;
+
+    #[test]
+    fn same_files_have_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), None);
+    }
+
+    #[test]
+    fn changes_are_shown_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n12\n13\n";
+        assert_eq!(
+            unified_diff(old, new, "a/types.ts", "b/types.ts").unwrap(),
+            "--- a/types.ts
++++ b/types.ts
+@@ -2,7 +2,7 @@
+ 2
+ 3
+ 4
+-5
++five
+ 6
+ 7
+ 8
+@@ -10,3 +10,4 @@
+ 10
+ 11
+ 12
++13
+"
+        );
+    }
+
+    #[test]
+    fn new_files_are_shown_as_added() {
+        assert_eq!(
+            unified_diff("", "a\n", "a/types.ts", "b/types.ts").unwrap(),
+            "--- a/types.ts\n+++ b/types.ts\n@@ -0,0 +1,1 @@\n+a\n"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! Writing reference documentation of the typeshared types, as Markdown or HTML.
+use crate::{args::Args, config, ir::write_output, override_configuration, plugin::parse_crates};
+use anyhow::Context;
+use std::path::Path;
+use typeshare_core::docs::DocsFormat;
+
+/// The format to write the documentation in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocsFormatArg {
+    /// A Markdown page.
+    Markdown,
+    /// A standalone HTML page.
+    Html,
+}
+
+/// Write the documentation of the types of every crate of the directories, to the output
+/// file or stdout.
+pub fn write_docs(
+    config_file: Option<&Path>,
+    options: &Args,
+    format: DocsFormatArg,
+) -> anyhow::Result<()> {
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    anyhow::ensure!(
+        options.output.folder.is_none() && !options.reads_stdin(),
+        "The documentation of Rust files is written to a single file or stdout"
+    );
+    let crates = parse_crates(&config, options, true)?;
+    let format = match format {
+        DocsFormatArg::Markdown => DocsFormat::Markdown,
+        DocsFormatArg::Html => DocsFormat::Html,
+    };
+    write_output(options, typeshare_core::docs::generate(&crates, format))
+}
@@ -0,0 +1,94 @@
+//! The classes of failures, which typeshare exits with distinct codes for so that
+//! scripts can tell them apart.
+use std::{fmt, io, process::ExitCode};
+
+/// Any failure without a class of its own, like an invalid configuration.
+const GENERAL_EXIT_CODE: u8 = 1;
+
+/// A class of failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// Rust source couldn't be parsed.
+    Parse,
+    /// Typeshared items use Rust typeshare doesn't support, or warnings were denied.
+    Unsupported,
+    /// Reading or writing a file failed.
+    Io,
+    /// `--check` found generated files which are out of date.
+    OutOfDate,
+    /// `typeshare diff --deny-breaking` found breaking changes.
+    Breaking,
+    /// A generated file which was edited since it was generated wasn't overwritten.
+    Modified,
+}
+
+impl Failure {
+    /// The code typeshare exits with. 2 is used by invalid command line arguments.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Failure::Parse => 3,
+            Failure::Unsupported => 4,
+            Failure::Io => 5,
+            Failure::OutOfDate => 6,
+            Failure::Breaking => 7,
+            Failure::Modified => 8,
+        }
+    }
+
+    /// An error of this class.
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        Failed {
+            failure: self,
+            message: message.into(),
+        }
+        .into()
+    }
+}
+
+/// An error of a class of failures.
+#[derive(Debug)]
+struct Failed {
+    failure: Failure,
+    message: String,
+}
+
+impl fmt::Display for Failed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failed {}
+
+/// The exit code for an error, by the class of its first cause which has one. Causes
+/// which are IO errors are [`Failure::Io`].
+pub fn exit_code(error: &anyhow::Error) -> ExitCode {
+    let failure = error.chain().find_map(|cause| {
+        if let Some(failed) = cause.downcast_ref::<Failed>() {
+            Some(failed.failure)
+        } else {
+            cause.downcast_ref::<io::Error>().map(|_| Failure::Io)
+        }
+    });
+    ExitCode::from(failure.map_or(GENERAL_EXIT_CODE, Failure::exit_code))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{exit_code, Failure};
+    use anyhow::Context;
+    use std::{io, process::ExitCode};
+
+    #[test]
+    fn exit_codes_of_failures() {
+        let error = Err::<(), _>(Failure::OutOfDate.error("2 generated files are out of date"))
+            .context("typeshare failed")
+            .unwrap_err();
+        assert_eq!(exit_code(&error), ExitCode::from(6));
+
+        let error = anyhow::Error::from(io::Error::other("denied")).context("Failed to write");
+        assert_eq!(exit_code(&error), ExitCode::from(5));
+
+        assert_eq!(exit_code(&anyhow::anyhow!("Invalid")), ExitCode::from(1));
+    }
+}
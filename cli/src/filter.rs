@@ -0,0 +1,306 @@
+//! Filtering the generated types by their names, or by the types they are referenced from.
+use anyhow::Context;
+use regex::Regex;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    mem,
+};
+use typeshare_core::{
+    language::CrateName,
+    parser::{ParsedData, SkippedItem},
+    rust_types::{Id, RustEnumVariant, RustType},
+};
+
+/// Patterns for the Rust names of the types to generate.
+#[derive(Debug, Default)]
+pub struct TypeFilter {
+    only: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl TypeFilter {
+    /// A filter generating the types with a name matching any of the `only`
+    /// patterns, if there are any, and none of the `exclude` patterns. Patterns
+    /// have to match the whole name.
+    pub fn new(only: &[String], exclude: &[String]) -> anyhow::Result<Self> {
+        let compile = |pattern: &String| {
+            Regex::new(&format!("^(?:{pattern})$"))
+                .with_context(|| format!("Invalid type name pattern {pattern:?}"))
+        };
+        Ok(Self {
+            only: only.iter().map(compile).collect::<Result<_, _>>()?,
+            exclude: exclude.iter().map(compile).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Whether the type with the given Rust name is generated.
+    pub fn matches(&self, name: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|regex| regex.is_match(name)))
+            && !self.exclude.iter().any(|regex| regex.is_match(name))
+    }
+
+    /// Remove the types which don't match the filter.
+    pub fn apply(&self, crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>) {
+        if self.only.is_empty() && self.exclude.is_empty() {
+            return;
+        }
+        retain_types(
+            crate_parsed_data,
+            "its name is filtered out by --only or --exclude-type",
+            |id| self.matches(&id.original),
+        );
+    }
+}
+
+/// Remove the types, and constants, which aren't referenced from any of the root
+/// types, directly or through other types. Root types are given by their Rust names.
+///
+/// This has to run after the references to renamed types have been reconciled.
+pub fn retain_referenced(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    root_types: &[String],
+) -> anyhow::Result<()> {
+    if root_types.is_empty() {
+        return Ok(());
+    }
+
+    let references = References::of(crate_parsed_data);
+    let roots = root_types
+        .iter()
+        .map(|root| {
+            references
+                .renamed
+                .get(root.as_str())
+                .copied()
+                .with_context(|| format!("Unknown root type {root}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let referenced = references.reachable(roots);
+
+    let reason = "it isn't referenced from a --root-type";
+    retain_types(crate_parsed_data, reason, |id| {
+        referenced.contains(&id.renamed)
+    });
+    for parsed_data in crate_parsed_data.values_mut() {
+        for c in parsed_data.consts.drain(..) {
+            parsed_data.type_names.remove(&c.id.renamed);
+            parsed_data.skipped.push(SkippedItem {
+                name: c.id.original,
+                reason: reason.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Remove the types, and constants, of the path dependencies of the selected packages
+/// which aren't referenced from the types of those packages, directly or through other
+/// types. The types of dependencies are given by their Rust names.
+///
+/// This has to run after the references to renamed types have been reconciled.
+pub fn retain_referenced_dependencies(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    dependency_types: &HashSet<String>,
+) {
+    if dependency_types.is_empty() {
+        return;
+    }
+
+    let references = References::of(crate_parsed_data);
+    let roots = references
+        .renamed
+        .iter()
+        .filter(|(original, _)| !dependency_types.contains(**original))
+        .map(|(_, renamed)| *renamed)
+        .collect::<Vec<_>>();
+    let referenced = references.reachable(roots);
+
+    let reason = "it's in a dependency and isn't referenced from the selected packages";
+    retain_types(crate_parsed_data, reason, |id| {
+        !dependency_types.contains(&id.original) || referenced.contains(&id.renamed)
+    });
+    for parsed_data in crate_parsed_data.values_mut() {
+        let (removed, consts) = mem::take(&mut parsed_data.consts)
+            .into_iter()
+            .partition::<Vec<_>, _>(|c| dependency_types.contains(&c.id.original));
+        parsed_data.consts = consts;
+        for c in removed {
+            parsed_data.type_names.remove(&c.id.renamed);
+            parsed_data.skipped.push(SkippedItem {
+                name: c.id.original,
+                reason: reason.to_owned(),
+            });
+        }
+    }
+}
+
+/// The Rust names of the types, and constants, of the crates.
+pub fn type_names(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) -> HashSet<String> {
+    crate_parsed_data
+        .values()
+        .flat_map(|parsed_data| {
+            let structs = parsed_data.structs.iter().map(|s| &s.id);
+            let enums = parsed_data.enums.iter().map(|e| &e.shared().id);
+            let aliases = parsed_data.aliases.iter().map(|a| &a.id);
+            let consts = parsed_data.consts.iter().map(|c| &c.id);
+            structs.chain(enums).chain(aliases).chain(consts)
+        })
+        .map(|id| id.original.clone())
+        .collect()
+}
+
+/// The types referenced by every type. Types are referenced by their renamed names.
+struct References<'a> {
+    /// The renamed names of the types, by their Rust names.
+    renamed: HashMap<&'a str, &'a str>,
+    references: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> References<'a> {
+    fn of(crate_parsed_data: &'a BTreeMap<CrateName, ParsedData>) -> Self {
+        let mut renamed = HashMap::new();
+        let mut references = HashMap::<&str, Vec<&str>>::new();
+        for parsed_data in crate_parsed_data.values() {
+            for s in &parsed_data.structs {
+                renamed.insert(s.id.original.as_str(), s.id.renamed.as_str());
+                references.entry(&s.id.renamed).or_default().extend(
+                    s.fields
+                        .iter()
+                        .flat_map(|f| f.ty.all_reference_type_names()),
+                );
+            }
+            for e in &parsed_data.enums {
+                let shared = e.shared();
+                renamed.insert(shared.id.original.as_str(), shared.id.renamed.as_str());
+                let variant_types = shared.variants.iter().flat_map(|v| match v {
+                    RustEnumVariant::Unit(_) => Vec::new(),
+                    RustEnumVariant::Tuple { ty, .. } => vec![ty],
+                    RustEnumVariant::AnonymousStruct { fields, .. } => {
+                        fields.iter().map(|f| &f.ty).collect()
+                    }
+                });
+                references
+                    .entry(&shared.id.renamed)
+                    .or_default()
+                    .extend(variant_types.flat_map(RustType::all_reference_type_names));
+            }
+            for a in &parsed_data.aliases {
+                renamed.insert(a.id.original.as_str(), a.id.renamed.as_str());
+                references
+                    .entry(&a.id.renamed)
+                    .or_default()
+                    .extend(a.r#type.all_reference_type_names());
+            }
+        }
+        Self {
+            renamed,
+            references,
+        }
+    }
+
+    /// The renamed names of the roots and the types referenced from them, directly or
+    /// through other types.
+    fn reachable(&self, roots: Vec<&'a str>) -> HashSet<String> {
+        let mut unvisited = roots;
+        let mut reachable = HashSet::new();
+        while let Some(name) = unvisited.pop() {
+            if reachable.insert(name.to_owned()) {
+                unvisited.extend(self.references.get(name).into_iter().flatten());
+            }
+        }
+        reachable
+    }
+}
+
+/// Remove the types for which `keep` returns false, recording them as skipped
+/// for the given reason.
+fn retain_types(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    reason: &str,
+    keep: impl Fn(&Id) -> bool,
+) {
+    for parsed_data in crate_parsed_data.values_mut() {
+        // Type names are the renamed names, used for imports
+        let mut removed = HashSet::new();
+        let mut keep = |id: &Id| {
+            let keep = keep(id);
+            if !keep {
+                removed.insert(id.renamed.clone());
+                parsed_data.skipped.push(SkippedItem {
+                    name: id.original.clone(),
+                    reason: reason.to_owned(),
+                });
+            }
+            keep
+        };
+        parsed_data.structs.retain(|s| keep(&s.id));
+        parsed_data.enums.retain(|e| keep(&e.shared().id));
+        parsed_data.aliases.retain(|a| keep(&a.id));
+        parsed_data
+            .type_names
+            .retain(|name| !removed.contains(name));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{retain_referenced, TypeFilter};
+    use std::collections::BTreeMap;
+    use typeshare_core::{
+        context::{ParseContext, ParseFileContext},
+        language::SINGLE_FILE_CRATE_NAME,
+    };
+
+    #[test]
+    fn matches_whole_names() {
+        let filter = TypeFilter::new(&["Public.*".into()], &[".*Internal".into()]).unwrap();
+        assert!(filter.matches("PublicUser"));
+        assert!(!filter.matches("PublicUserInternal"));
+        assert!(!filter.matches("NotPublicUser"));
+
+        let filter = TypeFilter::new(&[], &["Secret".into()]).unwrap();
+        assert!(filter.matches("User"));
+        assert!(filter.matches("SecretKey"));
+        assert!(!filter.matches("Secret"));
+    }
+
+    #[test]
+    fn retains_referenced_types() {
+        let source = r#"
+            #[typeshare]
+            pub struct Envelope { pub data: Option<Vec<Payload>> }
+
+            #[typeshare]
+            #[serde(tag = "type", content = "content")]
+            pub enum Payload { User(UserId), Empty }
+
+            #[typeshare]
+            pub type UserId = String;
+
+            #[typeshare]
+            pub struct Internal { pub secret: String }
+        "#;
+        let parsed_data = typeshare_core::parser::parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source.into(),
+                crate_name: SINGLE_FILE_CRATE_NAME,
+                file_name: "types.ts".into(),
+                file_path: "src/lib.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let mut crate_parsed_data = BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]);
+
+        retain_referenced(&mut crate_parsed_data, &["Envelope".into()]).unwrap();
+
+        let parsed_data = &crate_parsed_data[&SINGLE_FILE_CRATE_NAME];
+        let structs = parsed_data.structs.iter().map(|s| s.id.original.as_str());
+        assert_eq!(structs.collect::<Vec<_>>(), ["Envelope"]);
+        assert_eq!(parsed_data.enums.len(), 1);
+        assert_eq!(parsed_data.aliases.len(), 1);
+
+        assert!(retain_referenced(&mut crate_parsed_data, &["Internal".into()]).is_err());
+    }
+}
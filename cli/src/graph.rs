@@ -0,0 +1,196 @@
+//! Exporting the dependency graph of the parsed types.
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+use typeshare_core::{
+    language::CrateName,
+    parser::ParsedData,
+    rust_types::{Id, RustEnumVariant, RustType},
+};
+
+/// The format to print the dependency graph in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// A Graphviz DOT digraph.
+    Dot,
+    /// A JSON object with the nodes and edges.
+    Json,
+}
+
+/// The dependency graph of the typeshared types, with an edge for every field
+/// referencing another type.
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Node {
+    /// The Rust name of the type.
+    name: String,
+    /// Whether the type is a struct, enum or type alias.
+    kind: &'static str,
+    /// The crate (or module) the type is generated into, when generating multiple files.
+    #[serde(rename = "crate", skip_serializing_if = "String::is_empty")]
+    crate_name: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Edge {
+    /// The Rust name of the referencing type.
+    from: String,
+    /// The Rust name of the referenced type.
+    to: String,
+    /// The field, or enum variant, with the reference.
+    field: String,
+}
+
+impl Graph {
+    /// Build the graph of the parsed types. References to types which aren't
+    /// typeshared, like standard library types, aren't part of it.
+    pub fn new(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) -> Self {
+        let mut graph = Graph::default();
+        let mut references = Vec::new();
+        for (crate_name, parsed_data) in crate_parsed_data {
+            let mut add_node = |id: &Id, kind, fields: Vec<(String, &RustType)>| {
+                graph.nodes.push(Node {
+                    name: id.original.clone(),
+                    kind,
+                    crate_name: crate_name.to_string(),
+                });
+                for (field, ty) in fields {
+                    references.extend(
+                        ty.all_reference_type_names()
+                            .map(|to| (id.original.clone(), to.to_owned(), field.clone())),
+                    );
+                }
+            };
+
+            for s in &parsed_data.structs {
+                let fields = s.fields.iter().map(|f| (f.id.original.clone(), &f.ty));
+                add_node(&s.id, "struct", fields.collect());
+            }
+            for e in &parsed_data.enums {
+                let fields = e.shared().variants.iter().flat_map(|v| match v {
+                    RustEnumVariant::Unit(_) => Vec::new(),
+                    RustEnumVariant::Tuple { ty, shared } => vec![(shared.id.original.clone(), ty)],
+                    RustEnumVariant::AnonymousStruct { fields, shared } => fields
+                        .iter()
+                        .map(|f| (format!("{}.{}", shared.id.original, f.id.original), &f.ty))
+                        .collect(),
+                });
+                add_node(&e.shared().id, "enum", fields.collect());
+            }
+            for a in &parsed_data.aliases {
+                add_node(&a.id, "alias", vec![(String::new(), &a.r#type)]);
+            }
+        }
+
+        // References use the renamed names of types.
+        let original_names = crate_parsed_data
+            .values()
+            .flat_map(|parsed_data| {
+                let structs = parsed_data.structs.iter().map(|s| &s.id);
+                let enums = parsed_data.enums.iter().map(|e| &e.shared().id);
+                let aliases = parsed_data.aliases.iter().map(|a| &a.id);
+                structs.chain(enums).chain(aliases)
+            })
+            .flat_map(|id| {
+                [
+                    (id.renamed.clone(), id.original.clone()),
+                    (id.original.clone(), id.original.clone()),
+                ]
+            })
+            .collect::<HashMap<_, _>>();
+        graph.edges = references
+            .into_iter()
+            .filter_map(|(from, to, field)| {
+                let to = original_names.get(&to)?.clone();
+                Some(Edge { from, to, field })
+            })
+            .collect();
+        graph
+    }
+
+    /// The graph in the given format.
+    pub fn format(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Json => {
+                serde_json::to_string_pretty(self).expect("the graph is serializable") + "\n"
+            }
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let quote = |name: &str| format!("\"{}\"", name.replace('"', "\\\""));
+        let mut dot = String::from("digraph typeshare {\n");
+        for node in &self.nodes {
+            writeln!(dot, "\t{} [shape=box];", quote(&node.name)).unwrap();
+        }
+        for edge in &self.edges {
+            writeln!(
+                dot,
+                "\t{} -> {} [label={}];",
+                quote(&edge.from),
+                quote(&edge.to),
+                quote(&edge.field)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Graph, GraphFormat};
+    use std::collections::BTreeMap;
+    use typeshare_core::{
+        context::{ParseContext, ParseFileContext},
+        language::SINGLE_FILE_CRATE_NAME,
+    };
+
+    #[test]
+    fn field_references() {
+        let source = r#"
+            #[typeshare]
+            pub struct Envelope { pub data: Option<Vec<Payload>>, pub id: String }
+
+            #[typeshare]
+            #[serde(tag = "type", content = "content")]
+            pub enum Payload { User(UserId), Empty }
+
+            #[typeshare]
+            pub type UserId = String;
+        "#;
+        let parsed_data = typeshare_core::parser::parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source.into(),
+                crate_name: SINGLE_FILE_CRATE_NAME,
+                file_name: "types.ts".into(),
+                file_path: "src/lib.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let graph = Graph::new(&BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]));
+
+        assert_eq!(
+            graph.format(GraphFormat::Dot),
+            r#"digraph typeshare {
+	"Envelope" [shape=box];
+	"Payload" [shape=box];
+	"UserId" [shape=box];
+	"Envelope" -> "Payload" [label="data"];
+	"Payload" -> "UserId" [label="User"];
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Scaffolding for setting up typeshare in a repository.
+use crate::config::DEFAULT_CONFIG_FILE_NAME;
+use anyhow::Context;
+use log::info;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Some options of every language, commented out with example values.
+const LANGUAGE_SECTIONS: &str = r#"
+# [swift]
+# prefix = ""
+# default_decorators = ["Sendable"]
+# coding_keys = "default"
+# formatter = ["swiftformat"]
+
+# [typescript]
+# module_format = "bundler"
+# field_naming = "wire"
+# formatter = ["prettier", "--write"]
+
+# [kotlin]
+# package = "com.example.types"
+# module_name = ""
+# prefix = ""
+# serializer = "kotlinx"
+
+# [scala]
+# package = "com.example.types"
+# module_name = ""
+
+# [reasonml]
+# formatter = ["refmt", "--in-place"]
+
+# [reasonml.type_mappings]
+# "DateTime" = "Js.Date.t"
+"#;
+
+#[cfg(feature = "go")]
+const GO_SECTION: &str = r#"
+# [go]
+# package = "types"
+# option_style = "pointer"
+# formatter = ["gofmt", "-w"]
+"#;
+
+#[cfg(feature = "python")]
+const PYTHON_SECTION: &str = r#"
+# [python]
+# formatter = ["black", "--quiet"]
+"#;
+
+const EXAMPLE_FILE_NAME: &str = "typeshare_example.rs";
+
+const EXAMPLE: &str = r#"//! An example of a typeshared type. Declare this module with `mod typeshare_example;`
+//! and add the `typeshare` and `serde` dependencies to use it in the crate.
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+/// A user of the application.
+#[typeshare]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub name: String,
+    pub email_address: Option<String>,
+}
+"#;
+
+/// Write a starter configuration file, with every language section commented out and
+/// the command for generating the types of the crates in the current directory. With
+/// `example`, an example typeshared type is written to the first crate too.
+pub fn init(config_file: Option<&Path>, example: bool) -> anyhow::Result<()> {
+    let config_file = config_file.unwrap_or(Path::new(DEFAULT_CONFIG_FILE_NAME));
+    let members = workspace_members(Path::new("."))?;
+
+    // Fail if trying to overwrite an existing config file
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(config_file)
+        .with_context(|| format!("Failed to create {config_file:?}"))?;
+    file.write_all(starter_config(&members).as_bytes())?;
+    info!("Wrote {}", config_file.display());
+
+    if example {
+        let folder = members.first().map_or(Path::new("."), PathBuf::as_path);
+        let example_file = folder.join("src").join(EXAMPLE_FILE_NAME);
+        if let Some(parent) = example_file.parent() {
+            fs::create_dir_all(parent).context("failed to create source directory")?;
+        }
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&example_file)
+            .and_then(|mut file| file.write_all(EXAMPLE.as_bytes()))
+            .with_context(|| format!("Failed to create {example_file:?}"))?;
+        info!("Wrote {}", example_file.display());
+    }
+    Ok(())
+}
+
+/// The starter configuration file, which configures nothing until its sections are
+/// uncommented.
+fn starter_config(members: &[PathBuf]) -> String {
+    let directories = if members.is_empty() {
+        ".".to_string()
+    } else {
+        members
+            .iter()
+            .map(|member| member.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut config = format!(
+        "# Configuration for typeshare, see https://1password.github.io/typeshare/usage/configuration.html\n\
+         #\n\
+         # Generate the types of the crates with:\n\
+         # typeshare {directories} --lang=typescript --output-folder=generated\n"
+    );
+    config += LANGUAGE_SECTIONS;
+    #[cfg(feature = "go")]
+    {
+        config += GO_SECTION;
+    }
+    #[cfg(feature = "python")]
+    {
+        config += PYTHON_SECTION;
+    }
+    config
+}
+
+/// The members of the Cargo workspace in the folder, relative to it. Members ending in
+/// `/*` are expanded to the crates in the folder. A package which isn't a workspace is
+/// its only member, and a folder without a manifest has none.
+fn workspace_members(folder: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let Ok(manifest) = fs::read_to_string(folder.join("Cargo.toml")) else {
+        return Ok(Vec::new());
+    };
+    let manifest: toml::Table = toml::from_str(&manifest).context("Failed to parse Cargo.toml")?;
+    let Some(workspace) = manifest.get("workspace") else {
+        return Ok(vec![PathBuf::from(".")]);
+    };
+
+    let mut members = Vec::new();
+    let patterns = workspace.get("members").and_then(toml::Value::as_array);
+    for pattern in patterns
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+    {
+        if let Some(parent) = pattern.strip_suffix("/*") {
+            let mut crates = fs::read_dir(folder.join(parent))
+                .with_context(|| format!("Failed to read workspace members {pattern:?}"))?
+                .filter_map(|entry| Some(entry.ok()?.file_name()))
+                .map(|name| Path::new(parent).join(name))
+                .filter(|member| folder.join(member).join("Cargo.toml").is_file())
+                .collect::<Vec<_>>();
+            crates.sort();
+            members.extend(crates);
+        } else {
+            members.push(PathBuf::from(pattern));
+        }
+    }
+    if manifest.contains_key("package") && !members.iter().any(|member| member == Path::new(".")) {
+        members.insert(0, PathBuf::from("."));
+    }
+    Ok(members)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{starter_config, workspace_members};
+    use crate::config::Config;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn starter_config_parses() {
+        let config = starter_config(&[PathBuf::from("core")]);
+        assert_eq!(
+            toml::from_str::<Config>(&config).unwrap(),
+            Config::default()
+        );
+
+        // Every commented out option is an option of the configuration
+        let uncommented = config
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(option) if option.starts_with('[') || option.contains(" = ") => option,
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(toml::from_str::<Config>(&uncommented).is_ok());
+    }
+
+    #[test]
+    fn finds_workspace_members() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        assert_eq!(
+            workspace_members(&workspace).unwrap(),
+            ["annotation", "cli", "core", "lib", "macros", "wasm"].map(PathBuf::from)
+        );
+    }
+}
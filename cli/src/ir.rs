@@ -0,0 +1,62 @@
+//! Writing the parsed types as JSON, for tools which build on typeshare's parsing.
+use crate::{
+    args::Args,
+    config, override_configuration,
+    plugin::parse_crates,
+    writer::{check_generated, write_files, Mode, WriteOptions},
+};
+use anyhow::Context;
+use std::path::Path;
+use typeshare_core::Ir;
+
+/// Write the parsed types of every crate of the directories as JSON, to the output file
+/// or stdout.
+pub fn write_ir(config_file: Option<&Path>, options: &Args) -> anyhow::Result<()> {
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    anyhow::ensure!(
+        options.output.folder.is_none() && !options.reads_stdin(),
+        "The parsed types of Rust files are written to a single file or stdout"
+    );
+    let crates = parse_crates(&config, options, true)?;
+    let json = Ir::new(&crates)
+        .to_json()
+        .context("Failed to serialize the parsed types")?;
+    write_output(options, json)
+}
+
+/// Write the contents to the output file, or to stdout without one.
+pub(crate) fn write_output(options: &Args, contents: String) -> anyhow::Result<()> {
+    let mode = if options.check {
+        Mode::Check
+    } else if options.dry_run {
+        Mode::DryRun
+    } else {
+        Mode::Write
+    };
+    match options.output.file.as_deref() {
+        Some(file) if file.as_os_str() != "-" => {
+            let files = write_files(
+                Path::new(""),
+                [(file.to_path_buf(), contents.into_bytes())],
+                &WriteOptions {
+                    mode,
+                    file_per_type: false,
+                    formatter: &[],
+                    checksum_comment: None,
+                    force: options.force,
+                },
+            )?;
+            check_generated(&files, mode)
+        }
+        _ => {
+            anyhow::ensure!(
+                mode == Mode::Write,
+                "--check and --dry-run need an output file to compare with"
+            );
+            print!("{contents}");
+            Ok(())
+        }
+    }
+}
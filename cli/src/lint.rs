@@ -0,0 +1,152 @@
+//! Lint levels for the warnings about the typeshared items.
+use crate::config::LintLevel;
+use anyhow::anyhow;
+use log::warn;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use typeshare_core::{
+    language::CrateName,
+    lint::Lint,
+    parser::{ErrorInfo, ParsedData},
+};
+
+/// The level of every lint.
+pub struct Lints {
+    levels: HashMap<Lint, LintLevel>,
+}
+
+impl Lints {
+    /// The lint levels of the configuration, by lint name. With `deny_warnings`, every
+    /// warning is denied, and with `strict` the items generated as fallbacks are.
+    pub fn new(
+        levels: &BTreeMap<String, LintLevel>,
+        deny_warnings: bool,
+        strict: bool,
+    ) -> anyhow::Result<Self> {
+        let mut lints = Lint::ALL
+            .into_iter()
+            .map(|lint| (lint, default_level(lint)))
+            .collect::<HashMap<_, _>>();
+        for (name, level) in levels {
+            let lint = name.parse().map_err(|err: String| anyhow!(err))?;
+            lints.insert(lint, *level);
+        }
+        if strict {
+            lints.insert(Lint::UnsupportedItem, LintLevel::Deny);
+        }
+        if deny_warnings {
+            for level in lints.values_mut() {
+                if *level == LintLevel::Warn {
+                    *level = LintLevel::Deny;
+                }
+            }
+        }
+        Ok(Self { levels: lints })
+    }
+
+    /// Log the warnings about the types of the parsed data, and record the denied
+    /// ones as errors. The warnings about types which aren't generated are dropped.
+    pub fn apply(&self, crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>) {
+        for parsed_data in crate_parsed_data.values_mut() {
+            let structs = parsed_data.structs.iter().map(|s| &s.id);
+            let enums = parsed_data.enums.iter().map(|e| &e.shared().id);
+            let aliases = parsed_data.aliases.iter().map(|a| &a.id);
+            let types = structs
+                .chain(enums)
+                .chain(aliases)
+                .map(|id| id.original.clone())
+                .collect::<HashSet<_>>();
+
+            for info in std::mem::take(&mut parsed_data.warnings) {
+                if !types.contains(&info.warning.item) {
+                    continue;
+                }
+                match (self.levels[&info.warning.lint], &info.warning.location) {
+                    (LintLevel::Allow, _) => {}
+                    (LintLevel::Warn, Some(location)) => warn!("{} at {location}", info.warning),
+                    (LintLevel::Warn, None) => {
+                        warn!("{} in file \"{}\"", info.warning, info.file_name)
+                    }
+                    (LintLevel::Deny, location) => parsed_data.errors.push(ErrorInfo {
+                        file_name: location.as_ref().map_or(info.file_name, |location| {
+                            location.file_path.to_string_lossy().into_owned()
+                        }),
+                        error: info.warning.to_string(),
+                        span: location.as_ref().map(|location| location.span),
+                        unsupported: true,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Skipping fields is usually deliberate, so it's only reported when asked for, while
+/// recursive structs are generated as structs which don't compile.
+fn default_level(lint: Lint) -> LintLevel {
+    match lint {
+        Lint::SkippedField => LintLevel::Allow,
+        Lint::RecursiveStruct => LintLevel::Deny,
+        _ => LintLevel::Warn,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lints;
+    use crate::{args::Args, config::LintLevel, generate_types, report::Report, tempdir::tempdir};
+    use clap::Parser;
+    use std::{collections::BTreeMap, fs};
+    use typeshare_core::lint::Lint;
+
+    #[test]
+    fn lint_levels() {
+        let levels = BTreeMap::from([("skipped_field".to_string(), LintLevel::Warn)]);
+        let lints = Lints::new(&levels, false, true).unwrap();
+        assert_eq!(lints.levels[&Lint::SkippedField], LintLevel::Warn);
+        assert_eq!(lints.levels[&Lint::UnsupportedItem], LintLevel::Deny);
+        assert_eq!(lints.levels[&Lint::LossyMapping], LintLevel::Warn);
+
+        let lints = Lints::new(&BTreeMap::new(), true, false).unwrap();
+        assert_eq!(lints.levels[&Lint::SkippedField], LintLevel::Allow);
+        assert_eq!(lints.levels[&Lint::LossyMapping], LintLevel::Deny);
+
+        let lints = Lints::new(&BTreeMap::new(), false, false).unwrap();
+        assert_eq!(lints.levels[&Lint::UnknownType], LintLevel::Warn);
+        assert_eq!(lints.levels[&Lint::RecursiveStruct], LintLevel::Deny);
+
+        let levels = BTreeMap::from([("lossy".to_string(), LintLevel::Deny)]);
+        assert!(Lints::new(&levels, false, false).is_err());
+    }
+
+    #[test]
+    fn strict_fails_on_items_generated_as_fallbacks() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\n#[serde(tag = \"type\", content = \"content\")]\n\
+             pub enum Shape {\n    Circle(u32),\n}\n\n\
+             #[typeshare]\npub struct Event {\n    pub payload: serde_json::Value,\n    pub initial: char,\n}\n",
+        )
+        .unwrap();
+        let output = root.path().join("output");
+        let generate = |language: &str, strict: bool| {
+            let mut args = vec!["typeshare", src.to_str().unwrap(), "--lang", language];
+            args.extend(["--output-file", output.to_str().unwrap()]);
+            args.extend(strict.then_some("--strict"));
+            #[cfg(feature = "go")]
+            args.extend(["--go-package", "proto"]);
+            let options = Args::parse_from(args);
+            generate_types(None, &options, None, &mut Report::default())
+        };
+
+        let languages = ["reasonml", "kotlin", "typescript"];
+        #[cfg(feature = "go")]
+        let languages = [&languages[..], &["go"]].concat();
+        for language in languages {
+            assert!(generate(language, false).is_ok(), "{language}");
+            assert!(generate(language, true).is_err(), "{language}");
+        }
+    }
+}
@@ -3,62 +3,215 @@
 //!
 
 mod args;
+mod cache;
+mod cargo;
+mod checksum;
 mod config;
+mod conformance;
+mod diagnostic;
+mod diff;
+mod docs;
+mod failure;
+mod filter;
+mod graph;
+mod init;
+mod ir;
+mod lint;
+mod manifest;
+mod overrides;
 mod parse;
+mod plugin;
+mod report;
+mod samples;
+mod schema;
+mod schema_diff;
+mod serve;
+mod sha256;
+mod snapshot;
+#[cfg(test)]
+mod tempdir;
+mod template;
+mod watch;
 mod writer;
 
 use std::{
     collections::{BTreeMap, HashMap},
     io,
-    path::Path,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process::ExitCode,
 };
 
-use anyhow::{anyhow, Context};
+use anyhow::Context;
 use clap::{CommandFactory, Parser};
 use clap_complete::aot::generate;
-use flexi_logger::AdaptiveFormat;
+use flexi_logger::{AdaptiveFormat, Duplicate, Logger};
 use ignore::{overrides::OverrideBuilder, types::TypesBuilder, WalkBuilder};
-use log::{error, info};
-use parse::parallel_parse;
-use typeshare_core::language::GenericConstraints;
-#[cfg(feature = "go")]
-use typeshare_core::language::Go;
+use log::{debug, error, info};
+use parse::{parallel_parse, parallel_parse_without_language, parse_stdin, SharedParse};
 #[cfg(feature = "python")]
 use typeshare_core::language::Python;
+use typeshare_core::language::{CodingKeyStrategy, GenericConstraints};
+#[cfg(feature = "go")]
+use typeshare_core::language::{Go, GoDateTimeFormat, GoOptionStyle};
 use typeshare_core::{
+    collision::name_collisions,
     context::ParseContext,
-    language::{CrateName, Kotlin, Language, ReasonML, Scala, SupportedLanguage, Swift, TypeScript},
-    parser::ParsedData,
-    reconcile::reconcile_aliases,
+    language::{
+        CodeStyle, CrateName, FileHeader, Indent, Kotlin, KotlinSerializer, Language,
+        OptionalFields, ReasonML, Scala, SupportedLanguage, Swift, TypeScript,
+        TypeScriptFieldNaming, TypeScriptModuleFormat,
+    },
+    parser::{ParsedData, WarningInfo},
+    reconcile::{collect_serde_renames, reconcile_aliases_with},
+    validate::reference_warnings,
 };
 
 use crate::{
     args::{Args, Command},
-    config::Config,
+    cache::Cache,
+    config::{Config, OutputDestination, SwiftCodingKeys},
+    failure::Failure,
+    filter::{retain_referenced, retain_referenced_dependencies, type_names, TypeFilter},
+    graph::Graph,
+    lint::Lints,
+    manifest::Manifest,
+    overrides::apply_overrides,
     parse::all_types,
-    writer::{write_generated, Output},
+    report::{Report, ReportLog},
+    writer::{check_generated, write_generated, Mode, Output, WriteOptions},
 };
 
-fn main() -> anyhow::Result<()> {
-    flexi_logger::Logger::try_with_env_or_str("info")?
-        .adaptive_format_for_stderr(AdaptiveFormat::Opt)
-        .adaptive_format_for_stdout(AdaptiveFormat::Opt)
-        .start()?;
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            failure::exit_code(&err)
+        }
+    }
+}
 
-    let options = Args::parse();
+fn run() -> anyhow::Result<()> {
+    let mut options = Args::parse();
 
-    if let Some(options) = options.subcommand {
-        match options {
+    // Without a verbosity flag, the log level can be set with RUST_LOG
+    let logger = match (options.quiet, options.verbose) {
+        (true, _) => Logger::try_with_str("error")?,
+        (false, 0) => Logger::try_with_env_or_str("info")?,
+        // The details of parsing are only logged from -vv
+        (false, 1) => Logger::try_with_str("info, typeshare=debug, typeshare_core=info")?,
+        (false, 2) => Logger::try_with_str("info, typeshare=debug, typeshare_core=debug")?,
+        _ => Logger::try_with_str("trace")?,
+    };
+    let logger = logger
+        .adaptive_format_for_stderr(AdaptiveFormat::Opt)
+        .adaptive_format_for_stdout(AdaptiveFormat::Opt);
+    // The report collects the warnings and errors, which are still logged to stderr.
+    let report_log = ReportLog::default();
+    let _logger = if options.report.is_some() {
+        logger
+            .log_to_writer(Box::new(report_log.clone()))
+            .duplicate_to_stderr(Duplicate::All)
+            .start()?
+    } else {
+        logger.start()?
+    };
+
+    if let Some(command) = options.subcommand.clone() {
+        match command {
             Command::Completions { shell } => {
                 let mut cmd = Args::command();
                 let bin_name = cmd.get_name().to_string();
                 generate(shell, &mut cmd, bin_name, &mut io::stdout());
             }
+            Command::ConfigSchema => {
+                let schema = serde_json::to_string_pretty(&schema::config_schema())
+                    .context("Failed to serialize the schema")?;
+                println!("{schema}");
+            }
+            Command::Init {
+                config_file,
+                example,
+            } => init::init(config_file.as_deref(), example)
+                .inspect_err(|err| error!("typeshare failed to initialize: {err}"))?,
+            Command::Diff {
+                old,
+                new,
+                baseline,
+                deny_breaking,
+            } => {
+                let result = match (new, baseline) {
+                    (Some(new), _) => schema_diff::print_diff(&old, &new),
+                    (None, Some(baseline)) => schema_diff::print_baseline_diff(&baseline, &old),
+                    (None, None) => unreachable!("clap requires the new types or a baseline"),
+                };
+                result
+                    .and_then(|breaking| {
+                        if deny_breaking && breaking > 0 {
+                            return Err(Failure::Breaking
+                                .error(format!("{breaking} of the changes are breaking")));
+                        }
+                        Ok(())
+                    })
+                    .inspect_err(|err| error!("typeshare failed to compare types: {err}"))?
+            }
+            Command::Serve {
+                directories,
+                config_file,
+                profile,
+            } => {
+                // The other options can't be given with a subcommand, so they're the defaults.
+                let mut options = Args {
+                    subcommand: None,
+                    directories,
+                    config_file,
+                    profile,
+                    ..options
+                };
+                if options.directories.is_empty() {
+                    use_workspace(&mut options)?;
+                }
+                serve::serve(options.config_file.as_deref(), &options)
+                    .inspect_err(|err| error!("typeshare failed to serve types: {err}"))?
+            }
+            Command::SnapshotTests {
+                directories,
+                snapshots,
+                language,
+                check,
+                config_file,
+                profile,
+            } => {
+                // The other options can't be given with a subcommand, so they're the defaults.
+                let mut options = Args {
+                    subcommand: None,
+                    directories,
+                    language,
+                    check,
+                    config_file,
+                    profile,
+                    ..options
+                };
+                if options.directories.is_empty() {
+                    use_workspace(&mut options)?;
+                }
+                snapshot::snapshot_tests(options.config_file.as_deref(), &options, &snapshots)
+                    .inspect_err(|err| error!("typeshare failed to snapshot types: {err}"))?
+            }
         }
 
         return Ok(());
     }
 
+    // Without directories, the packages of the Cargo workspace are processed, and
+    // generated into its target directory unless told otherwise.
+    if options.directories.is_empty() && !options.output.generate_config {
+        let target_directory = use_workspace(&mut options)
+            .inspect_err(|err| error!("typeshare failed to find the Cargo workspace: {err}"))?;
+        options.output.workspace_folder = Some(target_directory.join("typeshare"));
+    }
+
     // Note that this can be `None`; the relevant functions handle this case
     // on their own.
     let config_file = options.config_file.as_deref();
@@ -67,92 +220,550 @@ fn main() -> anyhow::Result<()> {
         override_configuration(Config::default(), &options)
             .and_then(|config| config::store_config(&config, config_file))
             .inspect_err(|err| error!("typeshare failed to create new config file: {err}"))
+    } else if options.watch {
+        watch::watch(config_file, &options)
+            .inspect_err(|err| error!("typeshare failed to watch for changes: {err}"))
     } else {
-        generate_types(config_file, &options).inspect_err(|err| {
+        let mut report = Report::default();
+        let result = generate_languages(config_file, &options, &mut report).inspect_err(|err| {
             error!("typeshare failed to generate types: {err}");
-        })
+        });
+        if let Some(format) = options.report {
+            print!("{}", report.finish(result.is_ok(), &report_log, format));
+        }
+        result
     }
 }
 
-fn generate_types(config_file: Option<&Path>, options: &Args) -> anyhow::Result<()> {
+/// Process the selected packages of the Cargo workspace, and the types of their path
+/// dependencies they reference. Returns the directory Cargo builds into.
+fn use_workspace(options: &mut Args) -> anyhow::Result<PathBuf> {
+    let workspace = cargo::workspace(&options.packages, &options.exclude_packages)?;
+    options.directories = workspace.directories;
+    if !workspace.dependencies.is_empty() {
+        let walker_builder = walker_builder(&workspace.dependencies, options, &Config::default())?;
+        let dependencies =
+            parallel_parse_without_language(&ParseContext::default(), false, walker_builder)?;
+        options.dependency_types = type_names(&dependencies);
+        options.directories.extend(workspace.dependencies);
+    }
+    Ok(workspace.target_directory)
+}
+
+/// Generate the types of the language given with `--lang`, or else of every language
+/// with outputs in the configuration file.
+fn generate_languages(
+    config_file: Option<&Path>,
+    options: &Args,
+    report: &mut Report,
+) -> anyhow::Result<()> {
+    if options.ir {
+        return ir::write_ir(config_file, options);
+    }
+    if options.samples {
+        return samples::write_samples(config_file, options);
+    }
+    if let Some(format) = options.docs {
+        return docs::write_docs(config_file, options, format);
+    }
+    if let Some(samples) = &options.conformance {
+        return conformance::generate_conformance(config_file, options, samples);
+    }
+    if let Some(name) = &options.plugin {
+        return plugin::generate_plugin(config_file, options, name);
+    }
+    if let Some(name) = &options.template {
+        return template::generate_template(config_file, options, name);
+    }
+    if options.language.is_some() {
+        return generate_types(config_file, options, None, report);
+    }
+
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let languages = configured_languages(&config);
+    anyhow::ensure!(
+        !languages.is_empty(),
+        "Give a language with --lang, or outputs for a language in the configuration file"
+    );
+    anyhow::ensure!(
+        languages.len() == 1 || (options.manifest.is_none() && config.manifest.is_none()),
+        "A manifest lists the files of a single language; give the language with --lang"
+    );
+    // The input files are parsed once, and every language is generated on its own from
+    // them, so they're generated concurrently, unless they share what they write: an
+    // output on the command line, the cache file, or what's printed.
+    let shared = SharedParse::default();
+    let concurrent = options.output.file.is_none()
+        && options.output.folder.is_none()
+        && config.cache_file.is_none()
+        && options.graph.is_none()
+        && !options.dry_run;
+    if !concurrent {
+        for language in languages {
+            let options = Args {
+                language: Some(language),
+                ..options.clone()
+            };
+            generate_types(config_file, &options, Some(&shared), report)?;
+        }
+        return Ok(());
+    }
+
+    let generated = std::thread::scope(|scope| {
+        let shared = &shared;
+        let handles = languages
+            .into_iter()
+            .map(|language| {
+                scope.spawn(move || {
+                    let options = Args {
+                        language: Some(language),
+                        ..options.clone()
+                    };
+                    let mut report = Report::default();
+                    let result = generate_types(config_file, &options, Some(shared), &mut report);
+                    (result, report)
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect::<Vec<_>>()
+    });
+    // Every language is generated even if another fails, and the first failure, in the
+    // order of the languages, is the result.
+    let mut result = Ok(());
+    for (language_result, language_report) in generated {
+        report.merge(language_report);
+        result = result.and(language_result);
+    }
+    result
+}
+
+/// The languages with outputs in the configuration.
+fn configured_languages(config: &Config) -> Vec<args::AvailableLanguage> {
+    [
+        (args::AvailableLanguage::Kotlin, &config.kotlin.outputs),
+        (args::AvailableLanguage::ReasonML, &config.reasonml.outputs),
+        (args::AvailableLanguage::Scala, &config.scala.outputs),
+        (args::AvailableLanguage::Swift, &config.swift.outputs),
+        (
+            args::AvailableLanguage::Typescript,
+            &config.typescript.outputs,
+        ),
+        #[cfg(feature = "go")]
+        (args::AvailableLanguage::Go, &config.go.outputs),
+        #[cfg(feature = "python")]
+        (args::AvailableLanguage::Python, &config.python.outputs),
+    ]
+    .into_iter()
+    .filter(|(_, outputs)| !outputs.is_empty())
+    .map(|(language, _)| language)
+    .collect()
+}
+
+/// The language of the core of a language of the command line.
+fn supported_language(language: args::AvailableLanguage) -> SupportedLanguage {
+    match language {
+        args::AvailableLanguage::Kotlin => SupportedLanguage::Kotlin,
+        args::AvailableLanguage::ReasonML => SupportedLanguage::ReasonML,
+        args::AvailableLanguage::Scala => SupportedLanguage::Scala,
+        args::AvailableLanguage::Swift => SupportedLanguage::Swift,
+        args::AvailableLanguage::Typescript => SupportedLanguage::TypeScript,
+        #[cfg(feature = "go")]
+        args::AvailableLanguage::Go => SupportedLanguage::Go,
+        #[cfg(feature = "python")]
+        args::AvailableLanguage::Python => SupportedLanguage::Python,
+    }
+}
+
+/// Generate the types of the language of the options, from the input files parsed for
+/// the other languages too, if they're `shared`.
+fn generate_types(
+    config_file: Option<&Path>,
+    options: &Args,
+    shared: Option<&SharedParse>,
+    report: &mut Report,
+) -> anyhow::Result<()> {
     info!("typeshare started generating types");
 
-    let config = config::load_config(config_file).context("Unable to read configuration file")?;
-    let config = override_configuration(config, options)?;
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let mut config = override_configuration(config, options)?;
 
     let directories = options.directories.as_slice();
+    // The options of the configuration files of crates apply to the files generated
+    // from those crates.
+    let mut crate_configs =
+        config::load_crate_configs(config_file, options.profile.as_deref(), directories)
+            .context("Unable to read the configuration file of a crate")?
+            .into_iter()
+            .map(|(crate_name, crate_config)| {
+                Ok((crate_name, override_configuration(crate_config, options)?))
+            })
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+    if std::iter::once(&config)
+        .chain(crate_configs.values())
+        .any(needs_revision)
+    {
+        let revision = git_revision()?;
+        for config in std::iter::once(&mut config).chain(crate_configs.values_mut()) {
+            config.revision = Some(revision.clone());
+        }
+    }
 
     info!("Using directories: {directories:?}");
 
     let language_type = match options.language {
-        None => panic!("no language specified; `clap` should have guaranteed its presence"),
-        Some(language) => match language {
-            args::AvailableLanguage::Kotlin => SupportedLanguage::Kotlin,
-            args::AvailableLanguage::ReasonML => SupportedLanguage::ReasonML,
-            args::AvailableLanguage::Scala => SupportedLanguage::Scala,
-            args::AvailableLanguage::Swift => SupportedLanguage::Swift,
-            args::AvailableLanguage::Typescript => SupportedLanguage::TypeScript,
-            #[cfg(feature = "go")]
-            args::AvailableLanguage::Go => SupportedLanguage::Go,
-            #[cfg(feature = "python")]
-            args::AvailableLanguage::Python => SupportedLanguage::Python,
-        },
+        None => panic!("no language specified; `generate_languages` should have set it"),
+        Some(language) => supported_language(language),
     };
+    report.add_language(language_type);
 
-    let destination = if let Some(ref file) = options.output.file {
-        Output::File(file)
-    } else if let Some(ref folder) = options.output.folder {
-        Output::Folder(folder)
+    let configured_outputs = match language_type {
+        SupportedLanguage::Kotlin => config.kotlin.outputs.as_slice(),
+        SupportedLanguage::ReasonML => config.reasonml.outputs.as_slice(),
+        SupportedLanguage::Scala => config.scala.outputs.as_slice(),
+        SupportedLanguage::Swift => config.swift.outputs.as_slice(),
+        SupportedLanguage::TypeScript => config.typescript.outputs.as_slice(),
+        #[cfg(feature = "go")]
+        SupportedLanguage::Go => config.go.outputs.as_slice(),
+        #[cfg(feature = "python")]
+        SupportedLanguage::Python => config.python.outputs.as_slice(),
+        #[cfg(not(all(feature = "go", feature = "python")))]
+        _ => &[],
+    };
+    let destinations = destinations(&options.output, configured_outputs);
+    let outputs = if destinations.is_empty() {
+        vec![Output::Stdout]
     } else {
-        panic!(
-            "Got neither a file nor a folder to output to; this indicates a
-            bug in typeshare, since `clap` is supposed to prevent this"
-        )
+        destinations
+            .iter()
+            .map(|destination| match destination {
+                OutputDestination::File(file) if file.as_os_str() == "-" => Output::Stdout,
+                OutputDestination::File(file) => Output::File(file),
+                OutputDestination::Folder(folder) => Output::Folder(folder),
+            })
+            .collect()
     };
 
-    let multi_file = matches!(destination, Output::Folder(_));
-    let target_os = config.target_os.clone();
-    let mut lang = language(language_type, config, multi_file);
+    anyhow::ensure!(
+        !options.reads_stdin()
+            || (directories.len() == 1
+                && outputs.len() == 1
+                && !matches!(outputs[0], Output::Folder(_))),
+        "Rust source can only be read from stdin on its own, and generated into a single file"
+    );
+    for destination in &outputs {
+        anyhow::ensure!(
+            !(options.check || options.dry_run) || !matches!(destination, Output::Stdout),
+            "--check and --dry-run need an output file or folder to compare the generated types with"
+        );
+        anyhow::ensure!(
+            options.report.is_none() || !matches!(destination, Output::Stdout),
+            "--report needs an output file or folder, as it's printed to stdout"
+        );
+        anyhow::ensure!(
+            config.manifest.is_none() || !matches!(destination, Output::Stdout),
+            "--manifest needs an output file or folder to list"
+        );
+        anyhow::ensure!(
+            !config.file_per_type || matches!(destination, Output::Folder(_)),
+            "A file per type can only be generated into an output folder"
+        );
+    }
 
-    let parse_context = ParseContext {
-        ignored_types: lang.ignored_reference_types(),
-        multi_file,
-        target_os,
+    let per_module = config.per_module
+        || match language_type {
+            SupportedLanguage::Kotlin => config.kotlin.package_per_module,
+            #[cfg(feature = "go")]
+            SupportedLanguage::Go => config.go.package_per_module,
+            _ => false,
+        };
+    anyhow::ensure!(
+        !config.file_per_type
+            || matches!(
+                language_type,
+                SupportedLanguage::Swift | SupportedLanguage::Kotlin
+            ),
+        "A file per type can only be generated for Swift and Kotlin"
+    );
+    let type_filter = TypeFilter::new(&config.only_types, &config.exclude_types)?;
+    // The manifest of a Swift package lists the targets of every file, which are generated
+    // with the same configuration.
+    let swift_package =
+        language_type == SupportedLanguage::Swift && config.swift.package_name.is_some();
+    anyhow::ensure!(
+        !swift_package || crate_configs.is_empty(),
+        "A Swift package can't be generated while crates have configuration files of their own"
+    );
+    let cache_file = match cache_bypass(language_type, &config, options, &outputs, &crate_configs) {
+        Some(reason) if config.cache_file.is_some() => {
+            info!("typeshare isn't using the cache file {reason}");
+            None
+        }
+        _ => config.cache_file.clone(),
+    };
+    let root_types = config.root_types.clone();
+    let mut cache = cache_file.as_deref().map(|cache_file| {
+        let mut cache = Cache::load(
+            cache_file,
+            config_file,
+            options.profile.as_deref(),
+            language_type,
+        );
+        // Generate everything again if the output was removed.
+        if let Output::File(path) | Output::Folder(path) = outputs[0] {
+            if !path.exists() {
+                cache.clear();
+            }
+        }
+        cache
+    });
+    let lints = Lints::new(&config.lints, options.deny_warnings, options.strict)?;
+    let formatter = match language_type {
+        SupportedLanguage::Kotlin => config.kotlin.formatter.clone(),
+        SupportedLanguage::ReasonML => config.reasonml.formatter.clone(),
+        SupportedLanguage::Scala => config.scala.formatter.clone(),
+        SupportedLanguage::Swift => config.swift.formatter.clone(),
+        SupportedLanguage::TypeScript => config.typescript.formatter.clone(),
+        #[cfg(feature = "go")]
+        SupportedLanguage::Go => config.go.formatter.clone(),
+        #[cfg(feature = "python")]
+        SupportedLanguage::Python => config.python.formatter.clone(),
+        #[cfg(not(all(feature = "go", feature = "python")))]
+        _ => Vec::new(),
+    };
+    let manifest_file = config.manifest.clone();
+    // Through a JSON value, whose keys are sorted, so the hash doesn't depend on the order of maps
+    let config_hash_input = serde_json::to_value(&config)
+        .context("Failed to serialize the configuration")?
+        .to_string();
+    let mode = if options.check {
+        Mode::Check
+    } else if options.dry_run {
+        Mode::DryRun
+    } else {
+        Mode::Write
+    };
+    let write_options = WriteOptions {
+        mode,
+        file_per_type: config.file_per_type,
+        formatter: &formatter,
+        checksum_comment: config
+            .checksum_markers
+            .then(|| checksum::line_comment(language_type)),
+        force: options.force,
     };
 
-    let mut parsed_data = parallel_parse(
-        &parse_context,
-        walker_builder(directories, options)?,
-        language_type,
-    )?;
+    // Whether the output is a folder changes how types are parsed and generated, so
+    // every output is generated on its own.
+    let mut files = Vec::new();
+    for (index, &destination) in outputs.iter().enumerate() {
+        let multi_file = matches!(destination, Output::Folder(_));
+        let walker_builder = if options.reads_stdin() {
+            None
+        } else {
+            Some(walker_builder(directories, options, &config)?)
+        };
+        let mut lang = language(language_type, config.clone(), multi_file);
 
-    reconcile_aliases(&mut parsed_data);
+        let parse_context = ParseContext {
+            ignored_types: lang.ignored_reference_types(),
+            multi_file,
+            target_os: config.target_os.clone(),
+        };
 
-    // Collect all the types into a map of the file name they
-    // belong too and the list of type names. Used for generating
-    // imports in generated files.
-    let import_candidates = if multi_file {
-        all_types(&mut parsed_data)
-    } else {
-        HashMap::new()
-    };
+        let mut unchanged = None;
+        let mut parsed_data = match (walker_builder, cache.as_mut()) {
+            (None, _) => parse_stdin(&parse_context, language_type, lang.as_ref())?,
+            (Some(walker_builder), None) => match shared {
+                Some(shared) => shared.parse(
+                    &parse_context,
+                    per_module,
+                    walker_builder,
+                    language_type,
+                    lang.as_ref(),
+                )?,
+                None => parallel_parse(
+                    &parse_context,
+                    per_module,
+                    walker_builder,
+                    language_type,
+                    lang.as_ref(),
+                )?,
+            },
+            (Some(walker_builder), Some(cache)) => {
+                let Some(changes) = cache.parse_changes(
+                    &parse_context,
+                    per_module,
+                    &walker_builder,
+                    language_type,
+                    lang.as_ref(),
+                )?
+                else {
+                    info!("typeshare found no input files changed since the last run");
+                    return Ok(());
+                };
+                unchanged = Some((changes.unchanged_types, changes.unchanged_renamed));
+                changes.parsed_data
+            }
+        };
+        let (unchanged_types, unchanged_renamed) = unchanged.unwrap_or_default();
 
-    check_parse_errors(&parsed_data)?;
+        apply_overrides(&config.types, &mut parsed_data, Some(language_type));
+        let mut serde_renamed = collect_serde_renames(&parsed_data);
+        for (original, renamed) in unchanged_renamed {
+            serde_renamed.entry(original).or_default().extend(renamed);
+        }
+        reconcile_aliases_with(&mut parsed_data, &serde_renamed);
+        if config.sort_types {
+            parsed_data.values_mut().for_each(ParsedData::sort_by_name);
+        }
+        retain_referenced(&mut parsed_data, &root_types)?;
+        retain_referenced_dependencies(&mut parsed_data, &options.dependency_types);
+        type_filter.apply(&mut parsed_data);
+        let reference_warnings = reference_warnings(
+            lang.as_mut(),
+            language_type,
+            &parsed_data,
+            unchanged_types.values().flatten().map(String::as_str),
+        );
+        for (crate_name, warnings) in reference_warnings {
+            if let Some(data) = parsed_data.get_mut(&crate_name) {
+                let file_name = data.file_name.clone();
+                data.warnings
+                    .extend(warnings.into_iter().map(|warning| WarningInfo {
+                        file_name: warning.location.as_ref().map_or_else(
+                            || file_name.clone(),
+                            |location| location.file_path.to_string_lossy().into_owned(),
+                        ),
+                        warning,
+                    }));
+            }
+        }
+        lints.apply(&mut parsed_data);
 
-    info!(
-        "typeshare started writing {} generated types",
-        parsed_data.len()
-    );
+        if let Some(format) = options.graph {
+            check_parse_errors(&parsed_data)?;
+            print!("{}", Graph::new(&parsed_data).format(format));
+            return Ok(());
+        }
+
+        // The files of a Swift package are in the folders of their targets.
+        if multi_file && swift_package {
+            for (crate_name, data) in &mut parsed_data {
+                let target = Swift::package_target(crate_name);
+                data.file_name = format!("Sources/{target}/{}", data.file_name);
+            }
+        }
+
+        // Collect all the types into a map of the file name they
+        // belong too and the list of type names. Used for generating
+        // imports in generated files.
+        let import_candidates = if multi_file {
+            let mut import_candidates = all_types(&mut parsed_data);
+            import_candidates.extend(unchanged_types);
+            import_candidates
+        } else {
+            HashMap::new()
+        };
+
+        check_parse_errors(&parsed_data)?;
+        check_name_collisions(lang.as_ref(), &parsed_data)?;
+
+        info!(
+            "typeshare started writing {} generated types",
+            parsed_data.len()
+        );
+
+        // Every output has the same types
+        if index == 0 {
+            if mode == Mode::DryRun {
+                print_type_summary(language_type, &parsed_data);
+            }
+            for skipped in parsed_data.values().flat_map(|data| &data.skipped) {
+                debug!("Skipped {} because {}", skipped.name, skipped.reason);
+            }
+            report.add_skipped(&parsed_data);
+        }
+        let mut generated = Vec::new();
+        if multi_file {
+            for (crate_name, crate_config) in &crate_configs {
+                let (crate_parsed_data, rest) = parsed_data
+                    .into_iter()
+                    .partition::<BTreeMap<_, _>, _>(|(name, _)| name.crate_name() == crate_name);
+                parsed_data = rest;
+                if crate_parsed_data.is_empty() {
+                    continue;
+                }
+                let mut crate_lang = language(language_type, crate_config.clone(), multi_file);
+                generated.extend(write_generated(
+                    destination,
+                    crate_lang.as_mut(),
+                    crate_parsed_data,
+                    import_candidates.clone(),
+                    &write_options,
+                )?);
+            }
+        }
+        generated.extend(write_generated(
+            destination,
+            lang.as_mut(),
+            parsed_data,
+            import_candidates,
+            &write_options,
+        )?);
+        report.add_files(language_type, &generated);
+        files.extend(generated);
+    }
+    check_generated(&files, mode)?;
+
+    if let (Some(manifest_file), Mode::Write) = (manifest_file, mode) {
+        let mut manifest = Manifest::new(language_type, &config_hash_input, &files);
+        if cache.is_some() {
+            manifest.keep_previous(&manifest_file);
+        }
+        manifest.store(&manifest_file)?;
+    }
 
-    write_generated(destination, lang.as_mut(), parsed_data, import_candidates)?;
+    if let (Some(cache), Some(cache_file), Mode::Write) = (cache, cache_file, mode) {
+        cache.store(&cache_file)?;
+    }
 
     info!("typeshare finished generating types");
     Ok(())
 }
 
+/// The files and folders to generate into, by precedence: the output file or folder on
+/// the command line, the outputs configured for the language, or the Cargo workspace's
+/// folder. Without any, the types are written to stdout.
+fn destinations(output: &args::Output, configured: &[OutputDestination]) -> Vec<OutputDestination> {
+    if let Some(file) = &output.file {
+        vec![OutputDestination::File(file.clone())]
+    } else if let Some(folder) = &output.folder {
+        vec![OutputDestination::Folder(folder.clone())]
+    } else if !configured.is_empty() {
+        configured.to_vec()
+    } else {
+        output
+            .workspace_folder
+            .iter()
+            .cloned()
+            .map(OutputDestination::Folder)
+            .collect()
+    }
+}
+
 fn walker_builder(
     directories: &[std::path::PathBuf],
     options: &Args,
+    config: &Config,
 ) -> anyhow::Result<WalkBuilder> {
     let mut types = TypesBuilder::new();
     types
@@ -163,12 +774,22 @@ fn walker_builder(
     let first_root = directories
         .first()
         .expect("directories is empty; this shouldn't be possible");
-    let overrides = OverrideBuilder::new(first_root)
-        // Don't process files inside of tools/typeshare/
+    let mut overrides = OverrideBuilder::new(first_root);
+    // Don't process files inside of tools/typeshare/
+    overrides
         .add("!**/tools/typeshare/**")
-        .context("Failed to parse override")?
-        .build()
-        .context("Failed to build override")?;
+        .context("Failed to parse override")?;
+    for pattern in &config.include {
+        overrides
+            .add(pattern)
+            .with_context(|| format!("Failed to parse include pattern {pattern:?}"))?;
+    }
+    for pattern in &config.exclude {
+        overrides
+            .add(&format!("!{pattern}"))
+            .with_context(|| format!("Failed to parse exclude pattern {pattern:?}"))?;
+    }
+    let overrides = overrides.build().context("Failed to build override")?;
     let mut walker_builder = WalkBuilder::new(first_root);
     walker_builder
         .sort_by_file_path(Path::cmp)
@@ -181,12 +802,71 @@ fn walker_builder(
     Ok(walker_builder)
 }
 
+/// Whether the header of the configuration has the git revision of the sources.
+fn needs_revision(config: &Config) -> bool {
+    config.header_revision
+        || config
+            .header
+            .as_ref()
+            .is_some_and(|header| header.contains("{revision}"))
+}
+
+/// The git revision of the current directory, for the headers of generated files.
+fn git_revision() -> anyhow::Result<String> {
+    let revision = schema_diff::git(&["rev-parse", "--short", "HEAD"], Path::new(""))
+        .context("The header has the git revision of the sources, which needs a git repository")?;
+    Ok(String::from_utf8_lossy(&revision).trim().to_owned())
+}
+
+/// The code style of a language, from the options of its table.
+fn code_style(
+    indent_style: Option<config::IndentStyle>,
+    indent_size: Option<NonZeroUsize>,
+    max_line_width: Option<NonZeroUsize>,
+) -> CodeStyle {
+    let indent = match (indent_style, indent_size) {
+        (Some(config::IndentStyle::Tab), _) => Some(Indent::Tabs),
+        (Some(config::IndentStyle::Space), None) => Some(Indent::Spaces(4)),
+        (_, Some(size)) => Some(Indent::Spaces(size.get())),
+        (None, None) => None,
+    };
+    CodeStyle {
+        indent,
+        max_line_width: max_line_width.map(NonZeroUsize::get),
+    }
+}
+
 /// Get the language trait impl for the given supported language and configuration.
 fn language(
     language_type: SupportedLanguage,
     config: Config,
     multi_file: bool,
 ) -> Box<dyn Language> {
+    let no_version_header =
+        language_type == SupportedLanguage::ReasonML && config.reasonml.no_version_header;
+    let template = match config.header {
+        Some(template) => Some(template),
+        None if (config.no_header_version || config.header_revision) && !no_version_header => {
+            Some(FileHeader::default_template(
+                language_type,
+                !config.no_header_version,
+                config.header_revision,
+            ))
+        }
+        None => None,
+    };
+    let header = template.map(|template| {
+        let header = FileHeader::new(template);
+        match config.revision {
+            Some(revision) => header.with_revision(revision),
+            None => header,
+        }
+    });
+    let optional_fields = config.optional_fields.map(|policy| match policy {
+        config::OptionalFields::Absent => OptionalFields::Absent,
+        config::OptionalFields::Null => OptionalFields::Null,
+        config::OptionalFields::Both => OptionalFields::Both,
+    });
     match language_type {
         SupportedLanguage::Swift => Box::new(Swift {
             prefix: config.swift.prefix,
@@ -197,6 +877,25 @@ fn language(
             ),
             multi_file,
             codablevoid_constraints: config.swift.codablevoid_constraints,
+            init_optional_defaults: config.swift.init_optional_defaults,
+            serde_default_values: config.swift.serde_default_values,
+            package_name: config.swift.package_name,
+            coding_keys: match config.swift.coding_keys {
+                SwiftCodingKeys::Default => CodingKeyStrategy::Default,
+                SwiftCodingKeys::ConvertFromSnakeCase => CodingKeyStrategy::ConvertFromSnakeCase,
+                SwiftCodingKeys::Explicit => CodingKeyStrategy::Explicit,
+            },
+            unknown_case: config.swift.unknown_case,
+            mocks: config.swift.mocks,
+            arbitraries: config.swift.arbitraries,
+            code_style: code_style(
+                config.swift.indent_style,
+                config.swift.indent_size,
+                config.swift.max_line_width,
+            ),
+            reserved_words: config.swift.reserved_words.into_iter().collect(),
+            header,
+            schema_hashes: config.schema_hashes,
             ..Default::default()
         }),
         SupportedLanguage::Kotlin => Box::new(Kotlin {
@@ -204,20 +903,86 @@ fn language(
             module_name: config.kotlin.module_name,
             prefix: config.kotlin.prefix,
             type_mappings: config.kotlin.type_mappings,
+            serializer: match config.kotlin.serializer {
+                config::KotlinSerializer::Kotlinx => KotlinSerializer::Kotlinx,
+                config::KotlinSerializer::Moshi => KotlinSerializer::Moshi,
+            },
+            sealed_interfaces: config.kotlin.sealed_interfaces,
+            serde_default_values: config.kotlin.serde_default_values,
+            parcelize: config.kotlin.parcelize,
+            newtype_value_classes: config.kotlin.newtype_value_classes,
+            multiplatform: config.kotlin.multiplatform,
+            arbitraries: config.kotlin.arbitraries,
+            code_style: code_style(
+                config.kotlin.indent_style,
+                config.kotlin.indent_size,
+                config.kotlin.max_line_width,
+            ),
+            reserved_words: config.kotlin.reserved_words.into_iter().collect(),
+            header,
+            schema_hashes: config.schema_hashes,
+            optional_fields,
             ..Default::default()
         }),
         SupportedLanguage::ReasonML => Box::new(ReasonML {
-            type_mappings: config.typescript.type_mappings.clone(), // Start with TS mappings as a base
-            ..Default::default()
+            // Start with TS mappings as a base
+            type_mappings: config
+                .typescript
+                .type_mappings
+                .into_iter()
+                .chain(config.reasonml.type_mappings)
+                .collect(),
+            no_version_header: config.reasonml.no_version_header,
+            code_style: code_style(
+                config.reasonml.indent_style,
+                config.reasonml.indent_size,
+                config.reasonml.max_line_width,
+            ),
+            reserved_words: config.reasonml.reserved_words.into_iter().collect(),
+            header,
+            schema_hashes: config.schema_hashes,
+            codecs: config.reasonml.codecs,
         }),
         SupportedLanguage::Scala => Box::new(Scala {
             package: config.scala.package,
             module_name: config.scala.module_name,
             type_mappings: config.scala.type_mappings,
+            circe: config.scala.circe,
+            scala3: config.scala.scala3,
+            opaque_newtypes: config.scala.opaque_newtypes,
+            scala_js: config.scala.scala_js,
+            code_style: code_style(
+                config.scala.indent_style,
+                config.scala.indent_size,
+                config.scala.max_line_width,
+            ),
+            reserved_words: config.scala.reserved_words.into_iter().collect(),
+            header,
+            schema_hashes: config.schema_hashes,
+            optional_fields,
             ..Default::default()
         }),
         SupportedLanguage::TypeScript => Box::new(TypeScript {
             type_mappings: config.typescript.type_mappings,
+            module_format: match config.typescript.module_format {
+                config::TypeScriptModuleFormat::Bundler => TypeScriptModuleFormat::Bundler,
+                config::TypeScriptModuleFormat::Esm => TypeScriptModuleFormat::Esm,
+                config::TypeScriptModuleFormat::CommonJs => TypeScriptModuleFormat::CommonJs,
+            },
+            import_extension: config.typescript.import_extension,
+            field_naming: match config.typescript.field_naming {
+                config::TypeScriptFieldNaming::Wire => TypeScriptFieldNaming::Wire,
+                config::TypeScriptFieldNaming::CamelCase => TypeScriptFieldNaming::CamelCase,
+            },
+            arbitraries: config.typescript.arbitraries,
+            code_style: code_style(
+                config.typescript.indent_style,
+                config.typescript.indent_size,
+                config.typescript.max_line_width,
+            ),
+            header,
+            schema_hashes: config.schema_hashes,
+            optional_fields,
             ..Default::default()
         }),
         #[cfg(feature = "go")]
@@ -226,6 +991,30 @@ fn language(
             type_mappings: config.go.type_mappings,
             uppercase_acronyms: config.go.uppercase_acronyms,
             no_pointer_slice: config.go.no_pointer_slice,
+            no_omitempty: config.go.no_omitempty,
+            option_style: match config.go.option_style {
+                config::GoOptionStyle::Pointer => GoOptionStyle::Pointer,
+                config::GoOptionStyle::Generic => GoOptionStyle::Generic,
+                config::GoOptionStyle::Null => GoOptionStyle::Null,
+            },
+            datetime_format: match config.go.datetime_format {
+                config::GoDateTimeFormat::Rfc3339 => GoDateTimeFormat::Rfc3339,
+                config::GoDateTimeFormat::Unix => GoDateTimeFormat::Unix,
+                config::GoDateTimeFormat::UnixMillis => GoDateTimeFormat::UnixMillis,
+                config::GoDateTimeFormat::Layout(layout) => GoDateTimeFormat::Layout(layout),
+            },
+            import_path: config.go.import_path,
+            package_names: config.go.package_names,
+            json_value_any: config.go.json_value_any,
+            enum_methods: config.go.enum_methods,
+            code_style: code_style(
+                config.go.indent_style,
+                config.go.indent_size,
+                config.go.max_line_width,
+            ),
+            header,
+            schema_hashes: config.schema_hashes,
+            optional_fields,
             ..Default::default()
         }),
         #[cfg(not(feature = "go"))]
@@ -235,6 +1024,15 @@ fn language(
         #[cfg(feature = "python")]
         SupportedLanguage::Python => Box::new(Python {
             type_mappings: config.python.type_mappings,
+            code_style: code_style(
+                config.python.indent_style,
+                config.python.indent_size,
+                config.python.max_line_width,
+            ),
+            reserved_words: config.python.reserved_words.into_iter().collect(),
+            header,
+            schema_hashes: config.schema_hashes,
+            optional_fields,
             ..Default::default()
         }),
         #[cfg(not(feature = "python"))]
@@ -244,16 +1042,110 @@ fn language(
     }
 }
 
+/// Why the cache file can't be used for a run, if it can't. Checking compares every
+/// generated file, and stdout always needs all the types. The types referenced from
+/// root types, or from the crates of `--package` by their path dependencies, can be in
+/// any crate, so all are needed, like for the dependency graph. The cache only records
+/// the input files of the last run, so it can't tell which of several outputs are out of
+/// date, and it records the types before they're overridden, by `types` or the
+/// configurations of crates. It doesn't record the revision of headers either, and the
+/// manifest of a Swift package lists the targets of every file.
+fn cache_bypass(
+    language_type: SupportedLanguage,
+    config: &Config,
+    options: &Args,
+    outputs: &[Output],
+    crate_configs: &BTreeMap<String, Config>,
+) -> Option<&'static str> {
+    if options.check {
+        Some("with --check")
+    } else if outputs.len() > 1 {
+        Some("with more than one output")
+    } else if matches!(outputs[0], Output::Stdout) {
+        Some("when writing to stdout")
+    } else if !config.root_types.is_empty() {
+        Some("with root types")
+    } else if !options.dependency_types.is_empty() {
+        Some("with the path dependencies of --package")
+    } else if !config.types.is_empty() {
+        Some("while types are overridden with `types`")
+    } else if !crate_configs.is_empty() {
+        Some("while crates have configuration files of their own")
+    } else if config.revision.is_some() {
+        Some("with a revision in the header")
+    } else if options.graph.is_some() {
+        Some("with --graph")
+    } else if matches!(outputs[0], Output::Folder(_))
+        && language_type == SupportedLanguage::Swift
+        && config.swift.package_name.is_some()
+    {
+        Some("for a Swift package, whose manifest lists the targets of every file")
+    } else {
+        None
+    }
+}
+
 /// Overrides any configuration values with provided arguments
 fn override_configuration(mut config: Config, options: &Args) -> anyhow::Result<Config> {
     if let Some(swift_prefix) = options.swift_prefix.as_ref() {
         config.swift.prefix = swift_prefix.clone();
     }
 
+    if options.per_module {
+        config.per_module = true;
+    }
+
+    if options.file_per_type {
+        config.file_per_type = true;
+    }
+
+    if options.sort_types {
+        config.sort_types = true;
+    }
+
+    if let Some(only_types) = options.only_types.as_ref() {
+        config.only_types = only_types.clone();
+    }
+
+    if let Some(exclude_types) = options.exclude_types.as_ref() {
+        config.exclude_types = exclude_types.clone();
+    }
+
+    if let Some(root_types) = options.root_types.as_ref() {
+        config.root_types = root_types.clone();
+    }
+
+    if let Some(manifest) = options.manifest.as_ref() {
+        config.manifest = Some(manifest.clone());
+    }
+
+    if options.checksum_markers {
+        config.checksum_markers = true;
+    }
+
+    if let Some(cache_file) = options.cache_file.as_ref() {
+        config.cache_file = Some(cache_file.clone());
+    }
+
+    if let Some(include) = options.include.as_ref() {
+        config.include = include.clone();
+    }
+
+    if let Some(exclude) = options.exclude.as_ref() {
+        config.exclude = exclude.clone();
+    }
+
     if let Some(kotlin_prefix) = options.kotlin_prefix.as_ref() {
         config.kotlin.prefix = kotlin_prefix.clone();
     }
 
+    anyhow::ensure!(
+        !config.kotlin.multiplatform
+            || (config.kotlin.serializer == config::KotlinSerializer::Kotlinx
+                && !config.kotlin.parcelize),
+        "Kotlin multiplatform output can only be combined with the kotlinx serializer and without parcelize"
+    );
+
     if let Some(java_package) = options.java_package.as_ref() {
         config.kotlin.package = java_package.clone();
     }
@@ -270,13 +1162,29 @@ fn override_configuration(mut config: Config, options: &Args) -> anyhow::Result<
         config.scala.module_name = scala_module_name.to_string();
     }
 
+    anyhow::ensure!(
+        !(config.scala.scala_js && config.scala.circe),
+        "Scala.js facades can't be combined with circe codecs"
+    );
+
     #[cfg(feature = "go")]
     {
         if let Some(go_package) = options.go_package.as_ref() {
             config.go.package = go_package.to_string();
         }
 
-        if matches!(options.language, Some(args::AvailableLanguage::Go)) {
+        // In multi-file mode, packages are named after their directories.
+        let single_file = match (&options.output.file, &options.output.folder) {
+            (_, Some(_)) => false,
+            (Some(_), None) => true,
+            (None, None) if !config.go.outputs.is_empty() => config
+                .go
+                .outputs
+                .iter()
+                .any(|output| matches!(output, OutputDestination::File(_))),
+            (None, None) => options.output.workspace_folder.is_none(),
+        };
+        if matches!(options.language, Some(args::AvailableLanguage::Go)) && single_file {
             anyhow::ensure!(
                     !config.go.package.is_empty(),
                    "Please provide a package name in the typeshare.toml or using --go-package <package name>"
@@ -289,26 +1197,158 @@ fn override_configuration(mut config: Config, options: &Args) -> anyhow::Result<
     Ok(config)
 }
 
+/// Prints how many of each kind of type are generated.
+fn print_type_summary(
+    language_type: SupportedLanguage,
+    crate_parsed_data: &BTreeMap<CrateName, ParsedData>,
+) {
+    let count = |len: fn(&ParsedData) -> usize| crate_parsed_data.values().map(len).sum::<usize>();
+    println!(
+        "Would generate {} structs, {} enums, {} type aliases and {} constants for {language_type:?}",
+        count(|data| data.structs.len()),
+        count(|data| data.enums.len()),
+        count(|data| data.aliases.len()),
+        count(|data| data.consts.len()),
+    );
+}
+
+/// Prints out every identifier the language would generate for more than one item, or
+/// field or variant of an item, if any, and returns Err.
+fn check_name_collisions(
+    lang: &dyn Language,
+    parsed_crates: &BTreeMap<CrateName, ParsedData>,
+) -> anyhow::Result<()> {
+    let mut collisions_encountered = false;
+    for collision in parsed_crates
+        .values()
+        .flat_map(|data| name_collisions(lang, data))
+    {
+        collisions_encountered = true;
+        error!("Name collision: {collision}");
+    }
+
+    if collisions_encountered {
+        Err(Failure::Unsupported.error("Types would be generated with the same identifier."))
+    } else {
+        Ok(())
+    }
+}
+
 /// Prints out all parsing errors if any and returns Err.
+///
+/// The failure is [`Failure::Unsupported`] if every error is about Rust typeshare
+/// doesn't support.
 fn check_parse_errors(parsed_crates: &BTreeMap<CrateName, ParsedData>) -> anyhow::Result<()> {
     let mut errors_encountered = false;
+    let mut all_unsupported = true;
     for data in parsed_crates
         .values()
         .filter(|parsed_data| !parsed_data.errors.is_empty())
     {
         errors_encountered = true;
         for error in &data.errors {
+            all_unsupported &= error.unsupported;
             error!(
-                "Parsing error: \"{}\" in file \"{}\"",
-                error.error, error.file_name
+                "Parsing error in file \"{}\":\n{}",
+                error.file_name,
+                diagnostic::render(&error.error, &error.file_name, error.span)
             );
         }
     }
 
     if errors_encountered {
         error!("Errors encountered during parsing.");
-        Err(anyhow!("Errors encountered during parsing."))
+        let failure = if all_unsupported {
+            Failure::Unsupported
+        } else {
+            Failure::Parse
+        };
+        Err(failure.error("Errors encountered during parsing."))
     } else {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{args::Args, generate_languages, generate_types, report::Report, tempdir::tempdir};
+    use clap::Parser;
+    use std::fs;
+
+    #[test]
+    fn include_and_exclude_globs_filter_the_input_files() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(src.join("api")).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct Root {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("api/account.rs"),
+            "#[typeshare]\npub struct Account {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("api/internal.rs"),
+            "#[typeshare]\npub struct Internal {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+        let output = root.path().join("types.ts");
+        let generate = |globs: &[&str]| {
+            let mut args = vec!["typeshare", src.to_str().unwrap(), "--lang", "typescript"];
+            args.extend(["--output-file", output.to_str().unwrap()]);
+            args.extend(globs);
+            let options = Args::parse_from(args);
+            generate_types(None, &options, None, &mut Report::default()).unwrap();
+            fs::read_to_string(&output).unwrap()
+        };
+
+        let generated = generate(&["--exclude", "**/internal.rs"]);
+        assert!(generated.contains("interface Root"));
+        assert!(generated.contains("interface Account"));
+        assert!(!generated.contains("interface Internal"));
+
+        let generated = generate(&["--include", "api/**", "--exclude", "**/internal.rs"]);
+        assert!(!generated.contains("interface Root"));
+        assert!(generated.contains("interface Account"));
+        assert!(!generated.contains("interface Internal"));
+    }
+
+    #[test]
+    fn generates_every_configured_language_without_lang() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct Account {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+        let typescript = root.path().join("web/types.ts");
+        let swift = root.path().join("ios/Types.swift");
+        let config_file = root.path().join("typeshare.toml");
+        fs::write(
+            &config_file,
+            format!(
+                "[typescript]\noutputs = [{{ file = {:?} }}]\n\n[swift]\noutputs = [{{ file = {:?} }}]\n",
+                typescript.to_str().unwrap(),
+                swift.to_str().unwrap(),
+            ),
+        )
+        .unwrap();
+
+        let options = Args::parse_from(["typeshare", src.to_str().unwrap()]);
+        generate_languages(Some(&config_file), &options, &mut Report::default()).unwrap();
+        assert!(fs::read_to_string(&typescript)
+            .unwrap()
+            .contains("interface Account"));
+        assert!(fs::read_to_string(&swift)
+            .unwrap()
+            .contains("struct Account"));
+
+        fs::write(&config_file, "").unwrap();
+        assert!(generate_languages(Some(&config_file), &options, &mut Report::default()).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! A manifest of the generated files, to detect stale or modified files.
+use crate::{sha256::sha256_hex, writer::GeneratedFile};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+use typeshare_core::language::SupportedLanguage;
+
+/// The generated files with the hashes of their contents, and what generated them.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Manifest {
+    /// The version of typeshare which generated the files.
+    typeshare_version: String,
+    /// The language the files were generated in.
+    language: String,
+    /// SHA-256 hash of the configuration, including the command line options.
+    config_hash: String,
+    /// The SHA-256 hash of every generated file, by its path.
+    files: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// The manifest of the generated files, which are read back to hash them as written.
+    /// `config` is the serialized configuration they were generated with.
+    pub fn new(language: SupportedLanguage, config: &str, files: &[GeneratedFile]) -> Self {
+        let mut hashes = BTreeMap::new();
+        for file in files {
+            // Empty outputs aren't written
+            let Ok(contents) = fs::read(&file.path) else {
+                continue;
+            };
+            hashes.insert(
+                file.path.to_string_lossy().into_owned(),
+                sha256_hex(&contents),
+            );
+        }
+        Self {
+            typeshare_version: env!("CARGO_PKG_VERSION").to_string(),
+            language: format!("{language:?}").to_lowercase(),
+            config_hash: sha256_hex(config.as_bytes()),
+            files: hashes,
+        }
+    }
+
+    /// Keep the files of the previous manifest at `path` which weren't generated this
+    /// time but still exist, like the outputs of crates skipped by the cache.
+    pub fn keep_previous(&mut self, path: &Path) {
+        let Some(previous) = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Manifest>(&contents).ok())
+        else {
+            return;
+        };
+        for (file, hash) in previous.files {
+            if Path::new(&file).is_file() {
+                self.files.entry(file).or_insert(hash);
+            }
+        }
+    }
+
+    /// Write the manifest, unless it's unchanged.
+    pub fn store(&self, path: &Path) -> anyhow::Result<()> {
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize the manifest")? + "\n";
+        if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+            return Ok(());
+        }
+        fs::write(path, contents).with_context(|| format!("Failed to write manifest {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Manifest;
+    use crate::{tempdir::tempdir, writer::GeneratedFile};
+    use std::fs;
+    use typeshare_core::language::SupportedLanguage;
+
+    #[test]
+    fn hashes_written_files() {
+        let folder = tempdir();
+        let path = folder.path().join("types.ts");
+        fs::write(&path, "abc").unwrap();
+        let files = [
+            GeneratedFile {
+                path: path.clone(),
+                types: Vec::new(),
+                up_to_date: false,
+            },
+            GeneratedFile {
+                path: folder.path().join("empty.ts"),
+                types: Vec::new(),
+                up_to_date: true,
+            },
+        ];
+
+        let manifest = Manifest::new(SupportedLanguage::TypeScript, "", &files);
+        assert_eq!(manifest.language, "typescript");
+        assert_eq!(
+            manifest.files.into_iter().collect::<Vec<_>>(),
+            [(
+                path.to_string_lossy().into_owned(),
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+            )]
+        );
+    }
+}
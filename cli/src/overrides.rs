@@ -0,0 +1,194 @@
+//! Overrides of typeshared types from the `[types]` tables of the configuration file.
+use crate::{config::TypeOverride, parse::output_file_name};
+use log::warn;
+use std::collections::BTreeMap;
+use typeshare_core::{
+    language::{CrateName, SupportedLanguage},
+    parser::{DecoratorKind, ParsedData},
+    reconcile::move_type,
+    rust_types::{DecoratorMap, Id},
+};
+
+/// Apply the overrides to the types, by their Rust names. This has to run before the
+/// references to renamed types are reconciled. Without a language, like for plugins,
+/// the files types are moved to have no file name, like those parsed without one.
+pub fn apply_overrides(
+    overrides: &BTreeMap<String, TypeOverride>,
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    language_type: Option<SupportedLanguage>,
+) {
+    for (name, type_override) in overrides {
+        let Some(crate_name) = crate_parsed_data
+            .iter()
+            .find(|(_, parsed_data)| parsed_data.has_type(name))
+            .map(|(crate_name, _)| crate_name.clone())
+        else {
+            warn!("The overrides of {name} aren't applied, as there's no typeshared type {name}");
+            continue;
+        };
+        let parsed_data = crate_parsed_data
+            .get_mut(&crate_name)
+            .expect("the crate was found");
+        let multi_file = parsed_data.multi_file;
+
+        let (id, decorators) = find_type(parsed_data, name).expect("the type was found");
+        // Like `#[serde(rename)]`, so the references to the type are renamed as well
+        let renamed = type_override.rename.as_ref().map(|rename| {
+            id.serde_rename = true;
+            (std::mem::replace(&mut id.renamed, rename.clone()), rename)
+        });
+        match decorators {
+            Some(decorators) => {
+                for (kind, added) in [
+                    (DecoratorKind::Swift, &type_override.swift),
+                    (DecoratorKind::Kotlin, &type_override.kotlin),
+                ] {
+                    if !added.is_empty() {
+                        decorators
+                            .entry(kind)
+                            .or_default()
+                            .extend(added.iter().cloned());
+                    }
+                }
+            }
+            None if !type_override.swift.is_empty() || !type_override.kotlin.is_empty() => {
+                warn!("The decorators of {name} aren't applied, as type aliases have none");
+            }
+            None => {}
+        }
+        if let Some((previous, rename)) = renamed {
+            parsed_data.type_names.remove(&previous);
+            parsed_data.type_names.insert(rename.clone());
+        }
+
+        if let Some(file) = type_override.file.as_deref().filter(|_| multi_file) {
+            let to = CrateName::from(file.to_owned());
+            let file_name = language_type
+                .map(|language_type| output_file_name(language_type, &to))
+                .unwrap_or_default();
+            move_type(crate_parsed_data, name, &crate_name, &to, file_name);
+        }
+    }
+}
+
+/// The identifier of the type with the Rust name, and its decorators unless it's an alias.
+fn find_type<'a>(
+    parsed_data: &'a mut ParsedData,
+    name: &str,
+) -> Option<(&'a mut Id, Option<&'a mut DecoratorMap>)> {
+    if let Some(s) = parsed_data
+        .structs
+        .iter_mut()
+        .find(|s| s.id.original == name)
+    {
+        return Some((&mut s.id, Some(&mut s.decorators)));
+    }
+    if let Some(e) = parsed_data
+        .enums
+        .iter_mut()
+        .find(|e| e.shared().id.original == name)
+    {
+        let shared = e.shared_mut();
+        return Some((&mut shared.id, Some(&mut shared.decorators)));
+    }
+    parsed_data
+        .aliases
+        .iter_mut()
+        .find(|a| a.id.original == name)
+        .map(|a| (&mut a.id, None))
+}
+
+#[cfg(test)]
+mod test {
+    use super::apply_overrides;
+    use crate::config::TypeOverride;
+    use std::collections::BTreeMap;
+    use typeshare_core::{
+        context::{ParseContext, ParseFileContext},
+        language::{CrateName, SupportedLanguage},
+        parser::{DecoratorKind, ParsedData},
+        reconcile::reconcile_aliases,
+    };
+
+    fn parse(crate_name: &str, source: &str) -> (CrateName, ParsedData) {
+        let parse_context = ParseContext {
+            multi_file: true,
+            ..Default::default()
+        };
+        let parsed_data = typeshare_core::parser::parse(
+            &parse_context,
+            ParseFileContext {
+                source_code: source.into(),
+                crate_name: crate_name.into(),
+                file_name: format!("{crate_name}.ts"),
+                file_path: format!("{crate_name}/src/lib.rs").into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        (crate_name.into(), parsed_data)
+    }
+
+    #[test]
+    fn renames_and_moves_types() {
+        let mut crate_parsed_data = BTreeMap::from([
+            parse(
+                "alpha",
+                r#"
+                #[typeshare]
+                pub struct User { pub role: Role }
+
+                #[typeshare]
+                pub enum Role { Admin, Member }
+                "#,
+            ),
+            parse(
+                "beta",
+                r#"
+                use alpha::User;
+
+                #[typeshare]
+                pub struct Team { pub owner: User }
+                "#,
+            ),
+        ]);
+        let overrides = BTreeMap::from([(
+            "User".to_string(),
+            TypeOverride {
+                rename: Some("Account".into()),
+                file: Some("shared".into()),
+                swift: vec!["Hashable".into()],
+                ..Default::default()
+            },
+        )]);
+
+        apply_overrides(
+            &overrides,
+            &mut crate_parsed_data,
+            Some(SupportedLanguage::TypeScript),
+        );
+        reconcile_aliases(&mut crate_parsed_data);
+
+        let shared = &crate_parsed_data[&CrateName::from("shared")];
+        assert_eq!(shared.file_name, "shared.ts");
+        assert_eq!(shared.structs[0].id.renamed, "Account");
+        assert!(shared.structs[0].decorators[&DecoratorKind::Swift].contains("Hashable"));
+        assert!(shared
+            .import_types
+            .iter()
+            .any(|import| import.base_crate == "alpha".into() && import.type_name == "Role"));
+        assert!(crate_parsed_data[&CrateName::from("alpha")]
+            .structs
+            .is_empty());
+
+        let beta = &crate_parsed_data[&CrateName::from("beta")];
+        assert!(beta
+            .import_types
+            .iter()
+            .any(|import| import.base_crate == "shared".into() && import.type_name == "Account"));
+        assert!(matches!(
+            &beta.structs[0].fields[0].ty,
+            typeshare_core::rust_types::RustType::Simple { id } if id == "Account"
+        ));
+    }
+}
@@ -1,35 +1,39 @@
 //! Source file parsing.
-use anyhow::anyhow;
+use crate::{diagnostic, failure::Failure};
 use anyhow::Context;
 use crossbeam::channel::bounded;
 use ignore::{DirEntry, WalkBuilder, WalkState};
+use log::debug;
 use std::{
-    collections::{BTreeMap, HashMap},
-    mem, thread,
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    io, mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
 };
 use typeshare_core::{
     context::{ParseContext, ParseFileContext},
     error::ParseErrorWithSpan,
-    language::{CrateName, CrateTypes, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
-    parser::ParsedData,
+    language::{CrateName, CrateTypes, Language, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
+    parser::{ParsedData, WarningInfo},
     RenameExt,
 };
 
+/// The context of parsing the file, whose output file is named for the language. Plugins
+/// name their own files.
 fn parse_file_context(
     multi_file: bool,
-    language_type: SupportedLanguage,
+    per_module: bool,
+    language_type: Option<SupportedLanguage>,
     dir_entry: &DirEntry,
 ) -> anyhow::Result<Option<ParseFileContext>> {
-    let crate_name = if multi_file {
-        let Some(crate_name) = CrateName::find_crate_name(dir_entry.path()) else {
-            return Ok(None);
-        };
-        crate_name
-    } else {
-        SINGLE_FILE_CRATE_NAME
+    let Some(crate_name) = file_crate_name(multi_file, per_module, dir_entry.path()) else {
+        return Ok(None);
     };
     let file_path = dir_entry.path().to_path_buf();
-    let out_file_name = output_file_name(language_type, &crate_name);
+    let out_file_name = language_type
+        .map(|language_type| output_file_name(language_type, &crate_name))
+        .unwrap_or_default();
 
     let input_file = file_path
         .to_str()
@@ -47,21 +51,40 @@ fn parse_file_context(
     Ok(Some(parse_file_context))
 }
 
+/// The crate (or module, with `per_module`) whose output a Rust file is generated into,
+/// or `None` if the file doesn't belong to a crate.
+pub fn file_crate_name(multi_file: bool, per_module: bool, path: &Path) -> Option<CrateName> {
+    if !multi_file {
+        Some(SINGLE_FILE_CRATE_NAME)
+    } else if per_module {
+        CrateName::find_module_name(path)
+    } else {
+        CrateName::find_crate_name(path)
+    }
+}
+
 /// The output file name to write to.
-fn output_file_name(language_type: SupportedLanguage, crate_name: &CrateName) -> String {
+pub(crate) fn output_file_name(language_type: SupportedLanguage, crate_name: &CrateName) -> String {
     let extension = language_type.language_extension();
 
     let snake_case = || format!("{crate_name}.{extension}");
+    // For languages whose module names can't contain a `.`
+    let file_stem = || format!("{}.{extension}", crate_name.file_stem());
     let pascal_case = || format!("{}.{extension}", crate_name.to_string().to_pascal_case());
 
     match language_type {
-        SupportedLanguage::Go => snake_case(),
+        // Go packages are directories, so every crate (or module) gets its own.
+        SupportedLanguage::Go => format!(
+            "{}/{}.{extension}",
+            crate_name.as_str().replace('.', "/"),
+            crate_name.module_name()
+        ),
         SupportedLanguage::Kotlin => snake_case(),
-        SupportedLanguage::ReasonML => snake_case(),
+        SupportedLanguage::ReasonML => file_stem(),
         SupportedLanguage::Scala => snake_case(),
         SupportedLanguage::Swift => pascal_case(),
         SupportedLanguage::TypeScript => snake_case(),
-        SupportedLanguage::Python => snake_case(),
+        SupportedLanguage::Python => file_stem(),
     }
 }
 
@@ -110,42 +133,213 @@ impl std::fmt::Display for ParseDirError {
 
 fn parse_dir_entry(
     parse_context: &ParseContext,
-    language_type: SupportedLanguage,
+    per_module: bool,
+    language_type: Option<SupportedLanguage>,
     dir_entry: &DirEntry,
 ) -> Result<Option<ParsedData>, ParseDirError> {
     if dir_entry.path().is_dir() {
         return Ok(None);
     }
 
-    let Some(parse_file_context) =
-        parse_file_context(parse_context.multi_file, language_type, dir_entry)
-            .map_err(|err| ParseDirError::IO(err.to_string()))?
+    let Some(parse_file_context) = parse_file_context(
+        parse_context.multi_file,
+        per_module,
+        language_type,
+        dir_entry,
+    )
+    .map_err(|err| ParseDirError::IO(err.to_string()))?
     else {
         return Ok(None);
     };
 
-    typeshare_core::parser::parse(parse_context, parse_file_context).map_err(Into::into)
+    let file_path = dir_entry.path();
+    let parsed_data = typeshare_core::parser::parse(parse_context, parse_file_context)?;
+    match &parsed_data {
+        Some(data) => debug!(
+            "Parsed {} typeshared items from {file_path:?}",
+            data.structs.len() + data.enums.len() + data.aliases.len() + data.consts.len()
+        ),
+        None => debug!("Skipped {file_path:?}, which has no #[typeshare] attributes"),
+    }
+    Ok(parsed_data)
+}
+
+/// Record the warnings of the language about the typeshared items of a file.
+fn collect_warnings(lang: &dyn Language, file_path: &Path, parsed_data: &mut ParsedData) {
+    for warning in lang.warnings(parsed_data) {
+        parsed_data.warnings.push(WarningInfo {
+            file_name: file_path.to_string_lossy().into_owned(),
+            warning,
+        });
+    }
 }
 
-/// Use parallel builder to walk all source directories concurrently.
+/// Parse Rust source read from stdin, which is generated as a single file.
+pub fn parse_stdin(
+    parse_context: &ParseContext,
+    language_type: SupportedLanguage,
+    lang: &dyn Language,
+) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
+    let source_code = io::read_to_string(io::stdin()).context("Failed to read stdin")?;
+    let parse_file_context = ParseFileContext {
+        source_code,
+        crate_name: SINGLE_FILE_CRATE_NAME,
+        file_name: output_file_name(language_type, &SINGLE_FILE_CRATE_NAME),
+        file_path: PathBuf::from("<stdin>"),
+    };
+
+    let mut parsed_data = typeshare_core::parser::parse(parse_context, parse_file_context)
+        .map_err(|err| Failure::Parse.error(format!("Parsing failed: <stdin>, {err}")))?;
+    if let Some(parsed_data) = parsed_data.as_mut() {
+        collect_warnings(lang, Path::new("<stdin>"), parsed_data);
+    }
+    Ok(parsed_data
+        .map(|parsed_data| (SINGLE_FILE_CRATE_NAME, parsed_data))
+        .into_iter()
+        .collect())
+}
+
+/// Use parallel builder to walk and parse all source directories concurrently.
+///
+/// The files are merged in the order of their paths, so that the generated output
+/// doesn't depend on which thread finished first.
+///
+/// With `per_module`, types are grouped by the Rust module they are defined in
+/// rather than by crate when generating multiple files.
 pub fn parallel_parse(
     parse_context: &ParseContext,
+    per_module: bool,
     walker_builder: WalkBuilder,
     language_type: SupportedLanguage,
+    lang: &dyn Language,
 ) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
-    let (tx, rx) = bounded::<anyhow::Result<ParsedData>>(100);
+    parallel_parse_files(
+        parse_context,
+        per_module,
+        walker_builder,
+        language_type,
+        lang,
+    )
+    .map(merge_by_crate)
+}
 
-    let collector_thread = thread::spawn(move || {
-        let mut crate_parsed_data: BTreeMap<CrateName, ParsedData> = BTreeMap::new();
+/// The input files parsed once for every language generated from them, like when the
+/// configured languages are generated together. Every language is given a copy of the
+/// files parsed without a language, with the output file names, imports and warnings
+/// of the language.
+#[derive(Default)]
+pub struct SharedParse {
+    /// The parsed data of every file by its path, for every `multi_file` and
+    /// `per_module`, which group the files into crates differently.
+    files: Mutex<HashMap<(bool, bool), BTreeMap<PathBuf, ParsedData>>>,
+}
 
-        for result in rx {
-            let parsed_data = result?;
-            let crate_name = parsed_data.crate_name.clone();
-            // Append each yielded parsed data by its respective crate.
-            *crate_parsed_data.entry(crate_name).or_default() += parsed_data;
+impl SharedParse {
+    /// Parse the files like [`parallel_parse`], unless they were parsed for another
+    /// language already.
+    pub fn parse(
+        &self,
+        parse_context: &ParseContext,
+        per_module: bool,
+        walker_builder: WalkBuilder,
+        language_type: SupportedLanguage,
+        lang: &dyn Language,
+    ) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
+        // Languages waiting for the files another is parsing use them when it's done.
+        let mut files = self.files.lock().unwrap();
+        let mut file_parsed_data = match files.entry((parse_context.multi_file, per_module)) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => {
+                let without_language = ParseContext {
+                    ignored_types: Vec::new(),
+                    multi_file: parse_context.multi_file,
+                    target_os: parse_context.target_os.clone(),
+                };
+                entry
+                    .insert(parse_files_for(
+                        &without_language,
+                        per_module,
+                        walker_builder,
+                        None,
+                    )?)
+                    .clone()
+            }
+        };
+        drop(files);
+
+        for (file_path, parsed_data) in &mut file_parsed_data {
+            parsed_data.file_name = output_file_name(language_type, &parsed_data.crate_name);
+            parsed_data.import_types.retain(|imported| {
+                !parse_context
+                    .ignored_types
+                    .contains(&imported.type_name.as_str())
+            });
+            collect_warnings(lang, file_path, parsed_data);
         }
+        Ok(merge_by_crate(file_parsed_data))
+    }
+}
 
-        Ok(crate_parsed_data)
+/// Merge the parsed data of every file by its crate, in the order of the file paths.
+pub fn merge_by_crate(
+    file_parsed_data: BTreeMap<PathBuf, ParsedData>,
+) -> BTreeMap<CrateName, ParsedData> {
+    let mut crate_parsed_data: BTreeMap<CrateName, ParsedData> = BTreeMap::new();
+    for parsed_data in file_parsed_data.into_values() {
+        let crate_name = parsed_data.crate_name.clone();
+        // Append each yielded parsed data by its respective crate.
+        *crate_parsed_data.entry(crate_name).or_default() += parsed_data;
+    }
+    crate_parsed_data
+}
+
+/// Use parallel builder to walk and parse all source directories concurrently,
+/// returning the parsed data of every file with typeshared types by its path, with
+/// the warnings of the language about them.
+pub fn parallel_parse_files(
+    parse_context: &ParseContext,
+    per_module: bool,
+    walker_builder: WalkBuilder,
+    language_type: SupportedLanguage,
+    lang: &dyn Language,
+) -> anyhow::Result<BTreeMap<PathBuf, ParsedData>> {
+    parse_files_for(
+        parse_context,
+        per_module,
+        walker_builder,
+        Some((language_type, lang)),
+    )
+}
+
+/// Use parallel builder to walk and parse all source directories concurrently without
+/// a language, like for a plugin, merging the files by their crate like
+/// [`parallel_parse`].
+pub fn parallel_parse_without_language(
+    parse_context: &ParseContext,
+    per_module: bool,
+    walker_builder: WalkBuilder,
+) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
+    parse_files_for(parse_context, per_module, walker_builder, None).map(merge_by_crate)
+}
+
+/// Parse all source directories concurrently, with the warnings of the language if
+/// there is one.
+fn parse_files_for(
+    parse_context: &ParseContext,
+    per_module: bool,
+    walker_builder: WalkBuilder,
+    language: Option<(SupportedLanguage, &dyn Language)>,
+) -> anyhow::Result<BTreeMap<PathBuf, ParsedData>> {
+    let language_type = language.map(|(language_type, _)| language_type);
+    let (tx, rx) = bounded::<anyhow::Result<(PathBuf, ParsedData)>>(100);
+
+    let collector_thread = thread::spawn(move || {
+        let mut file_parsed_data = BTreeMap::new();
+        for result in rx {
+            let (path, parsed_data) = result?;
+            file_parsed_data.insert(path, parsed_data);
+        }
+        anyhow::Ok(file_parsed_data)
     });
 
     walker_builder.build_parallel().run(|| {
@@ -153,8 +347,22 @@ pub fn parallel_parse(
 
         Box::new(move |result| {
             let result = result.context("Failed traversing").and_then(|dir_entry| {
-                parse_dir_entry(parse_context, language_type, &dir_entry)
-                    .map_err(|err| anyhow!("Parsing failed: {:?},  {err}", dir_entry.path()))
+                parse_dir_entry(parse_context, per_module, language_type, &dir_entry)
+                    .map_err(|err| match err {
+                        ParseDirError::ParseError(err) => Failure::Parse.error(format!(
+                            "Parsing failed:\n{}",
+                            diagnostic::render(
+                                &err.error().to_string(),
+                                &dir_entry.path().to_string_lossy(),
+                                Some(err.span())
+                            )
+                        )),
+                        err => Failure::Io
+                            .error(format!("Parsing failed: {:?},  {err}", dir_entry.path())),
+                    })
+                    .map(|parsed_data| {
+                        parsed_data.map(|parsed_data| (dir_entry.into_path(), parsed_data))
+                    })
             });
             match result {
                 Ok(Some(parsed_data)) => {
@@ -171,5 +379,72 @@ pub fn parallel_parse(
     });
 
     drop(tx);
-    collector_thread.join().unwrap()
+    let mut file_parsed_data = collector_thread.join().unwrap()?;
+    if let Some((_, lang)) = language {
+        for (file_path, parsed_data) in &mut file_parsed_data {
+            collect_warnings(lang, file_path, parsed_data);
+        }
+    }
+    Ok(file_parsed_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::SharedParse;
+    use crate::tempdir::tempdir;
+    use ignore::WalkBuilder;
+    use std::{collections::HashMap, fs};
+    use typeshare_core::{
+        context::ParseContext,
+        language::{CrateName, Language, SupportedLanguage, Swift, TypeScript},
+    };
+
+    #[test]
+    fn parses_the_files_once_for_every_language() {
+        let root = tempdir();
+        let src = root.path().join("foo").join("src");
+        fs::create_dir_all(&src).unwrap();
+        let file = src.join("lib.rs");
+        fs::write(
+            &file,
+            "use bar::{Bar, Uuid};\n\n#[typeshare]\npub struct Foo { pub bar: Bar, pub id: Uuid }\n",
+        )
+        .unwrap();
+
+        let shared = SharedParse::default();
+        let parse = |language_type: SupportedLanguage, lang: &dyn Language| {
+            let parse_context = ParseContext {
+                ignored_types: lang.ignored_reference_types(),
+                multi_file: true,
+                ..Default::default()
+            };
+            let walker_builder = WalkBuilder::new(root.path());
+            let mut parsed_data = shared
+                .parse(&parse_context, false, walker_builder, language_type, lang)
+                .unwrap();
+            parsed_data.remove(&CrateName::from("foo")).unwrap()
+        };
+
+        let typescript = TypeScript {
+            type_mappings: HashMap::from([("Uuid".to_string(), "string".to_string())]),
+            ..Default::default()
+        };
+        let parsed_data = parse(SupportedLanguage::TypeScript, &typescript);
+        assert_eq!(parsed_data.file_name, "foo.ts");
+        assert_eq!(
+            parsed_data
+                .import_types
+                .iter()
+                .map(|imported| imported.type_name.as_str())
+                .collect::<Vec<_>>(),
+            ["Bar"]
+        );
+
+        // The file isn't parsed again for the next language.
+        fs::write(&file, "#[typeshare]\npub struct Baz {}\n").unwrap();
+        let parsed_data = parse(SupportedLanguage::Swift, &Swift::default());
+        assert_eq!(parsed_data.file_name, "Foo.swift");
+        assert_eq!(parsed_data.structs[0].id.original, "Foo");
+        assert_eq!(parsed_data.import_types.len(), 2);
+    }
 }
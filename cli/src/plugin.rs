@@ -0,0 +1,127 @@
+//! Generating types with plugins, language backends outside of typeshare.
+use crate::{
+    args::Args,
+    check_parse_errors,
+    config::{self, Config},
+    filter::{retain_referenced, retain_referenced_dependencies, TypeFilter},
+    lint::Lints,
+    override_configuration,
+    overrides::apply_overrides,
+    parse::parallel_parse_without_language,
+    walker_builder,
+    writer::{check_generated, write_files, Mode, WriteOptions},
+};
+use anyhow::Context;
+use log::info;
+use std::{collections::BTreeMap, path::Path};
+use typeshare_core::{
+    context::ParseContext,
+    language::CrateName,
+    parser::ParsedData,
+    plugin::Plugin,
+    reconcile::{collect_serde_renames, reconcile_aliases_with},
+};
+
+/// Generate the types of the directories with the plugin of the configuration file, and
+/// write the files it generated into the output folder.
+pub fn generate_plugin(
+    config_file: Option<&Path>,
+    options: &Args,
+    name: &str,
+) -> anyhow::Result<()> {
+    info!("typeshare started generating types with the plugin {name:?}");
+
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    let params = config.plugins.get(name).with_context(|| {
+        format!("There's no plugin {name:?} in the configuration file, as `[plugins.{name}]`")
+    })?;
+    anyhow::ensure!(
+        !params.command.is_empty(),
+        "The plugin {name:?} needs a command"
+    );
+    anyhow::ensure!(
+        options.output.file.is_none() && !options.reads_stdin(),
+        "A plugin generates files into an output folder, from Rust files"
+    );
+    let output_folder = options
+        .output
+        .folder
+        .as_ref()
+        .or(params.output_folder.as_ref())
+        .or(options.output.workspace_folder.as_ref())
+        .with_context(|| {
+            format!("The plugin {name:?} needs an output folder, like `--output-folder`")
+        })?;
+
+    // The plugin is given every crate, and names the files it generates itself.
+    let parsed_data = parse_crates(&config, options, true)?;
+
+    let plugin = Plugin {
+        command: params.command.clone(),
+        options: serde_json::to_value(&params.options)
+            .context("Failed to serialize the options of the plugin")?,
+    };
+    let files = plugin
+        .generate(&parsed_data)
+        .with_context(|| format!("The plugin {name:?} failed to generate types"))?;
+
+    let mode = if options.check {
+        Mode::Check
+    } else if options.dry_run {
+        Mode::DryRun
+    } else {
+        Mode::Write
+    };
+    let generated = write_files(
+        output_folder,
+        files
+            .into_iter()
+            .map(|file| (file.path, file.contents.into_bytes())),
+        &WriteOptions {
+            mode,
+            file_per_type: false,
+            formatter: &[],
+            checksum_comment: None,
+            force: options.force,
+        },
+    )?;
+    check_generated(&generated, mode)?;
+
+    info!("typeshare finished generating types with the plugin {name:?}");
+    Ok(())
+}
+
+/// Parse the Rust files of the directories, for a single file or a file per crate, like
+/// generation does: with the overrides of the `[types]` tables applied, reconciled, and
+/// filtered by the root types, the referenced types of dependencies and the type filters.
+/// The warnings of the parser are reported at their lint levels, but there are no
+/// warnings of a language, like about types it can't represent, and the files types are
+/// moved to by overrides have no file name.
+pub(crate) fn parse_crates(
+    config: &Config,
+    options: &Args,
+    multi_file: bool,
+) -> anyhow::Result<BTreeMap<CrateName, ParsedData>> {
+    let parse_context = ParseContext {
+        ignored_types: Vec::new(),
+        multi_file,
+        target_os: config.target_os.clone(),
+    };
+    let walker_builder = walker_builder(&options.directories, options, config)?;
+    let mut parsed_data =
+        parallel_parse_without_language(&parse_context, config.per_module, walker_builder)?;
+    apply_overrides(&config.types, &mut parsed_data, None);
+    let serde_renamed = collect_serde_renames(&parsed_data);
+    reconcile_aliases_with(&mut parsed_data, &serde_renamed);
+    if config.sort_types {
+        parsed_data.values_mut().for_each(ParsedData::sort_by_name);
+    }
+    retain_referenced(&mut parsed_data, &config.root_types)?;
+    retain_referenced_dependencies(&mut parsed_data, &options.dependency_types);
+    TypeFilter::new(&config.only_types, &config.exclude_types)?.apply(&mut parsed_data);
+    Lints::new(&config.lints, options.deny_warnings, options.strict)?.apply(&mut parsed_data);
+    check_parse_errors(&parsed_data)?;
+    Ok(parsed_data)
+}
@@ -0,0 +1,232 @@
+//! Machine-readable summary of generating types.
+use crate::writer::GeneratedFile;
+use flexi_logger::{writers::LogWriter, DeferredNow};
+use log::{Level, Record};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use typeshare_core::{
+    language::{CrateName, SupportedLanguage},
+    parser::ParsedData,
+};
+
+/// The format to print the report in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// A JSON object.
+    Json,
+}
+
+/// What happened while generating types, printed to stdout when it's done.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// The languages the types were generated for.
+    languages: Vec<String>,
+    /// Whether generating the types succeeded.
+    success: bool,
+    /// The generated files.
+    files: Vec<ReportFile>,
+    /// The typeshared items which weren't generated.
+    skipped: Vec<SkippedItem>,
+    /// The logged warnings.
+    warnings: Vec<String>,
+    /// The logged errors.
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportFile {
+    path: PathBuf,
+    /// The language the file was generated in.
+    language: String,
+    /// The Rust names of the types in the file.
+    types: Vec<String>,
+    status: FileStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FileStatus {
+    /// The file changed and was written.
+    Written,
+    /// The file was already up to date.
+    Unchanged,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct SkippedItem {
+    name: String,
+    reason: String,
+}
+
+impl Report {
+    /// Record a language the types are generated for.
+    pub fn add_language(&mut self, language: SupportedLanguage) {
+        self.languages.push(language_name(language));
+    }
+
+    /// Record the typeshared items which aren't generated.
+    pub fn add_skipped(&mut self, crate_parsed_data: &BTreeMap<CrateName, ParsedData>) {
+        self.skipped.extend(
+            crate_parsed_data
+                .values()
+                .flat_map(|parsed_data| &parsed_data.skipped)
+                .map(|skipped| SkippedItem {
+                    name: skipped.name.clone(),
+                    reason: skipped.reason.clone(),
+                }),
+        );
+    }
+
+    /// Record the files generated in the language.
+    pub fn add_files(&mut self, language: SupportedLanguage, files: &[GeneratedFile]) {
+        self.files.extend(files.iter().map(|file| ReportFile {
+            path: file.path.clone(),
+            language: language_name(language),
+            types: file.types.clone(),
+            status: if file.up_to_date {
+                FileStatus::Unchanged
+            } else {
+                FileStatus::Written
+            },
+        }));
+    }
+
+    /// Record what the report of a language generated on its own recorded, after what
+    /// this report recorded.
+    pub fn merge(&mut self, other: Report) {
+        self.languages.extend(other.languages);
+        self.files.extend(other.files);
+        // Every language skips the same items.
+        for skipped in other.skipped {
+            if !self.skipped.contains(&skipped) {
+                self.skipped.push(skipped);
+            }
+        }
+    }
+
+    /// The finished report in the given format, with the warnings and errors logged
+    /// while generating.
+    pub fn finish(mut self, success: bool, log: &ReportLog, format: ReportFormat) -> String {
+        self.success = success;
+        for (level, message) in log.messages.lock().unwrap().drain(..) {
+            match level {
+                Level::Error => self.errors.push(message),
+                _ => self.warnings.push(message),
+            }
+        }
+        match format {
+            ReportFormat::Json => {
+                serde_json::to_string_pretty(&self).expect("the report is serializable") + "\n"
+            }
+        }
+    }
+}
+
+fn language_name(language: SupportedLanguage) -> String {
+    format!("{language:?}").to_lowercase()
+}
+
+/// A log writer recording the warnings and errors for the report.
+#[derive(Debug, Default, Clone)]
+pub struct ReportLog {
+    messages: Arc<Mutex<Vec<(Level, String)>>>,
+}
+
+impl LogWriter for ReportLog {
+    fn write(&self, _now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        if record.level() <= Level::Warn {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Report, ReportFormat, ReportLog};
+    use crate::writer::GeneratedFile;
+    use flexi_logger::{writers::LogWriter, DeferredNow};
+    use log::{Level, Record};
+    use typeshare_core::language::SupportedLanguage;
+
+    #[test]
+    fn reports_files_and_warnings() {
+        let mut report = Report::default();
+        report.add_language(SupportedLanguage::TypeScript);
+        report.add_files(
+            SupportedLanguage::TypeScript,
+            &[GeneratedFile {
+                path: "out/foo.ts".into(),
+                types: vec!["Foo".to_string()],
+                up_to_date: true,
+            }],
+        );
+        let log = ReportLog::default();
+        log.write(
+            &mut DeferredNow::new(),
+            &Record::builder()
+                .level(Level::Warn)
+                .args(format_args!("careful"))
+                .build(),
+        )
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.finish(true, &log, ReportFormat::Json)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "languages": ["typescript"],
+                "success": true,
+                "files": [{
+                    "path": "out/foo.ts",
+                    "language": "typescript",
+                    "types": ["Foo"],
+                    "status": "unchanged",
+                }],
+                "skipped": [],
+                "warnings": ["careful"],
+                "errors": [],
+            })
+        );
+    }
+
+    #[test]
+    fn merges_the_reports_of_languages() {
+        let mut report = Report::default();
+        let file = |path: &str| GeneratedFile {
+            path: path.into(),
+            types: vec!["Foo".to_string()],
+            up_to_date: false,
+        };
+        report.add_language(SupportedLanguage::TypeScript);
+        report.add_files(SupportedLanguage::TypeScript, &[file("out/foo.ts")]);
+        let mut swift = Report::default();
+        swift.add_language(SupportedLanguage::Swift);
+        swift.add_files(SupportedLanguage::Swift, &[file("out/Foo.swift")]);
+        report.merge(swift);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.finish(true, &ReportLog::default(), ReportFormat::Json))
+                .unwrap();
+        assert_eq!(
+            json["languages"],
+            serde_json::json!(["typescript", "swift"])
+        );
+        assert_eq!(json["files"][0]["path"], "out/foo.ts");
+        assert_eq!(json["files"][0]["language"], "typescript");
+        assert_eq!(json["files"][1]["path"], "out/Foo.swift");
+        assert_eq!(json["files"][1]["language"], "swift");
+    }
+}
@@ -0,0 +1,22 @@
+//! Writing example JSON of the typeshared types, like the samples of `--conformance`.
+use crate::{args::Args, config, ir::write_output, override_configuration, plugin::parse_crates};
+use anyhow::Context;
+use std::path::Path;
+use typeshare_core::language::SINGLE_FILE_CRATE_NAME;
+
+/// Write samples of every type which isn't generic, by its Rust name, to the output file
+/// or stdout.
+pub fn write_samples(config_file: Option<&Path>, options: &Args) -> anyhow::Result<()> {
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    anyhow::ensure!(
+        options.output.folder.is_none() && !options.reads_stdin(),
+        "The samples of Rust files are written to a single file or stdout"
+    );
+    let mut crates = parse_crates(&config, options, false)?;
+    let parsed_data = crates.remove(&SINGLE_FILE_CRATE_NAME).unwrap_or_default();
+    let samples = typeshare_core::samples::generate(&parsed_data);
+    let json = serde_json::to_string_pretty(&samples).context("Failed to serialize the samples")?;
+    write_output(options, json + "\n")
+}
@@ -0,0 +1,468 @@
+//! The JSON schema of the configuration file, for editor completion, and validating
+//! configuration files against it.
+use serde_json::{json, Map, Value};
+
+/// The JSON schema of `typeshare.toml`.
+pub fn config_schema() -> Value {
+    let mut schema = config_table();
+    let properties = schema["properties"]
+        .as_object_mut()
+        .expect("the configuration has properties");
+    properties.insert(
+        "extends".into(),
+        string("A configuration file whose options this file overrides, relative to this file."),
+    );
+    properties.insert(
+        "profile".into(),
+        map(
+            json!({ "$ref": "#/definitions/profile" }),
+            "Profiles selected with `--profile <name>`, whose options override the others.",
+        ),
+    );
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    schema["title"] = json!("typeshare.toml");
+    schema["definitions"] = json!({ "profile": config_table() });
+    schema
+}
+
+/// The options a configuration file or profile can have.
+fn config_table() -> Value {
+    table(
+        "The configuration of typeshare.",
+        [
+            ("per_module", boolean("When using `-d`, write a file per Rust module instead of per crate.")),
+            ("file_per_type", boolean("When using `-d`, write every type to its own file (Swift and Kotlin only).")),
+            ("sort_types", boolean("Generate the types sorted by name.")),
+            ("include", strings("Only process the Rust files matching these globs.")),
+            ("exclude", strings("Skip the Rust files matching these globs.")),
+            ("cache_file", string("Only generate the crates whose input files changed since the run which wrote this cache file.")),
+            ("manifest", string("Write a manifest listing the generated files with their hashes to this file.")),
+            ("checksum_markers", boolean("End generated files with a checksum, to not overwrite files which were edited by hand.")),
+            ("only_types", strings("Only generate the types whose Rust names match these regular expressions.")),
+            ("exclude_types", strings("Don't generate the types whose Rust names match these regular expressions.")),
+            ("root_types", strings("Only generate these types, by their Rust names, and the types they reference.")),
+            ("lints", map(enumeration(&["allow", "warn", "deny"], "The level of the lint."), "The level of each lint, by its name.")),
+            ("header", string("A header replacing the \"Generated by typeshare\" comment, with `{version}`, `{timestamp}`, `{crate}` and `{revision}` placeholders.")),
+            ("no_header_version", boolean("Leave the version of typeshare out of the \"Generated by typeshare\" comment.")),
+            ("header_revision", boolean("Add the git revision of the sources to the \"Generated by typeshare\" comment.")),
+            ("optional_fields", enumeration(&["absent", "null", "both"], "How fields of `Option` types are represented in every language.")),
+            ("schema_hashes", boolean("Write a constant with the schema hash of every type after it, in every language.")),
+            ("types", map(type_override(), "Overrides of typeshared types, by their Rust names.")),
+            ("plugins", map(plugin(), "Language backends outside of typeshare, run with `--plugin <name>`.")),
+            ("templates", map(template(), "Languages rendered with templates, run with `--template <name>`.")),
+            ("conformance", table("How the round-trip tests of `--conformance` refer to the generated types.", [
+                ("module", string("The module the generated types are imported from: the Swift module, the TypeScript import path or the ReasonML module.")),
+            ])),
+            ("swift", language("Swift", [
+                ("reserved_words", strings("Identifiers which are escaped with backticks like Swift keywords.")),
+                ("prefix", string("A prefix of the names of generated types.")),
+                ("default_decorators", strings("Protocols every generated type conforms to.")),
+                ("default_generic_constraints", strings("Constraints of the generic parameters of every generated type.")),
+                ("codablevoid_constraints", strings("Protocols `CodableVoid` conforms to.")),
+                ("init_optional_defaults", boolean("Default optional `public init` parameters to `nil`.")),
+                ("serde_default_values", boolean("Decode missing `#[serde(default)]` fields to their default value instead of `nil`.")),
+                ("package_name", string("When using `-d`, write a SwiftPM package with this name.")),
+                ("coding_keys", enumeration(&["default", "convert_from_snake_case", "explicit"], "How `CodingKeys` are generated.")),
+                ("unknown_case", string("A fallback case of enums for unknown values, like `unknown`.")),
+                ("mocks", boolean("Generate a `static func mock(...)` factory for every struct.")),
+                ("arbitraries", boolean("Conform every struct and enum to SwiftCheck's `Arbitrary`, for property-based tests.")),
+            ])),
+            ("typescript", language("TypeScript", [
+                ("module_format", enumeration(&["bundler", "esm", "commonjs"], "The module system the generated files are consumed with.")),
+                ("import_extension", string("The extension of the import specifiers between generated files.")),
+                ("field_naming", enumeration(&["wire", "camel_case"], "How the properties of generated types are named.")),
+                ("arbitraries", boolean("Generate a fast-check arbitrary, `arb<Type>`, for every type, for property-based tests.")),
+            ])),
+            ("kotlin", language("Kotlin", [
+                ("reserved_words", strings("Identifiers of properties which are escaped with backticks, like framework names.")),
+                ("package", string("The package of the generated types.")),
+                ("module_name", string("The name of the object the generated types are nested in.")),
+                ("prefix", string("A prefix of the names of generated types.")),
+                ("serializer", enumeration(&["kotlinx", "moshi"], "The serialization framework the types are annotated for.")),
+                ("sealed_interfaces", boolean("Generate algebraic enums as sealed interfaces instead of sealed classes.")),
+                ("serde_default_values", boolean("Give `#[serde(default)]` fields their default value instead of `null`.")),
+                ("parcelize", boolean("Annotate types with `@Parcelize` and make them `Parcelable`.")),
+                ("package_per_module", boolean("When using `-d`, generate a file and package per Rust module.")),
+                ("newtype_value_classes", boolean("Generate newtype structs as `@JvmInline value class`es.")),
+                ("multiplatform", boolean("Generate common Kotlin Multiplatform source.")),
+                ("arbitraries", boolean("Generate a Kotest arbitrary, `arb<Type>`, for every type, for property-based tests.")),
+            ])),
+            ("reasonml", language("ReasonML", [
+                ("reserved_words", strings("Identifiers which are quoted like ReasonML keywords.")),
+                ("no_version_header", boolean("Don't write the \"Generated by typeshare\" comment.")),
+                ("codecs", boolean("Write `decode<Type>` and `encode<Type>` functions converting every type from and to `Js.Json.t`.")),
+            ])),
+            ("scala", language("Scala", [
+                ("reserved_words", strings("Identifiers which are escaped with backticks like Scala keywords.")),
+                ("package", string("The package of the generated types.")),
+                ("module_name", string("The name of the package object the generated types are nested in.")),
+                ("circe", boolean("Generate circe `Encoder` and `Decoder` instances for all types.")),
+                ("scala3", boolean("Generate Scala 3 code instead of Scala 2.")),
+                ("opaque_newtypes", boolean("With `scala3`, generate newtype structs as `opaque type`s.")),
+                ("scala_js", boolean("Generate Scala.js facades instead of case classes.")),
+            ])),
+            ("python", language("Python", [
+                ("reserved_words", strings("Identifiers which get a trailing underscore like Python keywords.")),
+            ])),
+            ("go", language("Go", [
+                ("package", string("The package of the generated types.")),
+                ("uppercase_acronyms", strings("Acronyms written in uppercase in Go names, like `ID`.")),
+                ("no_pointer_slice", boolean("Don't generate optional slices as pointers.")),
+                ("no_omitempty", boolean("Don't add `,omitempty` to the JSON tags of optional fields.")),
+                ("option_style", enumeration(&["pointer", "generic", "null"], "How `Option<T>` is represented.")),
+                ("datetime_format", string("The wire format of `OffsetDateTime`: `rfc3339`, `unix`, `unix_millis` or a Go time layout.")),
+                ("import_path", string("When using `-d`, the Go import path of the output folder.")),
+                ("package_names", map(string("The package name."), "Package names for the directories of crates or modules.")),
+                ("json_value_any", boolean("Generate `serde_json::Value` fields as `any` instead of `json.RawMessage`.")),
+                ("enum_methods", boolean("Generate `String`, `MarshalJSON` and `UnmarshalJSON` methods for unit enums, which reject other values.")),
+                ("package_per_module", boolean("When using `-d`, generate a package per Rust module.")),
+            ])),
+        ],
+    )
+}
+
+/// The options of a language, besides those every language has.
+fn language<const N: usize>(name: &str, properties: [(&str, Value); N]) -> Value {
+    table(
+        &format!("The options of {name}."),
+        properties.into_iter().chain(common_options()),
+    )
+}
+
+/// The options every language has.
+fn common_options() -> [(&'static str, Value); 6] {
+    [
+        (
+            "type_mappings",
+            map(
+                string("The type in the language."),
+                "Mappings from Rust type names to types in the language.",
+            ),
+        ),
+        (
+            "formatter",
+            strings("A command run on every generated file, with the path of the file appended."),
+        ),
+        (
+            "indent_style",
+            enumeration(
+                &["tab", "space"],
+                "Indent with tabs or spaces, instead of the indentation of the language.",
+            ),
+        ),
+        (
+            "indent_size",
+            positive("The number of spaces of every level of indentation."),
+        ),
+        (
+            "max_line_width",
+            positive("Wrap comments longer than this width."),
+        ),
+        (
+            "outputs",
+            json!({
+                "type": "array",
+                "description": "The files and folders to generate into when no output is given on the command line.",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "file": string("A file to generate into."),
+                        "folder": string("A folder to generate a file per crate into."),
+                    },
+                    "additionalProperties": false,
+                    "minProperties": 1,
+                    "maxProperties": 1,
+                },
+            }),
+        ),
+    ]
+}
+
+/// The overrides of a typeshared type.
+fn type_override() -> Value {
+    table(
+        "Overrides of a typeshared type.",
+        [
+            ("rename", string("The name of the generated type.")),
+            (
+                "file",
+                string(
+                    "When using `-d`, the crate or module whose file the type is generated into.",
+                ),
+            ),
+            ("swift", strings("Protocols the Swift type conforms to.")),
+            ("kotlin", strings("Decorators of the Kotlin type.")),
+        ],
+    )
+}
+
+/// A language backend outside of typeshare.
+fn plugin() -> Value {
+    table(
+        "A language backend outside of typeshare.",
+        [
+            (
+                "command",
+                strings("The program of the plugin and its arguments."),
+            ),
+            (
+                "output_folder",
+                string("The folder to generate into when no output folder is given on the command line."),
+            ),
+            (
+                "options",
+                json!({
+                    "type": "object",
+                    "description": "Options which are passed on to the plugin.",
+                    "additionalProperties": true,
+                }),
+            ),
+        ],
+    )
+}
+
+/// A language rendered with templates.
+fn template() -> Value {
+    table(
+        "A language rendered with templates.",
+        [
+            (
+                "extension",
+                string("The extension of the files generated into an output folder."),
+            ),
+            (
+                "header",
+                string("The template file written at the start of every file."),
+            ),
+            (
+                "struct",
+                string("The template file written for every struct."),
+            ),
+            ("enum", string("The template file written for every enum.")),
+            (
+                "alias",
+                string("The template file written for every type alias."),
+            ),
+            (
+                "const",
+                string("The template file written for every constant."),
+            ),
+            (
+                "footer",
+                string("The template file written at the end of every file."),
+            ),
+        ]
+        .into_iter()
+        .chain(
+            common_options()
+                .into_iter()
+                .filter(|(key, _)| matches!(*key, "type_mappings" | "outputs")),
+        ),
+    )
+}
+
+fn table<'a>(description: &str, properties: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+    let properties = properties
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect::<Map<_, _>>();
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+fn map(values: Value, description: &str) -> Value {
+    json!({ "type": "object", "description": description, "additionalProperties": values })
+}
+
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+fn boolean(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+fn strings(description: &str) -> Value {
+    json!({ "type": "array", "description": description, "items": { "type": "string" } })
+}
+
+fn positive(description: &str) -> Value {
+    json!({ "type": "integer", "description": description, "minimum": 1 })
+}
+
+fn enumeration(values: &[&str], description: &str) -> Value {
+    json!({ "type": "string", "description": description, "enum": values })
+}
+
+/// The unknown keys of the configuration table, with suggestions for the keys they may
+/// be typos of, or the tables they may belong in.
+pub fn unknown_keys(table: &toml::Table) -> Vec<String> {
+    let schema = config_schema();
+    let mut errors = Vec::new();
+    check_table(&schema, &schema, table, "", &mut errors);
+    errors
+}
+
+fn check_table(
+    root: &Value,
+    schema: &Value,
+    table: &toml::Table,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let schema = resolve(root, schema);
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let additional = schema.get("additionalProperties");
+    // Tables of any keys, like the options of plugins, aren't checked.
+    if additional == Some(&Value::Bool(true)) {
+        return;
+    }
+    let additional = additional.filter(|a| a.is_object());
+    for (key, value) in table {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        let value_schema = match (properties.and_then(|p| p.get(key)), additional) {
+            (Some(value_schema), _) | (None, Some(value_schema)) => value_schema,
+            (None, None) => {
+                errors.push(unknown_key(root, properties, path, key));
+                continue;
+            }
+        };
+        check_value(root, value_schema, value, &key_path, errors);
+    }
+}
+
+fn check_value(
+    root: &Value,
+    schema: &Value,
+    value: &toml::Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    match value {
+        toml::Value::Table(table) => check_table(root, schema, table, path, errors),
+        toml::Value::Array(values) => {
+            if let Some(items) = resolve(root, schema).get("items") {
+                for (index, value) in values.iter().enumerate() {
+                    check_value(root, items, value, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .unwrap_or(root),
+        None => schema,
+    }
+}
+
+/// The error for an unknown key, suggesting a similar key of the same table, or the
+/// tables which have an option of that name.
+fn unknown_key(
+    root: &Value,
+    properties: Option<&Map<String, Value>>,
+    path: &str,
+    key: &str,
+) -> String {
+    let qualified = |key: &str| {
+        if path.is_empty() {
+            format!("`{key}`")
+        } else {
+            format!("`{path}.{key}`")
+        }
+    };
+    let similar = properties
+        .into_iter()
+        .flat_map(Map::keys)
+        .min_by_key(|candidate| edit_distance(key, candidate));
+    if let Some(similar) =
+        similar.filter(|similar| edit_distance(key, similar) <= key.len().div_ceil(3))
+    {
+        return format!(
+            "unknown key {}, did you mean {}?",
+            qualified(key),
+            qualified(similar)
+        );
+    }
+    let tables = root["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, table)| table["properties"].get(key).is_some())
+        .map(|(table, _)| format!("`{table}.{key}`"))
+        .collect::<Vec<_>>();
+    if !tables.is_empty() && path.is_empty() {
+        return format!(
+            "unknown key {}, did you mean {}?",
+            qualified(key),
+            tables.join(" or ")
+        );
+    }
+    if root["properties"].get(key).is_some() {
+        return format!(
+            "unknown key {}, did you mean the top-level `{key}`?",
+            qualified(key)
+        );
+    }
+    format!("unknown key {}", qualified(key))
+}
+
+/// The Levenshtein distance between the strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{config_schema, unknown_keys};
+    use crate::config::Config;
+    use serde_json::Value;
+
+    /// Every key of the value is a property of the schema, recursively.
+    fn assert_in_schema(schema: &Value, value: &Value, path: &str) {
+        let Value::Object(object) = value else {
+            return;
+        };
+        let Some(properties) = schema.get("properties") else {
+            return;
+        };
+        for (key, value) in object {
+            let property = properties
+                .get(key)
+                .unwrap_or_else(|| panic!("`{path}{key}` is missing from the schema"));
+            assert_in_schema(property, value, &format!("{path}{key}."));
+        }
+    }
+
+    #[test]
+    fn schema_has_every_option() {
+        let schema = config_schema();
+        let config = serde_json::to_value(Config::default()).unwrap();
+        assert_in_schema(&schema, &config, "");
+        let config = toml::Table::try_from(Config::default()).unwrap();
+        assert_eq!(unknown_keys(&config), Vec::<String>::new());
+    }
+}
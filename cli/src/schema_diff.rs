@@ -0,0 +1,561 @@
+//! Semantic differences between two versions of the typeshared types, classified
+//! by whether they can break the consumers of the serialized types.
+use anyhow::Context;
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    path::{Component, Path, PathBuf},
+    process,
+};
+use typeshare_core::{
+    context::ParseContext,
+    generate::parse_inputs,
+    parser::ParsedData,
+    rust_types::{RustConstExpr, RustEnum, RustEnumVariant, RustField},
+};
+
+/// Whether a change can break the consumers of the serialized types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// Values serialized with one version can fail to deserialize with the other.
+    Breaking,
+    /// Values of the old version deserialize with the new one.
+    NonBreaking,
+}
+
+impl fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compatibility::Breaking => "breaking",
+            Compatibility::NonBreaking => "non-breaking",
+        })
+    }
+}
+
+/// A change of a typeshared item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Whether the change can break consumers.
+    pub compatibility: Compatibility,
+    /// The Rust name of the item.
+    pub item: String,
+    /// What changed.
+    pub description: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.compatibility, self.item, self.description
+        )
+    }
+}
+
+/// The parts of a typeshared item which determine how it's serialized.
+#[derive(Debug, PartialEq)]
+struct Schema {
+    kind: &'static str,
+    /// The serialized name of the item.
+    name: String,
+    generic_types: Vec<String>,
+    /// The tag and content keys of an algebraic enum.
+    tagging: Option<(String, String)>,
+    /// The type of an alias, or the type and value of a constant.
+    target: Option<String>,
+    /// The fields or variants, by their serialized names.
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Member {
+    /// The type of a field, or the content of a variant.
+    ty: Option<String>,
+    /// Whether a field can be missing when deserializing.
+    optional: bool,
+}
+
+impl Schema {
+    /// What the members of the item are called.
+    fn member_kind(&self) -> &'static str {
+        match self.kind {
+            "struct" => "field",
+            _ => "variant",
+        }
+    }
+}
+
+/// Print the changes from the typeshared types of the old Rust file or directory to
+/// those of the new one, followed by how many are breaking. Returns how many are.
+pub fn print_diff(old: &Path, new: &Path) -> anyhow::Result<usize> {
+    let changes = diff(&parse(old)?, &parse(new)?);
+    for change in &changes {
+        println!("{change}");
+    }
+    let breaking = changes
+        .iter()
+        .filter(|change| change.compatibility == Compatibility::Breaking)
+        .count();
+    println!("{} changes, {breaking} of them breaking", changes.len());
+    Ok(breaking)
+}
+
+/// Print the changes from the typeshared types of a Rust file or directory at a git
+/// revision to its current ones, like [`print_diff`].
+pub fn print_baseline_diff(revision: &str, path: &Path) -> anyhow::Result<usize> {
+    let baseline = Baseline::checkout(revision, path, Path::new(""))?;
+    print_diff(&baseline.folder.join(path), path)
+}
+
+fn parse(input: &Path) -> anyhow::Result<ParsedData> {
+    parse_inputs(&ParseContext::default(), &[input.to_path_buf()])
+        .map(|(parsed_data, _)| parsed_data)
+        .with_context(|| format!("Failed to parse the types of {input:?}"))
+}
+
+/// The Rust files of a path at a git revision, in a temporary folder which is removed
+/// when dropped.
+struct Baseline {
+    folder: PathBuf,
+}
+
+impl Baseline {
+    /// Check out the Rust files of the path, relative to the directory, or the current
+    /// directory if it's empty.
+    fn checkout(revision: &str, path: &Path, directory: &Path) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            path.components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir)),
+            "The path compared with the baseline must be inside the current directory: {path:?}"
+        );
+        let files = git_in(
+            directory,
+            &["ls-tree", "-r", "-z", "--name-only", revision, "--"],
+            path,
+        )?;
+        let baseline = Baseline {
+            folder: env::temp_dir().join(format!("typeshare-baseline-{}", process::id())),
+        };
+        let files = String::from_utf8(files).context("git file names aren't UTF-8")?;
+        for file in files.split('\0').filter(|file| file.ends_with(".rs")) {
+            // `./` makes the path relative to the current directory instead of the repository
+            let source = git_in(
+                directory,
+                &["show", &format!("{revision}:./{file}")],
+                Path::new(""),
+            )?;
+            let baseline_file = baseline.folder.join(file);
+            if let Some(parent) = baseline_file.parent() {
+                fs::create_dir_all(parent).context("failed to create baseline directory")?;
+            }
+            fs::write(&baseline_file, source)
+                .with_context(|| format!("failed to write {baseline_file:?}"))?;
+        }
+        Ok(baseline)
+    }
+}
+
+impl Drop for Baseline {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.folder);
+    }
+}
+
+/// Run git with the arguments, followed by the path unless it's empty, returning its
+/// output.
+pub(crate) fn git(args: &[&str], path: &Path) -> anyhow::Result<Vec<u8>> {
+    git_in(Path::new(""), args, path)
+}
+
+/// Run git like [`git`] in the directory, or the current directory if it's empty.
+fn git_in(directory: &Path, args: &[&str], path: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut command = process::Command::new("git");
+    if !directory.as_os_str().is_empty() {
+        command.current_dir(directory);
+    }
+    command.args(args);
+    if !path.as_os_str().is_empty() {
+        command.arg(path);
+    }
+    let output = command
+        .stderr(process::Stdio::inherit())
+        .output()
+        .context("Failed to run git")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {} failed with {}",
+        args.join(" "),
+        output.status
+    );
+    Ok(output.stdout)
+}
+
+/// The changes from the old to the new typeshared types, by the Rust names of the
+/// items.
+pub fn diff(old: &ParsedData, new: &ParsedData) -> Vec<Change> {
+    let old = schemas(old);
+    let mut new = schemas(new);
+    let mut changes = Vec::new();
+    for (item, old) in old {
+        let change = |compatibility, description| Change {
+            compatibility,
+            item: item.clone(),
+            description,
+        };
+        let Some(new) = new.remove(&item) else {
+            changes.push(change(
+                Compatibility::Breaking,
+                format!("removed {}", old.kind),
+            ));
+            continue;
+        };
+        changes.extend(
+            diff_schemas(&old, &new)
+                .into_iter()
+                .map(|(compatibility, description)| change(compatibility, description)),
+        );
+    }
+    changes.extend(new.into_iter().map(|(item, new)| Change {
+        compatibility: Compatibility::NonBreaking,
+        item,
+        description: format!("added {}", new.kind),
+    }));
+    changes.sort_by(|a, b| a.item.cmp(&b.item));
+    changes
+}
+
+fn diff_schemas(old: &Schema, new: &Schema) -> Vec<(Compatibility, String)> {
+    use Compatibility::*;
+    if old.kind != new.kind {
+        return vec![(
+            Breaking,
+            format!("changed from a {} to a {}", old.kind, new.kind),
+        )];
+    }
+
+    let mut changes = Vec::new();
+    if old.name != new.name {
+        changes.push((
+            Breaking,
+            format!("renamed from `{}` to `{}`", old.name, new.name),
+        ));
+    }
+    if old.generic_types != new.generic_types {
+        changes.push((
+            Breaking,
+            format!(
+                "changed the generic parameters from <{}> to <{}>",
+                old.generic_types.join(", "),
+                new.generic_types.join(", ")
+            ),
+        ));
+    }
+    if let (Some((old_tag, old_content)), Some((new_tag, new_content))) =
+        (&old.tagging, &new.tagging)
+    {
+        if old_tag != new_tag || old_content != new_content {
+            changes.push((
+                Breaking,
+                format!(
+                    "changed the tag and content keys from `{old_tag}` and `{old_content}` to `{new_tag}` and `{new_content}`"
+                ),
+            ));
+        }
+    } else if old.tagging.is_some() != new.tagging.is_some() {
+        changes.push((Breaking, "changed how the variants are tagged".to_string()));
+    }
+    if old.target != new.target {
+        changes.push((
+            Breaking,
+            format!(
+                "changed from `{}` to `{}`",
+                old.target.as_deref().unwrap_or_default(),
+                new.target.as_deref().unwrap_or_default()
+            ),
+        ));
+    }
+
+    let member = old.member_kind();
+    for (name, old_member) in &old.members {
+        let Some(new_member) = new.members.get(name) else {
+            changes.push((Breaking, format!("removed {member} `{name}`")));
+            continue;
+        };
+        if old_member.ty != new_member.ty {
+            changes.push((
+                Breaking,
+                format!(
+                    "changed the type of {member} `{name}` from `{}` to `{}`",
+                    old_member.ty.as_deref().unwrap_or("()"),
+                    new_member.ty.as_deref().unwrap_or("()")
+                ),
+            ));
+        } else if old_member.optional && !new_member.optional {
+            changes.push((Breaking, format!("made {member} `{name}` required")));
+        } else if !old_member.optional && new_member.optional {
+            changes.push((NonBreaking, format!("made {member} `{name}` optional")));
+        }
+    }
+    for (name, new_member) in &new.members {
+        if old.members.contains_key(name) {
+            continue;
+        }
+        changes.push(match (new.kind, new_member.optional) {
+            ("struct", false) => (Breaking, format!("added required {member} `{name}`")),
+            ("struct", true) => (NonBreaking, format!("added optional {member} `{name}`")),
+            _ => (NonBreaking, format!("added {member} `{name}`")),
+        });
+    }
+    changes
+}
+
+/// The schemas of the typeshared items, by their Rust names.
+fn schemas(parsed_data: &ParsedData) -> BTreeMap<String, Schema> {
+    let mut schemas = BTreeMap::new();
+    for s in &parsed_data.structs {
+        schemas.insert(
+            s.id.original.clone(),
+            Schema {
+                kind: "struct",
+                name: s.id.renamed.clone(),
+                generic_types: s.generic_types.clone(),
+                tagging: None,
+                target: None,
+                members: fields(&s.fields),
+            },
+        );
+    }
+    for e in &parsed_data.enums {
+        let shared = e.shared();
+        let (kind, tagging) = match e {
+            RustEnum::Unit(_) => ("unit enum", None),
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                ..
+            } => (
+                "algebraic enum",
+                Some((tag_key.clone(), content_key.clone())),
+            ),
+        };
+        let members = shared
+            .variants
+            .iter()
+            .map(|v| {
+                let ty = match v {
+                    RustEnumVariant::Unit(_) => None,
+                    RustEnumVariant::Tuple { ty, .. } => Some(ty.to_string()),
+                    RustEnumVariant::AnonymousStruct { fields, .. } => Some(format!(
+                        "{{ {} }}",
+                        fields
+                            .iter()
+                            .map(|f| format!("{}: {}", f.id.renamed, f.ty))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                };
+                let member = Member {
+                    ty,
+                    optional: false,
+                };
+                (v.shared().id.renamed.clone(), member)
+            })
+            .collect();
+        schemas.insert(
+            shared.id.original.clone(),
+            Schema {
+                kind,
+                name: shared.id.renamed.clone(),
+                generic_types: shared.generic_types.clone(),
+                tagging,
+                target: None,
+                members,
+            },
+        );
+    }
+    for a in &parsed_data.aliases {
+        schemas.insert(
+            a.id.original.clone(),
+            Schema {
+                kind: "type alias",
+                name: a.id.renamed.clone(),
+                generic_types: a.generic_types.clone(),
+                tagging: None,
+                target: Some(a.r#type.to_string()),
+                members: BTreeMap::new(),
+            },
+        );
+    }
+    for c in &parsed_data.consts {
+        let RustConstExpr::Int(value) = c.expr;
+        schemas.insert(
+            c.id.original.clone(),
+            Schema {
+                kind: "constant",
+                name: c.id.renamed.clone(),
+                generic_types: Vec::new(),
+                tagging: None,
+                target: Some(format!("{}: {value}", c.r#type)),
+                members: BTreeMap::new(),
+            },
+        );
+    }
+    schemas
+}
+
+fn fields(fields: &[RustField]) -> BTreeMap<String, Member> {
+    fields
+        .iter()
+        .map(|f| {
+            let member = Member {
+                ty: Some(f.ty.to_string()),
+                optional: f.ty.is_optional() || f.has_default,
+            };
+            (f.id.renamed.clone(), member)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff, git_in, Baseline, Compatibility::*};
+    use crate::tempdir::tempdir;
+    use std::{fs, path::Path};
+    use typeshare_core::{
+        context::{ParseContext, ParseFileContext},
+        language::SINGLE_FILE_CRATE_NAME,
+        parser::{parse, ParsedData},
+    };
+
+    fn parsed(source: &str) -> ParsedData {
+        parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source.to_string(),
+                crate_name: SINGLE_FILE_CRATE_NAME,
+                file_name: String::new(),
+                file_path: "lib.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let old = parsed(
+            r#"
+            #[typeshare]
+            pub struct User { pub name: String, pub email: String, pub age: u32 }
+            #[typeshare]
+            pub enum Role { Admin, Guest }
+            #[typeshare]
+            pub type Id = String;
+            "#,
+        );
+        let new = parsed(
+            r#"
+            #[typeshare]
+            pub struct User {
+                pub name: String,
+                pub age: u16,
+                pub nickname: Option<String>,
+                #[serde(default)]
+                pub email: String,
+                pub created: String,
+            }
+            #[typeshare]
+            pub enum Role { Admin, Member }
+            #[typeshare]
+            pub struct Team { pub name: String }
+            "#,
+        );
+        let changes = diff(&old, &new)
+            .into_iter()
+            .map(|change| (change.compatibility, change.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (Breaking, "breaking: Id: removed type alias".to_string()),
+                (
+                    Breaking,
+                    "breaking: Role: removed variant `Guest`".to_string()
+                ),
+                (
+                    NonBreaking,
+                    "non-breaking: Role: added variant `Member`".to_string()
+                ),
+                (NonBreaking, "non-breaking: Team: added struct".to_string()),
+                (
+                    Breaking,
+                    "breaking: User: changed the type of field `age` from `u32` to `u16`"
+                        .to_string()
+                ),
+                (
+                    NonBreaking,
+                    "non-breaking: User: made field `email` optional".to_string()
+                ),
+                (
+                    Breaking,
+                    "breaking: User: added required field `created`".to_string()
+                ),
+                (
+                    NonBreaking,
+                    "non-breaking: User: added optional field `nickname`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_with_a_git_baseline() {
+        let repository = tempdir();
+        let repository = repository.path();
+        let src = repository.join("src");
+        fs::create_dir_all(&src).unwrap();
+        let git = |args: &[&str]| git_in(repository, args, Path::new("")).unwrap();
+        git(&["init", "--quiet"]);
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct User { pub name: String, pub email: String }\n",
+        )
+        .unwrap();
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=typeshare",
+            "-c",
+            "user.email=typeshare@example.com",
+            "commit",
+            "--quiet",
+            "--message",
+            "baseline",
+        ]);
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct User { pub name: String }\n",
+        )
+        .unwrap();
+
+        let baseline = Baseline::checkout("HEAD", Path::new("src"), repository).unwrap();
+        let changes = diff(
+            &super::parse(&baseline.folder.join("src")).unwrap(),
+            &super::parse(&src).unwrap(),
+        )
+        .into_iter()
+        .map(|change| (change.compatibility, change.to_string()))
+        .collect::<Vec<_>>();
+        let folder = baseline.folder.clone();
+        drop(baseline);
+        assert_eq!(
+            changes,
+            [(
+                Breaking,
+                "breaking: User: removed field `email`".to_string()
+            )]
+        );
+        assert!(!folder.exists());
+    }
+}
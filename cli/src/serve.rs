@@ -0,0 +1,258 @@
+//! Answering requests for the generated source of types over stdin and stdout, for
+//! editors previewing the types under the cursor.
+//!
+//! Every line of stdin is a JSON request, answered by a line of JSON on stdout with the
+//! same `id`. A request names the language, and the type by its Rust name, or the Rust
+//! file and the 1-based line of the cursor:
+//!
+//! ```json
+//! {"id": 1, "lang": "typescript", "file": "src/user.rs", "line": 12}
+//! {"id": 2, "lang": "swift", "type": "User"}
+//! ```
+//!
+//! The response has the Rust name of the type and its generated source, or an error:
+//!
+//! ```json
+//! {"id": 1, "result": {"type": "User", "source": "export interface User {\n..."}}
+//! {"id": 2, "error": "User isn't typeshared"}
+//! ```
+use crate::{
+    args::{Args, AvailableLanguage},
+    config, override_configuration,
+    snapshot::type_outputs,
+    supported_language,
+    watch::{snapshot, Snapshot},
+};
+use anyhow::Context;
+use clap::ValueEnum;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+use typeshare_core::{
+    language::{ItemOutput, SupportedLanguage},
+    parser::item_at_line,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    /// Returned with the response, so requests can be answered out of order.
+    #[serde(default)]
+    id: Value,
+    lang: String,
+    #[serde(rename = "type")]
+    type_name: Option<String>,
+    file: Option<PathBuf>,
+    line: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    id: Value,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Result {
+        #[serde(rename = "type")]
+        type_name: String,
+        source: String,
+    },
+    Error(String),
+}
+
+/// The generated types of every language asked for, until the input files change.
+struct Server<'a> {
+    config_file: Option<&'a Path>,
+    options: &'a Args,
+    snapshot: Option<Snapshot>,
+    outputs: HashMap<SupportedLanguage, Vec<ItemOutput>>,
+}
+
+/// Answer the requests of stdin on stdout until stdin is closed.
+pub fn serve(config_file: Option<&Path>, options: &Args) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !options.reads_stdin(),
+        "Requests are read from stdin, so the Rust source can't be"
+    );
+    info!("typeshare is answering requests on stdin");
+    let mut server = Server {
+        config_file,
+        options,
+        snapshot: None,
+        outputs: HashMap::new(),
+    };
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line.context("Failed to read a request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = server.answer(&line);
+        let json = serde_json::to_string(&response).context("Failed to serialize a response")?;
+        writeln!(stdout, "{json}")
+            .and_then(|()| stdout.flush())
+            .context("Failed to write a response")?;
+    }
+    Ok(())
+}
+
+impl Server<'_> {
+    fn answer(&mut self, line: &str) -> Response {
+        let request = match serde_json::from_str::<Request>(line) {
+            Ok(request) => request,
+            Err(error) => {
+                return Response {
+                    id: Value::Null,
+                    outcome: Outcome::Error(format!("Invalid request: {error}")),
+                }
+            }
+        };
+        let outcome = match self.preview(&request) {
+            Ok((type_name, source)) => Outcome::Result { type_name, source },
+            Err(error) => Outcome::Error(format!("{error:#}")),
+        };
+        Response {
+            id: request.id,
+            outcome,
+        }
+    }
+
+    /// The Rust name and the generated source of the type of the request.
+    fn preview(&mut self, request: &Request) -> anyhow::Result<(String, String)> {
+        let type_name = match (&request.type_name, &request.file, request.line) {
+            (Some(type_name), None, None) => type_name.clone(),
+            (None, Some(file), Some(line)) => {
+                let source =
+                    fs::read_to_string(file).with_context(|| format!("Failed to read {file:?}"))?;
+                item_at_line(&source, line)
+                    .with_context(|| format!("Failed to parse {file:?}"))?
+                    .with_context(|| format!("There's no type at line {line} of {file:?}"))?
+            }
+            _ => anyhow::bail!("A request has either a type, or a file and a line"),
+        };
+        let outputs = self.outputs(&request.lang)?;
+        let output = outputs
+            .iter()
+            .find(|output| output.name == type_name)
+            .with_context(|| format!("{type_name} isn't typeshared"))?;
+        Ok((type_name, output.source()))
+    }
+
+    /// The generated types of the language, generated again if the input files changed.
+    fn outputs(&mut self, lang: &str) -> anyhow::Result<&[ItemOutput]> {
+        let language = AvailableLanguage::from_str(lang, true)
+            .map_err(|_| anyhow::anyhow!("Unsupported language {lang:?}"))?;
+        let current = snapshot(self.config_file, self.options)?;
+        if self.snapshot.as_ref() != Some(&current) {
+            self.outputs.clear();
+            self.snapshot = Some(current);
+        }
+        let language = supported_language(language);
+        if !self.outputs.contains_key(&language) {
+            let config = config::load_config(self.config_file, self.options.profile.as_deref())
+                .context("Unable to read configuration file")?;
+            let config = override_configuration(config, self.options)?;
+            let outputs = type_outputs(&config, self.options, language)?;
+            self.outputs.insert(language, outputs);
+        }
+        Ok(&self.outputs[&language])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Server;
+    use crate::args::Args;
+    use crate::tempdir::tempdir;
+    use clap::Parser;
+    use std::{collections::HashMap, fs};
+
+    #[test]
+    fn previews_the_generated_types() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        let file = src.join("lib.rs");
+        fs::write(
+            &file,
+            "/// A user.\n#[typeshare]\npub struct User {\n    pub name: String,\n}\n\n\
+             #[typeshare]\npub enum Role {\n    Admin,\n}\n",
+        )
+        .unwrap();
+        let options = Args::parse_from(["typeshare", src.to_str().unwrap()]);
+        let mut server = Server {
+            config_file: None,
+            options: &options,
+            snapshot: None,
+            outputs: HashMap::new(),
+        };
+        let mut answer = |request: serde_json::Value| {
+            serde_json::to_value(server.answer(&request.to_string())).unwrap()
+        };
+
+        assert_eq!(
+            answer(serde_json::json!({ "id": 1, "lang": "typescript", "type": "User" })),
+            serde_json::json!({
+                "id": 1,
+                "result": {
+                    "type": "User",
+                    "source": "/** A user. */\nexport interface User {\n\tname: string;\n}\n\n",
+                },
+            })
+        );
+        let by_line = answer(serde_json::json!({
+            "id": "role", "lang": "swift", "file": file, "line": 9,
+        }));
+        assert_eq!(by_line["id"], "role");
+        assert_eq!(by_line["result"]["type"], "Role");
+        assert!(by_line["result"]["source"]
+            .as_str()
+            .unwrap()
+            .contains("public enum Role"));
+        // Doc comments are part of the type.
+        assert_eq!(
+            answer(serde_json::json!({ "id": 3, "lang": "typescript", "file": file, "line": 1 }))
+                ["result"]["type"],
+            "User"
+        );
+        assert_eq!(
+            answer(serde_json::json!({ "id": 4, "lang": "typescript", "file": file, "line": 6 })),
+            serde_json::json!({
+                "id": 4,
+                "error": format!("There's no type at line 6 of {file:?}"),
+            })
+        );
+        assert_eq!(
+            answer(serde_json::json!({ "id": 5, "lang": "cobol", "type": "User" }))["error"],
+            "Unsupported language \"cobol\""
+        );
+        assert_eq!(
+            answer(serde_json::json!({ "id": 6, "lang": "kotlin", "type": "Admin" }))["error"],
+            "Admin isn't typeshared"
+        );
+        // Languages which can't generate the types answer with the error.
+        assert!(
+            answer(serde_json::json!({ "id": 7, "lang": "scala", "type": "User" }))["error"]
+                .as_str()
+                .unwrap()
+                .ends_with("package name must be provided")
+        );
+        fs::write(&file, "#[typeshare]\npub const LIMIT: u32 = 10;\n").unwrap();
+        assert!(
+            answer(serde_json::json!({ "id": 8, "lang": "kotlin", "type": "LIMIT" }))["error"]
+                .as_str()
+                .unwrap()
+                .ends_with("typeshare doesn't generate constants in Kotlin")
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Snapshots of the generated source of every type, which consumer projects keep in their
+//! repository to notice when the generated types change, like with an upgrade of
+//! typeshare or a change of the configuration.
+use crate::{
+    args::Args,
+    check_parse_errors,
+    config::{self, Config},
+    configured_languages,
+    failure::Failure,
+    filter::TypeFilter,
+    language, override_configuration,
+    overrides::apply_overrides,
+    parse::parallel_parse,
+    supported_language, walker_builder,
+    writer::{check_generated, write_files, Mode, WriteOptions},
+};
+use anyhow::Context;
+use log::info;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+use typeshare_core::{
+    context::ParseContext,
+    language::{ItemOutput, SupportedLanguage, SINGLE_FILE_CRATE_NAME},
+    reconcile::{collect_serde_renames, reconcile_aliases_with},
+};
+
+/// Write the snapshots of every type, in every language with outputs in the
+/// configuration or the language of the options, into a folder per language. With
+/// `--check`, they're verified instead, failing if any snapshot changed, is missing or is
+/// of a type which isn't typeshared anymore.
+pub fn snapshot_tests(
+    config_file: Option<&Path>,
+    options: &Args,
+    snapshots: &Path,
+) -> anyhow::Result<()> {
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    let languages = match options.language {
+        Some(language) => vec![language],
+        None => configured_languages(&config),
+    };
+    anyhow::ensure!(
+        !languages.is_empty(),
+        "Give a language with --lang, or outputs for a language in the configuration file"
+    );
+    let mode = if options.check {
+        Mode::Check
+    } else {
+        Mode::Write
+    };
+
+    let mut stale = Vec::new();
+    let mut generated = Vec::new();
+    for language_type in languages.into_iter().map(supported_language) {
+        let folder = snapshots.join(format!("{language_type:?}").to_lowercase());
+        let files = type_snapshots(&config, options, language_type)?;
+        stale.extend(stale_snapshots(&folder, language_type, &files)?);
+        generated.extend(write_files(
+            &folder,
+            files,
+            &WriteOptions {
+                mode,
+                file_per_type: false,
+                formatter: &[],
+                checksum_comment: None,
+                force: options.force,
+            },
+        )?);
+    }
+
+    check_generated(&generated, mode)?;
+    if mode == Mode::Check {
+        anyhow::ensure!(
+            stale.is_empty(),
+            Failure::OutOfDate.error(format!(
+                "There are snapshots of types which aren't typeshared anymore: {stale:?}"
+            ))
+        );
+    }
+    for file in &stale {
+        info!("Removing the snapshot {file:?}");
+        fs::remove_file(file).with_context(|| format!("Failed to remove the snapshot {file:?}"))?;
+    }
+    Ok(())
+}
+
+/// The generated source of every type in the language, by the file of its snapshot in
+/// the folder of the language.
+fn type_snapshots(
+    config: &Config,
+    options: &Args,
+    language_type: SupportedLanguage,
+) -> anyhow::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let extension = language_type.language_extension();
+    Ok(type_outputs(config, options, language_type)?
+        .into_iter()
+        .map(|item| {
+            (
+                PathBuf::from(format!("{}.{extension}", item.name)),
+                item.source().into_bytes(),
+            )
+        })
+        .collect())
+}
+
+/// The generated source of every type in the language, generated into a single file.
+pub(crate) fn type_outputs(
+    config: &Config,
+    options: &Args,
+    language_type: SupportedLanguage,
+) -> anyhow::Result<Vec<ItemOutput>> {
+    let mut lang = language(language_type, config.clone(), false);
+    let parse_context = ParseContext {
+        ignored_types: lang.ignored_reference_types(),
+        multi_file: false,
+        target_os: config.target_os.clone(),
+    };
+    let walker_builder = walker_builder(&options.directories, options, config)?;
+    let mut parsed_data = parallel_parse(
+        &parse_context,
+        false,
+        walker_builder,
+        language_type,
+        lang.as_ref(),
+    )?;
+    apply_overrides(&config.types, &mut parsed_data, Some(language_type));
+    let serde_renamed = collect_serde_renames(&parsed_data);
+    reconcile_aliases_with(&mut parsed_data, &serde_renamed);
+    TypeFilter::new(&config.only_types, &config.exclude_types)?.apply(&mut parsed_data);
+    check_parse_errors(&parsed_data)?;
+
+    let parsed_data = parsed_data
+        .remove(&SINGLE_FILE_CRATE_NAME)
+        .unwrap_or_default();
+    let output = lang
+        .emit(&HashMap::new(), parsed_data)
+        .with_context(|| format!("Failed to generate the {language_type:?} types"))?;
+    Ok(output.items)
+}
+
+/// The snapshots in the folder of the language which aren't of any of its types.
+fn stale_snapshots(
+    folder: &Path,
+    language_type: SupportedLanguage,
+    files: &[(PathBuf, Vec<u8>)],
+) -> anyhow::Result<Vec<PathBuf>> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Ok(Vec::new());
+    };
+    let snapshots = files
+        .iter()
+        .map(|(path, _)| path.as_os_str())
+        .collect::<BTreeSet<_>>();
+    let mut stale = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("Failed to read the snapshots in {folder:?}"))?
+            .path();
+        let is_snapshot = path
+            .extension()
+            .is_some_and(|extension| extension == language_type.language_extension());
+        if is_snapshot
+            && path
+                .file_name()
+                .is_some_and(|name| !snapshots.contains(name))
+        {
+            stale.push(path);
+        }
+    }
+    stale.sort();
+    Ok(stale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::snapshot_tests;
+    use crate::args::Args;
+    use crate::tempdir::tempdir;
+    use clap::Parser;
+    use std::fs;
+
+    #[test]
+    fn writes_and_verifies_snapshots() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct User { pub name: String }\n",
+        )
+        .unwrap();
+        let snapshots = root.path().join("snapshots");
+        let options = |check: &[&str]| {
+            let mut args = vec!["typeshare", "--lang", "typescript"];
+            args.extend(check);
+            args.push(src.to_str().unwrap());
+            Args::parse_from(args)
+        };
+
+        snapshot_tests(None, &options(&[]), &snapshots).unwrap();
+        assert_eq!(
+            fs::read_to_string(snapshots.join("typescript/User.ts")).unwrap(),
+            "export interface User {\n\tname: string;\n}\n\n"
+        );
+        snapshot_tests(None, &options(&["--check"]), &snapshots).unwrap();
+
+        fs::write(snapshots.join("typescript/Group.ts"), "").unwrap();
+        assert!(snapshot_tests(None, &options(&["--check"]), &snapshots).is_err());
+        snapshot_tests(None, &options(&[]), &snapshots).unwrap();
+        assert!(!snapshots.join("typescript/Group.ts").exists());
+    }
+}
@@ -0,0 +1,34 @@
+//! Temporary directories for tests, like those of the `tempfile` crate, which are
+//! removed when they're dropped, even if the test fails.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Create a new empty directory, which no other test of the process uses.
+pub fn tempdir() -> TempDir {
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+        "typeshare-{}-{}",
+        process::id(),
+        CREATED.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&path).unwrap();
+    TempDir(path)
+}
@@ -0,0 +1,111 @@
+//! Generating types with templates, for output formats typeshare has no language for.
+use crate::{
+    args::Args,
+    config::{self, OutputDestination},
+    destinations, override_configuration,
+    plugin::parse_crates,
+    writer::{check_generated, write_generated, Mode, Output, WriteOptions},
+};
+use anyhow::Context;
+use log::info;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use typeshare_core::language::{Template, TemplateSources};
+
+/// Generate the types of the directories with the templates of the configuration file,
+/// into their outputs.
+pub fn generate_template(
+    config_file: Option<&Path>,
+    options: &Args,
+    name: &str,
+) -> anyhow::Result<()> {
+    info!("typeshare started generating types with the template {name:?}");
+
+    let config = config::load_config(config_file, options.profile.as_deref())
+        .context("Unable to read configuration file")?;
+    let config = override_configuration(config, options)?;
+    let params = config.templates.get(name).with_context(|| {
+        format!("There's no template {name:?} in the configuration file, as `[templates.{name}]`")
+    })?;
+    anyhow::ensure!(
+        !options.reads_stdin(),
+        "Templates generate types from Rust files"
+    );
+    let read = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map(|path| {
+                fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read the template {path:?}"))
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    };
+    let sources = TemplateSources {
+        header: read(&params.header)?,
+        struct_template: read(&params.struct_template)?,
+        enum_template: read(&params.enum_template)?,
+        alias_template: read(&params.alias)?,
+        const_template: read(&params.const_template)?,
+        footer: read(&params.footer)?,
+    };
+    let mut template = Template::new(sources, params.type_mappings.clone())?;
+
+    let destinations = destinations(&options.output, &params.outputs);
+    let outputs = if destinations.is_empty() {
+        vec![Output::Stdout]
+    } else {
+        destinations
+            .iter()
+            .map(|destination| match destination {
+                OutputDestination::File(file) if file.as_os_str() == "-" => Output::Stdout,
+                OutputDestination::File(file) => Output::File(file),
+                OutputDestination::Folder(folder) => Output::Folder(folder),
+            })
+            .collect()
+    };
+    let mode = if options.check {
+        Mode::Check
+    } else if options.dry_run {
+        Mode::DryRun
+    } else {
+        Mode::Write
+    };
+    let write_options = WriteOptions {
+        mode,
+        file_per_type: false,
+        formatter: &[],
+        checksum_comment: None,
+        force: options.force,
+    };
+
+    let mut files = Vec::new();
+    for destination in outputs {
+        anyhow::ensure!(
+            mode == Mode::Write || !matches!(destination, Output::Stdout),
+            "--check and --dry-run need an output file or folder to compare the generated types with"
+        );
+        let multi_file = matches!(destination, Output::Folder(_));
+        anyhow::ensure!(
+            !multi_file || !params.extension.is_empty(),
+            "The template {name:?} needs an `extension` to generate into an output folder"
+        );
+        let mut parsed_data = parse_crates(&config, options, multi_file)?;
+        for (crate_name, parsed_data) in &mut parsed_data {
+            parsed_data.file_name = format!("{crate_name}.{}", params.extension);
+        }
+        files.extend(write_generated(
+            destination,
+            &mut template,
+            parsed_data,
+            HashMap::new(),
+            &write_options,
+        )?);
+    }
+    check_generated(&files, mode)?;
+
+    info!("typeshare finished generating types with the template {name:?}");
+    Ok(())
+}
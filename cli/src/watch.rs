@@ -0,0 +1,88 @@
+//! Regenerating types when the input files change.
+use crate::{
+    args::Args,
+    config::{config_files, crate_config_files, load_config},
+    generate_languages, override_configuration,
+    report::Report,
+    walker_builder,
+};
+use anyhow::Context;
+use log::{error, info};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the input files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of the files typeshare reads.
+pub(crate) type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Generate types, and generate them again whenever a Rust file in the input
+/// directories or the configuration file changes. Generated files are only
+/// written when their contents change, so only the outputs affected by a change
+/// are touched.
+///
+/// This runs until the process is interrupted. Errors while generating are
+/// logged instead of returned, so that they can be fixed while watching.
+pub fn watch(config_file: Option<&Path>, options: &Args) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !options.reads_stdin(),
+        "Rust source read from stdin can't be watched for changes"
+    );
+
+    let mut last_snapshot = None;
+    loop {
+        let current = snapshot(config_file, options)?;
+        if last_snapshot.as_ref() != Some(&current) {
+            if last_snapshot.is_some() {
+                info!("typeshare detected changes in the input files");
+            }
+            if let Err(err) = generate_languages(config_file, options, &mut Report::default()) {
+                error!("typeshare failed to generate types: {err}");
+            }
+            info!("typeshare is watching {} files for changes", current.len());
+            last_snapshot = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Take a snapshot of the Rust files in the input directories and the configuration file.
+pub(crate) fn snapshot(config_file: Option<&Path>, options: &Args) -> anyhow::Result<Snapshot> {
+    // An invalid configuration is reported when generating, until then all Rust files are watched
+    let config = load_config(config_file, options.profile.as_deref())
+        .map_err(anyhow::Error::from)
+        .and_then(|config| override_configuration(config, options))
+        .unwrap_or_default();
+    let mut snapshot = walker_builder(&options.directories, options, &config)?
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.into_path(), modified))
+        })
+        .collect::<Snapshot>();
+
+    // The configuration files, of the crates as well, and the files they extend
+    let crate_files = crate_config_files(config_file, &options.directories).into_values();
+    let config_files = config_files(config_file)
+        .into_iter()
+        .chain(crate_files.flat_map(|crate_file| config_files(Some(&crate_file))));
+    for config_file in config_files {
+        if !config_file.exists() {
+            continue;
+        }
+        let modified = fs::metadata(&config_file)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Failed to read the modification time of {config_file:?}"))?;
+        snapshot.insert(config_file, modified);
+    }
+
+    Ok(snapshot)
+}
@@ -1,10 +1,13 @@
 //! Generated source file output.
+use crate::{checksum, diff::unified_diff, failure::Failure};
 use anyhow::Context;
-use log::info;
+use log::{debug, error, info};
 use std::{
     collections::{BTreeMap, HashMap},
-    fs,
-    path::Path,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
 };
 use typeshare_core::{
     language::{CrateName, CrateTypes, Language, SINGLE_FILE_CRATE_NAME},
@@ -15,21 +18,103 @@ use typeshare_core::{
 pub enum Output<'a> {
     File(&'a Path),
     Folder(&'a Path),
+    Stdout,
+}
+
+/// What to do with the generated files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Write the files that changed.
+    Write,
+    /// Print a diff of the files that changed without writing them, and fail if any did.
+    Check,
+    /// Print which files would be created or changed without writing them.
+    DryRun,
+}
+
+/// How the generated files are written.
+#[derive(Debug, Clone, Copy)]
+pub struct WriteOptions<'a> {
+    /// What to do with the generated files.
+    pub mode: Mode,
+    /// Write every type of an output folder to its own file.
+    pub file_per_type: bool,
+    /// Command formatting every file before it's compared with the existing one,
+    /// unless it's empty.
+    pub formatter: &'a [String],
+    /// The line comment of the language, to end every file with a checksum marker.
+    pub checksum_comment: Option<&'a str>,
+    /// Overwrite files that were edited since they were generated.
+    pub force: bool,
+}
+
+/// A file generated from the parsed data.
+#[derive(Debug)]
+pub struct GeneratedFile {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The Rust names of the types in the file.
+    pub types: Vec<String>,
+    /// Whether the existing file was already up to date.
+    pub up_to_date: bool,
 }
 
 /// Write the parsed data to the one or more files depending on command line options.
+/// Returns the generated files, which is empty when writing to stdout. Check the
+/// returned files with [`check_generated`].
 pub fn write_generated(
     destination: Output<'_>,
     lang: &mut (impl Language + ?Sized),
     crate_parsed_data: BTreeMap<CrateName, ParsedData>,
     import_candidates: CrateTypes,
-) -> Result<(), anyhow::Error> {
+    options: &WriteOptions<'_>,
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
     match destination {
-        Output::File(path) => write_single_file(lang, path, crate_parsed_data),
+        Output::File(path) => write_single_file(lang, path, crate_parsed_data, options),
         Output::Folder(path) => {
-            write_multiple_files(lang, path, crate_parsed_data, import_candidates)
+            write_multiple_files(lang, path, crate_parsed_data, import_candidates, options)
+        }
+        Output::Stdout => {
+            write_stdout(lang, crate_parsed_data)?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Write the contents of the files, by their paths relative to the output folder, like
+/// the files of a plugin. Check the returned files with [`check_generated`].
+pub fn write_files(
+    output_folder: &Path,
+    files: impl IntoIterator<Item = (PathBuf, Vec<u8>)>,
+    options: &WriteOptions<'_>,
+) -> anyhow::Result<Vec<GeneratedFile>> {
+    files
+        .into_iter()
+        .map(|(path, contents)| {
+            let path = output_folder.join(path);
+            Ok(GeneratedFile {
+                up_to_date: check_write_file(&path, contents, options)?,
+                path,
+                types: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Fail if any of the generated files is out of date when checking, and print how
+/// many files would be written for a dry run.
+pub fn check_generated(files: &[GeneratedFile], mode: Mode) -> anyhow::Result<()> {
+    let out_of_date = files.iter().filter(|file| !file.up_to_date).count();
+    match mode {
+        Mode::Check if out_of_date > 0 => {
+            return Err(
+                Failure::OutOfDate.error(format!("{out_of_date} generated files are out of date"))
+            )
         }
+        Mode::DryRun => println!("{out_of_date} generated files would be written"),
+        _ => {}
     }
+    Ok(())
 }
 
 /// Write multiple module files.
@@ -38,34 +123,184 @@ fn write_multiple_files(
     output_folder: &Path,
     crate_parsed_data: BTreeMap<CrateName, ParsedData>,
     import_candidates: CrateTypes,
-) -> Result<(), anyhow::Error> {
-    for (_crate_name, parsed_data) in crate_parsed_data {
+    options: &WriteOptions<'_>,
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
+    let mut generated_files = Vec::new();
+    let files = crate_parsed_data.into_values().flat_map(|parsed_data| {
+        if options.file_per_type {
+            split_by_type(parsed_data)
+        } else {
+            vec![parsed_data]
+        }
+    });
+    for parsed_data in files {
         let outfile = Path::new(output_folder).join(&parsed_data.file_name);
+        let types = rust_type_names(&parsed_data);
         let mut generated_contents = Vec::new();
         lang.generate_types(&mut generated_contents, &import_candidates, parsed_data)?;
-        check_write_file(&outfile, generated_contents)?;
+        generated_files.push(GeneratedFile {
+            up_to_date: check_write_file(&outfile, generated_contents, options)?,
+            path: outfile,
+            types,
+        });
     }
 
-    lang.post_generation(&output_folder.as_os_str().to_string_lossy())
-        .context("Post generation failed")?;
+    // The files written after generation, like Swift package manifests, aren't checked
+    if options.mode == Mode::Write {
+        lang.post_generation(&output_folder.as_os_str().to_string_lossy())
+            .context("Post generation failed")?;
+    }
 
-    Ok(())
+    Ok(generated_files)
 }
 
-/// Write the file if the contents have changed.
-fn check_write_file(outfile: &Path, output: Vec<u8>) -> anyhow::Result<()> {
-    match fs::read(outfile) {
-        Ok(buf) if buf == output => {
+/// The Rust names of the types, and constants, of the parsed data.
+fn rust_type_names(parsed_data: &ParsedData) -> Vec<String> {
+    let structs = parsed_data.structs.iter().map(|s| &s.id);
+    let enums = parsed_data.enums.iter().map(|e| &e.shared().id);
+    let aliases = parsed_data.aliases.iter().map(|a| &a.id);
+    let consts = parsed_data.consts.iter().map(|c| &c.id);
+    structs
+        .chain(enums)
+        .chain(aliases)
+        .chain(consts)
+        .map(|id| id.original.clone())
+        .collect()
+}
+
+/// Split the parsed data of a file into a file per type, named after the type,
+/// in a folder named after the original file. Constants stay in the original file.
+fn split_by_type(parsed_data: ParsedData) -> Vec<ParsedData> {
+    let ParsedData {
+        structs,
+        enums,
+        aliases,
+        consts,
+        import_types,
+        crate_name,
+        file_name,
+        multi_file,
+        ..
+    } = parsed_data;
+
+    let file_path = Path::new(&file_name);
+    let folder = file_path.with_extension("");
+    let extension = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let type_file = |type_name: &str| {
+        folder
+            .join(format!("{type_name}.{extension}"))
+            .to_string_lossy()
+            .into_owned()
+    };
+    // Every file keeps the imports of the original file
+    let file = |file_name: String| ParsedData {
+        import_types: import_types.clone(),
+        crate_name: crate_name.clone(),
+        file_name,
+        multi_file,
+        ..Default::default()
+    };
+
+    let mut files = Vec::new();
+    for s in structs {
+        let file_name = type_file(&s.id.renamed);
+        files.push(ParsedData {
+            structs: vec![s],
+            ..file(file_name)
+        });
+    }
+    for e in enums {
+        let file_name = type_file(&e.shared().id.renamed);
+        files.push(ParsedData {
+            enums: vec![e],
+            ..file(file_name)
+        });
+    }
+    for a in aliases {
+        let file_name = type_file(&a.id.renamed);
+        files.push(ParsedData {
+            aliases: vec![a],
+            ..file(file_name)
+        });
+    }
+    if !consts.is_empty() {
+        files.push(ParsedData {
+            consts,
+            ..file(file_name.clone())
+        });
+    }
+    files
+}
+
+/// Write the file if the contents have changed. Returns whether the file was up to date.
+/// Empty outputs aren't written, and remove the file generated before instead.
+///
+/// In [`Mode::Check`], a diff of the changes is printed instead of writing the file, and
+/// in [`Mode::DryRun`] only the file name. Unless forced, files which were edited since
+/// their checksum marker was added aren't written.
+fn check_write_file(
+    outfile: &Path,
+    output: Vec<u8>,
+    options: &WriteOptions<'_>,
+) -> anyhow::Result<bool> {
+    let mode = options.mode;
+    let mut output = if options.formatter.is_empty() || output.is_empty() {
+        output
+    } else {
+        format_output(options.formatter, outfile, output)?
+    };
+    if let Some(comment) = options.checksum_comment.filter(|_| !output.is_empty()) {
+        checksum::add_marker(&mut output, comment);
+    }
+    let existing = fs::read(outfile).ok();
+    match existing {
+        Some(ref buf) if *buf == output => {
             // avoid writing the file to leave the mtime intact
             // for tools which might use it to know when to
             // rebuild.
             info!("Skipping writing to {outfile:?} no changes");
-            return Ok(());
+            return Ok(true);
         }
+        // Empty outputs aren't written
+        None if output.is_empty() => return Ok(true),
         _ => {}
     }
 
-    if !output.is_empty() {
+    if mode == Mode::Check {
+        let old = String::from_utf8_lossy(existing.as_deref().unwrap_or_default());
+        let new = String::from_utf8_lossy(&output);
+        let name = outfile.to_string_lossy();
+        error!("{outfile:?} is out of date");
+        if let Some(diff) = unified_diff(&old, &new, &name, &name) {
+            print!("{diff}");
+        }
+        return Ok(false);
+    }
+
+    if mode == Mode::DryRun {
+        let action = match existing {
+            Some(_) if output.is_empty() => "remove",
+            Some(_) => "change",
+            None => "create",
+        };
+        println!("Would {action} {}", outfile.display());
+        return Ok(false);
+    }
+
+    if !options.force && existing.as_deref().is_some_and(checksum::is_modified) {
+        return Err(Failure::Modified.error(format!(
+            "{outfile:?} was edited since it was generated, pass --force to overwrite it"
+        )));
+    }
+
+    if output.is_empty() {
+        fs::remove_file(outfile)
+            .with_context(|| format!("failed to remove {}", outfile.to_string_lossy()))?;
+        debug!("Removed {outfile:?}");
+    } else {
         let out_dir = outfile
             .parent()
             .with_context(|| format!("Could not get parent for {outfile:?}"))?;
@@ -76,8 +311,42 @@ fn check_write_file(outfile: &Path, output: Vec<u8>) -> anyhow::Result<()> {
 
         fs::write(outfile, output)
             .with_context(|| format!("failed to write output: {}", outfile.to_string_lossy()))?;
+        debug!("Wrote {outfile:?}");
     }
-    Ok(())
+    Ok(false)
+}
+
+/// Run the formatter command on the generated contents of a file. The contents are
+/// formatted in a temporary file next to the output file, so that the formatter finds
+/// the same configuration as for the output file.
+fn format_output(formatter: &[String], outfile: &Path, output: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    let file_name = outfile
+        .file_name()
+        .with_context(|| format!("Could not get file name of {outfile:?}"))?;
+    let folder = outfile
+        .parent()
+        .filter(|folder| folder.is_dir())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(env::temp_dir);
+    let mut temp_name = std::ffi::OsString::from(".typeshare.");
+    temp_name.push(file_name);
+    let temp_file = folder.join(temp_name);
+    fs::write(&temp_file, output)
+        .with_context(|| format!("failed to write temporary file {temp_file:?}"))?;
+
+    let (command, args) = formatter.split_first().expect("formatter isn't empty");
+    let status = process::Command::new(command)
+        .args(args)
+        .arg(&temp_file)
+        .status();
+    let formatted = fs::read(&temp_file);
+    let _ = fs::remove_file(&temp_file);
+    let status = status.with_context(|| format!("Failed to run formatter {command:?}"))?;
+    anyhow::ensure!(
+        status.success(),
+        "Formatter {command:?} failed on {outfile:?} with {status}"
+    );
+    formatted.with_context(|| format!("failed to read formatted file {temp_file:?}"))
 }
 
 /// Write all types to a single file.
@@ -85,15 +354,176 @@ fn write_single_file(
     lang: &mut (impl Language + ?Sized),
     file_name: &Path,
     mut crate_parsed_data: BTreeMap<CrateName, ParsedData>,
-) -> Result<(), anyhow::Error> {
+    options: &WriteOptions<'_>,
+) -> Result<Vec<GeneratedFile>, anyhow::Error> {
     let parsed_data = crate_parsed_data
         .remove(&SINGLE_FILE_CRATE_NAME)
         .context("Could not get parsed data for single file output")?;
 
+    let types = rust_type_names(&parsed_data);
     let mut output = Vec::new();
     lang.generate_types(&mut output, &HashMap::new(), parsed_data)?;
 
     let outfile = Path::new(file_name).to_path_buf();
-    check_write_file(&outfile, output)?;
-    Ok(())
+    Ok(vec![GeneratedFile {
+        up_to_date: check_write_file(&outfile, output, options)?,
+        path: outfile,
+        types,
+    }])
+}
+
+/// Write all types to stdout.
+fn write_stdout(
+    lang: &mut (impl Language + ?Sized),
+    mut crate_parsed_data: BTreeMap<CrateName, ParsedData>,
+) -> Result<(), anyhow::Error> {
+    let mut output = Vec::new();
+    if let Some(parsed_data) = crate_parsed_data.remove(&SINGLE_FILE_CRATE_NAME) {
+        lang.generate_types(&mut output, &HashMap::new(), parsed_data)?;
+    }
+
+    io::stdout()
+        .write_all(&output)
+        .context("failed to write output to stdout")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_write_file, Mode, WriteOptions};
+    use crate::{args::Args, generate_types, report::Report, tempdir::tempdir};
+    use clap::Parser;
+    use std::{fs, path::Path};
+
+    fn options(mode: Mode) -> WriteOptions<'static> {
+        WriteOptions {
+            mode,
+            file_per_type: false,
+            formatter: &[],
+            checksum_comment: None,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn writing_empty_output_removes_the_file() {
+        let folder = tempdir();
+        let path = folder.path().join("types.ts");
+        fs::write(&path, "export type Id = string;\n").unwrap();
+
+        let up_to_date = check_write_file(&path, Vec::new(), &options(Mode::Write)).unwrap();
+        let exists = path.exists();
+        let checked = check_write_file(&path, Vec::new(), &options(Mode::Check)).unwrap();
+        assert!(!up_to_date);
+        assert!(!exists);
+        assert!(checked);
+    }
+
+    #[test]
+    fn checking_empty_output_reports_the_stale_file() {
+        let folder = tempdir();
+        let path = folder.path().join("types.ts");
+        fs::write(&path, "export type Id = string;\n").unwrap();
+
+        let checked = check_write_file(&path, Vec::new(), &options(Mode::Check)).unwrap();
+        let exists = path.exists();
+        assert!(!checked);
+        assert!(exists);
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let folder = tempdir();
+        let existing = folder.path().join("types.ts");
+        fs::write(&existing, "export type Id = string;\n").unwrap();
+        let created = folder.path().join("nested/types.ts");
+
+        let options = options(Mode::DryRun);
+        let output = b"export type Id = number;\n".to_vec();
+        let changed = check_write_file(&existing, output.clone(), &options).unwrap();
+        let created_up_to_date = check_write_file(&created, output, &options).unwrap();
+        let removed = check_write_file(&existing, Vec::new(), &options).unwrap();
+        assert!(!changed);
+        assert!(!created_up_to_date);
+        assert!(!removed);
+        assert_eq!(
+            fs::read_to_string(&existing).unwrap(),
+            "export type Id = string;\n"
+        );
+        assert!(!folder.path().join("nested").exists());
+    }
+
+    #[test]
+    fn formatter_runs_on_the_generated_file() {
+        let folder = tempdir();
+        let path = folder.path().join("types.ts");
+        // The formatter gets the file to format in place as its last argument
+        let formatter = [
+            "sh".to_string(),
+            "-c".to_string(),
+            "tr a-z A-Z < \"$0\" > \"$0.tmp\" && mv \"$0.tmp\" \"$0\"".to_string(),
+        ];
+        let options = WriteOptions {
+            formatter: &formatter,
+            ..options(Mode::Write)
+        };
+        let output = b"export type Id = string;\n".to_vec();
+        let up_to_date = check_write_file(&path, output.clone(), &options).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let up_to_date_again = check_write_file(&path, output, &options).unwrap();
+        let names = file_names(folder.path());
+        assert!(!up_to_date);
+        assert_eq!(written, "EXPORT TYPE ID = STRING;\n");
+        // Compared with the existing file after formatting
+        assert!(up_to_date_again);
+        assert_eq!(names, ["types.ts"]);
+
+        let failing = ["false".to_string()];
+        let options = WriteOptions {
+            formatter: &failing,
+            ..options
+        };
+        assert!(check_write_file(&path, b"export {};\n".to_vec(), &options).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), written);
+    }
+
+    /// The names of the files in the folder and its subfolders.
+    fn file_names(folder: &Path) -> Vec<String> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(folder).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                names.extend(file_names(&path));
+            } else {
+                names.push(path.file_name().unwrap().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn file_per_type_writes_every_type_to_its_own_file() {
+        let root = tempdir();
+        let src = root.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[typeshare]\npub struct Account {\n    pub id: String,\n}\n\n\
+             #[typeshare]\npub enum Status {\n    Open,\n    Closed,\n}\n",
+        )
+        .unwrap();
+        let output = root.path().join("output");
+        let options = Args::parse_from([
+            "typeshare",
+            src.to_str().unwrap(),
+            "--lang",
+            "swift",
+            "--output-folder",
+            output.to_str().unwrap(),
+            "--file-per-type",
+        ]);
+        generate_types(None, &options, None, &mut Report::default()).unwrap();
+
+        assert_eq!(file_names(&output), ["Account.swift", "Status.swift"]);
+    }
 }
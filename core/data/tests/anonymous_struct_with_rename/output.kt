@@ -3,13 +3,17 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// Generated type representing the anonymous struct variant `List` of the `AnonymousStructWithRename` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `List` of the [AnonymousStructWithRename] Rust enum
+ */
 @Serializable
 data class AnonymousStructWithRenameListInner (
 	val list: List<String>
 )
 
-/// Generated type representing the anonymous struct variant `LongFieldNames` of the `AnonymousStructWithRename` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `LongFieldNames` of the [AnonymousStructWithRename] Rust enum
+ */
 @Serializable
 data class AnonymousStructWithRenameLongFieldNamesInner (
 	val some_long_field_name: String,
@@ -17,7 +21,9 @@ data class AnonymousStructWithRenameLongFieldNamesInner (
 	val but_one_more: List<String>
 )
 
-/// Generated type representing the anonymous struct variant `KebabCase` of the `AnonymousStructWithRename` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `KebabCase` of the [AnonymousStructWithRename] Rust enum
+ */
 @Serializable
 data class AnonymousStructWithRenameKebabCaseInner (
 	@SerialName("another-list")
@@ -0,0 +1,11 @@
+#[typeshare]
+pub struct Selector {
+    #[serde(rename = "class")]
+    pub class_name: String,
+    #[serde(rename = "in")]
+    pub within: Vec<String>,
+    #[serde(rename = "object")]
+    pub target: String,
+    #[serde(rename = "val")]
+    pub value: u32,
+}
@@ -0,0 +1,13 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+@Serializable
+data class Selector (
+	val `class`: String,
+	val `in`: List<String>,
+	val `object`: String,
+	val `val`: UInt
+)
+
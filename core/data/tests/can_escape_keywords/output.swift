@@ -0,0 +1,15 @@
+import Foundation
+
+public struct Selector: Codable {
+	public let `class`: String
+	public let `in`: [String]
+	public let object: String
+	public let val: UInt32
+
+	public init(class: String, in: [String], object: String, val: UInt32) {
+		self.class = `class`
+		self.in = `in`
+		self.object = object
+		self.val = val
+	}
+}
@@ -0,0 +1,7 @@
+export interface Profile {
+	name: string;
+	nickname?: string | null;
+	avatar_url?: string | null;
+	age?: number;
+}
+
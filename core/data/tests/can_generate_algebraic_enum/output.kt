@@ -3,14 +3,20 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// Struct comment
+/**
+ * Struct comment
+ */
 @Serializable
 object ItemDetailsFieldValue
 
-/// Enum comment
+/**
+ * Enum comment
+ */
 @Serializable
 sealed class AdvancedColors {
-	/// This is a case comment
+	/**
+	 * This is a case comment
+	 */
 	@Serializable
 	@SerialName("String")
 	data class String(val content: String): AdvancedColors()
@@ -23,7 +29,9 @@ sealed class AdvancedColors {
 	@Serializable
 	@SerialName("NumberArray")
 	data class NumberArray(val content: List<Int>): AdvancedColors()
-	/// Comment on the last element
+	/**
+	 * Comment on the last element
+	 */
 	@Serializable
 	@SerialName("ReallyCoolType")
 	data class ReallyCoolType(val content: ItemDetailsFieldValue): AdvancedColors()
@@ -31,7 +39,9 @@ sealed class AdvancedColors {
 
 @Serializable
 sealed class AdvancedColors2 {
-	/// This is a case comment
+	/**
+	 * This is a case comment
+	 */
 	@Serializable
 	@SerialName("string")
 	data class String(val content: String): AdvancedColors2()
@@ -41,7 +51,9 @@ sealed class AdvancedColors2 {
 	@Serializable
 	@SerialName("number-array")
 	data class NumberArray(val content: List<Int>): AdvancedColors2()
-	/// Comment on the last element
+	/**
+	 * Comment on the last element
+	 */
 	@Serializable
 	@SerialName("really-cool-type")
 	data class ReallyCoolType(val content: ItemDetailsFieldValue): AdvancedColors2()
@@ -0,0 +1,39 @@
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub type Tags = Vec<String>;
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Shape {
+    Circle(f64),
+    Rectangle { width: u32, height: u32 },
+    Empty,
+}
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+#[typeshare]
+pub struct User {
+    pub name: String,
+    pub age: u8,
+    pub tags: Tags,
+    pub scores: HashMap<String, i32>,
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    pub role: Role,
+    pub shapes: Page<Shape>,
+    pub manager: Option<Box<User>>,
+}
+
+#[typeshare]
+pub struct Empty {}
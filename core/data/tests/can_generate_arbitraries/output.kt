@@ -0,0 +1,94 @@
+typealias Tags = List<String>
+
+val arbTags: Arb<Tags> = arbitrary { Arb.list(Arb.string()).bind() }
+
+@Serializable
+object Empty
+
+val arbEmpty: Arb<Empty> = Arb.constant(Empty)
+
+@Serializable
+data class Page<T> (
+	val items: List<T>,
+	val next: String? = null
+)
+
+fun <T> arbPage(arbT: Arb<T>): Arb<Page<T>> = arbitrary {
+	Page(
+		items = Arb.list(arbT).bind(),
+		next = Arb.string().orNull().bind(),
+	)
+}
+
+@Serializable
+enum class Role(val string: String) {
+	@SerialName("Admin")
+	Admin("Admin"),
+	@SerialName("Member")
+	Member("Member"),
+}
+
+val arbRole: Arb<Role> = Arb.enum<Role>()
+
+/**
+ * Generated type representing the anonymous struct variant `Rectangle` of the [Shape] Rust enum
+ */
+@Serializable
+data class ShapeRectangleInner (
+	val width: UInt,
+	val height: UInt
+)
+
+val arbShapeRectangleInner: Arb<ShapeRectangleInner> = arbitrary {
+	ShapeRectangleInner(
+		width = Arb.uInt().bind(),
+		height = Arb.uInt().bind(),
+	)
+}
+
+@Serializable
+sealed class Shape {
+	@Serializable
+	@SerialName("Circle")
+	data class Circle(val content: Double): Shape()
+	@Serializable
+	@SerialName("Rectangle")
+	data class Rectangle(val content: ShapeRectangleInner): Shape()
+	@Serializable
+	@SerialName("Empty")
+	object Empty: Shape()
+}
+
+val arbShape: Arb<Shape> = Arb.choice(
+	arbitrary { Shape.Circle(Arb.numericDouble().bind()) },
+	arbitrary { Shape.Rectangle(arbShapeRectangleInner.bind()) },
+	Arb.constant(Shape.Empty),
+)
+
+@Serializable
+data class User (
+	val name: String,
+	val age: UByte,
+	val tags: Tags,
+	val scores: HashMap<String, Int>,
+	val nickname: String? = null,
+	val verified: Boolean? = null,
+	val role: Role,
+	val shapes: Page<Shape>,
+	val manager: User? = null
+)
+
+val arbUser: Arb<User> = arbitrary {
+	User(
+		name = Arb.string().bind(),
+		age = Arb.uByte().bind(),
+		tags = arbTags.bind(),
+		scores = Arb.map(Arb.string(), Arb.int()).map { HashMap(it) }.bind(),
+		nickname = Arb.string().orNull().bind(),
+		verified = Arb.boolean().orNull().bind(),
+		role = arbRole.bind(),
+		shapes = arbPage(arbShape).bind(),
+		manager = arbUser.orNull().bind(),
+	)
+}
+
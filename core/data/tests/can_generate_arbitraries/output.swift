@@ -0,0 +1,156 @@
+import Foundation
+import SwiftCheck
+
+public typealias Tags = [String]
+
+public struct Empty: Codable {
+	public init() {}
+}
+
+extension Empty: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.pure(Empty())
+	}
+}
+
+public struct Page<T: Codable>: Codable {
+	public let items: [T]
+	public let next: String?
+
+	public init(items: [T], next: String?) {
+		self.items = items
+		self.next = next
+	}
+}
+
+extension Page: Arbitrary where T: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.compose { c in
+			Page(items: c.generate(), next: c.generate())
+		}
+	}
+}
+
+public enum Role: String, Codable {
+	case admin = "Admin"
+	case member = "Member"
+}
+
+extension Role: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.fromElements(of: [.admin, .member])
+	}
+}
+
+
+/// Generated type representing the anonymous struct variant `Rectangle` of the `Shape` Rust enum
+public struct ShapeRectangleInner: Codable {
+	public let width: UInt32
+	public let height: UInt32
+
+	public init(width: UInt32, height: UInt32) {
+		self.width = width
+		self.height = height
+	}
+}
+
+extension ShapeRectangleInner: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.compose { c in
+			ShapeRectangleInner(width: c.generate(), height: c.generate())
+		}
+	}
+}
+public enum Shape: Codable {
+	case circle(Double)
+	case rectangle(ShapeRectangleInner)
+	case empty
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case circle = "Circle",
+			rectangle = "Rectangle",
+			empty = "Empty"
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .circle:
+				if let content = try? container.decode(Double.self, forKey: .content) {
+					self = .circle(content)
+					return
+				}
+			case .rectangle:
+				if let content = try? container.decode(ShapeRectangleInner.self, forKey: .content) {
+					self = .rectangle(content)
+					return
+				}
+			case .empty:
+				self = .empty
+				return
+			}
+		}
+		throw DecodingError.typeMismatch(Shape.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Shape"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .circle(let content):
+			try container.encode(CodingKeys.circle, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .rectangle(let content):
+			try container.encode(CodingKeys.rectangle, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .empty:
+			try container.encode(CodingKeys.empty, forKey: .type)
+		}
+	}
+}
+
+extension Shape: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.one(of: [
+			Gen<Self>.compose { c in .circle(c.generate()) },
+			Gen<Self>.compose { c in .rectangle(c.generate()) },
+			Gen<Self>.pure(.empty),
+		])
+	}
+}
+
+public struct User: Codable {
+	public let name: String
+	public let age: UInt8
+	public let tags: Tags
+	public let scores: [String: Int32]
+	public let nickname: String?
+	public let verified: Bool?
+	public let role: Role
+	public let shapes: Page<Shape>
+	public let manager: User?
+
+	public init(name: String, age: UInt8, tags: Tags, scores: [String: Int32], nickname: String?, verified: Bool?, role: Role, shapes: Page<Shape>, manager: User?) {
+		self.name = name
+		self.age = age
+		self.tags = tags
+		self.scores = scores
+		self.nickname = nickname
+		self.verified = verified
+		self.role = role
+		self.shapes = shapes
+		self.manager = manager
+	}
+}
+
+extension User: Arbitrary {
+	public static var arbitrary: Gen<Self> {
+		Gen.compose { c in
+			User(name: c.generate(), age: c.generate(), tags: c.generate(), scores: c.generate(), nickname: c.generate(), verified: c.generate(), role: c.generate(), shapes: c.generate(), manager: c.generate())
+		}
+	}
+}
@@ -0,0 +1,69 @@
+import * as fc from "fast-check";
+
+export type Tags = string[];
+
+export const arbTags: fc.Arbitrary<Tags> = fc.array(fc.string());
+
+export interface Empty {
+}
+
+export const arbEmpty: fc.Arbitrary<Empty> = fc.record({});
+
+export interface Page<T> {
+	items: T[];
+	next?: string;
+}
+
+export const arbPage = <T>(arbT: fc.Arbitrary<T>): fc.Arbitrary<Page<T>> => fc.record({
+	items: fc.array(arbT),
+	next: fc.string(),
+}, { requiredKeys: ["items"] });
+
+export enum Role {
+	Admin = "Admin",
+	Member = "Member",
+}
+
+export const arbRole: fc.Arbitrary<Role> = fc.constantFrom(Role.Admin, Role.Member);
+
+export type Shape = 
+	| { type: "Circle", content: number }
+	| { type: "Rectangle", content: {
+	width: number;
+	height: number;
+}}
+	| { type: "Empty", content?: undefined };
+
+export const arbShape: fc.Arbitrary<Shape> = fc.oneof(
+	fc.record({ type: fc.constant("Circle" as const), content: fc.double({ noNaN: true, noDefaultInfinity: true }) }),
+	fc.record({ type: fc.constant("Rectangle" as const), content: fc.record({
+		width: fc.bigInt({ min: 0n, max: 4294967295n }).map(Number),
+		height: fc.bigInt({ min: 0n, max: 4294967295n }).map(Number),
+	}) }),
+	fc.record({ type: fc.constant("Empty" as const) }),
+);
+
+export interface User {
+	name: string;
+	age: number;
+	tags: Tags;
+	scores: Record<string, number>;
+	nickname?: string;
+	verified?: boolean;
+	role: Role;
+	shapes: Page<Shape>;
+	manager?: User;
+}
+
+export const arbUser: fc.Arbitrary<User> = fc.record({
+	name: fc.string(),
+	age: fc.integer({ min: 0, max: 255 }),
+	tags: arbTags,
+	scores: fc.dictionary(fc.string(), fc.integer()),
+	nickname: fc.string(),
+	verified: fc.boolean(),
+	role: arbRole,
+	shapes: arbPage(arbShape),
+	manager: fc.constant(null).chain(() => arbUser),
+}, { requiredKeys: ["name", "age", "tags", "scores", "role", "shapes"] });
+
@@ -0,0 +1,47 @@
+/// A user of the app.
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    /// The identifier of the user.
+    user_id: UserId,
+    name: String,
+    /// What the user may do.
+    #[serde(default)]
+    role: Option<Role>,
+    #[deprecated(note = "Use `name` instead")]
+    nickname: Option<String>,
+}
+
+/// Identifies a user.
+#[typeshare]
+pub type UserId = String;
+
+#[typeshare]
+pub enum Role {
+    /// Can do anything.
+    Admin,
+    #[deprecated]
+    Guest,
+    Member,
+}
+
+/// Something which happened to a user.
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    Joined(User),
+    Left { user_id: UserId, reason: Option<String> },
+    Ping,
+}
+
+/// A page of items.
+#[typeshare]
+#[deprecated = "Use cursors"]
+#[serde(rename = "UserPage")]
+pub struct Page<T> {
+    items: Vec<T>,
+    users: HashMap<UserId, Vec<User>>,
+}
+
+#[typeshare]
+pub const MAX_USERS: u32 = 100;
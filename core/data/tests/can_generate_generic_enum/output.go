@@ -0,0 +1,271 @@
+package proto
+
+import "encoding/json"
+
+type GenericEnumTypes string
+const (
+	GenericEnumTypeVariantVariantA GenericEnumTypes = "VariantA"
+	GenericEnumTypeVariantVariantB GenericEnumTypes = "VariantB"
+)
+type GenericEnum[A any, B any] struct{ 
+	Type GenericEnumTypes `json:"type"`
+	content interface{}
+}
+
+func (g *GenericEnum[A, B]) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    GenericEnumTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	g.Type = enum.Tag
+	switch g.Type {
+	case GenericEnumTypeVariantVariantA:
+		var res A
+		g.content = &res
+	case GenericEnumTypeVariantVariantB:
+		var res B
+		g.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &g.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (g GenericEnum[A, B]) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    GenericEnumTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = g.Type
+    enum.Content = g.content
+    return json.Marshal(enum)
+}
+
+func (g GenericEnum[A, B]) VariantA() A {
+	res, _ := g.content.(*A)
+	return *res
+}
+func (g GenericEnum[A, B]) VariantB() B {
+	res, _ := g.content.(*B)
+	return *res
+}
+
+func NewGenericEnumTypeVariantVariantA[A any, B any](content A) GenericEnum[A, B] {
+    return GenericEnum[A, B]{
+        Type: GenericEnumTypeVariantVariantA,
+        content: &content,
+    }
+}
+func NewGenericEnumTypeVariantVariantB[A any, B any](content B) GenericEnum[A, B] {
+    return GenericEnum[A, B]{
+        Type: GenericEnumTypeVariantVariantB,
+        content: &content,
+    }
+}
+
+type StructUsingGenericEnum struct {
+	EnumField GenericEnum[string, int] `json:"enum_field"`
+}
+type GenericEnumUsingGenericEnumTypes string
+const (
+	GenericEnumUsingGenericEnumTypeVariantVariantC GenericEnumUsingGenericEnumTypes = "VariantC"
+	GenericEnumUsingGenericEnumTypeVariantVariantD GenericEnumUsingGenericEnumTypes = "VariantD"
+	GenericEnumUsingGenericEnumTypeVariantVariantE GenericEnumUsingGenericEnumTypes = "VariantE"
+)
+type GenericEnumUsingGenericEnum[T any] struct{ 
+	Type GenericEnumUsingGenericEnumTypes `json:"type"`
+	content interface{}
+}
+
+func (g *GenericEnumUsingGenericEnum[T]) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    GenericEnumUsingGenericEnumTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	g.Type = enum.Tag
+	switch g.Type {
+	case GenericEnumUsingGenericEnumTypeVariantVariantC:
+		var res GenericEnum[T, T]
+		g.content = &res
+	case GenericEnumUsingGenericEnumTypeVariantVariantD:
+		var res GenericEnum[string, map[string]T]
+		g.content = &res
+	case GenericEnumUsingGenericEnumTypeVariantVariantE:
+		var res GenericEnum[string, uint32]
+		g.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &g.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (g GenericEnumUsingGenericEnum[T]) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    GenericEnumUsingGenericEnumTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = g.Type
+    enum.Content = g.content
+    return json.Marshal(enum)
+}
+
+func (g GenericEnumUsingGenericEnum[T]) VariantC() GenericEnum[T, T] {
+	res, _ := g.content.(*GenericEnum[T, T])
+	return *res
+}
+func (g GenericEnumUsingGenericEnum[T]) VariantD() GenericEnum[string, map[string]T] {
+	res, _ := g.content.(*GenericEnum[string, map[string]T])
+	return *res
+}
+func (g GenericEnumUsingGenericEnum[T]) VariantE() GenericEnum[string, uint32] {
+	res, _ := g.content.(*GenericEnum[string, uint32])
+	return *res
+}
+
+func NewGenericEnumUsingGenericEnumTypeVariantVariantC[T any](content GenericEnum[T, T]) GenericEnumUsingGenericEnum[T] {
+    return GenericEnumUsingGenericEnum[T]{
+        Type: GenericEnumUsingGenericEnumTypeVariantVariantC,
+        content: &content,
+    }
+}
+func NewGenericEnumUsingGenericEnumTypeVariantVariantD[T any](content GenericEnum[string, map[string]T]) GenericEnumUsingGenericEnum[T] {
+    return GenericEnumUsingGenericEnum[T]{
+        Type: GenericEnumUsingGenericEnumTypeVariantVariantD,
+        content: &content,
+    }
+}
+func NewGenericEnumUsingGenericEnumTypeVariantVariantE[T any](content GenericEnum[string, uint32]) GenericEnumUsingGenericEnum[T] {
+    return GenericEnumUsingGenericEnum[T]{
+        Type: GenericEnumUsingGenericEnumTypeVariantVariantE,
+        content: &content,
+    }
+}
+
+// Generated type representing the anonymous struct variant `VariantF` of the `GenericEnumsUsingStructVariants` Rust enum
+type GenericEnumsUsingStructVariantsVariantFInner[T any] struct {
+	Action T `json:"action"`
+}
+// Generated type representing the anonymous struct variant `VariantG` of the `GenericEnumsUsingStructVariants` Rust enum
+type GenericEnumsUsingStructVariantsVariantGInner[T any, U any] struct {
+	Action T `json:"action"`
+	Response U `json:"response"`
+}
+// Generated type representing the anonymous struct variant `VariantH` of the `GenericEnumsUsingStructVariants` Rust enum
+type GenericEnumsUsingStructVariantsVariantHInner struct {
+	NonGeneric int `json:"non_generic"`
+}
+// Generated type representing the anonymous struct variant `VariantI` of the `GenericEnumsUsingStructVariants` Rust enum
+type GenericEnumsUsingStructVariantsVariantIInner[T any, U any] struct {
+	Vec []T `json:"vec"`
+	Action MyType[T, U] `json:"action"`
+}
+type GenericEnumsUsingStructVariantsTypes string
+const (
+	GenericEnumsUsingStructVariantsTypeVariantVariantF GenericEnumsUsingStructVariantsTypes = "VariantF"
+	GenericEnumsUsingStructVariantsTypeVariantVariantG GenericEnumsUsingStructVariantsTypes = "VariantG"
+	GenericEnumsUsingStructVariantsTypeVariantVariantH GenericEnumsUsingStructVariantsTypes = "VariantH"
+	GenericEnumsUsingStructVariantsTypeVariantVariantI GenericEnumsUsingStructVariantsTypes = "VariantI"
+)
+type GenericEnumsUsingStructVariants[T any, U any] struct{ 
+	Type GenericEnumsUsingStructVariantsTypes `json:"type"`
+	content interface{}
+}
+
+func (g *GenericEnumsUsingStructVariants[T, U]) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    GenericEnumsUsingStructVariantsTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	g.Type = enum.Tag
+	switch g.Type {
+	case GenericEnumsUsingStructVariantsTypeVariantVariantF:
+		var res GenericEnumsUsingStructVariantsVariantFInner[T]
+		g.content = &res
+	case GenericEnumsUsingStructVariantsTypeVariantVariantG:
+		var res GenericEnumsUsingStructVariantsVariantGInner[T, U]
+		g.content = &res
+	case GenericEnumsUsingStructVariantsTypeVariantVariantH:
+		var res GenericEnumsUsingStructVariantsVariantHInner
+		g.content = &res
+	case GenericEnumsUsingStructVariantsTypeVariantVariantI:
+		var res GenericEnumsUsingStructVariantsVariantIInner[T, U]
+		g.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &g.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (g GenericEnumsUsingStructVariants[T, U]) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    GenericEnumsUsingStructVariantsTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = g.Type
+    enum.Content = g.content
+    return json.Marshal(enum)
+}
+
+func (g GenericEnumsUsingStructVariants[T, U]) VariantF() *GenericEnumsUsingStructVariantsVariantFInner[T] {
+	res, _ := g.content.(*GenericEnumsUsingStructVariantsVariantFInner[T])
+	return res
+}
+func (g GenericEnumsUsingStructVariants[T, U]) VariantG() *GenericEnumsUsingStructVariantsVariantGInner[T, U] {
+	res, _ := g.content.(*GenericEnumsUsingStructVariantsVariantGInner[T, U])
+	return res
+}
+func (g GenericEnumsUsingStructVariants[T, U]) VariantH() *GenericEnumsUsingStructVariantsVariantHInner {
+	res, _ := g.content.(*GenericEnumsUsingStructVariantsVariantHInner)
+	return res
+}
+func (g GenericEnumsUsingStructVariants[T, U]) VariantI() *GenericEnumsUsingStructVariantsVariantIInner[T, U] {
+	res, _ := g.content.(*GenericEnumsUsingStructVariantsVariantIInner[T, U])
+	return res
+}
+
+func NewGenericEnumsUsingStructVariantsTypeVariantVariantF[T any, U any](content *GenericEnumsUsingStructVariantsVariantFInner[T]) GenericEnumsUsingStructVariants[T, U] {
+    return GenericEnumsUsingStructVariants[T, U]{
+        Type: GenericEnumsUsingStructVariantsTypeVariantVariantF,
+        content: content,
+    }
+}
+func NewGenericEnumsUsingStructVariantsTypeVariantVariantG[T any, U any](content *GenericEnumsUsingStructVariantsVariantGInner[T, U]) GenericEnumsUsingStructVariants[T, U] {
+    return GenericEnumsUsingStructVariants[T, U]{
+        Type: GenericEnumsUsingStructVariantsTypeVariantVariantG,
+        content: content,
+    }
+}
+func NewGenericEnumsUsingStructVariantsTypeVariantVariantH[T any, U any](content *GenericEnumsUsingStructVariantsVariantHInner) GenericEnumsUsingStructVariants[T, U] {
+    return GenericEnumsUsingStructVariants[T, U]{
+        Type: GenericEnumsUsingStructVariantsTypeVariantVariantH,
+        content: content,
+    }
+}
+func NewGenericEnumsUsingStructVariantsTypeVariantVariantI[T any, U any](content *GenericEnumsUsingStructVariantsVariantIInner[T, U]) GenericEnumsUsingStructVariants[T, U] {
+    return GenericEnumsUsingStructVariants[T, U]{
+        Type: GenericEnumsUsingStructVariantsTypeVariantVariantI,
+        content: content,
+    }
+}
+
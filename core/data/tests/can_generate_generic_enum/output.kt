@@ -31,26 +31,34 @@ sealed class GenericEnumUsingGenericEnum<T> {
 	data class VariantE<T>(val content: GenericEnum<String, UInt>): GenericEnumUsingGenericEnum<T>()
 }
 
-/// Generated type representing the anonymous struct variant `VariantF` of the `GenericEnumsUsingStructVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `VariantF` of the [GenericEnumsUsingStructVariants] Rust enum
+ */
 @Serializable
 data class GenericEnumsUsingStructVariantsVariantFInner<T> (
 	val action: T
 )
 
-/// Generated type representing the anonymous struct variant `VariantG` of the `GenericEnumsUsingStructVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `VariantG` of the [GenericEnumsUsingStructVariants] Rust enum
+ */
 @Serializable
 data class GenericEnumsUsingStructVariantsVariantGInner<T, U> (
 	val action: T,
 	val response: U
 )
 
-/// Generated type representing the anonymous struct variant `VariantH` of the `GenericEnumsUsingStructVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `VariantH` of the [GenericEnumsUsingStructVariants] Rust enum
+ */
 @Serializable
 data class GenericEnumsUsingStructVariantsVariantHInner (
 	val non_generic: Int
 )
 
-/// Generated type representing the anonymous struct variant `VariantI` of the `GenericEnumsUsingStructVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `VariantI` of the [GenericEnumsUsingStructVariants] Rust enum
+ */
 @Serializable
 data class GenericEnumsUsingStructVariantsVariantIInner<T, U> (
 	val vec: List<T>,
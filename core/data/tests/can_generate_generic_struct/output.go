@@ -0,0 +1,109 @@
+package proto
+
+import "encoding/json"
+
+type GenericStruct[A any, B any] struct {
+	FieldA A `json:"field_a"`
+	FieldB []B `json:"field_b"`
+}
+type GenericStructUsingGenericStruct[T any] struct {
+	StructField GenericStruct[string, T] `json:"struct_field"`
+	SecondStructField GenericStruct[T, string] `json:"second_struct_field"`
+	ThirdStructField GenericStruct[T, []T] `json:"third_struct_field"`
+}
+type EnumUsingGenericStructTypes string
+const (
+	EnumUsingGenericStructTypeVariantVariantA EnumUsingGenericStructTypes = "VariantA"
+	EnumUsingGenericStructTypeVariantVariantB EnumUsingGenericStructTypes = "VariantB"
+	EnumUsingGenericStructTypeVariantVariantC EnumUsingGenericStructTypes = "VariantC"
+	EnumUsingGenericStructTypeVariantVariantD EnumUsingGenericStructTypes = "VariantD"
+)
+type EnumUsingGenericStruct struct{ 
+	Type EnumUsingGenericStructTypes `json:"type"`
+	content interface{}
+}
+
+func (e *EnumUsingGenericStruct) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    EnumUsingGenericStructTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	e.Type = enum.Tag
+	switch e.Type {
+	case EnumUsingGenericStructTypeVariantVariantA:
+		var res GenericStruct[string, float32]
+		e.content = &res
+	case EnumUsingGenericStructTypeVariantVariantB:
+		var res GenericStruct[string, int]
+		e.content = &res
+	case EnumUsingGenericStructTypeVariantVariantC:
+		var res GenericStruct[string, bool]
+		e.content = &res
+	case EnumUsingGenericStructTypeVariantVariantD:
+		var res GenericStructUsingGenericStruct[struct{}]
+		e.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &e.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (e EnumUsingGenericStruct) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    EnumUsingGenericStructTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = e.Type
+    enum.Content = e.content
+    return json.Marshal(enum)
+}
+
+func (e EnumUsingGenericStruct) VariantA() GenericStruct[string, float32] {
+	res, _ := e.content.(*GenericStruct[string, float32])
+	return *res
+}
+func (e EnumUsingGenericStruct) VariantB() GenericStruct[string, int] {
+	res, _ := e.content.(*GenericStruct[string, int])
+	return *res
+}
+func (e EnumUsingGenericStruct) VariantC() GenericStruct[string, bool] {
+	res, _ := e.content.(*GenericStruct[string, bool])
+	return *res
+}
+func (e EnumUsingGenericStruct) VariantD() GenericStructUsingGenericStruct[struct{}] {
+	res, _ := e.content.(*GenericStructUsingGenericStruct[struct{}])
+	return *res
+}
+
+func NewEnumUsingGenericStructTypeVariantVariantA(content GenericStruct[string, float32]) EnumUsingGenericStruct {
+    return EnumUsingGenericStruct{
+        Type: EnumUsingGenericStructTypeVariantVariantA,
+        content: &content,
+    }
+}
+func NewEnumUsingGenericStructTypeVariantVariantB(content GenericStruct[string, int]) EnumUsingGenericStruct {
+    return EnumUsingGenericStruct{
+        Type: EnumUsingGenericStructTypeVariantVariantB,
+        content: &content,
+    }
+}
+func NewEnumUsingGenericStructTypeVariantVariantC(content GenericStruct[string, bool]) EnumUsingGenericStruct {
+    return EnumUsingGenericStruct{
+        Type: EnumUsingGenericStructTypeVariantVariantC,
+        content: &content,
+    }
+}
+func NewEnumUsingGenericStructTypeVariantVariantD(content GenericStructUsingGenericStruct[struct{}]) EnumUsingGenericStruct {
+    return EnumUsingGenericStruct{
+        Type: EnumUsingGenericStructTypeVariantVariantD,
+        content: &content,
+    }
+}
+
@@ -0,0 +1,8 @@
+package proto
+
+import "encoding/json"
+
+type GenericTypeAlias[T any] []T
+
+type NonGenericAlias GenericTypeAlias[*string]
+
@@ -0,0 +1,10 @@
+#[typeshare]
+pub struct Value {
+    pub amount: u32,
+}
+
+#[typeshare]
+pub struct Invoice {
+    pub total: Value,
+    pub payload: serde_json::Value,
+}
@@ -0,0 +1,11 @@
+package proto
+
+import "encoding/json"
+
+type Value struct {
+	Amount uint32 `json:"amount"`
+}
+type Invoice struct {
+	Total Value `json:"total"`
+	Payload json.RawMessage `json:"payload"`
+}
@@ -0,0 +1,14 @@
+/// A color, whose values are checked when it's decoded.
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub enum Color {
+    Red,
+    #[serde(rename = "verde")]
+    Green,
+    BrightBlue,
+}
+
+#[typeshare]
+pub struct Paint {
+    pub color: Color,
+}
@@ -0,0 +1,42 @@
+package proto
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// A color, whose values are checked when it's decoded.
+type Color string
+const (
+	ColorRed Color = "red"
+	ColorGreen Color = "verde"
+	ColorBrightBlue Color = "bright_blue"
+)
+
+func (c Color) String() string {
+	return string(c)
+}
+
+func (c Color) MarshalJSON() ([]byte, error) {
+	switch c {
+	case "", ColorRed, ColorGreen, ColorBrightBlue:
+		return json.Marshal(string(c))
+	}
+	return nil, fmt.Errorf("invalid Color: %q", string(c))
+}
+
+func (c *Color) UnmarshalJSON(data []byte) error {
+	var value string
+	if err := json.Unmarshal(data, &value); err != nil {
+		return err
+	}
+	switch enum := Color(value); enum {
+	case "", ColorRed, ColorGreen, ColorBrightBlue:
+		*c = enum
+		return nil
+	}
+	return fmt.Errorf("invalid Color: %q", value)
+}
+type Paint struct {
+	Color Color `json:"color"`
+}
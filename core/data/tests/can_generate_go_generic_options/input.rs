@@ -0,0 +1,10 @@
+#[typeshare]
+pub struct Settings {
+    pub theme: Option<String>,
+    pub font_size: Option<u32>,
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub compact: bool,
+    #[typeshare(go(option = "pointer"))]
+    pub parent: Option<Box<Settings>>,
+}
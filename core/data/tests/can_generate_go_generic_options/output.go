@@ -0,0 +1,38 @@
+package proto
+
+import "encoding/json"
+
+// Option is an optional value that is serialized as `null` when it is not set.
+type Option[T any] struct {
+	Value T
+	Valid bool
+}
+
+// Some returns a set Option holding value.
+func Some[T any](value T) Option[T] {
+	return Option[T]{Value: value, Valid: true}
+}
+
+func (o Option[T]) MarshalJSON() ([]byte, error) {
+	if !o.Valid {
+		return []byte("null"), nil
+	}
+	return json.Marshal(o.Value)
+}
+
+func (o *Option[T]) UnmarshalJSON(data []byte) error {
+	if string(data) == "null" {
+		*o = Option[T]{}
+		return nil
+	}
+	o.Valid = true
+	return json.Unmarshal(data, &o.Value)
+}
+
+type Settings struct {
+	Theme Option[string] `json:"theme,omitempty"`
+	FontSize Option[uint32] `json:"font_size,omitempty"`
+	Tags Option[[]string] `json:"tags,omitempty"`
+	Compact Option[bool] `json:"compact,omitempty"`
+	Parent *Settings `json:"parent,omitempty"`
+}
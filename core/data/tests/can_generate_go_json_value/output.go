@@ -0,0 +1,11 @@
+package proto
+
+import "encoding/json"
+
+type Event struct {
+	Kind string `json:"kind"`
+	// Decoded once `kind` is known
+	Payload json.RawMessage `json:"payload"`
+	Metadata *json.RawMessage `json:"metadata,omitempty"`
+	Attributes map[string]json.RawMessage `json:"attributes"`
+}
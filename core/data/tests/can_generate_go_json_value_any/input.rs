@@ -0,0 +1,11 @@
+use serde_json::Value;
+
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub kind: String,
+    /// Decoded once `kind` is known
+    pub payload: serde_json::Value,
+    pub metadata: Option<serde_json::Value>,
+    pub attributes: HashMap<String, Value>,
+}
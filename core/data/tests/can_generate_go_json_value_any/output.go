@@ -0,0 +1,11 @@
+package proto
+
+import "encoding/json"
+
+type Event struct {
+	Kind string `json:"kind"`
+	// Decoded once `kind` is known
+	Payload any `json:"payload"`
+	Metadata any `json:"metadata,omitempty"`
+	Attributes map[string]any `json:"attributes"`
+}
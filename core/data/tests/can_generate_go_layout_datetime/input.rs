@@ -0,0 +1,6 @@
+#[typeshare]
+pub struct Event {
+    pub name: String,
+    pub starts_at: OffsetDateTime,
+    pub ends_at: Option<OffsetDateTime>,
+}
@@ -0,0 +1,34 @@
+package proto
+
+import (
+	"encoding/json"
+	"time"
+)
+
+// DateTime is a time.Time that is serialized as the layout "2006-01-02".
+type DateTime struct {
+	time.Time
+}
+
+func (t DateTime) MarshalJSON() ([]byte, error) {
+	return json.Marshal(t.Format("2006-01-02"))
+}
+
+func (t *DateTime) UnmarshalJSON(data []byte) error {
+	var value string
+	if err := json.Unmarshal(data, &value); err != nil {
+		return err
+	}
+	parsed, err := time.Parse("2006-01-02", value)
+	if err != nil {
+		return err
+	}
+	t.Time = parsed
+	return nil
+}
+
+type Event struct {
+	Name string `json:"name"`
+	StartsAt DateTime `json:"starts_at"`
+	EndsAt *DateTime `json:"ends_at,omitempty"`
+}
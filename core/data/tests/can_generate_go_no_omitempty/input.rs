@@ -0,0 +1,19 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+    /// Serialized as `null` when it is missing
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub age: u32,
+    /// Left out entirely when it is missing
+    #[typeshare(go(omitempty = "true"))]
+    pub avatar_url: Option<String>,
+}
+
+#[typeshare]
+pub struct Patch {
+    #[typeshare(go(omitempty = "false"))]
+    pub value: Option<String>,
+    pub comment: Option<String>,
+}
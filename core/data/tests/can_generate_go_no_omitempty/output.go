@@ -0,0 +1,16 @@
+package proto
+
+import "encoding/json"
+
+type Patch struct {
+	Value *string `json:"value"`
+	Comment *string `json:"comment"`
+}
+type Profile struct {
+	Name string `json:"name"`
+	// Serialized as `null` when it is missing
+	Nickname *string `json:"nickname"`
+	Age *uint32 `json:"age"`
+	// Left out entirely when it is missing
+	AvatarUrl *string `json:"avatarUrl,omitempty"`
+}
@@ -0,0 +1,20 @@
+#[typeshare]
+pub struct Account {
+    pub name: Option<String>,
+    pub verified: Option<bool>,
+    pub balance: Option<i32>,
+    pub sequence: Option<U53>,
+    pub external_id: Option<ExternalId>,
+    pub ratio: Option<f64>,
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub login_count: u32,
+    pub owner: Option<Owner>,
+    #[typeshare(go(option = "generic"))]
+    pub nickname: Option<String>,
+}
+
+#[typeshare]
+pub struct Owner {
+    pub id: String,
+}
@@ -0,0 +1,49 @@
+package proto
+
+import (
+	"encoding/json"
+	"gopkg.in/guregu/null.v4"
+)
+
+// Option is an optional value that is serialized as `null` when it is not set.
+type Option[T any] struct {
+	Value T
+	Valid bool
+}
+
+// Some returns a set Option holding value.
+func Some[T any](value T) Option[T] {
+	return Option[T]{Value: value, Valid: true}
+}
+
+func (o Option[T]) MarshalJSON() ([]byte, error) {
+	if !o.Valid {
+		return []byte("null"), nil
+	}
+	return json.Marshal(o.Value)
+}
+
+func (o *Option[T]) UnmarshalJSON(data []byte) error {
+	if string(data) == "null" {
+		*o = Option[T]{}
+		return nil
+	}
+	o.Valid = true
+	return json.Unmarshal(data, &o.Value)
+}
+
+type Owner struct {
+	Id string `json:"id"`
+}
+type Account struct {
+	Name null.String `json:"name,omitempty"`
+	Verified null.Bool `json:"verified,omitempty"`
+	Balance null.Int `json:"balance,omitempty"`
+	Sequence null.Int `json:"sequence,omitempty"`
+	ExternalId *uint64 `json:"external_id,omitempty"`
+	Ratio null.Float `json:"ratio,omitempty"`
+	CreatedAt null.Time `json:"created_at,omitempty"`
+	LoginCount null.Int `json:"login_count,omitempty"`
+	Owner *Owner `json:"owner,omitempty"`
+	Nickname Option[string] `json:"nickname,omitempty"`
+}
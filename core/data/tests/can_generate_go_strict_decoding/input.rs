@@ -0,0 +1,11 @@
+#[typeshare]
+#[serde(deny_unknown_fields)]
+pub struct Request {
+    pub name: String,
+    pub limit: Option<u32>,
+}
+
+#[typeshare]
+pub struct Response {
+    pub items: Vec<String>,
+}
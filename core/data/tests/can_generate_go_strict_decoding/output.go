@@ -0,0 +1,27 @@
+package proto
+
+import (
+	"bytes"
+	"encoding/json"
+)
+
+type Request struct {
+	Name string `json:"name"`
+	Limit *uint32 `json:"limit,omitempty"`
+}
+
+func (r *Request) UnmarshalJSON(data []byte) error {
+	// A type without this method, so that decoding doesn't recurse
+	type plain Request
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+	var value plain
+	if err := decoder.Decode(&value); err != nil {
+		return err
+	}
+	*r = Request(value)
+	return nil
+}
+type Response struct {
+	Items []string `json:"items"`
+}
@@ -0,0 +1,30 @@
+package proto
+
+import (
+	"encoding/json"
+	"time"
+)
+
+// DateTime is a time.Time that is serialized as a Unix timestamp in milliseconds.
+type DateTime struct {
+	time.Time
+}
+
+func (t DateTime) MarshalJSON() ([]byte, error) {
+	return json.Marshal(t.UnixMilli())
+}
+
+func (t *DateTime) UnmarshalJSON(data []byte) error {
+	var value int64
+	if err := json.Unmarshal(data, &value); err != nil {
+		return err
+	}
+	t.Time = time.UnixMilli(value)
+	return nil
+}
+
+type Event struct {
+	Name string `json:"name"`
+	StartsAt DateTime `json:"starts_at"`
+	EndsAt *DateTime `json:"ends_at,omitempty"`
+}
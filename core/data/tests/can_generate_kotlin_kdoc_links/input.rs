@@ -0,0 +1,22 @@
+/// A user of the service.
+///
+/// Their [`Role`] decides what they can access, see [crate::Permissions] or
+/// the [docs](https://example.com). `Unknown` and [`Unknown`] are not linked.
+#[typeshare]
+pub struct User {
+    /// The `Role` of this user
+    pub role: Role,
+}
+
+#[typeshare]
+pub enum Role {
+    /// Can do anything `*/`
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub struct Permissions {
+    /// Held by a [User]
+    pub read: bool,
+}
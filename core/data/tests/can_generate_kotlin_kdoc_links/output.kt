@@ -0,0 +1,38 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+
+@Serializable
+data class Permissions (
+	/**
+	 * Held by a [User]
+	 */
+	val read: Boolean
+)
+
+@Serializable
+enum class Role(val string: String) {
+	/**
+	 * Can do anything `*&#47;`
+	 */
+	@SerialName("Admin")
+	Admin("Admin"),
+	@SerialName("Member")
+	Member("Member"),
+}
+
+/**
+ * A user of the service.
+ *
+ * Their [Role] decides what they can access, see [Permissions] or
+ * the [docs](https://example.com). `Unknown` and [`Unknown`] are not linked.
+ */
+@Serializable
+data class User (
+	/**
+	 * The [Role] of this user
+	 */
+	val role: Role
+)
+
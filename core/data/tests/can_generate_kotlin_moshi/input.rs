@@ -0,0 +1,26 @@
+#[typeshare]
+pub struct Person {
+    pub name: String,
+    #[serde(rename = "home-town")]
+    pub home_town: Option<String>,
+}
+
+#[typeshare]
+pub struct Nothing {}
+
+#[typeshare(kotlin = "JvmInline")]
+pub type UserId = String;
+
+#[typeshare]
+pub enum Colors {
+    Red,
+    #[serde(rename = "blue-ish")]
+    Blue,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Shape {
+    Circle(u32),
+    Empty,
+}
@@ -0,0 +1,35 @@
+package com.agilebits.onepassword
+
+import com.squareup.moshi.Json
+import com.squareup.moshi.JsonClass
+
+@JsonClass(generateAdapter = true, inline = true)
+@JvmInline
+value class UserId(
+	val value: String
+)
+
+@JsonClass(generateAdapter = true)
+class Nothing
+
+@JsonClass(generateAdapter = true)
+data class Person (
+	@Json(name = "name")
+	val name: String,
+	@Json(name = "home-town")
+	val home_town: String? = null
+)
+
+enum class Colors(val string: String) {
+	@Json(name = "Red")
+	Red("Red"),
+	@Json(name = "blue-ish")
+	Blue("blue-ish"),
+}
+
+sealed class Shape {
+	@JsonClass(generateAdapter = true)
+	data class Circle(val content: UInt): Shape()
+	object Empty: Shape()
+}
+
@@ -0,0 +1,11 @@
+/// A user identifier
+#[typeshare]
+pub struct UserId(String);
+
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: UserId,
+    pub display_name: String,
+    pub settings: HashMap<String, String>,
+}
@@ -0,0 +1,22 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+import kotlin.jvm.JvmInline
+
+/**
+ * A user identifier
+ */
+@Serializable
+@JvmInline
+value class UserId(
+	val value: String
+)
+
+@Serializable
+data class User (
+	val id: UserId,
+	val displayName: String,
+	val settings: Map<String, String>
+)
+
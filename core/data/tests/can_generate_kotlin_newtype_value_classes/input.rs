@@ -0,0 +1,15 @@
+/// A user identifier
+#[typeshare]
+pub struct UserId(String);
+
+#[typeshare]
+pub struct Wrapper<T>(T);
+
+#[typeshare]
+pub type Name = String;
+
+#[typeshare]
+pub struct User {
+    pub id: UserId,
+    pub name: Name,
+}
@@ -0,0 +1,23 @@
+typealias Name = String
+
+/**
+ * A user identifier
+ */
+@Serializable
+@JvmInline
+value class UserId(
+	val value: String
+)
+
+@Serializable
+@JvmInline
+value class Wrapper<T>(
+	val value: T
+)
+
+@Serializable
+data class User (
+	val id: UserId,
+	val name: Name
+)
+
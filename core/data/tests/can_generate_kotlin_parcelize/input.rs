@@ -0,0 +1,15 @@
+#[typeshare]
+pub struct Person {
+    pub name: String,
+    pub age: u8,
+}
+
+#[typeshare]
+pub struct Nothing {}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Shape {
+    Circle(u32),
+    Empty,
+}
@@ -0,0 +1,31 @@
+package com.agilebits.onepassword
+
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.SerialName
+import android.os.Parcelable
+import kotlinx.parcelize.Parcelize
+
+@Serializable
+@Parcelize
+object Nothing : Parcelable
+
+@Serializable
+@Parcelize
+data class Person (
+	val name: String,
+	val age: UByte
+) : Parcelable
+
+@Serializable
+@Parcelize
+sealed class Shape : Parcelable {
+	@Serializable
+	@SerialName("Circle")
+	@Parcelize
+	data class Circle(val content: UInt): Shape()
+	@Serializable
+	@SerialName("Empty")
+	@Parcelize
+	object Empty: Shape()
+}
+
@@ -0,0 +1,15 @@
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Shape {
+    Circle(u32),
+    #[serde(rename = "rect")]
+    Rectangle { width: u32, height: u32 },
+    Empty,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Wrapper<T> {
+    Value(T),
+    Nothing,
+}
@@ -0,0 +1,32 @@
+/**
+ * Generated type representing the anonymous struct variant `Rectangle` of the [Shape] Rust enum
+ */
+@Serializable
+data class ShapeRectangleInner (
+	val width: UInt,
+	val height: UInt
+)
+
+@Serializable
+sealed interface Shape {
+	@Serializable
+	@SerialName("Circle")
+	data class Circle(val content: UInt): Shape
+	@Serializable
+	@SerialName("rect")
+	data class Rectangle(val content: ShapeRectangleInner): Shape
+	@Serializable
+	@SerialName("Empty")
+	object Empty: Shape
+}
+
+@Serializable
+sealed interface Wrapper<T> {
+	@Serializable
+	@SerialName("Value")
+	data class Value<T>(val content: T): Wrapper<T>
+	@Serializable
+	@SerialName("Nothing")
+	object Nothing: Wrapper<T>
+}
+
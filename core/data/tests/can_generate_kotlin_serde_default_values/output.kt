@@ -0,0 +1,16 @@
+@Serializable
+data class Theme (
+	val color: String
+)
+
+@Serializable
+data class Settings (
+	val name: String,
+	val enabled: Boolean = false,
+	val retryCount: UInt = 0u,
+	val tags: List<String> = listOf(),
+	val labels: HashMap<String, String> = hashMapOf(),
+	val description: String? = null,
+	val theme: Theme? = null
+)
+
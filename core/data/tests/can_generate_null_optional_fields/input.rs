@@ -0,0 +1,9 @@
+#[typeshare]
+pub struct Profile {
+    pub name: String,
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub age: u32,
+}
@@ -0,0 +1,10 @@
+package proto
+
+import "encoding/json"
+
+type Profile struct {
+	Name string `json:"name"`
+	Nickname *string `json:"nickname"`
+	AvatarUrl *string `json:"avatar_url"`
+	Age *uint32 `json:"age,omitempty"`
+}
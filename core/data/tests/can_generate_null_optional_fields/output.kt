@@ -0,0 +1,8 @@
+@Serializable
+data class Profile (
+	val name: String,
+	val nickname: String?,
+	val avatar_url: String? = null,
+	val age: UInt? = null
+)
+
@@ -0,0 +1,12 @@
+from __future__ import annotations
+
+from pydantic import BaseModel, Field
+from typing import Optional
+
+
+class Profile(BaseModel):
+    name: str
+    nickname: Optional[str]
+    avatar_url: Optional[str] = Field(default=None)
+    age: Optional[int] = Field(default=None)
+
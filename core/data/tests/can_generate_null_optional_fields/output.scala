@@ -0,0 +1,20 @@
+package com.agilebits
+
+package object onepassword {
+
+type UByte = Byte
+type UShort = Short
+type UInt = Int
+type ULong = Int
+
+}
+package onepassword {
+
+case class Profile (
+	name: String,
+	nickname: Option[String],
+	avatar_url: Option[String] = None,
+	age: UInt = _
+)
+
+}
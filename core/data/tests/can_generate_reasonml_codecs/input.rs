@@ -0,0 +1,41 @@
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub struct Address {
+    pub street: String,
+    pub postal_code: Option<String>,
+}
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+pub struct User {
+    pub id: String,
+    pub role: Role,
+    pub verified: bool,
+    pub login_count: u32,
+    pub home: Option<Address>,
+    pub addresses_by_label: HashMap<String, Address>,
+    pub friends: Vec<User>,
+}
+
+#[typeshare]
+pub type UserPage = Page<User>;
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    SignedUp(User),
+    LoggedOut,
+}
@@ -0,0 +1,6 @@
+#[typeshare]
+pub struct Item {
+    pub description: String,
+    pub hash: String,
+    pub name: String,
+}
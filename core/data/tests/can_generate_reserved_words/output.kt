@@ -0,0 +1,7 @@
+@Serializable
+data class Item (
+	val `description`: String,
+	val `hash`: String,
+	val name: String
+)
+
@@ -0,0 +1,12 @@
+from __future__ import annotations
+
+from pydantic import BaseModel, ConfigDict, Field
+
+
+class Item(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    description_: str = Field(alias="description")
+    hash_: str = Field(alias="hash")
+    name: str
+
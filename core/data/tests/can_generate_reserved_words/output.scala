@@ -0,0 +1,11 @@
+package com.agilebits
+
+package onepassword {
+
+case class Item (
+	`description`: String,
+	`hash`: String,
+	name: String
+)
+
+}
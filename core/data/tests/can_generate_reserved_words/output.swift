@@ -0,0 +1,13 @@
+import Foundation
+
+public struct Item: Codable {
+	public let `description`: String
+	public let `hash`: String
+	public let name: String
+
+	public init(description: String, hash: String, name: String) {
+		self.description = `description`
+		self.hash = `hash`
+		self.name = name
+	}
+}
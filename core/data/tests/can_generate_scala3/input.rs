@@ -0,0 +1,40 @@
+#[typeshare]
+pub struct Item {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Active,
+    OnHold,
+}
+
+#[typeshare]
+#[serde(tag = "kind", content = "data")]
+pub enum Event {
+    Created(Item),
+    Renamed { from: String, to: String },
+    Deleted,
+}
+
+#[typeshare]
+pub type Items = Vec<Item>;
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Outcome<S, F> {
+    Success(S),
+    Failure(F),
+}
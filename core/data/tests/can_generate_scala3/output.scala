@@ -0,0 +1,36 @@
+package com.agilebits.onepassword
+
+type Items = Vector[Item]
+
+case class Empty()
+
+case class Item(
+	name: String,
+	tags: Vector[String],
+	note: Option[String] = None
+)
+
+case class Page[T](
+	items: Vector[T],
+	next: Option[String] = None
+)
+
+// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+case class EventRenamedInner(
+	from: String,
+	to: String
+)
+
+enum Event(val serialName: String):
+	case Created(data: Item) extends Event("Created")
+	case Renamed(data: EventRenamedInner) extends Event("Renamed")
+	case Deleted extends Event("Deleted")
+
+enum Outcome[S, F](val serialName: String):
+	case Success[S, F](content: S) extends Outcome[S, F]("Success")
+	case Failure[S, F](content: F) extends Outcome[S, F]("Failure")
+
+enum Status(val serialName: String):
+	case Active extends Status("active")
+	case OnHold extends Status("on_hold")
+
@@ -0,0 +1,56 @@
+package com.agilebits.onepassword
+
+import io.circe.{Codec, Decoder, DecodingFailure, Encoder, Json}
+import io.circe.syntax.*
+
+case class Empty() derives Codec.AsObject
+
+case class Item(
+	name: String,
+	tags: Vector[String],
+	note: Option[String] = None
+) derives Codec.AsObject
+
+case class Page[T](
+	items: Vector[T],
+	next: Option[String] = None
+) derives Codec.AsObject
+
+// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+case class EventRenamedInner(
+	from: String,
+	to: String
+) derives Codec.AsObject
+
+enum Event(val serialName: String):
+	case Created(data: Item) extends Event("Created")
+	case Renamed(data: EventRenamedInner) extends Event("Renamed")
+	case Deleted extends Event("Deleted")
+
+object Event:
+	given Encoder[Event] = Encoder.instance {
+		case Created(content) => Json.obj("kind" -> Json.fromString("Created"), "data" -> content.asJson)
+		case Renamed(content) => Json.obj("kind" -> Json.fromString("Renamed"), "data" -> content.asJson)
+		case Deleted => Json.obj("kind" -> Json.fromString("Deleted"))
+	}
+	given Decoder[Event] = Decoder.instance { c =>
+		c.downField("kind").as(Decoder.decodeString).flatMap {
+			case "Created" => c.downField("data").as[Item].map(Created(_))
+			case "Renamed" => c.downField("data").as[EventRenamedInner].map(Renamed(_))
+			case "Deleted" => Right(Deleted)
+			case other => Left(DecodingFailure(s"Unknown Event: $other", c.history))
+		}
+	}
+
+enum Status(val serialName: String):
+	case Active extends Status("active")
+	case OnHold extends Status("on_hold")
+
+object Status:
+	given Encoder[Status] = Encoder.encodeString.contramap(_.serialName)
+	given Decoder[Status] = Decoder.decodeString.emap {
+		case "active" => Right(Active)
+		case "on_hold" => Right(OnHold)
+		case other => Left(s"Unknown Status: $other")
+	}
+
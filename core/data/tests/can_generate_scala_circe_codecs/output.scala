@@ -0,0 +1,94 @@
+package com.agilebits
+
+import io.circe.{Decoder, DecodingFailure, Encoder, Json}
+import io.circe.generic.semiauto.{deriveDecoder, deriveEncoder}
+import io.circe.syntax._
+
+package onepassword {
+
+class Empty extends Serializable
+
+object Empty {
+	implicit val encoder: Encoder[Empty] = Encoder.instance(_ => Json.obj())
+	implicit val decoder: Decoder[Empty] = Decoder.const(new Empty)
+}
+
+case class Item (
+	name: String,
+	tags: Vector[String],
+	note: Option[String] = None
+)
+
+object Item {
+	implicit val encoder: Encoder[Item] = deriveEncoder
+	implicit val decoder: Decoder[Item] = deriveDecoder
+}
+
+case class Page[T] (
+	items: Vector[T],
+	next: Option[String] = None
+)
+
+object Page {
+	implicit def encoder[T: Encoder]: Encoder[Page[T]] = deriveEncoder
+	implicit def decoder[T: Decoder]: Decoder[Page[T]] = deriveDecoder
+}
+
+// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+case class EventRenamedInner (
+	from: String,
+	to: String
+)
+
+object EventRenamedInner {
+	implicit val encoder: Encoder[EventRenamedInner] = deriveEncoder
+	implicit val decoder: Decoder[EventRenamedInner] = deriveDecoder
+}
+
+sealed trait Event {
+	def serialName: String
+}
+object Event {
+	case class Created(data: Item) extends Event {
+		val serialName: String = "Created"
+	}
+	case class Renamed(data: EventRenamedInner) extends Event {
+		val serialName: String = "Renamed"
+	}
+	case object Deleted extends Event {
+		val serialName: String = "Deleted"
+	}
+	implicit val encoder: Encoder[Event] = Encoder.instance {
+		case Created(content) => Json.obj("kind" -> Json.fromString("Created"), "data" -> content.asJson)
+		case Renamed(content) => Json.obj("kind" -> Json.fromString("Renamed"), "data" -> content.asJson)
+		case Deleted => Json.obj("kind" -> Json.fromString("Deleted"))
+	}
+	implicit val decoder: Decoder[Event] = Decoder.instance { c =>
+		c.downField("kind").as(Decoder.decodeString).flatMap {
+			case "Created" => c.downField("data").as[Item].map(Created(_))
+			case "Renamed" => c.downField("data").as[EventRenamedInner].map(Renamed(_))
+			case "Deleted" => Right(Deleted)
+			case other => Left(DecodingFailure(s"Unknown Event: $other", c.history))
+		}
+	}
+}
+
+sealed trait Status {
+	def serialName: String
+}
+object Status {
+	case object Active extends Status {
+		val serialName: String = "active"
+	}
+	case object OnHold extends Status {
+		val serialName: String = "on_hold"
+	}
+	implicit val encoder: Encoder[Status] = Encoder.encodeString.contramap(_.serialName)
+	implicit val decoder: Decoder[Status] = Decoder.decodeString.emap {
+		case "active" => Right(Active)
+		case "on_hold" => Right(OnHold)
+		case other => Left(s"Unknown Status: $other")
+	}
+}
+
+}
@@ -0,0 +1,37 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Missing or `null`
+    pub note: Option<String>,
+    pub counts: HashMap<String, u32>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(rename = "item-type")]
+    pub item_type: String,
+}
+
+#[typeshare]
+pub struct Page<T> {
+    pub items: Vec<T>,
+}
+
+#[typeshare]
+pub type Items = Page<Item>;
+
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Active,
+    /// Waiting for review
+    OnHold,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    Created(Item),
+    Renamed { from: String, to: String },
+    Deleted,
+}
@@ -0,0 +1,62 @@
+package com.agilebits
+
+import scala.scalajs.js
+import scala.scalajs.js.|
+
+package object onepassword {
+
+type Items = Page[Item]
+
+}
+package onepassword {
+
+@js.native
+trait Item extends js.Object {
+	val name: String = js.native
+	val tags: js.Array[String] = js.native
+	// Missing or `null`
+	val note: js.UndefOr[String | Null] = js.native
+	val counts: js.Dictionary[Double] = js.native
+	val archived: js.UndefOr[Boolean] = js.native
+	@js.annotation.JSName("item-type")
+	val item_type: String = js.native
+}
+
+@js.native
+trait Page[T] extends js.Object {
+	val items: js.Array[T] = js.native
+}
+
+// Generated type representing the anonymous struct variant `Renamed` of the `Event` Rust enum
+@js.native
+trait EventRenamedInner extends js.Object {
+	val from: String = js.native
+	val to: String = js.native
+}
+
+@js.native
+sealed trait Event extends js.Object {
+	val `type`: String = js.native
+}
+object Event {
+	@js.native
+	sealed trait Created extends Event {
+		val content: Item = js.native
+	}
+	@js.native
+	sealed trait Renamed extends Event {
+		val content: EventRenamedInner = js.native
+	}
+	@js.native
+	sealed trait Deleted extends Event
+}
+
+@js.native
+sealed trait Status extends js.Any
+object Status {
+	val Active: Status = "active".asInstanceOf[Status]
+	// Waiting for review
+	val OnHold: Status = "on_hold".asInstanceOf[Status]
+}
+
+}
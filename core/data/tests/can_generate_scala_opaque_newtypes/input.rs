@@ -0,0 +1,16 @@
+/// The identifier of a user
+#[typeshare]
+pub struct UserId(String);
+
+#[typeshare]
+pub struct Tags<T>(Vec<T>);
+
+#[typeshare]
+pub type Name = String;
+
+#[typeshare]
+pub struct User {
+    pub id: UserId,
+    pub name: Name,
+    pub tags: Tags<String>,
+}
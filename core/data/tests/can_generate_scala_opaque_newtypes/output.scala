@@ -0,0 +1,30 @@
+package com.agilebits.onepassword
+
+import io.circe.{Codec, Decoder, DecodingFailure, Encoder, Json}
+import io.circe.syntax.*
+
+type Name = String
+
+opaque type Tags[T] = Vector[T]
+
+object Tags:
+	def apply[T](value: Vector[T]): Tags[T] = value
+	extension [T] (self: Tags[T]) def value: Vector[T] = self
+	given [T: Encoder]: Encoder[Tags[T]] = Encoder[Vector[T]]
+	given [T: Decoder]: Decoder[Tags[T]] = Decoder[Vector[T]]
+
+// The identifier of a user
+opaque type UserId = String
+
+object UserId:
+	def apply(value: String): UserId = value
+	extension (self: UserId) def value: String = self
+	given Encoder[UserId] = Encoder[String]
+	given Decoder[UserId] = Decoder[String]
+
+case class User(
+	id: UserId,
+	name: Name,
+	tags: Tags[String]
+) derives Codec.AsObject
+
@@ -0,0 +1,24 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    user_id: UserId,
+    name: String,
+    #[serde(default)]
+    role: Option<Role>,
+}
+
+#[typeshare]
+pub type UserId = String;
+
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    Joined(User),
+    Left { user_id: UserId },
+}
@@ -0,0 +1,96 @@
+package proto
+
+import "encoding/json"
+
+type UserId string
+
+const UserIdSchemaHash = "844445efc67f6857"
+
+type Role string
+const (
+	RoleAdmin Role = "Admin"
+	RoleMember Role = "Member"
+)
+const RoleSchemaHash = "c95e773aa7ce3b79"
+
+type User struct {
+	UserId UserId `json:"userId"`
+	Name string `json:"name"`
+	Role *Role `json:"role,omitempty"`
+}
+const UserSchemaHash = "5376e68c27b8dc37"
+
+// Generated type representing the anonymous struct variant `Left` of the `Event` Rust enum
+type EventLeftInner struct {
+	UserId UserId `json:"user_id"`
+}
+type EventTypes string
+const (
+	EventTypeVariantJoined EventTypes = "Joined"
+	EventTypeVariantLeft EventTypes = "Left"
+)
+type Event struct{ 
+	Type EventTypes `json:"type"`
+	content interface{}
+}
+
+func (e *Event) UnmarshalJSON(data []byte) error {
+	var enum struct {
+		Tag    EventTypes   `json:"type"`
+		Content json.RawMessage `json:"content"`
+	}
+	if err := json.Unmarshal(data, &enum); err != nil {
+		return err
+	}
+
+	e.Type = enum.Tag
+	switch e.Type {
+	case EventTypeVariantJoined:
+		var res User
+		e.content = &res
+	case EventTypeVariantLeft:
+		var res EventLeftInner
+		e.content = &res
+
+	}
+	if err := json.Unmarshal(enum.Content, &e.content); err != nil {
+		return err
+	}
+
+	return nil
+}
+
+func (e Event) MarshalJSON() ([]byte, error) {
+    var enum struct {
+		Tag    EventTypes   `json:"type"`
+		Content interface{} `json:"content,omitempty"`
+    }
+    enum.Tag = e.Type
+    enum.Content = e.content
+    return json.Marshal(enum)
+}
+
+func (e Event) Joined() *User {
+	res, _ := e.content.(*User)
+	return res
+}
+func (e Event) Left() *EventLeftInner {
+	res, _ := e.content.(*EventLeftInner)
+	return res
+}
+
+func NewEventTypeVariantJoined(content *User) Event {
+    return Event{
+        Type: EventTypeVariantJoined,
+        content: content,
+    }
+}
+func NewEventTypeVariantLeft(content *EventLeftInner) Event {
+    return Event{
+        Type: EventTypeVariantLeft,
+        content: content,
+    }
+}
+
+const EventSchemaHash = "60524f4bf2cc01f2"
+
@@ -0,0 +1,43 @@
+typealias UserId = String
+
+const val UserIdSchemaHash = "844445efc67f6857"
+
+@Serializable
+enum class Role(val string: String) {
+	@SerialName("Admin")
+	Admin("Admin"),
+	@SerialName("Member")
+	Member("Member"),
+}
+
+const val RoleSchemaHash = "c95e773aa7ce3b79"
+
+@Serializable
+data class User (
+	val userId: UserId,
+	val name: String,
+	val role: Role? = null
+)
+
+const val UserSchemaHash = "5376e68c27b8dc37"
+
+/**
+ * Generated type representing the anonymous struct variant `Left` of the [Event] Rust enum
+ */
+@Serializable
+data class EventLeftInner (
+	val user_id: UserId
+)
+
+@Serializable
+sealed class Event {
+	@Serializable
+	@SerialName("Joined")
+	data class Joined(val content: User): Event()
+	@Serializable
+	@SerialName("Left")
+	data class Left(val content: EventLeftInner): Event()
+}
+
+const val EventSchemaHash = "60524f4bf2cc01f2"
+
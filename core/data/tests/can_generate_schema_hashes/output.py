@@ -0,0 +1,46 @@
+from __future__ import annotations
+
+from enum import Enum
+from pydantic import BaseModel, ConfigDict, Field
+from typing import Literal, Optional, Union
+
+
+UserId = str
+
+UserIdSchemaHash: str = "844445efc67f6857"
+
+class Role(str, Enum):
+    ADMIN = "Admin"
+    MEMBER = "Member"
+RoleSchemaHash: str = "c95e773aa7ce3b79"
+
+class User(BaseModel):
+    model_config = ConfigDict(populate_by_name=True)
+
+    user_id: UserId = Field(alias="userId")
+    name: str
+    role: Optional[Role] = Field(default=None)
+
+UserSchemaHash: str = "5376e68c27b8dc37"
+
+class EventLeftInner(BaseModel):
+    """
+    Generated type representing the anonymous struct variant `Left` of the `Event` Rust enum
+    """
+    user_id: UserId
+
+class EventTypes(str, Enum):
+    JOINED = "Joined"
+    LEFT = "Left"
+
+class EventJoined(BaseModel):
+    type: Literal[EventTypes.JOINED] = EventTypes.JOINED
+    content: User
+
+class EventLeft(BaseModel):
+    type: Literal[EventTypes.LEFT] = EventTypes.LEFT
+    content: EventLeftInner
+
+Event = Union[EventJoined, EventLeft]
+EventSchemaHash: str = "60524f4bf2cc01f2"
+
@@ -0,0 +1,53 @@
+package com.agilebits
+
+package object onepassword {
+
+type UserId = String
+
+val UserIdSchemaHash: String = "844445efc67f6857"
+
+val UserSchemaHash: String = "5376e68c27b8dc37"
+
+val EventSchemaHash: String = "60524f4bf2cc01f2"
+
+val RoleSchemaHash: String = "c95e773aa7ce3b79"
+
+}
+package onepassword {
+
+case class User (
+	userId: UserId,
+	name: String,
+	role: Option[Role] = None
+)
+
+// Generated type representing the anonymous struct variant `Left` of the `Event` Rust enum
+case class EventLeftInner (
+	user_id: UserId
+)
+
+sealed trait Event {
+	def serialName: String
+}
+object Event {
+	case class Joined(content: User) extends Event {
+		val serialName: String = "Joined"
+	}
+	case class Left(content: EventLeftInner) extends Event {
+		val serialName: String = "Left"
+	}
+}
+
+sealed trait Role {
+	def serialName: String
+}
+object Role {
+	case object Admin extends Role {
+		val serialName: String = "Admin"
+	}
+	case object Member extends Role {
+		val serialName: String = "Member"
+	}
+}
+
+}
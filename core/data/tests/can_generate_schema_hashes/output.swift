@@ -0,0 +1,82 @@
+import Foundation
+
+public typealias UserId = String
+
+public let UserIdSchemaHash = "844445efc67f6857"
+
+public enum Role: String, Codable {
+	case admin = "Admin"
+	case member = "Member"
+}
+
+public let RoleSchemaHash = "c95e773aa7ce3b79"
+
+public struct User: Codable {
+	public let userId: UserId
+	public let name: String
+	public let role: Role?
+
+	public init(userId: UserId, name: String, role: Role?) {
+		self.userId = userId
+		self.name = name
+		self.role = role
+	}
+}
+
+public let UserSchemaHash = "5376e68c27b8dc37"
+
+
+/// Generated type representing the anonymous struct variant `Left` of the `Event` Rust enum
+public struct EventLeftInner: Codable {
+	public let user_id: UserId
+
+	public init(user_id: UserId) {
+		self.user_id = user_id
+	}
+}
+public enum Event: Codable {
+	case joined(User)
+	case left(EventLeftInner)
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case joined = "Joined",
+			left = "Left"
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .joined:
+				if let content = try? container.decode(User.self, forKey: .content) {
+					self = .joined(content)
+					return
+				}
+			case .left:
+				if let content = try? container.decode(EventLeftInner.self, forKey: .content) {
+					self = .left(content)
+					return
+				}
+			}
+		}
+		throw DecodingError.typeMismatch(Event.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Event"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .joined(let content):
+			try container.encode(CodingKeys.joined, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .left(let content):
+			try container.encode(CodingKeys.left, forKey: .type)
+			try container.encode(content, forKey: .content)
+		}
+	}
+}
+
+public let EventSchemaHash = "60524f4bf2cc01f2"
@@ -0,0 +1,27 @@
+export type UserId = string;
+
+export const UserIdSchemaHash = "844445efc67f6857";
+
+export enum Role {
+	Admin = "Admin",
+	Member = "Member",
+}
+
+export const RoleSchemaHash = "c95e773aa7ce3b79";
+
+export interface User {
+	userId: UserId;
+	name: string;
+	role?: Role;
+}
+
+export const UserSchemaHash = "5376e68c27b8dc37";
+
+export type Event = 
+	| { type: "Joined", content: User }
+	| { type: "Left", content: {
+	user_id: UserId;
+}};
+
+export const EventSchemaHash = "60524f4bf2cc01f2";
+
@@ -6,10 +6,14 @@ import kotlinx.serialization.SerialName
 @Serializable
 object Location
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class Person (
-	/// This is another comment
+	/**
+	 * This is another comment
+	 */
 	val name: String,
 	val age: UByte,
 	val info: String? = null,
@@ -0,0 +1,22 @@
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub struct UserProfile {
+    pub display_name: String,
+    pub id: String,
+    #[serde(rename = "home-url")]
+    pub home_url: Option<String>,
+    #[serde(rename = "_internal_flag_")]
+    pub internal_flag: bool,
+    #[serde(rename = "URL_value")]
+    pub url_value: String,
+}
+
+#[typeshare]
+pub struct Empty {}
+
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub struct Session {
+    pub access_token: String,
+    pub expires_in: u32,
+}
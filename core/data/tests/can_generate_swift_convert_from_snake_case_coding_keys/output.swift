@@ -0,0 +1,39 @@
+import Foundation
+
+public struct Empty: Codable {
+	public init() {}
+}
+
+public struct Session: Codable {
+	public let accessToken: String
+	public let expiresIn: UInt32
+
+	public init(accessToken: String, expiresIn: UInt32) {
+		self.accessToken = accessToken
+		self.expiresIn = expiresIn
+	}
+}
+
+public struct UserProfile: Codable {
+	public let displayName: String
+	public let id: String
+	public let home_url: String?
+	public let _internalFlag_: Bool
+	public let URLValue: String
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case displayName,
+			id,
+			home_url = "home-url",
+			_internalFlag_,
+			URLValue
+	}
+
+	public init(displayName: String, id: String, home_url: String?, _internalFlag_: Bool, URLValue: String) {
+		self.displayName = displayName
+		self.id = id
+		self.home_url = home_url
+		self._internalFlag_ = _internalFlag_
+		self.URLValue = URLValue
+	}
+}
@@ -0,0 +1,13 @@
+#[typeshare]
+#[serde(rename_all = "snake_case")]
+pub struct UserProfile {
+    pub display_name: String,
+    pub id: String,
+    #[serde(rename = "home-url")]
+    pub home_url: Option<String>,
+    #[serde(rename = "_internal_flag_")]
+    pub internal_flag: bool,
+}
+
+#[typeshare]
+pub struct Empty {}
@@ -0,0 +1,26 @@
+import Foundation
+
+public struct Empty: Codable {
+	public init() {}
+}
+
+public struct UserProfile: Codable {
+	public let display_name: String
+	public let id: String
+	public let home_url: String?
+	public let _internal_flag_: Bool
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case display_name,
+			id,
+			home_url = "home-url",
+			_internal_flag_
+	}
+
+	public init(display_name: String, id: String, home_url: String?, _internal_flag_: Bool) {
+		self.display_name = display_name
+		self.id = id
+		self.home_url = home_url
+		self._internal_flag_ = _internal_flag_
+	}
+}
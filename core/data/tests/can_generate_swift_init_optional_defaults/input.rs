@@ -0,0 +1,8 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub age: u32,
+}
@@ -0,0 +1,13 @@
+import Foundation
+
+public struct Profile: Codable {
+	public let name: String
+	public let nickname: String?
+	public let age: UInt32?
+
+	public init(name: String, nickname: String? = nil, age: UInt32? = nil) {
+		self.name = name
+		self.nickname = nickname
+		self.age = age
+	}
+}
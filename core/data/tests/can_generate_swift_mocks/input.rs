@@ -0,0 +1,33 @@
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub struct Address {
+    pub street: String,
+    pub number: u32,
+}
+
+#[typeshare]
+pub struct User {
+    pub name: String,
+    pub verified: bool,
+    pub tags: Vec<String>,
+    pub settings: HashMap<String, String>,
+    pub nickname: Option<String>,
+    pub role: Role,
+    pub address: Address,
+    #[serde(default)]
+    pub score: f64,
+    pub payload: Payload<String>,
+}
+
+#[typeshare]
+pub struct Payload<T> {
+    pub data: T,
+}
+
+#[typeshare]
+pub struct Empty {}
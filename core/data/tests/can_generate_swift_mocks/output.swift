@@ -0,0 +1,91 @@
+import Foundation
+
+public struct Address: Codable {
+	public let street: String
+	public let number: UInt32
+
+	public init(street: String, number: UInt32) {
+		self.street = street
+		self.number = number
+	}
+}
+
+public extension Address {
+	static func mock(
+		street: String = "",
+		number: UInt32 = 0
+	) -> Address {
+		Address(street: street, number: number)
+	}
+}
+
+public struct Empty: Codable {
+	public init() {}
+}
+
+public extension Empty {
+	static func mock() -> Empty {
+		Empty()
+	}
+}
+
+public struct Payload<T: Codable>: Codable {
+	public let data: T
+
+	public init(data: T) {
+		self.data = data
+	}
+}
+
+public extension Payload {
+	static func mock(
+		data: T
+	) -> Payload {
+		Payload(data: data)
+	}
+}
+
+public enum Role: String, Codable {
+	case admin = "Admin"
+	case member = "Member"
+}
+
+public struct User: Codable {
+	public let name: String
+	public let verified: Bool
+	public let tags: [String]
+	public let settings: [String: String]
+	public let nickname: String?
+	public let role: Role
+	public let address: Address
+	public let score: Double?
+	public let payload: Payload<String>
+
+	public init(name: String, verified: Bool, tags: [String], settings: [String: String], nickname: String?, role: Role, address: Address, score: Double?, payload: Payload<String>) {
+		self.name = name
+		self.verified = verified
+		self.tags = tags
+		self.settings = settings
+		self.nickname = nickname
+		self.role = role
+		self.address = address
+		self.score = score
+		self.payload = payload
+	}
+}
+
+public extension User {
+	static func mock(
+		name: String = "",
+		verified: Bool = false,
+		tags: [String] = [],
+		settings: [String: String] = [:],
+		nickname: String? = nil,
+		role: Role = .admin,
+		address: Address = .mock(),
+		score: Double? = nil,
+		payload: Payload<String>
+	) -> User {
+		User(name: name, verified: verified, tags: tags, settings: settings, nickname: nickname, role: role, address: address, score: score, payload: payload)
+	}
+}
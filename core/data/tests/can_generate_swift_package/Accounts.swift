@@ -0,0 +1,9 @@
+import Foundation
+
+public struct Account: Codable {
+	public let id: String
+
+	public init(id: String) {
+		self.id = id
+	}
+}
@@ -0,0 +1,15 @@
+import Foundation
+import Accounts
+
+public struct Order: Codable {
+	public let account: Account
+	public let receipt: CodableVoid
+
+	public init(account: Account, receipt: CodableVoid) {
+		self.account = account
+		self.receipt = receipt
+	}
+}
+
+/// () isn't codable, so we use this instead to represent Rust's unit type
+public struct CodableVoid: Codable {}
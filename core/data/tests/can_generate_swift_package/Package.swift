@@ -0,0 +1,13 @@
+// swift-tools-version:5.5
+import PackageDescription
+
+let package = Package(
+	name: "Shop",
+	products: [
+		.library(name: "Shop", targets: ["Accounts", "Orders"]),
+	],
+	targets: [
+		.target(name: "Accounts"),
+		.target(name: "Orders", dependencies: ["Accounts"]),
+	]
+)
@@ -0,0 +1,4 @@
+#[typeshare]
+pub struct Account {
+    pub id: String,
+}
@@ -0,0 +1,7 @@
+use accounts::Account;
+
+#[typeshare]
+pub struct Order {
+    pub account: Account,
+    pub receipt: (),
+}
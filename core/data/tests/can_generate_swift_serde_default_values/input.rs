@@ -0,0 +1,28 @@
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub name: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub retry_count: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub external_id: ExternalId,
+    #[serde(default)]
+    pub revision: Revision,
+    #[serde(default)]
+    pub updated_at: Timestamp,
+}
+
+#[typeshare]
+pub struct Theme {
+    pub color: String,
+}
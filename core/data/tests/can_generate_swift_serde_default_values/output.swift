@@ -0,0 +1,62 @@
+import Foundation
+
+public struct Theme: Codable {
+	public let color: String
+
+	public init(color: String) {
+		self.color = color
+	}
+}
+
+public struct Settings: Codable {
+	public let name: String
+	public let enabled: Bool
+	public let retryCount: UInt32
+	public let tags: [String]
+	public let labels: [String: String]
+	public let description: String?
+	public let theme: Theme?
+	public let externalId: String
+	public let revision: UInt64
+	public let updatedAt: Date?
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case name,
+			enabled,
+			retryCount,
+			tags,
+			labels,
+			description,
+			theme,
+			externalId,
+			revision,
+			updatedAt
+	}
+
+	public init(name: String, enabled: Bool = false, retryCount: UInt32 = 0, tags: [String] = [], labels: [String: String] = [:], description: String?, theme: Theme?, externalId: String = "", revision: UInt64 = 0, updatedAt: Date?) {
+		self.name = name
+		self.enabled = enabled
+		self.retryCount = retryCount
+		self.tags = tags
+		self.labels = labels
+		self.description = description
+		self.theme = theme
+		self.externalId = externalId
+		self.revision = revision
+		self.updatedAt = updatedAt
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: CodingKeys.self)
+		self.name = try container.decode(String.self, forKey: .name)
+		self.enabled = try container.decodeIfPresent(Bool.self, forKey: .enabled) ?? false
+		self.retryCount = try container.decodeIfPresent(UInt32.self, forKey: .retryCount) ?? 0
+		self.tags = try container.decodeIfPresent([String].self, forKey: .tags) ?? []
+		self.labels = try container.decodeIfPresent([String: String].self, forKey: .labels) ?? [:]
+		self.description = try container.decodeIfPresent(String.self, forKey: .description)
+		self.theme = try container.decodeIfPresent(Theme.self, forKey: .theme)
+		self.externalId = try container.decodeIfPresent(String.self, forKey: .externalId) ?? ""
+		self.revision = try container.decodeIfPresent(UInt64.self, forKey: .revision) ?? 0
+		self.updatedAt = try container.decodeIfPresent(Date.self, forKey: .updatedAt)
+	}
+}
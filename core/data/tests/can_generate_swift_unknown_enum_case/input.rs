@@ -0,0 +1,16 @@
+/// A unit enum
+#[typeshare]
+pub enum Colors {
+    Red,
+    #[serde(rename = "blue-ish")]
+    Blue,
+    Green,
+}
+
+/// An algebraic enum
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Shape {
+    Circle(u32),
+    Nothing,
+}
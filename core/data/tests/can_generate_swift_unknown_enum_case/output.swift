@@ -0,0 +1,78 @@
+import Foundation
+
+/// A unit enum
+public enum Colors: Codable {
+	case red
+	case blue
+	case green
+	case unknown(String)
+
+	public init(from decoder: Decoder) throws {
+		let value = try decoder.singleValueContainer().decode(String.self)
+		switch value {
+		case "Red": self = .red
+		case "blue-ish": self = .blue
+		case "Green": self = .green
+		default: self = .unknown(value)
+		}
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.singleValueContainer()
+		switch self {
+		case .red: try container.encode("Red")
+		case .blue: try container.encode("blue-ish")
+		case .green: try container.encode("Green")
+		case .unknown(let value): try container.encode(value)
+		}
+	}
+}
+
+/// An algebraic enum
+public enum Shape: Codable {
+	case circle(UInt32)
+	case nothing
+	case unknown(String)
+
+	enum CodingKeys: String, CodingKey, Codable {
+		case circle = "Circle",
+			nothing = "Nothing"
+	}
+
+	private enum ContainerCodingKeys: String, CodingKey {
+		case type, content
+	}
+
+	public init(from decoder: Decoder) throws {
+		let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+		if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+			switch type {
+			case .circle:
+				if let content = try? container.decode(UInt32.self, forKey: .content) {
+					self = .circle(content)
+					return
+				}
+			case .nothing:
+				self = .nothing
+				return
+			}
+		} else if let type = try? container.decode(String.self, forKey: .type) {
+			self = .unknown(type)
+			return
+		}
+		throw DecodingError.typeMismatch(Shape.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for Shape"))
+	}
+
+	public func encode(to encoder: Encoder) throws {
+		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+		switch self {
+		case .circle(let content):
+			try container.encode(CodingKeys.circle, forKey: .type)
+			try container.encode(content, forKey: .content)
+		case .nothing:
+			try container.encode(CodingKeys.nothing, forKey: .type)
+		case .unknown(let type):
+			try container.encode(type, forKey: .type)
+		}
+	}
+}
@@ -0,0 +1,39 @@
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub struct Address {
+    pub street_name: String,
+    pub postal_code: Option<String>,
+}
+
+#[typeshare]
+pub struct Page<T> {
+    pub page_items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[typeshare]
+pub struct User {
+    pub user_id: String,
+    pub role: Role,
+    pub home_address: Option<Address>,
+    pub past_addresses: Vec<Address>,
+    pub addresses_by_label: HashMap<String, Address>,
+    #[serde(default)]
+    pub login_count: u32,
+}
+
+#[typeshare]
+pub type UserPage = Page<User>;
+
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    SignedUp(User),
+    Moved { old_address: Address, new_address: Address },
+    LoggedOut,
+}
@@ -0,0 +1,128 @@
+export interface Page<T> {
+	pageItems: T[];
+	nextCursor?: string;
+}
+
+export function decodePage<T>(json: any, decodeT: (json: any) => T): Page<T> {
+	return {
+		pageItems: json.page_items.map((v0: any) => decodeT(v0)),
+		nextCursor: json.next_cursor,
+	};
+}
+
+export function encodePage<T>(value: Page<T>, encodeT: (value: T) => any): any {
+	return {
+		page_items: value.pageItems.map((v0: any) => encodeT(v0)),
+		next_cursor: value.nextCursor,
+	};
+}
+
+export enum Role {
+	Admin = "Admin",
+	Member = "Member",
+}
+
+export function decodeRole(json: any): Role {
+	return json;
+}
+
+export function encodeRole(value: Role): any {
+	return value;
+}
+
+export interface Address {
+	streetName: string;
+	postalCode?: string;
+}
+
+export function decodeAddress(json: any): Address {
+	return {
+		streetName: json.street_name,
+		postalCode: json.postal_code,
+	};
+}
+
+export function encodeAddress(value: Address): any {
+	return {
+		street_name: value.streetName,
+		postal_code: value.postalCode,
+	};
+}
+
+export interface User {
+	userId: string;
+	role: Role;
+	homeAddress?: Address;
+	pastAddresses: Address[];
+	addressesByLabel: Record<string, Address>;
+	loginCount?: number;
+}
+
+export function decodeUser(json: any): User {
+	return {
+		userId: json.user_id,
+		role: decodeRole(json.role),
+		homeAddress: json.home_address == null ? json.home_address : decodeAddress(json.home_address),
+		pastAddresses: json.past_addresses.map((v0: any) => decodeAddress(v0)),
+		addressesByLabel: Object.fromEntries(Object.entries(json.addresses_by_label).map(([k0, v0]: [string, any]) => [k0, decodeAddress(v0)])),
+		loginCount: json.login_count,
+	};
+}
+
+export function encodeUser(value: User): any {
+	return {
+		user_id: value.userId,
+		role: encodeRole(value.role),
+		home_address: value.homeAddress == null ? value.homeAddress : encodeAddress(value.homeAddress),
+		past_addresses: value.pastAddresses.map((v0: any) => encodeAddress(v0)),
+		addresses_by_label: Object.fromEntries(Object.entries(value.addressesByLabel).map(([k0, v0]: [string, any]) => [k0, encodeAddress(v0)])),
+		login_count: value.loginCount,
+	};
+}
+
+export type UserPage = Page<User>;
+
+export function decodeUserPage(json: any): UserPage {
+	return decodePage(json, (v0: any) => decodeUser(v0));
+}
+
+export function encodeUserPage(value: UserPage): any {
+	return encodePage(value, (v0: any) => encodeUser(v0));
+}
+
+export type Event = 
+	| { type: "SignedUp", content: User }
+	| { type: "Moved", content: {
+	oldAddress: Address;
+	newAddress: Address;
+}}
+	| { type: "LoggedOut", content?: undefined };
+
+export function decodeEvent(json: any): Event {
+	switch (json.type) {
+		case "SignedUp":
+			return { type: json.type, content: decodeUser(json.content) };
+		case "Moved":
+			return { type: json.type, content: {
+				oldAddress: decodeAddress(json.content.old_address),
+				newAddress: decodeAddress(json.content.new_address),
+			} };
+		default:
+			return json;
+	}
+}
+
+export function encodeEvent(value: Event): any {
+	switch (value.type) {
+		case "SignedUp":
+			return { type: value.type, content: encodeUser(value.content) };
+		case "Moved":
+			return { type: value.type, content: {
+				old_address: encodeAddress(value.content.oldAddress),
+				new_address: encodeAddress(value.content.newAddress),
+			} };
+		default:
+			return value;
+	}
+}
+
@@ -0,0 +1,16 @@
+/// A user of the service, with the identifier which is given when they sign up and which never changes
+#[typeshare]
+pub struct User {
+    /// The identifier of the user, which is given when they sign up and never changes afterwards
+    pub id: String,
+    pub name: Option<String>,
+}
+
+/// The state of a user
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum UserState {
+    Active,
+    /// A suspended user, which can't sign in until the suspension ends at the given time
+    Suspended { until: String },
+}
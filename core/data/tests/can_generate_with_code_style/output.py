@@ -0,0 +1,43 @@
+from __future__ import annotations
+
+from enum import Enum
+from pydantic import BaseModel, Field
+from typing import Literal, Optional, Union
+
+
+class User(BaseModel):
+  """
+  A user of the service, with the identifier which is given
+  when they sign up and which never changes
+  """
+  id: str
+  """
+  The identifier of the user, which is given when they sign
+  up and never changes afterwards
+  """
+  name: Optional[str] = Field(default=None)
+
+class UserStateSuspendedInner(BaseModel):
+  """
+  Generated type representing the anonymous struct variant
+  `Suspended` of the `UserState` Rust enum
+  """
+  until: str
+
+class UserStateTypes(str, Enum):
+  ACTIVE = "Active"
+  SUSPENDED = "Suspended"
+
+class UserStateActive(BaseModel):
+  type: Literal[UserStateTypes.ACTIVE] = UserStateTypes.ACTIVE
+
+class UserStateSuspended(BaseModel):
+  """
+  A suspended user, which can't sign in until the suspension
+  ends at the given time
+  """
+  type: Literal[UserStateTypes.SUSPENDED] = UserStateTypes.SUSPENDED
+  content: UserStateSuspendedInner
+
+# The state of a user
+UserState = Union[UserStateActive, UserStateSuspended]
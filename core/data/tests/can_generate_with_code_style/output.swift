@@ -0,0 +1,70 @@
+import Foundation
+
+/// A user of the service, with the identifier which is
+/// given when they sign up and which never changes
+public struct User: Codable {
+  /// The identifier of the user, which is given when they
+  /// sign up and never changes afterwards
+  public let id: String
+  public let name: String?
+
+  public init(id: String, name: String?) {
+    self.id = id
+    self.name = name
+  }
+}
+
+
+/// Generated type representing the anonymous struct variant
+/// `Suspended` of the `UserState` Rust enum
+public struct UserStateSuspendedInner: Codable {
+  public let until: String
+
+  public init(until: String) {
+    self.until = until
+  }
+}
+/// The state of a user
+public enum UserState: Codable {
+  case active
+  /// A suspended user, which can't sign in until the
+  /// suspension ends at the given time
+  case suspended(UserStateSuspendedInner)
+
+  enum CodingKeys: String, CodingKey, Codable {
+    case active = "Active",
+      suspended = "Suspended"
+  }
+
+  private enum ContainerCodingKeys: String, CodingKey {
+    case type, content
+  }
+
+  public init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: ContainerCodingKeys.self)
+    if let type = try? container.decode(CodingKeys.self, forKey: .type) {
+      switch type {
+      case .active:
+        self = .active
+        return
+      case .suspended:
+        if let content = try? container.decode(UserStateSuspendedInner.self, forKey: .content) {
+          self = .suspended(content)
+          return
+        }
+      }
+    }
+    throw DecodingError.typeMismatch(UserState.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for UserState"))
+  }
+
+  public func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: ContainerCodingKeys.self)
+    switch self {
+    case .active:
+      try container.encode(CodingKeys.active, forKey: .type)
+    case .suspended(let content):
+      try container.encode(CodingKeys.suspended, forKey: .type)
+      try container.encode(content, forKey: .content)
+    }
+  }
+}
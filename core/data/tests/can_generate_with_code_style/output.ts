@@ -0,0 +1,24 @@
+/**
+ * A user of the service, with the identifier which is given
+ * when they sign up and which never changes
+ */
+export interface User {
+  /**
+   * The identifier of the user, which is given when they
+   * sign up and never changes afterwards
+   */
+  id: string;
+  name?: string;
+}
+
+/** The state of a user */
+export type UserState = 
+  | { type: "Active", content?: undefined }
+  /**
+   * A suspended user, which can't sign in until the
+   * suspension ends at the given time
+   */
+  | { type: "Suspended", content: {
+  until: string;
+}};
+
@@ -3,49 +3,71 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// Generated type representing the anonymous struct variant `Us` of the `AutofilledBy` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `Us` of the [AutofilledBy] Rust enum
+ */
 @Serializable
 data class AutofilledByUsInner (
-	/// The UUID for the fill
+	/**
+	 * The UUID for the fill
+	 */
 	val uuid: String
 )
 
-/// Generated type representing the anonymous struct variant `SomethingElse` of the `AutofilledBy` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `SomethingElse` of the [AutofilledBy] Rust enum
+ */
 @Serializable
 data class AutofilledBySomethingElseInner (
-	/// The UUID for the fill
+	/**
+	 * The UUID for the fill
+	 */
 	val uuid: String,
-	/// Some other thing
+	/**
+	 * Some other thing
+	 */
 	val thing: Int
 )
 
-/// Enum keeping track of who autofilled a field
+/**
+ * Enum keeping track of who autofilled a field
+ */
 @Serializable
 sealed class AutofilledBy {
-	/// This field was autofilled by us
+	/**
+	 * This field was autofilled by us
+	 */
 	@Serializable
 	@SerialName("Us")
 	data class Us(val content: AutofilledByUsInner): AutofilledBy()
-	/// Something else autofilled this field
+	/**
+	 * Something else autofilled this field
+	 */
 	@Serializable
 	@SerialName("SomethingElse")
 	data class SomethingElse(val content: AutofilledBySomethingElseInner): AutofilledBy()
 }
 
-/// Generated type representing the anonymous struct variant `AnonVariant` of the `EnumWithManyVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `AnonVariant` of the [EnumWithManyVariants] Rust enum
+ */
 @Serializable
 data class EnumWithManyVariantsAnonVariantInner (
 	val uuid: String
 )
 
-/// Generated type representing the anonymous struct variant `AnotherAnonVariant` of the `EnumWithManyVariants` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `AnotherAnonVariant` of the [EnumWithManyVariants] Rust enum
+ */
 @Serializable
 data class EnumWithManyVariantsAnotherAnonVariantInner (
 	val uuid: String,
 	val thing: Int
 )
 
-/// This is a comment (yareek sameek wuz here)
+/**
+ * This is a comment (yareek sameek wuz here)
+ */
 @Serializable
 sealed class EnumWithManyVariants {
 	@Serializable
@@ -3,7 +3,9 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// This is a Person struct with camelCase rename
+/**
+ * This is a Person struct with camelCase rename
+ */
 @Serializable
 data class Person (
 	val firstName: String,
@@ -13,7 +15,9 @@ data class Person (
 	val extraSpecialField2: List<String>? = null
 )
 
-/// This is a Person2 struct with UPPERCASE rename
+/**
+ * This is a Person2 struct with UPPERCASE rename
+ */
 @Serializable
 data class Person2 (
 	val FIRST_NAME: String,
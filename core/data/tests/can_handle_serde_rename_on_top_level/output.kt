@@ -6,7 +6,9 @@ import kotlinx.serialization.SerialName
 @Serializable
 object OtherType
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class PersonTwo (
 	val name: String,
@@ -3,13 +3,17 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// This struct has a unit field
+/**
+ * This struct has a unit field
+ */
 @Serializable
 data class StructHasVoidType (
 	val thisIsAUnit: Unit
 )
 
-/// This enum has a variant associated with unit data
+/**
+ * This enum has a variant associated with unit data
+ */
 @Serializable
 sealed class EnumHasVoidType {
 	@Serializable
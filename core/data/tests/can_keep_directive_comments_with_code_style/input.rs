@@ -0,0 +1,6 @@
+/// +build linux,amd64 darwin,arm64 windows,386 freebsd,amd64 openbsd,arm64
+/// The platforms the service runs on, which are given as build constraints
+#[typeshare]
+pub struct Platform {
+    pub name: String,
+}
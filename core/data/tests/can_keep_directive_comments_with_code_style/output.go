@@ -0,0 +1,10 @@
+package proto
+
+import "encoding/json"
+
+// +build linux,amd64 darwin,arm64 windows,386 freebsd,amd64 openbsd,arm64
+// The platforms the service runs on,
+// which are given as build constraints
+type Platform struct {
+	Name string `json:"name"`
+}
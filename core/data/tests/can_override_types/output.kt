@@ -8,7 +8,9 @@ data class OverrideStruct (
 	val fieldToOverride: Int
 )
 
-/// Generated type representing the anonymous struct variant `AnonymousStructVariant` of the `OverrideEnum` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `AnonymousStructVariant` of the [OverrideEnum] Rust enum
+ */
 @Serializable
 data class OverrideEnumAnonymousStructVariantInner (
 	val fieldToOverride: Int
@@ -0,0 +1,34 @@
+/// A user of the service
+#[typeshare(swift = "Equatable")]
+#[serde(rename_all = "camelCase")]
+pub struct User<T> {
+    /// The name of the user
+    pub user_name: String,
+    #[typeshare(typescript(readonly))]
+    pub emails: Vec<String>,
+    #[serde(default)]
+    pub age: Option<u32>,
+    pub data: T,
+}
+
+/// An event of a user
+#[typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum Event {
+    Joined(User<String>),
+    Renamed { old_name: String, new_name: String },
+    #[serde(rename = "left")]
+    Left,
+}
+
+#[typeshare]
+pub enum Role {
+    Admin,
+    Member,
+}
+
+#[typeshare]
+pub struct UserId(String);
+
+#[typeshare]
+pub const MAX_USERS: u32 = 100;
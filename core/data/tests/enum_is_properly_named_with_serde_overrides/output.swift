@@ -3,8 +3,8 @@ import Foundation
 /// This is a comment.
 /// Continued lovingly here
 public enum Colors: String, Codable {
-	case red
-	case blue
+	case red = "red"
+	case blue = "blue"
 	/// Green is a cool color
 	case green = "green-like"
 }
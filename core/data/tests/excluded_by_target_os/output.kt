@@ -3,8 +3,10 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// A struct with no target_os. Should be generated when
-/// we use --target-os.
+/**
+ * A struct with no target_os. Should be generated when
+ * we use --target-os.
+ */
 @Serializable
 object AlwaysAccept
 
@@ -40,13 +42,17 @@ enum class AlwaysAcceptEnum(val string: String) {
 enum class SomeEnum(val string: String) {
 }
 
-/// Generated type representing the anonymous struct variant `Variant7` of the `TestEnum` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `Variant7` of the [TestEnum] Rust enum
+ */
 @Serializable
 data class TestEnumVariant7Inner (
 	val field1: String
 )
 
-/// Generated type representing the anonymous struct variant `Variant9` of the `TestEnum` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `Variant9` of the [TestEnum] Rust enum
+ */
 @Serializable
 data class TestEnumVariant9Inner (
 	val field2: String
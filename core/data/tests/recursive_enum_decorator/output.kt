@@ -3,13 +3,17 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// Generated type representing the anonymous struct variant `Exactly` of the `MoreOptions` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `Exactly` of the [MoreOptions] Rust enum
+ */
 @Serializable
 data class MoreOptionsExactlyInner (
 	val config: String
 )
 
-/// Generated type representing the anonymous struct variant `Built` of the `MoreOptions` Rust enum
+/**
+ * Generated type representing the anonymous struct variant `Built` of the [MoreOptions] Rust enum
+ */
 @Serializable
 data class MoreOptionsBuiltInner (
 	val top: MoreOptions
@@ -5,7 +5,9 @@ import kotlinx.serialization.SerialName
 
 @Serializable
 sealed class SomeEnum {
-	/// The associated String contains some opaque context
+	/**
+	 * The associated String contains some opaque context
+	 */
 	@Serializable
 	@SerialName("Context")
 	data class Context(val content: String): SomeEnum()
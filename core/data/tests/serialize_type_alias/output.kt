@@ -5,7 +5,9 @@ import kotlinx.serialization.SerialName
 
 typealias Uuid = String
 
-/// Unique identifier for an Account
+/**
+ * Unique identifier for an Account
+ */
 typealias AccountUuid = Uuid
 
 typealias AlsoString = String
@@ -3,7 +3,9 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class ArcyColors (
 	val red: UByte,
@@ -11,33 +13,43 @@ data class ArcyColors (
 	val green: List<String>
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class CellyColors (
 	val red: String,
 	val blue: List<String>
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class CowyColors (
 	val lifetime: String
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class LockyColors (
 	val red: String
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class MutexyColors (
 	val blue: List<String>,
 	val green: String
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 data class RcyColors (
 	val red: String,
@@ -45,7 +57,9 @@ data class RcyColors (
 	val green: String
 )
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 sealed class BoxyColors {
 	@Serializable
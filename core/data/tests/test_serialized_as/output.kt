@@ -5,6 +5,8 @@ import kotlinx.serialization.SerialName
 
 typealias ItemId = String
 
-/// Options that you could pick
+/**
+ * Options that you could pick
+ */
 typealias Options = String
 
@@ -3,7 +3,9 @@ package com.agilebits.onepassword
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.SerialName
 
-/// This is a comment.
+/**
+ * This is a comment.
+ */
 @Serializable
 enum class Colors(val string: String) {
 	@SerialName("red")
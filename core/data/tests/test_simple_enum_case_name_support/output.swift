@@ -2,7 +2,7 @@ import Foundation
 
 /// This is a comment.
 public enum Colors: String, Codable {
-	case red
+	case red = "red"
 	case blue = "blue-ish"
 	case green = "Green"
 }
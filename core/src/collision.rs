@@ -0,0 +1,180 @@
+//! Detecting typeshared items, fields or variants which a language generates with the
+//! same identifier, like `user_id` and `userId` once both are camelCased, which would
+//! generate source that doesn't compile or silently drops one of them.
+use crate::{
+    error::SourceLocation,
+    language::Language,
+    parser::ParsedData,
+    rust_types::{RustEnumVariant, RustField},
+};
+use std::{collections::HashMap, fmt};
+
+/// Two Rust items, or two fields or variants of an item, which are generated with the
+/// same identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameCollision {
+    /// The generated identifier.
+    pub identifier: String,
+    /// The Rust name of the first item, field or variant, like `User` or `User::user_id`.
+    pub first: String,
+    /// The Rust name of the one after it with the same identifier.
+    pub second: String,
+    /// Where the second one is in the Rust source, if known.
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for NameCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {} are both generated as {}",
+            self.first, self.second, self.identifier
+        )?;
+        match &self.location {
+            Some(location) => write!(f, ", at {location}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The identifiers of the parsed data which the language generates for more than one
+/// item, or for more than one field or variant of an item, in the order of the items.
+pub fn name_collisions(lang: &dyn Language, data: &ParsedData) -> Vec<NameCollision> {
+    let mut collisions = Vec::new();
+
+    let types = data
+        .structs
+        .iter()
+        .map(|s| (&s.id, &s.location))
+        .chain(
+            data.enums
+                .iter()
+                .map(|e| (&e.shared().id, &e.shared().location)),
+        )
+        .chain(data.aliases.iter().map(|a| (&a.id, &a.location)))
+        .map(|(id, location)| {
+            let name = id.original.clone();
+            (lang.type_identifier(id), name, location.clone())
+        });
+    collect_collisions(types, &mut collisions);
+
+    for s in &data.structs {
+        let location = s.location.as_ref();
+        collect_field_collisions(lang, &s.id.original, location, &s.fields, &mut collisions);
+    }
+    for e in &data.enums {
+        let shared = e.shared();
+        let location = shared.location.as_ref();
+        let variants = shared.variants.iter().map(|v| {
+            (
+                lang.variant_identifier(v),
+                format!("{}::{}", shared.id.original, v.shared().id.original),
+                location.map(|l| l.at(v.shared().span)),
+            )
+        });
+        collect_collisions(variants, &mut collisions);
+        for v in &shared.variants {
+            if let RustEnumVariant::AnonymousStruct { fields, shared: vs } = v {
+                let name = format!("{}::{}", shared.id.original, vs.id.original);
+                collect_field_collisions(lang, &name, location, fields, &mut collisions);
+            }
+        }
+    }
+    collisions
+}
+
+/// The collisions of the identifiers of the fields of an item.
+fn collect_field_collisions(
+    lang: &dyn Language,
+    item: &str,
+    location: Option<&SourceLocation>,
+    fields: &[RustField],
+    collisions: &mut Vec<NameCollision>,
+) {
+    let fields = fields.iter().map(|f| {
+        (
+            lang.field_identifier(f),
+            format!("{item}::{}", f.id.original),
+            location.map(|l| l.at(f.span)),
+        )
+    });
+    collect_collisions(fields, collisions);
+}
+
+/// The collisions of identifiers with the Rust names they were generated from, and
+/// where those are.
+fn collect_collisions(
+    names: impl Iterator<Item = (String, String, Option<SourceLocation>)>,
+    collisions: &mut Vec<NameCollision>,
+) {
+    let mut seen = HashMap::<String, String>::new();
+    for (identifier, name, location) in names {
+        match seen.get(&identifier) {
+            Some(first) => collisions.push(NameCollision {
+                identifier,
+                first: first.clone(),
+                second: name,
+                location,
+            }),
+            None => {
+                seen.insert(identifier, name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::name_collisions;
+    use crate::{
+        language::{Kotlin, Swift},
+        parser::parse,
+        ParseFileContext,
+    };
+
+    #[test]
+    fn detects_colliding_variants_and_fields_of_variants() {
+        let parsed_data = parse(
+            &Default::default(),
+            ParseFileContext {
+                source_code: r#"
+                #[typeshare]
+                #[serde(tag = "type", content = "content")]
+                pub enum Event {
+                    Sign_In { user_id: String, #[serde(rename = "user-id")] user: String },
+                    SignIn,
+                }
+                "#
+                .to_owned(),
+                crate_name: Default::default(),
+                file_name: "input.rs".to_owned(),
+                file_path: "input.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let collisions = |collisions: Vec<super::NameCollision>| {
+            collisions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            collisions(name_collisions(&Swift::default(), &parsed_data)),
+            [
+                "Event::Sign_In and Event::SignIn are both generated as signIn, at input.rs:6:20",
+                "Event::Sign_In::user_id and Event::Sign_In::user are both generated as user_id, \
+                 at input.rs:5:76",
+            ]
+        );
+        assert_eq!(
+            collisions(name_collisions(&Kotlin::default(), &parsed_data)),
+            [
+                "Event::Sign_In and Event::SignIn are both generated as SignIn, at input.rs:6:20",
+                "Event::Sign_In::user_id and Event::Sign_In::user are both generated as user_id, \
+                 at input.rs:5:76",
+            ]
+        );
+    }
+}
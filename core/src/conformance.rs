@@ -0,0 +1,446 @@
+//! Round-trip tests of the generated types, which decode samples serialized by serde
+//! and encode them again, to check that the types of every language agree with the
+//! Rust serializer.
+//!
+//! The samples are JSON values by the Rust name of their type, like those of
+//! `serde_json::to_value`. A test file is generated for a test framework of every
+//! [`Harness`], which decodes every sample into its generated type, encodes it again
+//! and compares the JSON with the sample:
+//!
+//! ```
+//! use typeshare_core::conformance::{self, Harness, Samples};
+//!
+//! let parsed_data = typeshare_core::parse(
+//!     &Default::default(),
+//!     typeshare_core::ParseFileContext {
+//!         source_code: "#[typeshare] pub struct User { pub name: String }".to_owned(),
+//!         crate_name: Default::default(),
+//!         file_name: "user.rs".to_owned(),
+//!         file_path: "user.rs".into(),
+//!     },
+//! )
+//! .unwrap()
+//! .unwrap();
+//! let samples = Samples::from([(
+//!     "User".to_owned(),
+//!     vec![serde_json::json!({ "name": "Alice" })],
+//! )]);
+//! let test = conformance::generate(
+//!     &Harness::TypeScript {
+//!         import: "./types".to_owned(),
+//!         codecs: false,
+//!         reviver: false,
+//!     },
+//!     &parsed_data,
+//!     &samples,
+//! )
+//! .unwrap();
+//! assert!(test.contains(r#"assertRoundTrip({"name":"Alice"} satisfies User);"#));
+//! ```
+use crate::{error::ConformanceError, parser::ParsedData, rename::RenameExt, rust_types::Id};
+use serde_json::Value;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Samples serialized by serde, by the Rust name of their type.
+pub type Samples = BTreeMap<String, Vec<Value>>;
+
+/// The test framework a round-trip test is generated for, with how it refers to the
+/// generated types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Harness {
+    /// An XCTest test case, decoding with `JSONDecoder`.
+    Swift {
+        /// The module of the generated types, which is imported with `@testable`.
+        module: Option<String>,
+        /// The prefix of the generated types.
+        prefix: String,
+    },
+    /// A JUnit test, decoding with `kotlinx.serialization`.
+    Kotlin {
+        /// The package of the generated types, which the test is in too.
+        package: String,
+        /// The prefix of the generated types.
+        prefix: String,
+    },
+    /// A vitest test, decoding with `JSON.parse` and the codecs of the generated types.
+    TypeScript {
+        /// The module the generated types are imported from, like `./types`.
+        import: String,
+        /// Whether the types have `decode<Type>` and `encode<Type>` functions, which
+        /// they have with camelCase properties. Without them, the samples are type
+        /// checked as the generated types.
+        codecs: bool,
+        /// Whether the generated file has a `ReviverFunc` and `ReplacerFunc`, which
+        /// convert values like dates when parsing and stringifying JSON.
+        reviver: bool,
+    },
+    /// A Jest test, with `@glennsl/bs-jest`, decoding with the `decode<Type>` and
+    /// `encode<Type>` functions of the generated types.
+    ReasonML {
+        /// The module of the generated types.
+        module: String,
+    },
+}
+
+/// Generate the round-trip test of the samples of the typeshared types of the parsed
+/// data.
+pub fn generate(
+    harness: &Harness,
+    parsed_data: &ParsedData,
+    samples: &Samples,
+) -> Result<String, ConformanceError> {
+    let types = samples
+        .iter()
+        .map(|(name, samples)| Ok((type_id(parsed_data, name)?, samples)))
+        .collect::<Result<Vec<_>, ConformanceError>>()?;
+
+    let mut test = String::new();
+    match harness {
+        Harness::Swift { module, prefix } => swift(&mut test, module.as_deref(), prefix, &types),
+        Harness::Kotlin { package, prefix } => kotlin(&mut test, package, prefix, &types),
+        Harness::TypeScript {
+            import,
+            codecs,
+            reviver,
+        } => typescript(&mut test, import, *codecs, *reviver, &types),
+        Harness::ReasonML { module } => reasonml(&mut test, module, &types),
+    }
+    .expect("writing to a string doesn't fail");
+    Ok(test)
+}
+
+/// The identifier of the type of the samples, which can't be generic, since the
+/// samples have concrete types.
+fn type_id<'a>(parsed_data: &'a ParsedData, name: &str) -> Result<&'a Id, ConformanceError> {
+    let structs = parsed_data
+        .structs
+        .iter()
+        .map(|s| (&s.id, s.generic_types.is_empty()));
+    let enums = parsed_data
+        .enums
+        .iter()
+        .map(|e| (&e.shared().id, e.shared().generic_types.is_empty()));
+    let aliases = parsed_data
+        .aliases
+        .iter()
+        .map(|a| (&a.id, a.generic_types.is_empty()));
+    match structs
+        .chain(enums)
+        .chain(aliases)
+        .find(|(id, _)| id.original == name)
+    {
+        Some((id, true)) => Ok(id),
+        Some((_, false)) => Err(ConformanceError::GenericType(name.to_owned())),
+        None => Err(ConformanceError::UnknownType(name.to_owned())),
+    }
+}
+
+fn swift(
+    w: &mut String,
+    module: Option<&str>,
+    prefix: &str,
+    types: &[(&Id, &Vec<Value>)],
+) -> std::fmt::Result {
+    writeln!(w, "import XCTest")?;
+    if let Some(module) = module {
+        writeln!(w, "@testable import {module}")?;
+    }
+    writeln!(w)?;
+    writeln!(w, "final class TypeshareConformanceTests: XCTestCase {{")?;
+    for (id, samples) in types {
+        writeln!(w, "\tfunc test{}() throws {{", id.original)?;
+        for sample in *samples {
+            let json = sample.to_string();
+            let hashes = "#".repeat(raw_string_hashes(&json));
+            writeln!(
+                w,
+                "\t\ttry assertRoundTrip({prefix}{}.self, {hashes}\"{json}\"{hashes})",
+                id.renamed
+            )?;
+        }
+        writeln!(w, "\t}}\n")?;
+    }
+    writeln!(
+        w,
+        r#"	private func assertRoundTrip<T: Codable>(_ type: T.Type, _ sample: String, file: StaticString = #filePath, line: UInt = #line) throws {{
+		let value = try JSONDecoder().decode(T.self, from: Data(sample.utf8))
+		let encoded = try JSONEncoder().encode(value)
+		let expected = try JSONSerialization.jsonObject(with: Data(sample.utf8), options: [.fragmentsAllowed])
+		let actual = try JSONSerialization.jsonObject(with: encoded, options: [.fragmentsAllowed])
+		XCTAssertEqual(expected as? NSObject, actual as? NSObject, file: file, line: line)
+	}}
+}}"#
+    )
+}
+
+/// The number of `#` a Swift raw string of the contents needs, so that no `"` in it
+/// ends the string.
+fn raw_string_hashes(contents: &str) -> usize {
+    contents
+        .split('"')
+        .skip(1)
+        .map(|after_quote| after_quote.chars().take_while(|c| *c == '#').count() + 1)
+        .max()
+        .unwrap_or(1)
+}
+
+fn kotlin(
+    w: &mut String,
+    package: &str,
+    prefix: &str,
+    types: &[(&Id, &Vec<Value>)],
+) -> std::fmt::Result {
+    if !package.is_empty() {
+        writeln!(w, "package {package}\n")?;
+    }
+    writeln!(
+        w,
+        r#"import kotlinx.serialization.decodeFromString
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.json.Json
+import org.junit.jupiter.api.Assertions.assertEquals
+import org.junit.jupiter.api.Test
+
+class TypeshareConformanceTest {{"#
+    )?;
+    for (id, samples) in types {
+        writeln!(w, "\t@Test")?;
+        writeln!(w, "\tfun {}() {{", id.original.to_camel_case())?;
+        for sample in *samples {
+            // `$` starts a template in raw strings, which have no escapes.
+            let json = sample.to_string().replace('$', "${'$'}");
+            writeln!(
+                w,
+                "\t\tassertRoundTrip<{prefix}{}>(\"\"\"{json}\"\"\")",
+                id.renamed
+            )?;
+        }
+        writeln!(w, "\t}}\n")?;
+    }
+    writeln!(
+        w,
+        r#"	private inline fun <reified T> assertRoundTrip(sample: String) {{
+		val value = Json.decodeFromString<T>(sample)
+		assertEquals(Json.parseToJsonElement(sample), Json.parseToJsonElement(Json.encodeToString(value)))
+	}}
+}}"#
+    )
+}
+
+fn typescript(
+    w: &mut String,
+    import: &str,
+    codecs: bool,
+    reviver: bool,
+    types: &[(&Id, &Vec<Value>)],
+) -> std::fmt::Result {
+    let import = Value::from(import);
+    let names = types.iter().map(|(id, _)| id.renamed.as_str());
+    writeln!(w, r#"import {{ describe, expect, test }} from "vitest";"#)?;
+    writeln!(
+        w,
+        "import type {{ {} }} from {import};",
+        names.collect::<Vec<_>>().join(", ")
+    )?;
+    let functions = types
+        .iter()
+        .filter(|_| codecs)
+        .flat_map(|(id, _)| {
+            [
+                format!("decode{}", id.renamed),
+                format!("encode{}", id.renamed),
+            ]
+        })
+        .chain(
+            reviver
+                .then(|| ["ReviverFunc".to_owned(), "ReplacerFunc".to_owned()])
+                .into_iter()
+                .flatten(),
+        )
+        .collect::<Vec<_>>();
+    if !functions.is_empty() {
+        writeln!(w, "import {{ {} }} from {import};", functions.join(", "))?;
+    }
+    for (id, samples) in types {
+        writeln!(w, "\ndescribe(\"{}\", () => {{", id.renamed)?;
+        for (index, sample) in samples.iter().enumerate() {
+            writeln!(w, "\ttest(\"sample {}\", () => {{", index + 1)?;
+            let name = &id.renamed;
+            if codecs {
+                writeln!(
+                    w,
+                    "\t\tassertRoundTrip<{name}>({sample}, decode{name}, encode{name});"
+                )?;
+            } else if reviver {
+                // The samples have the serialized form of values like dates.
+                writeln!(w, "\t\tassertRoundTrip<{name}>({sample});")?;
+            } else {
+                writeln!(w, "\t\tassertRoundTrip({sample} satisfies {name});")?;
+            }
+            writeln!(w, "\t}});")?;
+        }
+        writeln!(w, "}});")?;
+    }
+    let (reviver, replacer) = if reviver {
+        (", ReviverFunc", ", ReplacerFunc")
+    } else {
+        ("", "")
+    };
+    writeln!(
+        w,
+        r#"
+function assertRoundTrip<T>(
+	sample: unknown,
+	decode: (json: any) => T = (json) => json,
+	encode: (value: T) => any = (value) => value,
+) {{
+	const value = decode(JSON.parse(JSON.stringify(sample){reviver}));
+	expect(JSON.parse(JSON.stringify(encode(value){replacer}))).toEqual(sample);
+}}"#
+    )
+}
+
+fn reasonml(w: &mut String, module: &str, types: &[(&Id, &Vec<Value>)]) -> std::fmt::Result {
+    writeln!(w, "open Jest;")?;
+    writeln!(w, "open Expect;")?;
+    for (id, samples) in types {
+        let name = id.renamed.to_pascal_case();
+        writeln!(w, "\ndescribe(\"{}\", () => {{", id.renamed)?;
+        for (index, sample) in samples.iter().enumerate() {
+            writeln!(w, "  test(\"sample {}\", () => {{", index + 1)?;
+            writeln!(
+                w,
+                "    let json = Js.Json.parseExn({{json|{sample}|json}});"
+            )?;
+            writeln!(
+                w,
+                "    expect({module}.encode{name}({module}.decode{name}(json))) |> toEqual(json);"
+            )?;
+            writeln!(w, "  }});")?;
+        }
+        writeln!(w, "}});")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, raw_string_hashes, Harness, Samples};
+    use crate::{error::ConformanceError, parser::parse, ParseFileContext};
+    use serde_json::json;
+
+    fn parsed_data(source_code: &str) -> crate::parser::ParsedData {
+        parse(
+            &Default::default(),
+            ParseFileContext {
+                source_code: source_code.to_owned(),
+                crate_name: Default::default(),
+                file_name: "input.rs".to_owned(),
+                file_path: "input.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn generates_round_trips_of_the_samples() {
+        let parsed_data = parsed_data(
+            r#"
+            #[typeshare]
+            #[serde(rename = "Person")]
+            pub struct User { pub name: String }
+            "#,
+        );
+        let samples = Samples::from([("User".to_owned(), vec![json!({ "name": "A \"#" })])]);
+        let swift = generate(
+            &Harness::Swift {
+                module: Some("Types".to_owned()),
+                prefix: "Core".to_owned(),
+            },
+            &parsed_data,
+            &samples,
+        )
+        .unwrap();
+        assert!(swift.contains("@testable import Types\n"));
+        assert!(swift.contains(
+            "\tfunc testUser() throws {\n\t\ttry assertRoundTrip(CorePerson.self, ##\"{\"name\":\"A \\\"#\"}\"##)\n"
+        ));
+
+        let kotlin = generate(
+            &Harness::Kotlin {
+                package: "com.example".to_owned(),
+                prefix: String::new(),
+            },
+            &parsed_data,
+            &samples,
+        )
+        .unwrap();
+        assert!(kotlin.starts_with("package com.example\n"));
+        assert!(kotlin.contains("\t@Test\n\tfun user() {\n\t\tassertRoundTrip<Person>("));
+
+        let reasonml = generate(
+            &Harness::ReasonML {
+                module: "Types".to_owned(),
+            },
+            &parsed_data,
+            &samples,
+        )
+        .unwrap();
+        assert!(reasonml.contains(
+            "    expect(Types.encodePerson(Types.decodePerson(json))) |> toEqual(json);\n"
+        ));
+
+        let typescript = |codecs, reviver| {
+            generate(
+                &Harness::TypeScript {
+                    import: "./types".to_owned(),
+                    codecs,
+                    reviver,
+                },
+                &parsed_data,
+                &samples,
+            )
+            .unwrap()
+        };
+        let wire = typescript(false, false);
+        assert!(wire.contains("import type { Person } from \"./types\";\n"));
+        assert!(wire.contains("\t\tassertRoundTrip({\"name\":\"A \\\"#\"} satisfies Person);\n"));
+        assert!(wire.contains("decode(JSON.parse(JSON.stringify(sample)));"));
+        let codecs = typescript(true, true);
+        assert!(codecs.contains(
+            "import { decodePerson, encodePerson, ReviverFunc, ReplacerFunc } from \"./types\";\n"
+        ));
+        assert!(codecs.contains(
+            "assertRoundTrip<Person>({\"name\":\"A \\\"#\"}, decodePerson, encodePerson);"
+        ));
+        assert!(codecs.contains("decode(JSON.parse(JSON.stringify(sample), ReviverFunc));"));
+    }
+
+    #[test]
+    fn fails_on_types_without_concrete_samples() {
+        let parsed_data = parsed_data("#[typeshare] pub struct Page<T> { pub items: Vec<T> }");
+        let harness = Harness::TypeScript {
+            import: "./types".to_owned(),
+            codecs: false,
+            reviver: false,
+        };
+        let samples = Samples::from([("Page".to_owned(), Vec::new())]);
+        assert!(matches!(
+            generate(&harness, &parsed_data, &samples),
+            Err(ConformanceError::GenericType(name)) if name == "Page"
+        ));
+        let samples = Samples::from([("User".to_owned(), Vec::new())]);
+        assert!(matches!(
+            generate(&harness, &parsed_data, &samples),
+            Err(ConformanceError::UnknownType(name)) if name == "User"
+        ));
+    }
+
+    #[test]
+    fn counts_the_hashes_of_raw_strings() {
+        assert_eq!(raw_string_hashes("{}"), 1);
+        assert_eq!(raw_string_hashes(r#"{"a":"b"}"#), 1);
+        assert_eq!(raw_string_hashes(r##"{"a":"b"#"}"##), 2);
+    }
+}
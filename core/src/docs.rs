@@ -0,0 +1,499 @@
+//! Reference documentation of the typeshared types, as a Markdown or HTML page, for the
+//! consumers of an API.
+//!
+//! Every type is documented with its doc comments, its fields or variants with their
+//! serialized names and types, and what's deprecated with `#[deprecated]`. Types
+//! referenced by fields link to the documentation of the type. The types of every crate
+//! are under a heading of the crate, with its constants.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use typeshare_core::docs::{generate, DocsFormat};
+//!
+//! let parsed_data = typeshare_core::parse(
+//!     &Default::default(),
+//!     typeshare_core::ParseFileContext {
+//!         source_code: r#"
+//!             /// A user of the app.
+//!             #[typeshare]
+//!             pub struct User { pub name: String }
+//!         "#
+//!         .to_owned(),
+//!         crate_name: "users".into(),
+//!         file_name: "user.rs".to_owned(),
+//!         file_path: "user.rs".into(),
+//!     },
+//! )
+//! .unwrap()
+//! .unwrap();
+//! let crates = BTreeMap::from([("users".into(), parsed_data)]);
+//! let markdown = generate(&crates, DocsFormat::Markdown);
+//! assert!(markdown.contains("### User\n\nA user of the app.\n\n"));
+//! ```
+use crate::{
+    language::CrateName,
+    parser::ParsedData,
+    rust_types::{
+        Id, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustType, SpecialRustType,
+    },
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// The format the documentation is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    /// A Markdown page, with headings whose anchors GitHub and mdBook generate.
+    Markdown,
+    /// A standalone HTML page.
+    Html,
+}
+
+/// The documentation of the typeshared types of every crate, as a page in the format.
+pub fn generate(crates: &BTreeMap<CrateName, ParsedData>, format: DocsFormat) -> String {
+    let anchors = anchors(crates);
+    let mut blocks = vec![Block::Heading(1, "Types".to_owned())];
+    for (crate_name, data) in crates {
+        if !crate_name.as_str().is_empty() {
+            blocks.push(Block::Heading(2, crate_name.to_string()));
+        }
+        let page = Page {
+            blocks: &mut blocks,
+            anchors: &anchors,
+        };
+        page.crate_types(data);
+    }
+    match format {
+        DocsFormat::Markdown => markdown(&blocks),
+        DocsFormat::Html => html(&blocks),
+    }
+}
+
+/// The anchors of the headings of the types, by their Rust and serialized names. A type
+/// is linked to the first type with its name.
+fn anchors(crates: &BTreeMap<CrateName, ParsedData>) -> HashMap<String, String> {
+    let mut anchors = HashMap::new();
+    for data in crates.values() {
+        let ids = data
+            .structs
+            .iter()
+            .map(|s| &s.id)
+            .chain(data.enums.iter().map(|e| &e.shared().id))
+            .chain(data.aliases.iter().map(|a| &a.id));
+        for id in ids {
+            for name in [&id.original, &id.renamed] {
+                anchors
+                    .entry(name.clone())
+                    .or_insert_with(|| anchor(&id.renamed));
+            }
+        }
+    }
+    anchors
+}
+
+/// The anchor GitHub and mdBook generate for a heading.
+fn anchor(heading: &str) -> String {
+    heading
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == ' ')
+        .map(|c| {
+            if c == ' ' {
+                '-'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+enum Block {
+    Heading(usize, String),
+    Paragraph(Vec<Inline>),
+    Table(&'static [&'static str], Vec<Vec<Vec<Inline>>>),
+}
+
+enum Inline {
+    /// Text, which is Markdown in doc comments.
+    Text(String),
+    Strong(String),
+    Code(String),
+    /// A link to the heading of a type, with the name of the type as code.
+    Link(String, String),
+}
+
+/// What's documented, as it's written.
+struct Page<'a> {
+    blocks: &'a mut Vec<Block>,
+    anchors: &'a HashMap<String, String>,
+}
+
+impl Page<'_> {
+    fn crate_types(mut self, data: &ParsedData) {
+        for s in &data.structs {
+            self.heading(&s.id, &s.comments, s.deprecated.as_deref());
+            let fields = if s.fields.is_empty() {
+                "A struct without fields."
+            } else {
+                "A struct with the fields:"
+            };
+            self.kind(vec![Inline::Text(fields.to_owned())], &s.generic_types);
+            if !s.fields.is_empty() {
+                let rows = s
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        vec![
+                            field_name(field),
+                            self.ty(&field.ty, &s.generic_types),
+                            description(&field.comments, field.deprecated.as_deref()),
+                        ]
+                    })
+                    .collect();
+                self.blocks
+                    .push(Block::Table(&["Field", "Type", "Description"], rows));
+            }
+        }
+        for e in &data.enums {
+            let shared = e.shared();
+            self.heading(&shared.id, &shared.comments, shared.deprecated.as_deref());
+            match e {
+                RustEnum::Unit(_) => {
+                    let kind = "An enum, serialized as the name of its variant, one of:";
+                    self.kind(vec![Inline::Text(kind.to_owned())], &shared.generic_types);
+                    let rows = shared
+                        .variants
+                        .iter()
+                        .map(|variant| {
+                            let variant = variant.shared();
+                            vec![
+                                vec![Inline::Code(format!("{:?}", variant.id.renamed))],
+                                description(&variant.comments, variant.deprecated.as_deref()),
+                            ]
+                        })
+                        .collect();
+                    self.blocks
+                        .push(Block::Table(&["Variant", "Description"], rows));
+                }
+                RustEnum::Algebraic {
+                    tag_key,
+                    content_key,
+                    ..
+                } => {
+                    let kind = vec![
+                        Inline::Text(
+                            "An enum, serialized as an object with the name of its variant in "
+                                .to_owned(),
+                        ),
+                        Inline::Code(tag_key.clone()),
+                        Inline::Text(" and its content in ".to_owned()),
+                        Inline::Code(content_key.clone()),
+                        Inline::Text(", one of:".to_owned()),
+                    ];
+                    self.kind(kind, &shared.generic_types);
+                    let rows = shared
+                        .variants
+                        .iter()
+                        .map(|variant| {
+                            let content = match variant {
+                                RustEnumVariant::Unit(_) => Vec::new(),
+                                RustEnumVariant::Tuple { ty, .. } => {
+                                    self.ty(ty, &shared.generic_types)
+                                }
+                                RustEnumVariant::AnonymousStruct { fields, .. } => {
+                                    self.fields(fields, &shared.generic_types)
+                                }
+                            };
+                            let variant = variant.shared();
+                            vec![
+                                vec![Inline::Code(format!("{:?}", variant.id.renamed))],
+                                content,
+                                description(&variant.comments, variant.deprecated.as_deref()),
+                            ]
+                        })
+                        .collect();
+                    self.blocks
+                        .push(Block::Table(&["Variant", "Content", "Description"], rows));
+                }
+            }
+        }
+        for a in &data.aliases {
+            self.heading(&a.id, &a.comments, a.deprecated.as_deref());
+            let mut inlines = vec![Inline::Text("Serialized as ".to_owned())];
+            inlines.extend(self.ty(&a.r#type, &a.generic_types));
+            inlines.push(Inline::Text(".".to_owned()));
+            if !a.generic_types.is_empty() {
+                inlines.push(Inline::Text(" Generic over ".to_owned()));
+                inlines.extend(generics(&a.generic_types));
+                inlines.push(Inline::Text(".".to_owned()));
+            }
+            self.blocks.push(Block::Paragraph(inlines));
+        }
+        if !data.consts.is_empty() {
+            self.blocks.push(Block::Heading(3, "Constants".to_owned()));
+            let rows = data
+                .consts
+                .iter()
+                .map(|c| {
+                    let value = match c.expr {
+                        RustConstExpr::Int(value) => value.to_string(),
+                    };
+                    vec![
+                        vec![Inline::Code(c.id.renamed.clone())],
+                        self.ty(&c.r#type, &[]),
+                        vec![Inline::Code(value)],
+                    ]
+                })
+                .collect();
+            self.blocks
+                .push(Block::Table(&["Constant", "Type", "Value"], rows));
+        }
+    }
+
+    /// The heading of a type, with its deprecation and doc comments.
+    fn heading(&mut self, id: &Id, comments: &[String], deprecated: Option<&str>) {
+        self.blocks.push(Block::Heading(3, id.renamed.clone()));
+        if let Some(note) = deprecated {
+            self.blocks.push(Block::Paragraph(deprecation(note)));
+        }
+        if id.renamed != id.original {
+            self.blocks.push(Block::Paragraph(vec![
+                Inline::Text("The Rust type ".to_owned()),
+                Inline::Code(id.original.clone()),
+                Inline::Text(".".to_owned()),
+            ]));
+        }
+        if !comments.is_empty() {
+            let comments = comments.join("\n");
+            self.blocks
+                .push(Block::Paragraph(vec![Inline::Text(comments)]));
+        }
+    }
+
+    /// What a type is, like `A struct with the fields:`, and its generic parameters.
+    fn kind(&mut self, kind: Vec<Inline>, generic_types: &[String]) {
+        let mut inlines = Vec::new();
+        if !generic_types.is_empty() {
+            inlines.push(Inline::Text("Generic over ".to_owned()));
+            inlines.extend(generics(generic_types));
+            inlines.push(Inline::Text(". ".to_owned()));
+        }
+        inlines.extend(kind);
+        self.blocks.push(Block::Paragraph(inlines));
+    }
+
+    /// The fields of an anonymous struct variant, like `id: UserId, name: String`.
+    fn fields(&self, fields: &[RustField], generic_types: &[String]) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                inlines.push(Inline::Text(", ".to_owned()));
+            }
+            inlines.extend(field_name(field));
+            inlines.push(Inline::Text(": ".to_owned()));
+            inlines.extend(self.ty(&field.ty, generic_types));
+        }
+        inlines
+    }
+
+    /// The Rust type, with links to the types it references.
+    fn ty(&self, ty: &RustType, generic_types: &[String]) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        self.write_type(&mut inlines, ty, generic_types);
+        inlines
+    }
+
+    fn write_type(&self, inlines: &mut Vec<Inline>, ty: &RustType, generic_types: &[String]) {
+        let (name, parameters, end): (&str, Vec<&RustType>, String) = match ty {
+            RustType::Simple { id } => return self.write_name(inlines, id, generic_types),
+            RustType::Generic { id, parameters } => (id, parameters.iter().collect(), ">".into()),
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(ty) => ("Vec", vec![ty], ">".into()),
+                SpecialRustType::Array(ty, len) => ("", vec![ty], format!("; {len}]")),
+                SpecialRustType::Slice(ty) => ("", vec![ty], "]".into()),
+                SpecialRustType::Option(ty) => ("Option", vec![ty], ">".into()),
+                SpecialRustType::HashMap(key, value) => ("HashMap", vec![key, value], ">".into()),
+                _ => return code(inlines, special.id()),
+            },
+        };
+        self.write_name(inlines, name, generic_types);
+        if parameters.is_empty() {
+            return;
+        }
+        code(inlines, if name.is_empty() { "[" } else { "<" });
+        for (i, parameter) in parameters.into_iter().enumerate() {
+            if i > 0 {
+                code(inlines, ", ");
+            }
+            self.write_type(inlines, parameter, generic_types);
+        }
+        code(inlines, &end);
+    }
+
+    fn write_name(&self, inlines: &mut Vec<Inline>, name: &str, generic_types: &[String]) {
+        match self.anchors.get(name) {
+            Some(anchor) if !generic_types.iter().any(|generic| generic == name) => {
+                inlines.push(Inline::Link(name.to_owned(), anchor.clone()))
+            }
+            _ => code(inlines, name),
+        }
+    }
+}
+
+/// Add code to the inlines, after the code before it.
+fn code(inlines: &mut Vec<Inline>, code: &str) {
+    match inlines.last_mut() {
+        Some(Inline::Code(before)) => before.push_str(code),
+        _ => inlines.push(Inline::Code(code.to_owned())),
+    }
+}
+
+fn generics(generic_types: &[String]) -> Vec<Inline> {
+    vec![Inline::Code(generic_types.join(", "))]
+}
+
+/// The serialized name of a field, and whether it's optional.
+fn field_name(field: &RustField) -> Vec<Inline> {
+    let mut inlines = vec![Inline::Code(field.id.renamed.clone())];
+    if field.has_default || field.ty.is_optional() {
+        inlines.push(Inline::Text(" (optional)".to_owned()));
+    }
+    inlines
+}
+
+/// The doc comments and the deprecation of a field or variant, on a line.
+fn description(comments: &[String], deprecated: Option<&str>) -> Vec<Inline> {
+    let mut inlines = deprecated.map(deprecation).unwrap_or_default();
+    if !comments.is_empty() {
+        if !inlines.is_empty() {
+            inlines.push(Inline::Text(" ".to_owned()));
+        }
+        inlines.push(Inline::Text(comments.join(" ")));
+    }
+    inlines
+}
+
+fn deprecation(note: &str) -> Vec<Inline> {
+    let mut inlines = vec![Inline::Strong("Deprecated".to_owned())];
+    if !note.is_empty() {
+        inlines.push(Inline::Text(format!(": {note}")));
+    }
+    inlines
+}
+
+fn markdown(blocks: &[Block]) -> String {
+    let mut page = String::new();
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                page.push_str(&format!("{} {text}\n\n", "#".repeat(*level)));
+            }
+            Block::Paragraph(inlines) => {
+                page.push_str(&markdown_inlines(inlines));
+                page.push_str("\n\n");
+            }
+            Block::Table(header, rows) => {
+                page.push_str(&format!("| {} |\n", header.join(" | ")));
+                page.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+                for row in rows {
+                    let cells = row
+                        .iter()
+                        .map(|cell| {
+                            markdown_inlines(cell)
+                                .replace('\n', " ")
+                                .replace('|', "\\|")
+                        })
+                        .collect::<Vec<_>>();
+                    page.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                page.push('\n');
+            }
+        }
+    }
+    page
+}
+
+fn markdown_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.clone(),
+            Inline::Strong(text) => format!("**{text}**"),
+            Inline::Code(code) => format!("`{code}`"),
+            Inline::Link(name, anchor) => format!("[`{name}`](#{anchor})"),
+        })
+        .collect()
+}
+
+fn html(blocks: &[Block]) -> String {
+    let mut page = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Types</title>\n\
+         <style>\nbody { font-family: sans-serif; max-width: 60em; margin: auto; }\n\
+         table { border-collapse: collapse; }\n\
+         th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                page.push_str(&format!(
+                    "<h{level} id=\"{}\">{}</h{level}>\n",
+                    escape(&anchor(text)),
+                    escape(text)
+                ));
+            }
+            Block::Paragraph(inlines) => {
+                page.push_str(&format!("<p>{}</p>\n", html_inlines(inlines)));
+            }
+            Block::Table(header, rows) => {
+                page.push_str("<table>\n<tr>");
+                for cell in header.iter() {
+                    page.push_str(&format!("<th>{cell}</th>"));
+                }
+                page.push_str("</tr>\n");
+                for row in rows {
+                    page.push_str("<tr>");
+                    for cell in row {
+                        page.push_str(&format!("<td>{}</td>", html_inlines(cell)));
+                    }
+                    page.push_str("</tr>\n");
+                }
+                page.push_str("</table>\n");
+            }
+        }
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+fn html_inlines(inlines: &[Inline]) -> String {
+    // Code and the links between it are a single code element.
+    let is_code =
+        |inline: Option<&Inline>| matches!(inline, Some(Inline::Code(_) | Inline::Link(..)));
+    let mut html = String::new();
+    for (i, inline) in inlines.iter().enumerate() {
+        let code = is_code(Some(inline));
+        if code && !is_code(i.checked_sub(1).map(|before| &inlines[before])) {
+            html.push_str("<code>");
+        }
+        match inline {
+            Inline::Text(text) => html.push_str(&escape(text).replace('\n', "<br>\n")),
+            Inline::Strong(text) => html.push_str(&format!("<strong>{}</strong>", escape(text))),
+            Inline::Code(code) => html.push_str(&escape(code)),
+            Inline::Link(name, anchor) => html.push_str(&format!(
+                "<a href=\"#{}\">{}</a>",
+                escape(anchor),
+                escape(name)
+            )),
+        }
+        if code && !is_code(inlines.get(i + 1)) {
+            html.push_str("</code>");
+        }
+    }
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,6 +1,11 @@
 //! Error types for parsing.
+use crate::collision::NameCollision;
 use itertools::Itertools as _;
 use proc_macro2::Span;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -12,6 +17,24 @@ pub struct ParseErrorWithSpan {
     span: Span,
 }
 
+impl ParseErrorWithSpan {
+    /// The parse error, without its location.
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+
+    /// Where the error is in the Rust source.
+    pub fn span(&self) -> SourceSpan {
+        self.span.into()
+    }
+
+    /// The span of the tokens the error is about, like for reporting it with
+    /// `syn::Error` from a proc macro.
+    pub fn token_span(&self) -> Span {
+        self.span
+    }
+}
+
 impl std::error::Error for ParseErrorWithSpan {}
 
 impl std::fmt::Display for ParseErrorWithSpan {
@@ -26,6 +49,72 @@ impl std::fmt::Display for ParseErrorWithSpan {
     }
 }
 
+/// Where an error is in a Rust source file. Lines start at 1 and columns, counted in
+/// characters, at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// The line the span starts on.
+    pub line: usize,
+    /// The column the span starts at.
+    pub column: usize,
+    /// The line the span ends on.
+    pub end_line: usize,
+    /// The column after the end of the span.
+    pub end_column: usize,
+}
+
+impl From<Span> for SourceSpan {
+    fn from(span: Span) -> Self {
+        Self {
+            line: span.start().line,
+            column: span.start().column,
+            end_line: span.end().line,
+            end_column: span.end().column,
+        }
+    }
+}
+
+/// Where a parsed item, field or variant is: the Rust source file it's in, and its
+/// span in the file. It's displayed like the locations of compilers, as
+/// `src/lib.rs:3:4`, or as `line 3, column 4` for source which isn't from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The Rust source file.
+    pub file_path: PathBuf,
+    /// Where it is in the file.
+    pub span: SourceSpan,
+}
+
+impl SourceLocation {
+    /// The location of a span in a Rust source file.
+    pub fn new(file_path: &Path, span: impl Into<SourceSpan>) -> Self {
+        Self {
+            file_path: file_path.to_path_buf(),
+            span: span.into(),
+        }
+    }
+
+    /// The location of a span in the same file, like that of a field of an item, or
+    /// this location if the span isn't known.
+    pub fn at(&self, span: Option<SourceSpan>) -> Self {
+        Self {
+            file_path: self.file_path.clone(),
+            span: span.unwrap_or(self.span),
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SourceSpan { line, column, .. } = self.span;
+        if self.file_path.as_os_str().is_empty() {
+            write!(f, "line {line}, column {column}")
+        } else {
+            write!(f, "{}:{line}:{column}", self.file_path.display())
+        }
+    }
+}
+
 /// Errors that can occur while parsing Rust source input.
 #[derive(Debug, Error)]
 #[allow(missing_docs)]
@@ -56,10 +145,29 @@ pub enum ParseError {
     RustConstTypeInvalid,
     #[error("The serde flatten attribute is not currently supported")]
     SerdeFlattenNotAllowed,
+    #[error("Only structs, enums, type aliases and constants can be typeshared")]
+    UnsupportedItem,
     #[error("IO error: {0}")]
     IOError(String),
 }
 
+impl ParseError {
+    /// Whether the error is about Rust typeshare doesn't support, rather than Rust
+    /// which couldn't be parsed.
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            ParseError::SynError(_)
+            | ParseError::UnsupportedLanguage(_)
+            | ParseError::IOError(_) => false,
+            ParseError::RustTypeParseError(error) => !matches!(
+                error,
+                RustTypeParseError::UnexpectedToken(_) | RustTypeParseError::NumericLiteral(_)
+            ),
+            _ => true,
+        }
+    }
+}
+
 /// Parse error types that can capture a span and convert
 /// into the top level [ParseErrorWithSpan] type.
 pub trait WithSpan {
@@ -87,6 +195,82 @@ pub enum GenerationError {
     PostGeneration(String),
 }
 
+/// Errors while generating types with [`generate`](crate::generate()).
+#[derive(Debug, Error)]
+pub enum GenerateError {
+    /// An input couldn't be read.
+    #[error("Failed to read {path:?}: {error}")]
+    Read {
+        /// The file or directory.
+        path: PathBuf,
+        /// Why it couldn't be read.
+        error: io::Error,
+    },
+    /// A Rust file, or a typeshared item in it, couldn't be parsed.
+    #[error("Failed to parse {file_name}: {error}")]
+    Parse {
+        /// The Rust file.
+        file_name: String,
+        /// Why it couldn't be parsed.
+        error: String,
+        /// Where the error is in the file, if known.
+        span: Option<SourceSpan>,
+    },
+    /// Typeshared items, or fields or variants of an item, would be generated with the
+    /// same identifier.
+    #[error("Failed to generate types: {}", .0.iter().join(", "))]
+    NameCollisions(Vec<NameCollision>),
+    /// The types couldn't be generated.
+    #[error("Failed to generate types: {0}")]
+    Generate(#[from] io::Error),
+}
+
+/// Errors while generating files with a [`Plugin`](crate::plugin::Plugin).
+#[derive(Debug, Error)]
+pub enum PluginError {
+    /// The plugin has no command.
+    #[error("The plugin has no command")]
+    NoCommand,
+    /// The request couldn't be serialized.
+    #[error("Failed to serialize the types for the plugin: {0}")]
+    Serialize(serde_json::Error),
+    /// The plugin couldn't be run.
+    #[error("Failed to run the plugin {program:?}: {error}")]
+    Spawn {
+        /// The program of the plugin.
+        program: String,
+        /// Why it couldn't be run.
+        error: io::Error,
+    },
+    /// The plugin exited with an error.
+    #[error("The plugin {program:?} failed with {status}")]
+    Failed {
+        /// The program of the plugin.
+        program: String,
+        /// How it exited.
+        status: String,
+    },
+    /// The response of the plugin isn't valid.
+    #[error("The plugin responded with invalid JSON: {0}")]
+    Response(serde_json::Error),
+    /// The plugin generated a file outside of the output folder.
+    #[error("The plugin generated {0:?}, which isn't a relative path in the output folder")]
+    Path(PathBuf),
+}
+
+/// Errors while generating round-trip tests with [`conformance`](crate::conformance).
+#[derive(Debug, Error)]
+pub enum ConformanceError {
+    /// There are samples of a type which isn't typeshared.
+    #[error("There are samples of {0}, which isn't a typeshared type")]
+    UnknownType(String),
+    /// There are samples of a generic type, whose parameters aren't known.
+    #[error(
+        "There are samples of the generic type {0}, which can't be decoded without its parameters"
+    )]
+    GenericType(String),
+}
+
 #[derive(Debug, Error)]
 #[allow(missing_docs)]
 pub enum RustTypeParseError {
@@ -0,0 +1,230 @@
+//! Generating types without the command line tool, like from a build script:
+//!
+//! ```no_run
+//! use typeshare_core::{generate::GenerateConfig, language::TypeScript};
+//!
+//! let mut typescript = TypeScript::default();
+//! let outputs = typeshare_core::generate(GenerateConfig {
+//!     language: &mut typescript,
+//!     inputs: vec!["src".into()],
+//!     target_os: Vec::new(),
+//! })
+//! .unwrap();
+//! outputs.write("generated/types.ts").unwrap();
+//! outputs.rerun_if_changed();
+//! ```
+use crate::{
+    collision::name_collisions,
+    context::{ParseContext, ParseFileContext},
+    error::GenerateError,
+    language::{Language, SINGLE_FILE_CRATE_NAME},
+    parser::{self, ParsedData},
+    reconcile::reconcile_aliases,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+#[cfg(feature = "fs")]
+use std::{fs, io, path::PathBuf};
+
+/// What to generate types from, and how.
+#[cfg(feature = "fs")]
+pub struct GenerateConfig<'a> {
+    /// The language to generate the types in.
+    pub language: &'a mut dyn Language,
+    /// Rust files, or directories searched recursively for Rust files.
+    pub inputs: Vec<PathBuf>,
+    /// Only generate the types for these `target_os`, or for all of them if empty.
+    pub target_os: Vec<String>,
+}
+
+/// The types generated into a single file.
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outputs {
+    /// The generated source.
+    pub source: String,
+    /// The Rust files the types were generated from, in order.
+    pub files: Vec<PathBuf>,
+    /// The inputs of the configuration.
+    pub inputs: Vec<PathBuf>,
+}
+
+#[cfg(feature = "fs")]
+impl Outputs {
+    /// Write the generated source to a file, creating its directory if needed. An
+    /// unchanged file isn't written again, to leave its mtime intact.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if fs::read(path).is_ok_and(|existing| existing == self.source.as_bytes()) {
+            return Ok(());
+        }
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &self.source)
+    }
+
+    /// Tell Cargo to run the build script again when the inputs change, including
+    /// when Rust files are added to the input directories.
+    pub fn rerun_if_changed(&self) {
+        rerun_if_changed(&self.inputs);
+    }
+}
+
+/// Print a `cargo:rerun-if-changed` line for every path. Cargo scans directories
+/// recursively.
+pub fn rerun_if_changed(paths: impl IntoIterator<Item = impl AsRef<Path>>) {
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.as_ref().display());
+    }
+}
+
+/// Generate the types of the Rust files of the inputs into a single file.
+#[cfg(feature = "fs")]
+pub fn generate(config: GenerateConfig<'_>) -> Result<Outputs, GenerateError> {
+    let GenerateConfig {
+        language,
+        inputs,
+        target_os,
+    } = config;
+
+    let parse_context = ParseContext {
+        ignored_types: language.ignored_reference_types(),
+        multi_file: false,
+        target_os,
+    };
+    let (parsed_data, files) = parse_inputs(&parse_context, &inputs)?;
+    check_name_collisions(language, &parsed_data)?;
+
+    let mut source = Vec::new();
+    language.generate_types(&mut source, &HashMap::new(), parsed_data)?;
+    Ok(Outputs {
+        source: String::from_utf8_lossy(&source).into_owned(),
+        files,
+        inputs,
+    })
+}
+
+/// Parse the Rust files of the inputs, which are files or directories searched
+/// recursively, into the parsed data of a single file. Returns the parsed data and
+/// the Rust files, in order. Fails on the first error of any typeshared item.
+#[cfg(feature = "fs")]
+pub fn parse_inputs(
+    parse_context: &ParseContext,
+    inputs: &[PathBuf],
+) -> Result<(ParsedData, Vec<PathBuf>), GenerateError> {
+    let mut files = Vec::new();
+    for input in inputs {
+        rust_files(input, &mut files).map_err(|error| GenerateError::Read {
+            path: input.clone(),
+            error,
+        })?;
+    }
+
+    let mut parsed_data = ParsedData::default();
+    for file in &files {
+        let source_code = fs::read_to_string(file).map_err(|error| GenerateError::Read {
+            path: file.clone(),
+            error,
+        })?;
+        if let Some(parsed) = parse_file(parse_context, source_code, file)? {
+            parsed_data += parsed;
+        }
+    }
+    Ok((reconcile(parsed_data)?, files))
+}
+
+/// Generate the types of Rust source code, like the contents of a single file, for
+/// embedders which don't read the source from files.
+pub fn generate_source(
+    language: &mut dyn Language,
+    source_code: &str,
+) -> Result<String, GenerateError> {
+    let parse_context = ParseContext {
+        ignored_types: language.ignored_reference_types(),
+        multi_file: false,
+        target_os: Vec::new(),
+    };
+    let parsed = parse_file(&parse_context, source_code.to_owned(), Path::new(""))?;
+    let parsed_data = reconcile(parsed.unwrap_or_default())?;
+    check_name_collisions(language, &parsed_data)?;
+
+    let mut source = Vec::new();
+    language.generate_types(&mut source, &HashMap::new(), parsed_data)?;
+    Ok(String::from_utf8_lossy(&source).into_owned())
+}
+
+/// Parse the source code of the Rust file.
+fn parse_file(
+    parse_context: &ParseContext,
+    source_code: String,
+    file: &Path,
+) -> Result<Option<ParsedData>, GenerateError> {
+    let parse_file_context = ParseFileContext {
+        source_code,
+        crate_name: SINGLE_FILE_CRATE_NAME,
+        file_name: String::new(),
+        file_path: file.to_path_buf(),
+    };
+    parser::parse(parse_context, parse_file_context).map_err(|error| GenerateError::Parse {
+        file_name: file.to_string_lossy().into_owned(),
+        error: error.to_string(),
+        span: Some(error.span()),
+    })
+}
+
+/// Fail on the first error of any typeshared item, and reconcile the aliases of the
+/// parsed data of a single file.
+fn reconcile(parsed_data: ParsedData) -> Result<ParsedData, GenerateError> {
+    if let Some(error) = parsed_data.errors.first() {
+        return Err(GenerateError::Parse {
+            file_name: error.file_name.clone(),
+            error: error.error.clone(),
+            span: error.span,
+        });
+    }
+
+    let mut crate_parsed_data = BTreeMap::from([(SINGLE_FILE_CRATE_NAME, parsed_data)]);
+    reconcile_aliases(&mut crate_parsed_data);
+    Ok(crate_parsed_data
+        .remove(&SINGLE_FILE_CRATE_NAME)
+        .unwrap_or_default())
+}
+
+/// Fail if the language would generate items, or fields or variants of an item, with
+/// the same identifier.
+fn check_name_collisions(language: &dyn Language, data: &ParsedData) -> Result<(), GenerateError> {
+    let collisions = name_collisions(language, data);
+    if collisions.is_empty() {
+        Ok(())
+    } else {
+        Err(GenerateError::NameCollisions(collisions))
+    }
+}
+
+/// The Rust files of an input, sorted so the output doesn't depend on the order of
+/// the directory entries.
+#[cfg(feature = "fs")]
+fn rust_files(input: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !input.is_dir() {
+        files.push(input.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(input)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
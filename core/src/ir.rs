@@ -0,0 +1,45 @@
+//! The parsed types as JSON, for tools which build on typeshare's parsing of Rust
+//! without linking this crate.
+//!
+//! The JSON is an object with the version of its format, the version of typeshare, and
+//! the typeshared `structs`, `enums`, `aliases` and `consts` of every crate, serialized
+//! like [`ParsedData`], and the [schema hashes](crate::schema_hash) of the types of every
+//! crate, by their Rust names. Fields are only added to the format within a version; fields
+//! are removed or changed with a new [`IR_VERSION`].
+use crate::{language::CrateName, parser::ParsedData, schema_hash::schema_hashes};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The version of the format of the JSON.
+pub const IR_VERSION: u32 = 1;
+
+/// The parsed types of every crate, with the versions of the format and typeshare.
+#[derive(Debug, Serialize)]
+pub struct Ir<'a> {
+    /// The version of the format, [`IR_VERSION`].
+    pub version: u32,
+    /// The version of typeshare which parsed the types.
+    pub typeshare_version: &'a str,
+    /// The parsed types of every crate, by its name.
+    pub crates: &'a BTreeMap<CrateName, ParsedData>,
+    /// The schema hashes of the types of every crate, by the name of the crate and
+    /// the Rust name of the type.
+    pub schema_hashes: BTreeMap<CrateName, BTreeMap<String, String>>,
+}
+
+impl<'a> Ir<'a> {
+    /// The IR of the parsed types of the crates.
+    pub fn new(crates: &'a BTreeMap<CrateName, ParsedData>) -> Self {
+        Self {
+            version: IR_VERSION,
+            typeshare_version: env!("CARGO_PKG_VERSION"),
+            crates,
+            schema_hashes: schema_hashes(crates),
+        }
+    }
+
+    /// The IR as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self).map(|json| json + "\n")
+    }
+}
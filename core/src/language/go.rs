@@ -1,17 +1,95 @@
 use std::io::Write;
 
 use crate::language::SupportedLanguage;
+use crate::lint::{unsupported_types, Warning};
 use crate::parser::ParsedData;
 use crate::rename::RenameExt;
-use crate::rust_types::{RustConst, RustConstExpr, RustItem, RustTypeFormatError, SpecialRustType};
+use crate::rust_types::{
+    FieldDecorator, Id, RustConst, RustConstExpr, RustItem, RustType, RustTypeFormatError,
+    SpecialRustType,
+};
 use crate::{
     language::Language,
-    rust_types::{RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias},
-    topsort::topsort,
+    rust_types::{RustEnum, RustEnumShared, RustEnumVariant, RustField, RustStruct, RustTypeAlias},
 };
+use itertools::Itertools;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-use super::CrateTypes;
+use super::{
+    item_context, item_name, output::written, sorted_items, used_imports, write_item_schema_hash,
+    CodeStyle, CrateName, CrateTypes, FileHeader, FileOutput, ItemOutput, OptionalFields,
+    ScopedCrateTypes,
+};
+
+const GO_NULL_PACKAGE: &str = "gopkg.in/guregu/null.v4";
+
+const GO_OPTION_TYPE: &str = r#"// Option is an optional value that is serialized as `null` when it is not set.
+type Option[T any] struct {
+	Value T
+	Valid bool
+}
+
+// Some returns a set Option holding value.
+func Some[T any](value T) Option[T] {
+	return Option[T]{Value: value, Valid: true}
+}
+
+func (o Option[T]) MarshalJSON() ([]byte, error) {
+	if !o.Valid {
+		return []byte("null"), nil
+	}
+	return json.Marshal(o.Value)
+}
+
+func (o *Option[T]) UnmarshalJSON(data []byte) error {
+	if string(data) == "null" {
+		*o = Option[T]{}
+		return nil
+	}
+	o.Valid = true
+	return json.Unmarshal(data, &o.Value)
+}
+
+"#;
+
+/// How `Option<T>` is represented in generated Go code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GoOptionStyle {
+    /// A pointer, `*T`.
+    #[default]
+    Pointer,
+    /// A generic `Option[T]` value type, which is defined in the generated file.
+    Generic,
+    /// The nullable types of `gopkg.in/guregu/null.v4`, such as `null.String`.
+    /// Types without a `null` equivalent fall back to a pointer, as do `uint64` types
+    /// other than `U53`, which don't fit in the `int64` of `null.Int`.
+    Null,
+}
+
+impl GoOptionStyle {
+    fn from_decorator(value: &str) -> Option<Self> {
+        match value {
+            "pointer" => Some(Self::Pointer),
+            "generic" => Some(Self::Generic),
+            "null" => Some(Self::Null),
+            _ => None,
+        }
+    }
+}
+
+/// The wire format of `OffsetDateTime` values in generated Go code.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum GoDateTimeFormat {
+    /// RFC 3339, which is how `time.Time` is serialized by default.
+    #[default]
+    Rfc3339,
+    /// A Unix timestamp in seconds.
+    Unix,
+    /// A Unix timestamp in milliseconds.
+    UnixMillis,
+    /// A string in a custom Go time layout, such as `2006-01-02`.
+    Layout(String),
+}
 
 /// All information needed to generate Go type-code
 #[derive(Default)]
@@ -27,6 +105,16 @@ pub struct Go {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `const UserSchemaHash = "…"`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// How fields of `Option` types are represented, instead of the default of the
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
     /// Whether or not slices should be translated with a pointer redirection.
     ///
     /// It is rather unusual in Go to have pointers to slices. This is because, in Go, slices are already reference types.
@@ -45,34 +133,64 @@ pub struct Go {
     ///
     /// This, however, is rarely applicable in practice, and having this feature does not justify exposing an unintuitive user interface.
     pub no_pointer_slice: bool,
+    /// Whether or not to leave `,omitempty` off the JSON tags of optional fields.
+    ///
+    /// By default optional fields are omitted from the JSON output when they are nil, which
+    /// makes "absent" and "null" indistinguishable on the wire. Individual fields can override
+    /// this setting with `#[typeshare(go(omitempty = "true"))]` or `#[typeshare(go(omitempty = "false"))]`.
+    pub no_omitempty: bool,
+    /// How `Option<T>` and `#[serde(default)]` fields are represented.
+    /// Individual fields can override this setting with
+    /// `#[typeshare(go(option = "pointer"))]`, `"generic"` or `"null"`.
+    pub option_style: GoOptionStyle,
+    /// Will be set to true if one of your typeshared Rust types uses the generic `Option[T]`.
+    /// This will add a definition of the `Option` type to the generated Go code.
+    pub should_emit_option_type: bool,
+    /// The wire format of `OffsetDateTime` values. Formats other than RFC 3339 are
+    /// generated as a `DateTime` type wrapping `time.Time`, which is defined in the
+    /// generated file with matching JSON methods.
+    pub datetime_format: GoDateTimeFormat,
+    /// Will be set to true if one of your typeshared Rust types uses the generated `DateTime`.
+    pub should_emit_datetime_type: bool,
+    /// The Go import path of the output folder in multi-file mode, such as
+    /// `github.com/acme/api/types`. Every crate (or module) is written to its own
+    /// package directory in that folder, and imported from it by other packages.
+    pub import_path: String,
+    /// Package names for the output directory of a crate (or module, like `my_crate.models`)
+    /// in multi-file mode. Defaults to the name of the directory.
+    pub package_names: HashMap<String, String>,
+    /// Types referenced by the current file that are defined in other packages,
+    /// and the name of the package to qualify them with.
+    pub qualified_types: HashMap<String, String>,
+    /// Whether `serde_json::Value` fields are generated as `any` instead of `json.RawMessage`.
+    ///
+    /// `json.RawMessage` keeps the raw JSON of the value, so that it can be decoded later
+    /// once its shape is known, while `any` decodes it into maps, slices and primitives.
+    pub json_value_any: bool,
+    /// Whether unit enums get `String`, `MarshalJSON` and `UnmarshalJSON` methods, which
+    /// reject values other than their variants, and the zero value `""`.
+    pub enum_methods: bool,
 }
 
 impl Language for Go {
-    fn generate_types(
-        &mut self,
-        w: &mut dyn Write,
-        _imports: &CrateTypes,
-        data: ParsedData,
-    ) -> std::io::Result<()> {
-        self.begin_file(w, &data)?;
-
-        let ParsedData {
-            structs,
-            enums,
-            aliases,
-            consts,
-            ..
-        } = data;
-
-        let mut items = aliases
-            .into_iter()
-            .map(RustItem::Alias)
-            .chain(structs.into_iter().map(RustItem::Struct))
-            .chain(enums.into_iter().map(RustItem::Enum))
-            .chain(consts.into_iter().map(RustItem::Const))
-            .collect::<Vec<_>>();
-
-        topsort(&mut items);
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
+
+    fn emit(&mut self, imports: &CrateTypes, data: ParsedData) -> std::io::Result<FileOutput> {
+        self.imports.clear();
+        self.qualified_types.clear();
+        self.should_emit_option_type = false;
+        self.should_emit_datetime_type = false;
+
+        let header = written(|w| self.begin_file(w, &data))?;
+        let mut file_imports = if data.multi_file {
+            written(|w| self.write_imports(w, used_imports(&data, imports)))?
+        } else {
+            String::new()
+        };
+
+        let items = sorted_items(data);
 
         // Generate a list of all types that either are a struct or are aliased to a struct.
         // This is used to determine whether a type should be defined as a pointer or not.
@@ -95,23 +213,65 @@ impl Language for Go {
             }
         }
 
-        let mut body: Vec<u8> = Vec::new();
-        for thing in &items {
-            match thing {
-                RustItem::Enum(e) => self.write_enum(&mut body, e, &types_mapping_to_struct)?,
-                RustItem::Struct(s) => self.write_struct(&mut body, s)?,
-                RustItem::Alias(a) => self.write_type_alias(&mut body, a)?,
-                RustItem::Const(c) => self.write_const(&mut body, c)?,
+        let items = items
+            .iter()
+            .map(|thing| {
+                ItemOutput::new(thing, |w| {
+                    match thing {
+                        RustItem::Enum(e) => self.write_enum(w, e, &types_mapping_to_struct),
+                        RustItem::Struct(s) => self.write_struct(w, s),
+                        RustItem::Alias(a) => self.write_type_alias(w, a),
+                        RustItem::Const(c) => self.write_const(w, c),
+                    }?;
+                    write_item_schema_hash(self, w, thing)
+                })
+                .map_err(item_context(item_name(thing)))
+            })
+            .collect::<std::io::Result<_>>()?;
+
+        // The imports and helper types are known once the items are generated.
+        file_imports.push_str(&written(|w| self.write_all_imports(w))?);
+        let definitions = written(|w| {
+            if self.should_emit_option_type {
+                w.write_all(GO_OPTION_TYPE.as_bytes())?;
             }
-        }
-        self.write_all_imports(w)?;
-        w.write_all(&body)
+            if self.should_emit_datetime_type {
+                self.write_datetime_type(w)?;
+            }
+            Ok(())
+        })?;
+
+        Ok(FileOutput {
+            header,
+            imports: file_imports,
+            definitions,
+            items,
+            footer: String::new(),
+        })
     }
 
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
 
+    fn format_simple_type(
+        &mut self,
+        base: &str,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(if let Some(mapped) = self.type_map().get(base) {
+            mapped.into()
+        } else if let Some(package) = self
+            .qualified_types
+            .get(base)
+            .filter(|_| !generic_types.iter().any(|g| g == base))
+        {
+            format!("{package}.{base}")
+        } else {
+            base.into()
+        })
+    }
+
     fn format_generic_parameters(&mut self, parameters: Vec<String>) -> String {
         format!("[{}]", parameters.join(", "))
     }
@@ -133,17 +293,7 @@ impl Language for Go {
             SpecialRustType::Slice(rtype) => {
                 format!("[]{}", self.format_type(rtype, generic_types)?)
             }
-            SpecialRustType::Option(rtype) => {
-                format!(
-                    "{}{}",
-                    if rtype.is_vec() && self.no_pointer_slice {
-                        ""
-                    } else {
-                        "*"
-                    },
-                    self.format_type(rtype, generic_types)?
-                )
-            }
+            SpecialRustType::Option(rtype) => self.format_option(rtype, generic_types)?,
             SpecialRustType::HashMap(rtype1, rtype2) => format!(
                 "map[{}]{}",
                 self.format_type(rtype1, generic_types)?,
@@ -167,13 +317,22 @@ impl Language for Go {
             SpecialRustType::F64 => "float64".into(),
             SpecialRustType::DateTime => {
                 self.add_import("time");
-                "time.Time".into()
+                if self.datetime_format == GoDateTimeFormat::Rfc3339 {
+                    "time.Time".into()
+                } else {
+                    self.should_emit_datetime_type = true;
+                    "DateTime".into()
+                }
             }
+            SpecialRustType::JsonValue if self.json_value_any => "any".into(),
+            SpecialRustType::JsonValue => "json.RawMessage".into(),
         })
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> std::io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> std::io::Result<()> {
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "", "// ", "")?;
+        } else if !self.no_version_header {
             // This comment is specifically formatted to satisfy gosec's template for a generated file,
             // so the generated Go file can be ignored with `gosec -exclude-generated`.
             writeln!(
@@ -182,7 +341,11 @@ impl Language for Go {
                 env!("CARGO_PKG_VERSION")
             )?;
         }
-        writeln!(w, "package {}", self.package)?;
+        if parsed_data.multi_file {
+            writeln!(w, "package {}", self.package_name(&parsed_data.crate_name))?;
+        } else {
+            writeln!(w, "package {}", self.package)?;
+        }
         self.add_import("encoding/json");
         writeln!(w)?;
         Ok(())
@@ -193,9 +356,10 @@ impl Language for Go {
 
         writeln!(
             w,
-            "type {} {}\n",
+            "type {}{} {}\n",
             self.acronyms_to_uppercase(&ty.id.original),
-            self.format_type(&ty.r#type, &[])
+            generic_parameters_decl(&ty.generic_types),
+            self.format_type(&ty.r#type, &ty.generic_types)
                 .map_err(std::io::Error::other)?
         )?;
 
@@ -219,6 +383,19 @@ impl Language for Go {
         }
     }
 
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        hash: &str,
+    ) -> std::io::Result<()> {
+        writeln!(w, "const {name} = \"{hash}\"\n")
+    }
+
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
         write_comments(w, 0, &rs.comments)?;
         // TODO: Support generic bounds: https://github.com/1Password/typeshare/issues/222
@@ -226,33 +403,58 @@ impl Language for Go {
             w,
             "type {}{} struct {{",
             self.acronyms_to_uppercase(&rs.id.renamed),
-            if !rs.generic_types.is_empty() {
-                format!(
-                    "[{}]",
-                    rs.generic_types
-                        .iter()
-                        .map(|ty| format!("{ty} any"))
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                )
-            } else {
-                Default::default()
-            }
+            generic_parameters_decl(&rs.generic_types)
         )?;
 
         rs.fields
             .iter()
             .try_for_each(|f| self.write_field(w, f, rs.generic_types.as_slice()))?;
 
-        writeln!(w, "}}")
+        writeln!(w, "}}")?;
+
+        if rs.deny_unknown_fields {
+            self.write_strict_unmarshal(w, rs)?;
+        }
+        Ok(())
     }
 
+    /// Go imports are written together, after the body is generated, so this
+    /// only records the packages to import and the types they qualify.
     fn write_imports(
         &mut self,
         _writer: &mut dyn Write,
-        _imports: super::ScopedCrateTypes<'_>,
+        imports: ScopedCrateTypes<'_>,
     ) -> std::io::Result<()> {
-        unimplemented!()
+        for (crate_name, types) in imports {
+            let package = self.package_name(crate_name);
+            let package_dir = crate_name.as_str().replace('.', "/");
+            if self.import_path.is_empty() {
+                self.add_import(&package_dir);
+            } else {
+                self.add_import(&format!("{}/{package_dir}", self.import_path));
+            }
+            for ty in types {
+                self.qualified_types.insert(ty.to_owned(), package.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        unsupported_types(data, |ty| {
+            matches!(ty, SpecialRustType::Char).then(|| {
+                "`char` is generated as `rune` in Go, which is encoded as a number rather than a string"
+                    .to_string()
+            })
+        })
+    }
+
+    fn type_identifier(&self, id: &Id) -> String {
+        self.acronyms_to_uppercase(&id.renamed)
+    }
+
+    fn field_identifier(&self, field: &RustField) -> String {
+        self.format_field_name(field.id.original.to_string(), true)
     }
 }
 
@@ -303,7 +505,12 @@ impl Go {
                     _ => unreachable!(),
                 })?;
 
-                writeln!(w, "\n)")
+                writeln!(w, "\n)")?;
+
+                if self.enum_methods {
+                    self.write_unit_enum_methods(w, shared)?;
+                }
+                Ok(())
             }
             RustEnum::Algebraic {
                 tag_key,
@@ -315,6 +522,8 @@ impl Go {
                 let content_field = content_key.to_string().to_camel_case();
                 let tag_field = self.format_field_name(tag_key.to_string(), true);
                 let struct_short_name = shared.id.original[..1].to_lowercase();
+                let generic_decl = generic_parameters_decl(&shared.generic_types);
+                let generic_args = generic_arguments(&shared.generic_types);
                 let variant_key_type = format!(
                     "{}{}s",
                     struct_name,
@@ -331,11 +540,28 @@ impl Go {
                 for v in &shared.variants {
                     let variant_name = self.acronyms_to_uppercase(&v.shared().id.original);
                     let variant_type = match v {
-                        RustEnumVariant::Tuple { ty, .. } => {
-                            Some(self.format_type(ty, &[]).unwrap())
-                        }
-                        RustEnumVariant::AnonymousStruct { .. } => {
-                            Some(make_anonymous_struct_name(&variant_name))
+                        RustEnumVariant::Tuple { ty, .. } => Some(
+                            self.format_type(ty, &shared.generic_types)
+                                .map_err(std::io::Error::other)?,
+                        ),
+                        RustEnumVariant::AnonymousStruct { fields, .. } => {
+                            // The generated struct only takes the generic parameters its fields use
+                            let generic_types = fields
+                                .iter()
+                                .flat_map(|field| {
+                                    shared
+                                        .generic_types
+                                        .iter()
+                                        .filter(|g| field.ty.contains_type(g))
+                                })
+                                .unique()
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            Some(format!(
+                                "{}{}",
+                                make_anonymous_struct_name(&variant_name),
+                                generic_arguments(&generic_types)
+                            ))
                         }
                         RustEnumVariant::Unit(_) => None,
                     };
@@ -364,15 +590,15 @@ impl Go {
 ",
                         ));
                         variant_accessors.push(format!(
-                            r#"func ({struct_short_name} {struct_name}) {variant_name}() {variant_pointer}{formatted_variant_type} {{
+                            r#"func ({struct_short_name} {struct_name}{generic_args}) {variant_name}() {variant_pointer}{formatted_variant_type} {{
 	res, _ := {struct_short_name}.{content_field}.(*{formatted_variant_type})
 	return {variant_deref}res
 }}
 "#,
                         ));
                         variant_constructors.push(format!(
-                            r#"func New{variant_type_const}(content {variant_pointer}{formatted_variant_type}) {struct_name} {{
-    return {struct_name}{{
+                            r#"func New{variant_type_const}{generic_decl}(content {variant_pointer}{formatted_variant_type}) {struct_name}{generic_args} {{
+    return {struct_name}{generic_args}{{
         {tag_field}: {variant_type_const},
         {content_field}: {variant_ref}content,
     }}
@@ -383,8 +609,8 @@ impl Go {
                         decoding_cases.push("\t\treturn nil\n".to_string());
 
                         variant_constructors.push(format!(
-                            r#"func New{variant_type_const}() {struct_name} {{
-    return {struct_name}{{
+                            r#"func New{variant_type_const}{generic_decl}() {struct_name}{generic_args} {{
+    return {struct_name}{generic_args}{{
         {tag_field}: {variant_type_const},
     }}
 }}
@@ -404,7 +630,7 @@ impl Go {
 
                 writeln!(w, ")")?;
 
-                writeln!(w, "type {struct_name} struct{{ ")?;
+                writeln!(w, "type {struct_name}{generic_decl} struct{{ ")?;
                 writeln!(
                     w,
                     "\t{} {} `json:{:?}`",
@@ -418,7 +644,7 @@ impl Go {
                 writeln!(
                     w,
                     r#"
-func ({short_name} *{full_name}) UnmarshalJSON(data []byte) error {{
+func ({short_name} *{full_name}{generic_args}) UnmarshalJSON(data []byte) error {{
 	var enum struct {{
 		Tag    {variant_key_type}   `json:"{tag_key}"`
 		Content json.RawMessage `json:"{content_key}"`
@@ -438,7 +664,7 @@ func ({short_name} *{full_name}) UnmarshalJSON(data []byte) error {{
 	return nil
 }}
 
-func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
+func ({short_name} {full_name}{generic_args}) MarshalJSON() ([]byte, error) {{
     var enum struct {{
 		Tag    {variant_key_type}   `json:"{tag_key}"`
 		Content interface{{}} `json:"{content_key},omitempty"`
@@ -460,62 +686,213 @@ func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
                     content_key = content_key,
                     tag_key = tag_key,
                     variant_key_type = variant_key_type,
+                    generic_args = generic_args,
                 )
             }
         }
     }
 
+    /// Writes `String()`, and JSON methods that only accept the enum's variants.
+    /// Writes an `UnmarshalJSON` method for a struct with `#[serde(deny_unknown_fields)]`,
+    /// which rejects unknown fields like the Rust struct does.
+    fn write_strict_unmarshal(
+        &mut self,
+        w: &mut dyn Write,
+        rs: &RustStruct,
+    ) -> std::io::Result<()> {
+        self.add_import("bytes");
+
+        let type_name = format!(
+            "{}{}",
+            self.acronyms_to_uppercase(&rs.id.renamed),
+            generic_arguments(&rs.generic_types)
+        );
+        let short_name = rs.id.renamed[..1].to_lowercase();
+
+        writeln!(
+            w,
+            r#"
+func ({short_name} *{type_name}) UnmarshalJSON(data []byte) error {{
+	// A type without this method, so that decoding doesn't recurse
+	type plain {type_name}
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+	var value plain
+	if err := decoder.Decode(&value); err != nil {{
+		return err
+	}}
+	*{short_name} = {type_name}(value)
+	return nil
+}}"#
+        )
+    }
+
+    fn write_unit_enum_methods(
+        &mut self,
+        w: &mut dyn Write,
+        shared: &RustEnumShared,
+    ) -> std::io::Result<()> {
+        self.add_import("fmt");
+
+        let type_name = self.acronyms_to_uppercase(&shared.id.original);
+        let short_name = shared.id.original[..1].to_lowercase();
+        // The zero value is valid too, so structs with unset enums can be marshalled.
+        let variants = std::iter::once("\"\"".to_string())
+            .chain(shared.variants.iter().map(|v| {
+                format!(
+                    "{type_name}{}",
+                    self.acronyms_to_uppercase(&v.shared().id.original)
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let marshal_cases = format!(
+            r#"	switch {short_name} {{
+	case {variants}:
+		return json.Marshal(string({short_name}))
+	}}
+"#
+        );
+        let unmarshal_cases = format!(
+            r#"	switch enum := {type_name}(value); enum {{
+	case {variants}:
+		*{short_name} = enum
+		return nil
+	}}
+"#
+        );
+
+        writeln!(
+            w,
+            r#"
+func ({short_name} {type_name}) String() string {{
+	return string({short_name})
+}}
+
+func ({short_name} {type_name}) MarshalJSON() ([]byte, error) {{
+{marshal_cases}	return nil, fmt.Errorf("invalid {type_name}: %q", string({short_name}))
+}}
+
+func ({short_name} *{type_name}) UnmarshalJSON(data []byte) error {{
+	var value string
+	if err := json.Unmarshal(data, &value); err != nil {{
+		return err
+	}}
+{unmarshal_cases}	return fmt.Errorf("invalid {type_name}: %q", value)
+}}"#
+        )
+    }
+
     fn write_field(
         &mut self,
         w: &mut dyn Write,
         field: &RustField,
         generic_types: &[String],
     ) -> std::io::Result<()> {
-        fn option_symbol(optional: bool) -> &'static str {
-            if optional {
-                ",omitempty"
-            } else {
-                ""
-            }
-        }
-
         write_comments(w, 1, &field.comments)?;
 
+        let is_optional = field.ty.is_optional() || field.has_default;
+        let field_option_style = field_option_style(field).unwrap_or(self.option_style);
+        let option_style = std::mem::replace(&mut self.option_style, field_option_style);
+        // Fields with a default are represented like an `Option` of their type
+        let pointer_default = field.has_default && !field.ty.is_optional();
         let type_name = match field.type_override(SupportedLanguage::Go) {
-            Some(type_override) => type_override.to_owned(),
-            None => self
+            Some(type_override) => Ok(format!(
+                "{}{type_override}",
+                if pointer_default { "*" } else { "" }
+            )),
+            // Unlike `Option<Vec<T>>`, defaulted slices always use a pointer
+            None if pointer_default && self.option_style == GoOptionStyle::Pointer => self
                 .format_type(&field.ty, generic_types)
-                .map_err(std::io::Error::other)?,
+                .map(|ty| format!("*{ty}")),
+            None if pointer_default => self.format_option(&field.ty, generic_types),
+            None => self.format_type(&field.ty, generic_types),
         };
+        self.option_style = option_style;
 
-        let go_type = self.acronyms_to_uppercase(&type_name);
-        let is_optional = field.ty.is_optional() || field.has_default;
+        let go_type = self.acronyms_to_uppercase(&type_name.map_err(std::io::Error::other)?);
+        let omitempty = match field_omitempty(field) {
+            Some(omitempty) => omitempty,
+            None => match self.optional_fields.filter(|_| field.ty.is_optional()) {
+                // Absent and `null` fields are both decoded as `nil`
+                Some(OptionalFields::Null | OptionalFields::Both) => false,
+                Some(OptionalFields::Absent) | None => is_optional && !self.no_omitempty,
+            },
+        };
         let formatted_renamed_id = format!("{:?}", &field.id.renamed);
         let renamed_id = &formatted_renamed_id[1..formatted_renamed_id.len() - 1];
         writeln!(
             w,
-            "\t{} {}{} `json:\"{}{}\"`",
+            "\t{} {} `json:\"{}{}\"`",
             self.format_field_name(field.id.original.to_string(), true),
-            if field.has_default && !field.ty.is_optional() {
-                "*"
-            } else {
-                Default::default()
-            },
             go_type,
             renamed_id,
-            option_symbol(is_optional),
+            if omitempty { ",omitempty" } else { "" },
         )?;
 
         Ok(())
     }
 
+    fn format_option(
+        &mut self,
+        rtype: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        match self.option_style {
+            GoOptionStyle::Pointer => {
+                let ty = self.format_type(rtype, generic_types)?;
+                // `any` is nil already when the value is missing
+                Ok(
+                    if (rtype.is_vec() && self.no_pointer_slice) || ty == "any" {
+                        ty
+                    } else {
+                        format!("*{ty}")
+                    },
+                )
+            }
+            GoOptionStyle::Generic => {
+                self.should_emit_option_type = true;
+                Ok(format!(
+                    "Option[{}]",
+                    self.format_type(rtype, generic_types)?
+                ))
+            }
+            GoOptionStyle::Null => {
+                // Checked before formatting, so `time` isn't imported needlessly
+                if matches!(rtype, RustType::Special(SpecialRustType::DateTime))
+                    && self.datetime_format == GoDateTimeFormat::Rfc3339
+                    && !self
+                        .type_mappings
+                        .contains_key(&SpecialRustType::DateTime.to_string())
+                {
+                    self.add_import(GO_NULL_PACKAGE);
+                    return Ok("null.Time".into());
+                }
+                let go_type = self.format_type(rtype, generic_types)?;
+                let null_type = match go_type.as_str() {
+                    "string" => "null.String",
+                    "bool" => "null.Bool",
+                    // `null.Int` holds an `int64`, which `U53` fits in but not any `uint64`
+                    "int" | "int64" | "uint32" => "null.Int",
+                    "uint64" if matches!(rtype, RustType::Special(SpecialRustType::U53)) => {
+                        "null.Int"
+                    }
+                    "float32" | "float64" => "null.Float",
+                    _ => return Ok(format!("*{go_type}")),
+                };
+                self.add_import(GO_NULL_PACKAGE);
+                Ok(null_type.into())
+            }
+        }
+    }
+
     // Convert any of the configured acronyms to uppercase to follow Go's formatting standard.
     // If self.uppercase_acronyms contains ID (or id), Id will get replaced by ID.
     fn acronyms_to_uppercase(&self, name: &str) -> String {
         convert_acronyms_to_uppercase(self.uppercase_acronyms.clone(), name)
     }
 
-    fn format_field_name(&mut self, name: String, exported: bool) -> String {
+    fn format_field_name(&self, name: String, exported: bool) -> String {
         let name = if exported {
             name.to_pascal_case()
         } else {
@@ -524,6 +901,67 @@ func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
         self.acronyms_to_uppercase(&name)
     }
 
+    /// Writes the `DateTime` type, which serializes a `time.Time` in the configured format.
+    fn write_datetime_type(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        let (description, json_type, marshal_value, unmarshal_value) = match &self.datetime_format {
+            GoDateTimeFormat::Rfc3339 => return Ok(()),
+            GoDateTimeFormat::Unix => (
+                "a Unix timestamp in seconds".to_owned(),
+                "int64",
+                "t.Unix()".to_owned(),
+                "\tt.Time = time.Unix(value, 0)\n".to_owned(),
+            ),
+            GoDateTimeFormat::UnixMillis => (
+                "a Unix timestamp in milliseconds".to_owned(),
+                "int64",
+                "t.UnixMilli()".to_owned(),
+                "\tt.Time = time.UnixMilli(value)\n".to_owned(),
+            ),
+            GoDateTimeFormat::Layout(layout) => (
+                format!("the layout {layout:?}"),
+                "string",
+                format!("t.Format({layout:?})"),
+                format!(
+                    "\tparsed, err := time.Parse({layout:?}, value)
+\tif err != nil {{
+\t\treturn err
+\t}}
+\tt.Time = parsed
+"
+                ),
+            ),
+        };
+
+        writeln!(
+            w,
+            r#"// DateTime is a time.Time that is serialized as {description}.
+type DateTime struct {{
+	time.Time
+}}
+
+func (t DateTime) MarshalJSON() ([]byte, error) {{
+	return json.Marshal({marshal_value})
+}}
+
+func (t *DateTime) UnmarshalJSON(data []byte) error {{
+	var value {json_type}
+	if err := json.Unmarshal(data, &value); err != nil {{
+		return err
+	}}
+{unmarshal_value}	return nil
+}}
+"#
+        )
+    }
+
+    /// The name of the package a crate (or module) is generated in, in multi-file mode.
+    fn package_name(&self, crate_name: &CrateName) -> String {
+        self.package_names
+            .get(crate_name.as_str())
+            .cloned()
+            .unwrap_or_else(|| crate_name.module_name().to_owned())
+    }
+
     fn add_import(&mut self, name: &str) {
         self.imports.insert(name.to_string());
     }
@@ -546,6 +984,56 @@ func ({short_name} {full_name}) MarshalJSON() ([]byte, error) {{
     }
 }
 
+/// Returns the `omitempty` override set on a field with `#[typeshare(go(omitempty))]`, if any.
+fn field_omitempty(field: &RustField) -> Option<bool> {
+    field
+        .decorators
+        .get(&SupportedLanguage::Go)?
+        .iter()
+        .find_map(|fd| match fd {
+            FieldDecorator::Word(name) if name == "omitempty" => Some(true),
+            FieldDecorator::NameValue(name, value) if name == "omitempty" => Some(value != "false"),
+            _ => None,
+        })
+}
+
+/// Returns the `Option` representation set on a field with `#[typeshare(go(option = "..."))]`, if any.
+fn field_option_style(field: &RustField) -> Option<GoOptionStyle> {
+    field
+        .decorators
+        .get(&SupportedLanguage::Go)?
+        .iter()
+        .find_map(|fd| match fd {
+            FieldDecorator::NameValue(name, value) if name == "option" => {
+                GoOptionStyle::from_decorator(value)
+            }
+            _ => None,
+        })
+}
+
+/// The type parameter list of a generic Go type or function, such as `[A any, B any]`.
+fn generic_parameters_decl(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        return String::new();
+    }
+    format!(
+        "[{}]",
+        generic_types
+            .iter()
+            .map(|ty| format!("{ty} any"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// The type arguments used to refer to a generic Go type by its own parameters, such as `[A, B]`.
+fn generic_arguments(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        return String::new();
+    }
+    format!("[{}]", generic_types.join(", "))
+}
+
 fn write_comment(w: &mut dyn Write, indent: usize, comment: &str) -> std::io::Result<()> {
     writeln!(w, "{}// {}", "\t".repeat(indent), comment)?;
     Ok(())
@@ -601,4 +1089,65 @@ mod test {
         let go_slice = super::Language::format_special_type(&mut go, &optional_slice, &[]).unwrap();
         assert_eq!(go_slice, "[]int");
     }
+
+    #[test]
+    fn multi_file_imports_packages() {
+        use crate::context::{ParseContext, ParseFileContext};
+        use crate::language::{CrateName, Language};
+        use std::collections::{HashMap, HashSet};
+
+        let mut go = super::Go {
+            import_path: "github.com/acme/types".into(),
+            package_names: HashMap::from([("bar".into(), "barmodels".into())]),
+            no_version_header: true,
+            ..Default::default()
+        };
+
+        let parsed_data = crate::parser::parse(
+            &ParseContext {
+                multi_file: true,
+                ..Default::default()
+            },
+            ParseFileContext {
+                source_code: r#"
+                    use foo::net::Address;
+
+                    #[typeshare]
+                    pub struct Bar {
+                        pub address: Address,
+                    }
+                "#
+                .into(),
+                crate_name: "bar".into(),
+                file_name: "bar/bar.go".into(),
+                file_path: "bar/src/lib.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let imports = HashMap::from([(
+            CrateName::from("foo.net"),
+            HashSet::from(["Address".to_string()]),
+        )]);
+
+        let mut output = Vec::new();
+        go.generate_types(&mut output, &imports, parsed_data)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            r#"package barmodels
+
+import (
+	"encoding/json"
+	"github.com/acme/types/foo/net"
+)
+
+type Bar struct {
+	Address net.Address `json:"address"`
+}
+"#
+        );
+    }
 }
@@ -0,0 +1,180 @@
+//! Custom headers of generated files.
+use super::{CrateName, SupportedLanguage};
+use std::{
+    env,
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A custom header for generated files, which replaces the "Generated by typeshare"
+/// comment. Every language writes it as a comment at the top of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHeader {
+    template: String,
+    timestamp: String,
+    revision: String,
+}
+
+impl FileHeader {
+    /// A header from a template of one or more lines, with these placeholders:
+    /// - `{version}`: the version of typeshare.
+    /// - `{timestamp}`: the time the header was created at in UTC, like
+    ///   `2024-05-01T12:00:00Z`, or the Unix timestamp of the `SOURCE_DATE_EPOCH`
+    ///   environment variable for reproducible output.
+    /// - `{crate}`: the crate a file is generated from, which is empty when every crate
+    ///   is generated into a single file.
+    /// - `{revision}`: the revision of the sources given with
+    ///   [`with_revision`](Self::with_revision), like a git commit hash.
+    pub fn new(template: impl Into<String>) -> Self {
+        let seconds = env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|epoch| epoch.trim().parse().ok())
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_secs())
+            });
+        Self {
+            template: template.into(),
+            timestamp: rfc3339(seconds),
+            revision: String::new(),
+        }
+    }
+
+    /// The template of the "Generated by typeshare" comment of the language, with or
+    /// without the version of typeshare and the revision of the sources.
+    pub fn default_template(language: SupportedLanguage, version: bool, revision: bool) -> String {
+        let mut generator = "typeshare".to_owned();
+        if version {
+            generator.push_str(" {version}");
+        }
+        if revision {
+            generator.push_str(" from revision {revision}");
+        }
+        match language {
+            // Formatted to satisfy gosec's template for a generated file
+            SupportedLanguage::Go => format!("Code generated by {generator}. DO NOT EDIT."),
+            _ => format!("Generated by {generator}"),
+        }
+    }
+
+    /// The header with the revision of the sources to replace `{revision}` with.
+    pub fn with_revision(mut self, revision: impl Into<String>) -> Self {
+        self.revision = revision.into();
+        self
+    }
+
+    /// The lines of the header of a file generated from the crate.
+    pub fn lines(&self, crate_name: &CrateName) -> Vec<String> {
+        self.template
+            .replace("{version}", env!("CARGO_PKG_VERSION"))
+            .replace("{timestamp}", &self.timestamp)
+            .replace("{crate}", crate_name.as_str())
+            .replace("{revision}", &self.revision)
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Write the header as a comment, with `start` and `end` on their own lines unless
+    /// they're empty, and every line of the header after `prefix`.
+    pub(crate) fn write(
+        &self,
+        w: &mut dyn Write,
+        crate_name: &CrateName,
+        start: &str,
+        prefix: &str,
+        end: &str,
+    ) -> io::Result<()> {
+        if !start.is_empty() {
+            writeln!(w, "{start}")?;
+        }
+        for line in self.lines(crate_name) {
+            writeln!(w, "{}", format!("{prefix}{line}").trim_end())?;
+        }
+        if !end.is_empty() {
+            writeln!(w, "{end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 timestamp in UTC.
+fn rfc3339(seconds: u64) -> String {
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+    // The civil date of the days since 1970-01-01, counting from 0000-03-01 so leap
+    // days are at the end of years, in eras of 400 years.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rfc3339, FileHeader};
+    use crate::language::{CrateName, SupportedLanguage};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn writes_placeholders() {
+        let header = FileHeader {
+            template:
+                "Copyright Example\n\nGenerated from {crate} by typeshare {version} at {timestamp}"
+                    .to_string(),
+            timestamp: rfc3339(0),
+            revision: String::new(),
+        };
+        let mut output = Vec::new();
+        header
+            .write(&mut output, &CrateName::from("api"), "/*", " * ", " */")
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "/*\n * Copyright Example\n *\n * Generated from api by typeshare {} at 1970-01-01T00:00:00Z\n */\n",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn writes_default_templates() {
+        let header = FileHeader::new(FileHeader::default_template(
+            SupportedLanguage::Go,
+            false,
+            true,
+        ))
+        .with_revision("4c3b1b1");
+        assert_eq!(
+            header.lines(&CrateName::from("api")),
+            ["Code generated by typeshare from revision 4c3b1b1. DO NOT EDIT."]
+        );
+        assert_eq!(
+            FileHeader::default_template(SupportedLanguage::Swift, true, false),
+            "Generated by typeshare {version}"
+        );
+    }
+}
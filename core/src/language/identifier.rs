@@ -0,0 +1,88 @@
+//! Escaping the identifiers a language reserves, like its keywords, the same way for
+//! the types, fields, variants and generic parameters it generates.
+use crate::parser::remove_dash_from_identifier;
+use std::{borrow::Cow, collections::HashSet};
+
+/// How a language escapes an identifier it reserves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// Put the string on both sides of it, like the backticks of `` `class` `` in Swift.
+    Quote(&'static str),
+    /// Put the string in front of it, like the `r#` of `r#type` in Rust.
+    Prefix(&'static str),
+    /// Put the string after it, like the underscore of `class_` in Python.
+    Suffix(&'static str),
+}
+
+/// The keywords of a language, and how it escapes them and the reserved words
+/// configured for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentifierPolicy {
+    /// The identifiers the language always reserves.
+    pub keywords: &'static [&'static str],
+    /// How reserved identifiers are escaped.
+    pub escape: Escape,
+}
+
+impl IdentifierPolicy {
+    /// Whether the name is a keyword or one of the reserved words.
+    pub fn is_reserved(&self, name: &str, reserved_words: &HashSet<String>) -> bool {
+        self.keywords.contains(&name) || reserved_words.contains(name)
+    }
+
+    /// The name escaped, whether it's reserved or not.
+    pub fn escaped(&self, name: &str) -> String {
+        match self.escape {
+            Escape::Quote(quote) => format!("{quote}{name}{quote}"),
+            Escape::Prefix(prefix) => format!("{prefix}{name}"),
+            Escape::Suffix(suffix) => format!("{name}{suffix}"),
+        }
+    }
+
+    /// The name, escaped if it's a keyword or one of the reserved words.
+    pub fn escape<'a>(
+        &self,
+        name: impl Into<Cow<'a, str>>,
+        reserved_words: &HashSet<String>,
+    ) -> Cow<'a, str> {
+        let name = name.into();
+        if self.is_reserved(&name, reserved_words) {
+            Cow::Owned(self.escaped(&name))
+        } else {
+            name
+        }
+    }
+
+    /// The identifier of a serialized name, which may not be a valid identifier as is:
+    /// its dashes are replaced with underscores, like `user-id` as `user_id`, and it's
+    /// escaped if it's reserved.
+    pub fn identifier(&self, name: &str, reserved_words: &HashSet<String>) -> String {
+        self.escape(remove_dash_from_identifier(name), reserved_words)
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Escape, IdentifierPolicy};
+    use std::collections::HashSet;
+
+    #[test]
+    fn escapes_keywords_and_reserved_words() {
+        let reserved_words = HashSet::from(["hash".to_owned()]);
+        let policy = |escape| IdentifierPolicy {
+            keywords: &["class", "type"],
+            escape,
+        };
+
+        let quoted = policy(Escape::Quote("`"));
+        assert_eq!(quoted.escape("class", &reserved_words), "`class`");
+        assert_eq!(quoted.escape("hash", &reserved_words), "`hash`");
+        assert_eq!(quoted.escape("name", &reserved_words), "name");
+        assert_eq!(quoted.identifier("type", &reserved_words), "`type`");
+        assert_eq!(quoted.identifier("user-id", &reserved_words), "user_id");
+
+        assert_eq!(policy(Escape::Prefix("r#")).escaped("type"), "r#type");
+        assert_eq!(policy(Escape::Suffix("_")).escaped("class"), "class_");
+    }
+}
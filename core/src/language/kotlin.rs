@@ -1,7 +1,10 @@
-use super::{Language, ScopedCrateTypes};
+use super::{
+    CodeStyle, Escape, FileHeader, IdentifierPolicy, Language, OptionalFields, ScopedCrateTypes,
+};
 use crate::language::SupportedLanguage;
+use crate::lint::{lossy_mappings, unmapped_json_values, Warning};
 use crate::parser::{remove_dash_from_identifier, DecoratorKind, ParsedData};
-use crate::rust_types::{RustTypeFormatError, SpecialRustType};
+use crate::rust_types::{RustType, RustTypeFormatError, SpecialRustType};
 use crate::{
     rename::RenameExt,
     rust_types::{Id, RustConst, RustEnum, RustEnumVariant, RustField, RustStruct, RustTypeAlias},
@@ -9,10 +12,59 @@ use crate::{
 use itertools::Itertools;
 use joinery::JoinableIterator;
 use lazy_format::lazy_format;
-use std::{collections::BTreeSet, collections::HashMap, io::Write};
+use std::{collections::BTreeSet, collections::HashMap, collections::HashSet, io::Write};
 
 const INLINE: &str = "JvmInline";
 
+/// The hard keywords of Kotlin, which can't be identifiers without backticks. Soft
+/// keywords, like `value` or `data`, can be.
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+const IDENTIFIERS: IdentifierPolicy = IdentifierPolicy {
+    keywords: KOTLIN_KEYWORDS,
+    escape: Escape::Quote("`"),
+};
+
+/// The serialization framework that generated Kotlin types are annotated for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KotlinSerializer {
+    /// Annotate types with `kotlinx.serialization` (`@Serializable`, `@SerialName`).
+    #[default]
+    Kotlinx,
+    /// Annotate types with Moshi (`@JsonClass`, `@Json(name=)`).
+    /// Sealed classes need to be registered with a `PolymorphicJsonAdapterFactory`.
+    Moshi,
+}
+
 /// All information needed for Kotlin type-code
 #[derive(Default)]
 pub struct Kotlin {
@@ -27,6 +79,48 @@ pub struct Kotlin {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `const val UserSchemaHash = "…"`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// Identifiers of properties which are escaped with backticks, like framework names.
+    pub reserved_words: HashSet<String>,
+    /// How fields of `Option` types are represented, instead of the default of the
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
+    /// The serialization framework to annotate types for.
+    pub serializer: KotlinSerializer,
+    /// Whether to generate algebraic enums as sealed interfaces instead of sealed classes.
+    pub sealed_interfaces: bool,
+    /// Whether fields with `#[serde(default)]` should be generated as non-nullable
+    /// properties with a Kotlin default value, instead of nullable properties defaulting
+    /// to `null`. Only applies to fields whose type has a well-known default.
+    ///
+    /// Missing keys decode to the default value, and kotlinx.serialization omits
+    /// default values when encoding unless `encodeDefaults` is enabled, both of which
+    /// match serde's behaviour.
+    pub serde_default_values: bool,
+    /// Whether to annotate data classes, objects and sealed types with `@Parcelize`
+    /// and make them implement `Parcelable`.
+    pub parcelize: bool,
+    /// Whether to generate newtype structs, like `struct Id(String)`, as `@JvmInline`
+    /// value classes, as if they were annotated with `#[typeshare(kotlin = "JvmInline")]`.
+    pub newtype_value_classes: bool,
+    /// Whether to generate common Kotlin Multiplatform source, which only depends on
+    /// the Kotlin standard library and kotlinx.serialization. Maps are generated as the
+    /// read-only `Map` and `kotlin.jvm.JvmInline` is imported explicitly.
+    ///
+    /// The Moshi serializer and `parcelize` are JVM-only, and can't be combined with it.
+    pub multiplatform: bool,
+    /// Whether to generate a Kotest arbitrary, `arb<Type>`, for every type, which
+    /// generates values of it for property-based tests.
+    pub arbitraries: bool,
+    /// The Kotlin names of the types generated in the current file, by Rust name,
+    /// which references in doc comments are linked to. This is filled in when a file is generated.
+    pub kdoc_links: HashMap<String, String>,
 }
 
 impl Language for Kotlin {
@@ -34,14 +128,18 @@ impl Language for Kotlin {
         &self.type_mappings
     }
 
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
+
     fn format_simple_type(
         &mut self,
-        base: &String,
+        base: &str,
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         Ok(if let Some(mapped) = self.type_map().get(base) {
             mapped.into()
-        } else if generic_types.contains(base) {
+        } else if generic_types.iter().any(|g| g == base) {
             base.into()
         } else {
             format!("{}{}", self.prefix, base)
@@ -68,7 +166,8 @@ impl Language for Kotlin {
             }
             SpecialRustType::HashMap(rtype1, rtype2) => {
                 format!(
-                    "HashMap<{}, {}>",
+                    "{}<{}, {}>",
+                    if self.multiplatform { "Map" } else { "HashMap" },
                     self.format_type(rtype1, generic_types)?,
                     self.format_type(rtype2, generic_types)?
                 )
@@ -89,6 +188,9 @@ impl Language for Kotlin {
             SpecialRustType::Bool => "Boolean".into(),
             SpecialRustType::F32 => "Float".into(),
             SpecialRustType::F64 => "Double".into(),
+            SpecialRustType::JsonValue => {
+                self.format_simple_type(special_ty.id(), generic_types)?
+            }
             // TODO: https://github.com/1Password/typeshare/issues/237
             SpecialRustType::DateTime => {
                 return Err(RustTypeFormatError::UnsupportedSpecialType(
@@ -99,8 +201,26 @@ impl Language for Kotlin {
     }
 
     fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> std::io::Result<()> {
+        self.kdoc_links = parsed_data
+            .structs
+            .iter()
+            .map(|s| &s.id)
+            .chain(parsed_data.enums.iter().map(|e| &e.shared().id))
+            .chain(parsed_data.aliases.iter().map(|a| &a.id))
+            .flat_map(|id| {
+                let name = format!("{}{}", self.prefix, id.renamed);
+                [
+                    (id.original.to_string(), name.clone()),
+                    (id.renamed.to_string(), name),
+                ]
+            })
+            .collect();
+
         if !self.package.is_empty() {
-            if !self.no_version_header {
+            if let Some(header) = &self.header {
+                header.write(w, &parsed_data.crate_name, "/**", " * ", " */")?;
+                writeln!(w)?;
+            } else if !self.no_version_header {
                 writeln!(w, "/**")?;
                 writeln!(w, " * Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
                 writeln!(w, " */")?;
@@ -112,8 +232,32 @@ impl Language for Kotlin {
                 writeln!(w, "package {}", self.package)?;
             }
             writeln!(w)?;
-            writeln!(w, "import kotlinx.serialization.Serializable")?;
-            writeln!(w, "import kotlinx.serialization.SerialName")?;
+            match self.serializer {
+                KotlinSerializer::Kotlinx => {
+                    writeln!(w, "import kotlinx.serialization.Serializable")?;
+                    writeln!(w, "import kotlinx.serialization.SerialName")?;
+                }
+                KotlinSerializer::Moshi => {
+                    writeln!(w, "import com.squareup.moshi.Json")?;
+                    writeln!(w, "import com.squareup.moshi.JsonClass")?;
+                }
+            }
+            // `kotlin.jvm` is only imported by default on the JVM
+            if self.multiplatform
+                && parsed_data.aliases.iter().any(|a| {
+                    self.is_inline(&a.decorators) || (self.newtype_value_classes && a.is_newtype)
+                })
+            {
+                writeln!(w, "import kotlin.jvm.JvmInline")?;
+            }
+            if self.parcelize {
+                writeln!(w, "import android.os.Parcelable")?;
+                writeln!(w, "import kotlinx.parcelize.Parcelize")?;
+            }
+            if self.arbitraries {
+                writeln!(w, "import io.kotest.property.Arb")?;
+                writeln!(w, "import io.kotest.property.arbitrary.*")?;
+            }
             writeln!(w)?;
         }
 
@@ -124,10 +268,25 @@ impl Language for Kotlin {
         self.write_comments(w, 0, &ty.comments)?;
         let type_name = format!("{}{}", &self.prefix, ty.id.original);
 
-        if self.is_inline(&ty.decorators) {
-            writeln!(w, "@Serializable")?;
+        if self.is_inline(&ty.decorators) || (self.newtype_value_classes && ty.is_newtype) {
+            match self.serializer {
+                KotlinSerializer::Kotlinx => writeln!(w, "@Serializable")?,
+                KotlinSerializer::Moshi => {
+                    writeln!(w, "@JsonClass(generateAdapter = true, inline = true)")?
+                }
+            }
             writeln!(w, "@JvmInline")?;
-            writeln!(w, "value class {}{}(", self.prefix, ty.id.renamed)?;
+            writeln!(
+                w,
+                "value class {}{}{}(",
+                self.prefix,
+                ty.id.renamed,
+                if !ty.generic_types.is_empty() {
+                    format!("<{}>", ty.generic_types.join(", "))
+                } else {
+                    Default::default()
+                }
+            )?;
 
             self.write_element(
                 w,
@@ -139,10 +298,12 @@ impl Language for Kotlin {
                     },
                     ty: ty.r#type.clone(),
                     comments: vec![],
+                    deprecated: None,
                     has_default: false,
                     decorators: HashMap::new(),
+                    span: None,
                 },
-                &[],
+                ty.generic_types.as_slice(),
                 false,
                 match ty.is_redacted {
                     true => Visibility::Private,
@@ -163,6 +324,15 @@ impl Language for Kotlin {
             }
 
             writeln!(w)?;
+
+            if self.arbitraries {
+                let value = self
+                    .arbitrary_expr(&ty.r#type, &ty.generic_types)
+                    .map_err(std::io::Error::other)?;
+                let name = format!("{}{}", self.prefix, ty.id.renamed);
+                let arbitrary = format!("arbitrary {{ {name}({value}.bind()) }}");
+                self.write_arbitrary(w, &name, &ty.generic_types, &arbitrary)?;
+            }
         } else {
             writeln!(
                 w,
@@ -176,22 +346,59 @@ impl Language for Kotlin {
                 self.format_type(&ty.r#type, ty.generic_types.as_slice())
                     .map_err(std::io::Error::other)?
             )?;
+
+            if self.arbitraries {
+                // Defer the arbitrary of the aliased type, which may not be declared yet
+                let value = self
+                    .arbitrary_expr(&ty.r#type, &ty.generic_types)
+                    .map_err(std::io::Error::other)?;
+                let arbitrary = format!("arbitrary {{ {value}.bind() }}");
+                self.write_arbitrary(w, &type_name, &ty.generic_types, &arbitrary)?;
+            }
         }
 
         Ok(())
     }
 
     fn write_const(&mut self, _w: &mut dyn Write, _c: &RustConst) -> std::io::Result<()> {
-        todo!()
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "typeshare doesn't generate constants in Kotlin",
+        ))
+    }
+
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        hash: &str,
+    ) -> std::io::Result<()> {
+        writeln!(w, "const val {name} = \"{hash}\"\n")
     }
 
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
         self.write_comments(w, 0, &rs.comments)?;
-        writeln!(w, "@Serializable")?;
+        self.write_class_annotation(w, 0)?;
+        self.write_parcelize_annotation(w, 0)?;
 
         if rs.fields.is_empty() {
             // If the struct has no fields, we can define it as an static object.
-            writeln!(w, "object {}{}\n", self.prefix, rs.id.renamed)?;
+            // Moshi can't generate adapters for objects, so we use an empty class instead.
+            writeln!(
+                w,
+                "{} {}{}{}\n",
+                match self.serializer {
+                    KotlinSerializer::Kotlinx => "object",
+                    KotlinSerializer::Moshi => "class",
+                },
+                self.prefix,
+                rs.id.renamed,
+                self.parcelable_supertype()
+            )?;
         } else {
             writeln!(
                 w,
@@ -236,15 +443,47 @@ impl Language for Kotlin {
             }
 
             if rs.is_redacted {
-                writeln!(w, ") {{")?;
+                writeln!(w, "){} {{", self.parcelable_supertype())?;
                 writeln!(w, "\toverride fun toString(): String = {:?}", rs.id.renamed)?;
                 writeln!(w, "}}")?;
             } else {
-                writeln!(w, ")")?;
+                writeln!(w, "){}", self.parcelable_supertype())?;
             }
 
             writeln!(w)?;
         }
+
+        if self.arbitraries {
+            let name = format!("{}{}", self.prefix, rs.id.renamed);
+            let arbitrary = if rs.fields.is_empty() {
+                match self.serializer {
+                    KotlinSerializer::Kotlinx => format!("Arb.constant({name})"),
+                    KotlinSerializer::Moshi => format!("Arb.constant({name}())"),
+                }
+            } else {
+                let arguments = rs
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let mut value = match f.type_override(SupportedLanguage::Kotlin) {
+                            Some(type_override) => format!("Arb.bind<{type_override}>()"),
+                            None => self
+                                .arbitrary_expr(&f.ty, &rs.generic_types)
+                                .map_err(std::io::Error::other)?,
+                        };
+                        if f.has_default && !f.ty.is_optional() && self.default_value(f).is_none() {
+                            value = format!("{value}.orNull()");
+                        }
+                        Ok(format!(
+                            "\t\t{} = {value}.bind(),\n",
+                            self.identifier(&f.id.renamed)
+                        ))
+                    })
+                    .collect::<std::io::Result<String>>()?;
+                format!("arbitrary {{\n\t{name}(\n{arguments}\t)\n}}")
+            };
+            self.write_arbitrary(w, &name, &rs.generic_types, &arbitrary)?;
+        }
         Ok(())
     }
 
@@ -255,7 +494,11 @@ impl Language for Kotlin {
         })?;
 
         self.write_comments(w, 0, &e.shared().comments)?;
-        writeln!(w, "@Serializable")?;
+        // Moshi handles enum classes out of the box, and sealed classes through
+        // a `PolymorphicJsonAdapterFactory`
+        if self.serializer == KotlinSerializer::Kotlinx {
+            writeln!(w, "@Serializable")?;
+        }
 
         let generic_parameters = if !e.shared().generic_types.is_empty() {
             format!("<{}>", e.shared().generic_types.join(", "))
@@ -274,12 +517,19 @@ impl Language for Kotlin {
                 )?;
             }
             RustEnum::Algebraic { .. } => {
+                self.write_parcelize_annotation(w, 0)?;
                 write!(
                     w,
-                    "sealed class {}{}{} ",
+                    "sealed {} {}{}{}{} ",
+                    if self.sealed_interfaces {
+                        "interface"
+                    } else {
+                        "class"
+                    },
                     self.prefix,
                     &e.shared().id.renamed,
-                    generic_parameters
+                    generic_parameters,
+                    self.parcelable_supertype()
                 )?;
             }
         }
@@ -288,7 +538,18 @@ impl Language for Kotlin {
 
         self.write_enum_variants(w, e)?;
 
-        writeln!(w, "}}\n")
+        writeln!(w, "}}\n")?;
+
+        if self.arbitraries {
+            let name = format!("{}{}", self.prefix, e.shared().id.renamed);
+            let arbitrary = match e {
+                RustEnum::Unit(..) => format!("Arb.enum<{name}>()"),
+                RustEnum::Algebraic { .. } => self.arbitrary_algebraic_enum(e)?,
+            };
+            self.write_arbitrary(w, &name, &e.shared().generic_types, &arbitrary)?;
+        }
+
+        Ok(())
     }
 
     fn write_imports(
@@ -299,6 +560,9 @@ impl Language for Kotlin {
         for (path, ty) in imports {
             for t in ty {
                 writeln!(w, "import {}.{path}.{t}", self.package)?;
+                if self.arbitraries {
+                    writeln!(w, "import {}.{path}.arb{}{t}", self.package, self.prefix)?;
+                }
             }
         }
         writeln!(w)
@@ -307,6 +571,28 @@ impl Language for Kotlin {
     fn ignored_reference_types(&self) -> Vec<&str> {
         self.type_mappings.keys().map(|s| s.as_str()).collect()
     }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        let mut warnings = lossy_mappings(data, "Kotlin", |ty| match ty {
+            SpecialRustType::ISize => Some("Int"),
+            SpecialRustType::USize => Some("UInt"),
+            _ => None,
+        });
+        warnings.extend(unmapped_json_values(data, "Kotlin", &self.type_mappings));
+        warnings
+    }
+
+    fn type_identifier(&self, id: &Id) -> String {
+        format!("{}{}", self.prefix, id.renamed)
+    }
+
+    fn field_identifier(&self, field: &RustField) -> String {
+        remove_dash_from_identifier(&field.id.renamed)
+    }
+
+    fn variant_identifier(&self, variant: &RustEnumVariant) -> String {
+        variant_name(variant)
+    }
 }
 
 enum Visibility {
@@ -315,12 +601,18 @@ enum Visibility {
 }
 
 impl Kotlin {
+    /// The identifier of a serialized name, escaped with backticks if it's a Kotlin
+    /// keyword or a reserved word.
+    fn identifier(&self, name: &str) -> String {
+        IDENTIFIERS.identifier(name, &self.reserved_words)
+    }
+
     fn write_enum_variants(&mut self, w: &mut dyn Write, e: &RustEnum) -> std::io::Result<()> {
         match e {
             RustEnum::Unit(shared) => {
                 for v in &shared.variants {
                     self.write_comments(w, 1, &v.shared().comments)?;
-                    writeln!(w, "\t@SerialName({:?})", &v.shared().id.renamed)?;
+                    self.write_serial_name(w, 1, &v.shared().id.renamed)?;
                     writeln!(
                         w,
                         "\t{}({:?}),",
@@ -335,27 +627,21 @@ impl Kotlin {
                 ..
             } => {
                 for v in &shared.variants {
-                    let printed_value = format!(r##""{}""##, &v.shared().id.renamed);
                     self.write_comments(w, 1, &v.shared().comments)?;
-                    writeln!(w, "\t@Serializable")?;
-                    writeln!(w, "\t@SerialName({printed_value})")?;
-
-                    let variant_name = {
-                        let mut variant_name = v.shared().id.original.to_pascal_case();
-
-                        if variant_name
-                            .chars()
-                            .next()
-                            .map(|c| c.is_ascii_digit())
-                            .unwrap_or(false)
-                        {
-                            // If the name starts with a digit just add an underscore
-                            // to the front and make it valid
-                            variant_name = format!("_{variant_name}");
+                    match self.serializer {
+                        KotlinSerializer::Kotlinx => {
+                            writeln!(w, "\t@Serializable")?;
+                            self.write_serial_name(w, 1, &v.shared().id.renamed)?;
                         }
+                        // The variant's label is registered with the `PolymorphicJsonAdapterFactory`
+                        KotlinSerializer::Moshi if !matches!(v, RustEnumVariant::Unit(_)) => {
+                            self.write_class_annotation(w, 1)?;
+                        }
+                        KotlinSerializer::Moshi => (),
+                    }
+                    self.write_parcelize_annotation(w, 1)?;
 
-                        variant_name
-                    };
+                    let variant_name = variant_name(v);
 
                     match v {
                         RustEnumVariant::Unit(_) => {
@@ -425,14 +711,16 @@ impl Kotlin {
 
                     writeln!(
                         w,
-                        ": {}{}{}()",
+                        ": {}{}{}{}",
                         self.prefix,
                         e.shared().id.original,
                         if !e.shared().generic_types.is_empty() {
                             format!("<{}>", e.shared().generic_types.join(", "))
                         } else {
                             Default::default()
-                        }
+                        },
+                        // Interfaces have no constructor to call
+                        if self.sealed_interfaces { "" } else { "()" }
                     )?;
                 }
             }
@@ -441,6 +729,142 @@ impl Kotlin {
         Ok(())
     }
 
+    /// Write the Kotest arbitrary of a type, `arb<Type>`. Generic types get a function
+    /// taking the arbitraries of their type parameters.
+    fn write_arbitrary(
+        &self,
+        w: &mut dyn Write,
+        name: &str,
+        generic_types: &[String],
+        arbitrary: &str,
+    ) -> std::io::Result<()> {
+        if generic_types.is_empty() {
+            writeln!(w, "val arb{name}: Arb<{name}> = {arbitrary}\n")
+        } else {
+            let generic_parameters = generic_types.join(", ");
+            let arbitrary_parameters = generic_types
+                .iter()
+                .map(|t| format!("arb{t}: Arb<{t}>"))
+                .join(", ");
+            writeln!(
+                w,
+                "fun <{generic_parameters}> arb{name}({arbitrary_parameters}): Arb<{name}<{generic_parameters}>> = {arbitrary}\n"
+            )
+        }
+    }
+
+    /// The arbitrary of a sealed class, a choice of the arbitraries of its variants.
+    /// Those with values are deferred, since their types may not be declared yet.
+    fn arbitrary_algebraic_enum(&mut self, e: &RustEnum) -> std::io::Result<String> {
+        let shared = e.shared();
+        let name = format!("{}{}", self.prefix, shared.id.original);
+        let variants = shared
+            .variants
+            .iter()
+            .map(|v| {
+                let variant = format!("{name}.{}", variant_name(v));
+                let arbitrary = match v {
+                    RustEnumVariant::Unit(_) => format!("Arb.constant({variant})"),
+                    RustEnumVariant::Tuple { ty, .. } => {
+                        let value = self
+                            .arbitrary_expr(ty, &shared.generic_types)
+                            .map_err(std::io::Error::other)?;
+                        format!("arbitrary {{ {variant}({value}.bind()) }}")
+                    }
+                    RustEnumVariant::AnonymousStruct {
+                        shared: variant_shared,
+                        fields,
+                    } => {
+                        let generics = shared
+                            .generic_types
+                            .iter()
+                            .filter(|g| fields.iter().any(|f| f.ty.contains_type(g)))
+                            .map(|g| format!("arb{g}"))
+                            .collect_vec();
+                        let arguments = if generics.is_empty() {
+                            String::new()
+                        } else {
+                            format!("({})", generics.join(", "))
+                        };
+                        format!(
+                            "arbitrary {{ {variant}(arb{name}{}Inner{arguments}.bind()) }}",
+                            variant_shared.id.original
+                        )
+                    }
+                };
+                Ok(format!("\t{arbitrary},\n"))
+            })
+            .collect::<std::io::Result<String>>()?;
+        Ok(format!("Arb.choice(\n{variants})"))
+    }
+
+    /// The Kotest arbitrary generating values of type `ty`. Types of type mappings are
+    /// generated by reflection, with `Arb.bind`.
+    fn arbitrary_expr(
+        &mut self,
+        ty: &RustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(match ty {
+            RustType::Simple { id } | RustType::Generic { id, .. }
+                if self.type_mappings.contains_key(id.as_str()) =>
+            {
+                format!("Arb.bind<{}>()", self.format_type(ty, generic_types)?)
+            }
+            RustType::Simple { id } if generic_types.iter().any(|g| g == id) => format!("arb{id}"),
+            RustType::Simple { id } => format!("arb{}{id}", self.prefix),
+            RustType::Generic { id, parameters } => {
+                let arbitraries = parameters
+                    .iter()
+                    .map(|p| self.arbitrary_expr(p, generic_types))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("arb{}{id}({})", self.prefix, arbitraries.join(", "))
+            }
+            RustType::Special(special_ty) => match special_ty {
+                SpecialRustType::Vec(inner)
+                | SpecialRustType::Array(inner, _)
+                | SpecialRustType::Slice(inner) => {
+                    format!("Arb.list({})", self.arbitrary_expr(inner, generic_types)?)
+                }
+                SpecialRustType::Option(inner) => {
+                    format!("{}.orNull()", self.arbitrary_expr(inner, generic_types)?)
+                }
+                SpecialRustType::HashMap(key, value) => {
+                    let map = format!(
+                        "Arb.map({}, {})",
+                        self.arbitrary_expr(key, generic_types)?,
+                        self.arbitrary_expr(value, generic_types)?
+                    );
+                    if self.multiplatform {
+                        map
+                    } else {
+                        format!("{map}.map {{ HashMap(it) }}")
+                    }
+                }
+                SpecialRustType::Unit => "Arb.constant(Unit)".into(),
+                SpecialRustType::String | SpecialRustType::Char => "Arb.string()".into(),
+                SpecialRustType::I8 => "Arb.byte()".into(),
+                SpecialRustType::I16 => "Arb.short()".into(),
+                SpecialRustType::ISize | SpecialRustType::I32 => "Arb.int()".into(),
+                SpecialRustType::I54 | SpecialRustType::I64 => "Arb.long()".into(),
+                SpecialRustType::U8 => "Arb.uByte()".into(),
+                SpecialRustType::U16 => "Arb.uShort()".into(),
+                SpecialRustType::USize | SpecialRustType::U32 => "Arb.uInt()".into(),
+                SpecialRustType::U53 | SpecialRustType::U64 => "Arb.uLong()".into(),
+                SpecialRustType::Bool => "Arb.boolean()".into(),
+                // JSON has no NaN or infinities
+                SpecialRustType::F32 => "Arb.numericFloat()".into(),
+                SpecialRustType::F64 => "Arb.numericDouble()".into(),
+                SpecialRustType::DateTime => {
+                    return Err(RustTypeFormatError::UnsupportedSpecialType(
+                        special_ty.to_string(),
+                    ))
+                }
+                SpecialRustType::JsonValue => format!("arb{}{}", self.prefix, special_ty.id()),
+            },
+        })
+    }
+
     fn write_element(
         &mut self,
         w: &mut dyn Write,
@@ -451,7 +875,7 @@ impl Kotlin {
     ) -> std::io::Result<()> {
         self.write_comments(w, 1, &f.comments)?;
         if requires_serial_name {
-            writeln!(w, "\t@SerialName({:?})", &f.id.renamed)?;
+            self.write_serial_name(w, 1, &f.id.renamed)?;
         }
         let ty = match f.type_override(SupportedLanguage::Kotlin) {
             Some(type_override) => type_override.to_owned(),
@@ -464,45 +888,193 @@ impl Kotlin {
             Visibility::Public => write!(
                 w,
                 "\tval {}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                self.identifier(&f.id.renamed),
                 ty,
-                (f.has_default && !f.ty.is_optional())
-                    .then_some("? = null")
-                    .or_else(|| f.ty.is_optional().then_some(" = null"))
-                    .unwrap_or_default()
+                self.default_suffix(f)
             ),
             Visibility::Private => write!(
                 w,
                 "\tprivate val {}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                self.identifier(&f.id.renamed),
                 ty,
-                (f.has_default && !f.ty.is_optional())
-                    .then_some("? = null")
-                    .or_else(|| f.ty.is_optional().then_some(" = null"))
-                    .unwrap_or_default()
+                self.default_suffix(f)
             ),
         }
     }
 
+    /// Write the annotation that makes a class serializable.
+    fn write_class_annotation(&self, w: &mut dyn Write, indent: usize) -> std::io::Result<()> {
+        let annotation = match self.serializer {
+            KotlinSerializer::Kotlinx => "@Serializable",
+            KotlinSerializer::Moshi => "@JsonClass(generateAdapter = true)",
+        };
+        writeln!(w, "{}{annotation}", "\t".repeat(indent))
+    }
+
+    /// Write the `@Parcelize` annotation, if enabled.
+    fn write_parcelize_annotation(&self, w: &mut dyn Write, indent: usize) -> std::io::Result<()> {
+        if self.parcelize {
+            writeln!(w, "{}@Parcelize", "\t".repeat(indent))?;
+        }
+        Ok(())
+    }
+
+    /// The `Parcelable` supertype clause of a parcelized declaration, if enabled.
+    fn parcelable_supertype(&self) -> &'static str {
+        if self.parcelize {
+            " : Parcelable"
+        } else {
+            ""
+        }
+    }
+
+    /// Write the annotation that sets the serialized name of a field or variant.
+    fn write_serial_name(
+        &self,
+        w: &mut dyn Write,
+        indent: usize,
+        name: &str,
+    ) -> std::io::Result<()> {
+        match self.serializer {
+            KotlinSerializer::Kotlinx => {
+                writeln!(w, "{}@SerialName({name:?})", "\t".repeat(indent))
+            }
+            KotlinSerializer::Moshi => {
+                writeln!(w, "{}@Json(name = {name:?})", "\t".repeat(indent))
+            }
+        }
+    }
+
+    /// The suffix of a property declaration that makes it nullable and/or gives it
+    /// a default value.
+    fn default_suffix(&self, f: &RustField) -> String {
+        match self.default_value(f) {
+            Some(value) => format!(" = {value}"),
+            None if f.has_default && !f.ty.is_optional() => "? = null".to_owned(),
+            // Without a default, the property is required even though it's nullable
+            None if f.ty.is_optional()
+                && (f.has_default || self.optional_fields != Some(OptionalFields::Null)) =>
+            {
+                " = null".to_owned()
+            }
+            None => String::new(),
+        }
+    }
+
+    /// The Kotlin default value for a field with `#[serde(default)]`, if
+    /// `serde_default_values` is enabled and the field's type has a known default.
+    fn default_value(&self, f: &RustField) -> Option<&'static str> {
+        if !self.serde_default_values
+            || !f.has_default
+            || f.type_override(SupportedLanguage::Kotlin).is_some()
+        {
+            return None;
+        }
+
+        match &f.ty {
+            RustType::Special(special_ty) => match special_ty {
+                SpecialRustType::Vec(_)
+                | SpecialRustType::Array(_, _)
+                | SpecialRustType::Slice(_) => Some("listOf()"),
+                SpecialRustType::HashMap(_, _) => Some("hashMapOf()"),
+                SpecialRustType::String => Some(r#""""#),
+                SpecialRustType::Bool => Some("false"),
+                SpecialRustType::I8
+                | SpecialRustType::I16
+                | SpecialRustType::ISize
+                | SpecialRustType::I32
+                | SpecialRustType::I54
+                | SpecialRustType::I64 => Some("0"),
+                SpecialRustType::U8
+                | SpecialRustType::U16
+                | SpecialRustType::USize
+                | SpecialRustType::U32
+                | SpecialRustType::U53
+                | SpecialRustType::U64 => Some("0u"),
+                SpecialRustType::F32 => Some("0f"),
+                SpecialRustType::F64 => Some("0.0"),
+                SpecialRustType::Option(_)
+                | SpecialRustType::Unit
+                | SpecialRustType::Char
+                | SpecialRustType::DateTime
+                | SpecialRustType::JsonValue => None,
+            },
+            RustType::Generic { .. } | RustType::Simple { .. } => None,
+        }
+    }
+
     fn write_comment(
         &self,
         w: &mut dyn Write,
         indent: usize,
         comment: &str,
     ) -> std::io::Result<()> {
-        writeln!(w, "{}/// {}", "\t".repeat(indent), comment)?;
+        let comment = self.link_kdoc_references(comment).replace("*/", "*&#47;");
+        if comment.is_empty() {
+            writeln!(w, "{} *", "\t".repeat(indent))?;
+        } else {
+            writeln!(w, "{} * {}", "\t".repeat(indent), comment)?;
+        }
         Ok(())
     }
 
+    /// Writes doc comments as a KDoc block.
     fn write_comments(
         &self,
         w: &mut dyn Write,
         indent: usize,
         comments: &[String],
     ) -> std::io::Result<()> {
+        if comments.is_empty() {
+            return Ok(());
+        }
+        writeln!(w, "{}/**", "\t".repeat(indent))?;
         comments
             .iter()
-            .try_for_each(|comment| self.write_comment(w, indent, comment))
+            .try_for_each(|comment| self.write_comment(w, indent, comment))?;
+        writeln!(w, "{} */", "\t".repeat(indent))
+    }
+
+    /// Turns references to generated types in a comment, either as an intra-doc link
+    /// (``[`Type`]``, `[Type]`) or in backticks, into KDoc links (`[Type]`).
+    fn link_kdoc_references(&self, comment: &str) -> String {
+        let link = |reference: &str| {
+            // Intra-doc links may use a path, like `crate::module::Type`
+            let name = reference.rsplit("::").next().unwrap_or(reference);
+            self.kdoc_links.get(name).map(|name| format!("[{name}]"))
+        };
+
+        let mut linked = String::with_capacity(comment.len());
+        let mut rest = comment;
+        while let Some(start) = rest.find(['[', '`']) {
+            linked.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let close = if rest.starts_with('[') { ']' } else { '`' };
+            let Some(end) = rest[1..].find(close).map(|end| end + 1) else {
+                break;
+            };
+            let reference = rest[1..end].trim_matches('`');
+            let is_markdown_link = close == ']' && rest[end + 1..].starts_with(['(', '[']);
+
+            match link(reference).filter(|_| !is_markdown_link) {
+                Some(kdoc_link) => {
+                    linked.push_str(&kdoc_link);
+                    rest = &rest[end + 1..];
+                }
+                // Code spans are copied whole, so their closing backtick isn't mistaken for an opening one
+                None if close == '`' => {
+                    linked.push_str(&rest[..=end]);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    linked.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        linked.push_str(rest);
+        linked
     }
 
     fn is_inline(&self, decorators: &HashMap<DecoratorKind, BTreeSet<String>>) -> bool {
@@ -512,3 +1084,21 @@ impl Kotlin {
         }
     }
 }
+
+/// The name of the class of a variant of a sealed class.
+fn variant_name(v: &RustEnumVariant) -> String {
+    let variant_name = v.shared().id.original.to_pascal_case();
+
+    if variant_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+    {
+        // If the name starts with a digit just add an underscore
+        // to the front and make it valid
+        return format!("_{variant_name}");
+    }
+
+    variant_name
+}
@@ -1,16 +1,19 @@
 use crate::{
     error::{GenerationError, ParseError},
+    lint::Warning,
     parser::ParsedData,
     rust_types::{
-        Id, RustConst, RustEnum, RustEnumVariant, RustItem, RustStruct, RustType, RustTypeAlias,
-        RustTypeFormatError, SpecialRustType,
+        Id, RustConst, RustEnum, RustEnumVariant, RustField, RustItem, RustStruct, RustType,
+        RustTypeAlias, RustTypeFormatError, SpecialRustType,
     },
+    schema_hash::schema_hash,
     topsort::topsort,
     visitors::ImportedType,
 };
 use itertools::Itertools;
 use log::warn;
 use proc_macro2::Ident;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Display,
@@ -20,23 +23,48 @@ use std::{
 };
 
 mod go;
+mod header;
+mod identifier;
 mod kotlin;
+mod output;
 mod python;
 mod reasonml;
 mod scala;
+mod style;
 mod swift;
+#[cfg(feature = "template")]
+mod template;
 mod typescript;
 
-pub use go::Go;
-pub use kotlin::Kotlin;
+pub use go::{Go, GoDateTimeFormat, GoOptionStyle};
+pub use header::FileHeader;
+pub use identifier::{Escape, IdentifierPolicy};
+pub use kotlin::{Kotlin, KotlinSerializer};
+use output::{written, written_with_capacity};
+pub use output::{FileOutput, ItemKind, ItemOutput};
 pub use python::Python;
 pub use reasonml::ReasonML;
 pub use scala::Scala;
-pub use swift::GenericConstraints;
+pub use style::{CodeStyle, Indent};
 pub use swift::Swift;
-pub use typescript::TypeScript;
+pub use swift::{CodingKeyStrategy, GenericConstraints};
+#[cfg(feature = "template")]
+pub use template::{Template, TemplateSources};
+pub use typescript::{TypeScript, TypeScriptFieldNaming, TypeScriptModuleFormat};
+
+/// How fields of `Option` types are represented in every language, so that clients agree
+/// on whether a field without a value is left out or `null`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionalFields {
+    /// The field is left out, like with `#[serde(skip_serializing_if = "Option::is_none")]`.
+    Absent,
+    /// The field is always there, and `null` without a value.
+    Null,
+    /// The field is left out or `null`.
+    Both,
+}
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize)]
 /// A crate name.
 pub struct CrateName(String);
 
@@ -73,6 +101,50 @@ impl CrateName {
             .map(file_name_to_crate_name)
             .map(CrateName::from)
     }
+
+    /// Extract the module name from a given path. This is the crate name
+    /// followed by the path of the module within the crate, separated by `.`
+    /// (`my_crate.some_module.nested`).
+    pub fn find_module_name(path: &Path) -> Option<Self> {
+        let crate_name = Self::find_crate_name(path)?;
+        let mut modules = path
+            .iter()
+            .rev()
+            .take_while(|p| *p != "src")
+            .map(|p| p.to_str())
+            .collect::<Option<Vec<_>>>()?;
+        modules.reverse();
+
+        if let Some(file_name) = modules.pop() {
+            let module = file_name.strip_suffix(".rs").unwrap_or(file_name);
+            if !matches!(module, "lib" | "main" | "mod") {
+                modules.push(module);
+            }
+        }
+
+        Some(
+            std::iter::once(crate_name.as_str())
+                .chain(modules)
+                .join(".")
+                .into(),
+        )
+    }
+
+    /// The name of the crate this crate or module name belongs to.
+    pub fn crate_name(&self) -> &str {
+        self.0.split('.').next().unwrap_or_default()
+    }
+
+    /// The name of the file of this crate (or module) in languages where a `.`
+    /// can't be part of a module name, like `my_crate_models` for `my_crate.models`.
+    pub fn file_stem(&self) -> String {
+        self.0.replace('.', "_")
+    }
+
+    /// The name of the innermost module of this crate or module name.
+    pub fn module_name(&self) -> &str {
+        self.0.rsplit('.').next().unwrap_or_default()
+    }
 }
 
 impl From<&str> for CrateName {
@@ -97,7 +169,8 @@ pub type ScopedCrateTypes<'a> = BTreeMap<SortedCrateNames<'a>, SortedTypeNames<'
 
 /// All supported programming languages.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SupportedLanguage {
     Go,
     Kotlin,
@@ -162,47 +235,49 @@ impl TryFrom<&Ident> for SupportedLanguage {
 pub trait Language {
     /// Given `data`, generate type-code for this language and write it out to `writable`.
     /// Returns whether or not writing was successful.
+    ///
+    /// The file [emitted](Language::emit) by the language is rendered in its code
+    /// style, and post-processed, in memory, and written out with a single write.
     fn generate_types(
         &mut self,
         writable: &mut dyn Write,
         all_types: &CrateTypes,
         data: ParsedData,
     ) -> std::io::Result<()> {
-        self.begin_file(writable, &data)?;
-
-        if data.multi_file {
-            self.write_imports(writable, used_imports(&data, all_types))?;
-        }
+        let output = self.emit(all_types, data)?;
+        let style = self.code_style().cloned().unwrap_or_default();
+        let indent_width = self.indent_width();
+        let source = written_with_capacity(output.len(), |w| output.render(w))?;
+        let mut source = style.restyled(source, indent_width);
+        self.post_process(&mut source);
+        writable.write_all(source.as_bytes())
+    }
 
-        let ParsedData {
-            structs,
-            enums,
-            aliases,
-            consts,
-            ..
-        } = data;
-
-        let mut items = Vec::from_iter(
-            aliases
-                .into_iter()
-                .map(RustItem::Alias)
-                .chain(structs.into_iter().map(RustItem::Struct))
-                .chain(enums.into_iter().map(RustItem::Enum))
-                .chain(consts.into_iter().map(RustItem::Const)),
-        );
+    /// Generate the types of `data` by the parts of the file: what [`begin_file`],
+    /// [`write_imports`] and [`end_file`] write, and every item, in the order of their
+    /// dependencies.
+    ///
+    /// [`begin_file`]: Language::begin_file
+    /// [`write_imports`]: Language::write_imports
+    /// [`end_file`]: Language::end_file
+    fn emit(&mut self, all_types: &CrateTypes, data: ParsedData) -> std::io::Result<FileOutput> {
+        emit_items(self, all_types, data)
+    }
 
-        topsort(&mut items);
+    /// Rewrite the generated source of a file before it's written, like to add imports
+    /// or strip sections. It's left as it is by default.
+    fn post_process(&mut self, _output: &mut String) {}
 
-        for thing in &items {
-            match thing {
-                RustItem::Enum(e) => self.write_enum(writable, e)?,
-                RustItem::Struct(s) => self.write_struct(writable, s)?,
-                RustItem::Alias(a) => self.write_type_alias(writable, a)?,
-                RustItem::Const(c) => self.write_const(writable, c)?,
-            }
-        }
+    /// How the generated code is indented and wrapped, instead of the default of the
+    /// language.
+    fn code_style(&self) -> Option<&CodeStyle> {
+        None
+    }
 
-        self.end_file(writable)
+    /// The number of spaces of a level of indentation the language writes, when it's
+    /// not a tab.
+    fn indent_width(&self) -> usize {
+        4
     }
 
     /// Get the type mapping for this language `(Rust type name -> lang type name)`
@@ -223,14 +298,12 @@ pub trait Language {
         }
     }
 
-    // We need to pass in an &String for type mapping
     /// Format a simple type with no generic parameters.
     /// Note that we still need to take a list of generic types in case the implementors
     /// need to differentiate between a user-defined type and a generic type (for example: Swift)
-    #[allow(clippy::ptr_arg)]
     fn format_simple_type(
         &mut self,
-        base: &String,
+        base: &str,
         _generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         Ok(if let Some(mapped) = self.type_map().get(base) {
@@ -240,13 +313,11 @@ pub trait Language {
         })
     }
 
-    // We need to pass in an &String for type mapping
     /// Format a generic type that takes in generic arguments, which
     /// may be recursive.
-    #[allow(clippy::ptr_arg)]
     fn format_generic_type(
         &mut self,
-        base: &String,
+        base: &str,
         parameters: &[RustType],
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
@@ -318,6 +389,23 @@ pub trait Language {
         Ok(())
     }
 
+    /// Whether to write a constant with the [schema hash](crate::schema_hash) of every
+    /// type after it. They aren't written by default.
+    fn schema_hashes(&self) -> bool {
+        false
+    }
+
+    /// Write the constant with the schema hash of a type, like
+    /// `export const UserSchemaHash = "…";`, if the language supports it.
+    fn write_schema_hash(
+        &mut self,
+        _w: &mut dyn Write,
+        _name: &str,
+        _hash: &str,
+    ) -> std::io::Result<()> {
+        Ok(())
+    }
+
     /// Write a struct by converting it
     /// Example of a struct:
     /// ```ignore
@@ -412,8 +500,11 @@ pub trait Language {
                         &shared.id.original,
                         &e.shared().id.original,
                     )],
+                    deprecated: shared.deprecated.clone(),
                     decorators: e.shared().decorators.clone(),
                     is_redacted: e.shared().is_redacted,
+                    deny_unknown_fields: false,
+                    location: e.shared().location.as_ref().map(|l| l.at(shared.span)),
                 },
             )?;
         }
@@ -426,6 +517,38 @@ pub trait Language {
         Vec::new()
     }
 
+    /// Warnings about the typeshared items of the parsed data which this language
+    /// can't represent exactly, like items generated as a fallback or lossy types.
+    fn warnings(&self, _data: &ParsedData) -> Vec<Warning> {
+        Vec::new()
+    }
+
+    /// The identifier the language generates for a typeshared item, to detect
+    /// [collisions](crate::collision) of items generated with the same identifier.
+    /// It's the name of the item, renamed with serde, by default.
+    fn type_identifier(&self, id: &Id) -> String {
+        id.renamed.clone()
+    }
+
+    /// The identifier the language generates for a field of a struct or an anonymous
+    /// struct variant. It's the serde name of the field by default.
+    fn field_identifier(&self, field: &RustField) -> String {
+        field.id.renamed.clone()
+    }
+
+    /// The identifier the language generates for a variant of an enum. It's the serde
+    /// name of the variant by default.
+    fn variant_identifier(&self, variant: &RustEnumVariant) -> String {
+        variant.shared().id.renamed.clone()
+    }
+
+    /// Whether the structs of the language contain the structs their fields reference,
+    /// by value or in an optional, so structs can't contain each other in a cycle, like
+    /// Swift's. The cycles are reported with the `recursive_struct` lint.
+    fn contains_referenced_structs(&self) -> bool {
+        false
+    }
+
     /// Any other final steps after modules have been generated. For example creating a new
     /// module with special types.
     fn post_generation(&self, _output_folder: &str) -> Result<(), GenerationError> {
@@ -433,6 +556,118 @@ pub trait Language {
     }
 }
 
+/// The parts of the file of the parsed data, with its items in the order of their
+/// dependencies.
+pub(crate) fn emit_items(
+    lang: &mut (impl Language + ?Sized),
+    all_types: &CrateTypes,
+    data: ParsedData,
+) -> std::io::Result<FileOutput> {
+    emit_items_with(lang, all_types, data, |lang, thing| {
+        ItemOutput::new(thing, |w| {
+            match thing {
+                RustItem::Enum(e) => lang.write_enum(w, e),
+                RustItem::Struct(s) => lang.write_struct(w, s),
+                RustItem::Alias(a) => lang.write_type_alias(w, a),
+                RustItem::Const(c) => lang.write_const(w, c),
+            }?;
+            write_item_schema_hash(lang, w, thing)
+        })
+    })
+}
+
+/// The parts of the file of the parsed data like [`emit_items`], with every item
+/// emitted by `emit_item`.
+pub(crate) fn emit_items_with<L: Language + ?Sized>(
+    lang: &mut L,
+    all_types: &CrateTypes,
+    data: ParsedData,
+    mut emit_item: impl FnMut(&mut L, &RustItem) -> std::io::Result<ItemOutput>,
+) -> std::io::Result<FileOutput> {
+    let header = written(|w| lang.begin_file(w, &data))?;
+    let imports = if data.multi_file {
+        written(|w| lang.write_imports(w, used_imports(&data, all_types)))?
+    } else {
+        String::new()
+    };
+
+    let items = sorted_items(data)
+        .iter()
+        .map(|thing| emit_item(lang, thing).map_err(item_context(item_name(thing))))
+        .collect::<std::io::Result<_>>()?;
+
+    Ok(FileOutput {
+        header,
+        imports,
+        definitions: String::new(),
+        items,
+        footer: written(|w| lang.end_file(w))?,
+    })
+}
+
+/// Write the constant with the schema hash of the item after it, if the language writes
+/// them and the item is a type.
+pub(crate) fn write_item_schema_hash(
+    lang: &mut (impl Language + ?Sized),
+    w: &mut dyn Write,
+    item: &RustItem,
+) -> std::io::Result<()> {
+    if !lang.schema_hashes() {
+        return Ok(());
+    }
+    let id = match item {
+        RustItem::Struct(s) => &s.id,
+        RustItem::Enum(e) => &e.shared().id,
+        RustItem::Alias(a) => &a.id,
+        RustItem::Const(_) => return Ok(()),
+    };
+    match schema_hash(item) {
+        Some(hash) => {
+            let name = format!("{}SchemaHash", lang.type_identifier(id));
+            lang.write_schema_hash(w, &name, &hash)
+        }
+        None => Ok(()),
+    }
+}
+
+/// The items of the parsed data, in the order of their dependencies.
+pub(crate) fn sorted_items(data: ParsedData) -> Vec<RustItem> {
+    let ParsedData {
+        structs,
+        enums,
+        aliases,
+        consts,
+        ..
+    } = data;
+
+    let mut items = Vec::from_iter(
+        aliases
+            .into_iter()
+            .map(RustItem::Alias)
+            .chain(structs.into_iter().map(RustItem::Struct))
+            .chain(enums.into_iter().map(RustItem::Enum))
+            .chain(consts.into_iter().map(RustItem::Const)),
+    );
+    topsort(&mut items);
+    items
+}
+
+/// Name the item being generated, by its Rust name, in an error like a type which
+/// can't be formatted.
+fn item_context(name: &str) -> impl FnOnce(std::io::Error) -> std::io::Error + '_ {
+    move |err| std::io::Error::new(err.kind(), format!("Failed to generate {name}: {err}"))
+}
+
+/// The Rust name of an item.
+fn item_name(item: &RustItem) -> &str {
+    match item {
+        RustItem::Struct(s) => &s.id.original,
+        RustItem::Enum(e) => &e.shared().id.original,
+        RustItem::Alias(a) => &a.id.original,
+        RustItem::Const(c) => &c.id.original,
+    }
+}
+
 /// Lookup any refeferences to other typeshared types in order to build
 /// a list of imports for the generated module.
 fn used_imports<'a, 'b: 'a>(
@@ -454,7 +689,9 @@ fn used_imports<'a, 'b: 'a>(
             })
             .next()
         {
-            warn!("Warning: Using {crate_name} as module for {ty} which is not in referenced crate {}", referenced_import.base_crate);
+            if crate_name.crate_name() != referenced_import.base_crate.crate_name() {
+                warn!("Warning: Using {crate_name} as module for {ty} which is not in referenced crate {}", referenced_import.base_crate);
+            }
             used.entry(crate_name)
                 .and_modify(|v| {
                     v.insert(ty.as_str());
@@ -465,13 +702,23 @@ fn used_imports<'a, 'b: 'a>(
         }
     };
 
-    for referenced_import in data
+    let (same_module_imports, other_imports): (Vec<_>, Vec<_>) = data
         .import_types
         .iter()
-        // Skip over imports that reference the current crate. They
-        // are all collapsed into one module per crate.
-        .filter(|imp| imp.base_crate != data.crate_name)
-    {
+        .partition(|imp| imp.base_crate == data.crate_name);
+
+    // When generating a file per Rust module, imports referencing the current
+    // crate may refer to types from other modules of that crate.
+    let module_types = all_types.get(&data.crate_name);
+    for referenced_import in same_module_imports.into_iter().filter(|imp| {
+        imp.type_name != "*" && !module_types.is_some_and(|types| types.contains(&imp.type_name))
+    }) {
+        fallback(referenced_import, &mut used_imports);
+    }
+
+    // Skip over imports that reference the current crate. They
+    // are all collapsed into one module per crate.
+    for referenced_import in other_imports {
         // Look up the types for the referenced imported crate.
         if let Some(type_names) = all_types.get(&referenced_import.base_crate) {
             if referenced_import.type_name == "*" {
@@ -510,4 +757,36 @@ mod test {
         let path = Path::new("/some/path/to/projects/core/foundation/op-proxy/src/android.rs");
         assert_eq!(Some("op_proxy".into()), CrateName::find_crate_name(path));
     }
+
+    #[test]
+    fn test_module_name() {
+        let module_name = |path| CrateName::find_module_name(Path::new(path));
+
+        assert_eq!(
+            Some("op_proxy".into()),
+            module_name("/projects/op-proxy/src/lib.rs")
+        );
+        assert_eq!(
+            Some("op_proxy.android".into()),
+            module_name("/projects/op-proxy/src/android.rs")
+        );
+        assert_eq!(
+            Some("op_proxy.android.sync".into()),
+            module_name("/projects/op-proxy/src/android/sync/mod.rs")
+        );
+        assert_eq!(
+            Some("op_proxy.android.sync".into()),
+            module_name("/projects/op-proxy/src/android/sync.rs")
+        );
+        assert_eq!(None, module_name("/projects/op-proxy/android.rs"));
+    }
+
+    #[test]
+    fn test_module_crate_name() {
+        let module: CrateName = "op_proxy.android.sync".into();
+        assert_eq!("op_proxy", module.crate_name());
+
+        let crate_name: CrateName = "op_proxy".into();
+        assert_eq!("op_proxy", crate_name.crate_name());
+    }
 }
@@ -0,0 +1,214 @@
+//! The generated source of a file by its parts, which languages emit and the core
+//! renders, so the parts can be rearranged, like sorted or split into files, before
+//! they're written.
+use crate::rust_types::{RustEnum, RustItem};
+use std::io::{self, Write};
+
+/// The generated source of a file, by its parts, in the order they're rendered in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileOutput {
+    /// What the file starts with, like its header or package declaration.
+    pub header: String,
+    /// The imports of the file.
+    pub imports: String,
+    /// The definitions the items depend on, which the language writes itself, like
+    /// helper types.
+    pub definitions: String,
+    /// The generated items, in the order of their dependencies.
+    pub items: Vec<ItemOutput>,
+    /// What the file ends with.
+    pub footer: String,
+}
+
+/// The generated source of a typeshared item, by its parts, in the order they're
+/// rendered in. Languages which don't emit the parts on their own generate the whole
+/// item into its body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemOutput {
+    /// The Rust name of the item.
+    pub name: String,
+    /// What kind of item it is.
+    pub kind: ItemKind,
+    /// The comments of the Rust item.
+    pub comments: Vec<String>,
+    /// The doc comment generated from the comments of the item.
+    pub docs: String,
+    /// The generated declaration of the item, including the types generated for it,
+    /// like those of the anonymous structs of an enum.
+    pub body: String,
+    /// What's generated for the item after its declaration, like its codecs or the
+    /// constant with its schema hash.
+    pub companions: String,
+}
+
+/// The kinds of typeshared items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    /// A struct.
+    Struct,
+    /// An enum.
+    Enum,
+    /// A type alias.
+    Alias,
+    /// A constant.
+    Const,
+}
+
+impl ItemOutput {
+    /// The generated source of the item, all of it from what `write` writes.
+    pub(crate) fn new(
+        item: &RustItem,
+        write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<Self> {
+        let body = written_with_capacity(estimated_len(item), write)?;
+        Ok(Self::from_parts(item, String::new(), body, String::new()))
+    }
+
+    /// The generated source of the item, from its parts.
+    pub(crate) fn from_parts(
+        item: &RustItem,
+        docs: String,
+        body: String,
+        companions: String,
+    ) -> Self {
+        let (name, kind, comments) = match item {
+            RustItem::Struct(s) => (&s.id, ItemKind::Struct, s.comments.clone()),
+            RustItem::Enum(e) => (&e.shared().id, ItemKind::Enum, e.shared().comments.clone()),
+            RustItem::Alias(a) => (&a.id, ItemKind::Alias, a.comments.clone()),
+            RustItem::Const(c) => (&c.id, ItemKind::Const, Vec::new()),
+        };
+        Self {
+            name: name.original.clone(),
+            kind,
+            comments,
+            docs,
+            body,
+            companions,
+        }
+    }
+
+    /// Write the parts of the item in order.
+    pub fn render(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(self.docs.as_bytes())?;
+        w.write_all(self.body.as_bytes())?;
+        w.write_all(self.companions.as_bytes())
+    }
+
+    /// The rendered item.
+    pub fn source(&self) -> String {
+        [&self.docs, &self.body, &self.companions]
+            .into_iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The length of the rendered item, in bytes.
+    pub fn len(&self) -> usize {
+        self.docs.len() + self.body.len() + self.companions.len()
+    }
+
+    /// Whether the rendered item is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FileOutput {
+    /// Write the parts of the file in order.
+    pub fn render(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(self.header.as_bytes())?;
+        w.write_all(self.imports.as_bytes())?;
+        w.write_all(self.definitions.as_bytes())?;
+        for item in &self.items {
+            item.render(w)?;
+        }
+        w.write_all(self.footer.as_bytes())
+    }
+
+    /// The length of the rendered file, in bytes.
+    pub fn len(&self) -> usize {
+        self.header.len()
+            + self.imports.len()
+            + self.definitions.len()
+            + self.items.iter().map(ItemOutput::len).sum::<usize>()
+            + self.footer.len()
+    }
+
+    /// Whether the rendered file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// What `write` writes, as a string.
+pub(crate) fn written(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> io::Result<String> {
+    written_with_capacity(0, write)
+}
+
+/// What `write` writes, as a string, into a buffer of `capacity` bytes to begin with,
+/// so that writing what's expected doesn't reallocate it.
+pub(crate) fn written_with_capacity(
+    capacity: usize,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<String> {
+    let mut output = Vec::with_capacity(capacity);
+    write(&mut output)?;
+    String::from_utf8(output).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The bytes of the declaration of an item the buffers of generated items start with.
+/// Most items are a declaration and a line per field or variant.
+const DECLARATION_LEN: usize = 128;
+
+/// The bytes of every field or variant the buffers of generated items start with.
+const MEMBER_LEN: usize = 64;
+
+/// The expected length of the generated source of an item.
+fn estimated_len(item: &RustItem) -> usize {
+    let members = match item {
+        RustItem::Struct(s) => s.fields.len(),
+        RustItem::Enum(RustEnum::Unit(shared)) => shared.variants.len(),
+        // The variants of algebraic enums are encoded and decoded too
+        RustItem::Enum(RustEnum::Algebraic { shared, .. }) => 3 * shared.variants.len(),
+        RustItem::Alias(_) | RustItem::Const(_) => 0,
+    };
+    DECLARATION_LEN + members * MEMBER_LEN
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileOutput, ItemKind, ItemOutput};
+
+    #[test]
+    fn renders_the_parts_in_order() {
+        let item = |name: &str| ItemOutput {
+            name: name.to_owned(),
+            kind: ItemKind::Struct,
+            comments: vec![format!("A {name}")],
+            docs: format!("// A {name}\n"),
+            body: format!("struct {name}\n"),
+            companions: format!("func decode{name}\n"),
+        };
+        let output = FileOutput {
+            header: "// Generated\n".to_owned(),
+            imports: "import base\n".to_owned(),
+            definitions: "type Option\n".to_owned(),
+            items: vec![item("User"), item("Group")],
+            footer: "// End\n".to_owned(),
+        };
+        let mut rendered = Vec::new();
+        output.render(&mut rendered).unwrap();
+        assert_eq!(output.len(), rendered.len());
+        assert_eq!(
+            String::from_utf8(rendered).unwrap(),
+            "// Generated\nimport base\ntype Option\n\
+             // A User\nstruct User\nfunc decodeUser\n\
+             // A Group\nstruct Group\nfunc decodeGroup\n\
+             // End\n"
+        );
+        assert_eq!(
+            output.items[0].source(),
+            "// A User\nstruct User\nfunc decodeUser\n"
+        );
+    }
+}
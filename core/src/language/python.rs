@@ -1,6 +1,6 @@
+use crate::lint::{unmapped_json_values, Warning};
 use crate::parser::ParsedData;
 use crate::rust_types::{RustEnumShared, RustItem, RustType, RustTypeFormatError, SpecialRustType};
-use crate::topsort::topsort;
 use crate::RenameExt;
 use crate::{
     language::Language,
@@ -10,10 +10,12 @@ use crate::{
 };
 use std::collections::HashSet;
 use std::hash::Hash;
-use std::sync::OnceLock;
 use std::{collections::HashMap, io::Write};
 
-use super::CrateTypes;
+use super::{
+    output::written, sorted_items, CodeStyle, CrateTypes, Escape, FileHeader, FileOutput,
+    IdentifierPolicy, ItemOutput, OptionalFields,
+};
 
 use convert_case::{Case, Casing};
 use itertools::Itertools;
@@ -30,8 +32,8 @@ fn collect_generics_for_variant(variant_type: &RustType, generics: &[String]) ->
     let mut all = vec![];
     match variant_type {
         RustType::Generic { id, parameters } => {
-            if generics.contains(id) {
-                all.push(id.clone())
+            if generics.iter().any(|generic| generic == id) {
+                all.push(id.to_string())
             }
             // Recurse into the params for the case of `Foo(HashMap<K, V>)`
             for param in parameters {
@@ -39,8 +41,8 @@ fn collect_generics_for_variant(variant_type: &RustType, generics: &[String]) ->
             }
         }
         RustType::Simple { id } => {
-            if generics.contains(id) {
-                all.push(id.clone())
+            if generics.iter().any(|generic| generic == id) {
+                all.push(id.to_string())
             }
         }
         RustType::Special(special) => match &special {
@@ -92,6 +94,19 @@ pub struct Python {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `UserSchemaHash: str = "…"`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// Identifiers which get a trailing underscore like Python keywords, besides the
+    /// keywords.
+    pub reserved_words: HashSet<String>,
+    /// How fields of `Option` types are represented, instead of the default of the
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
     /// Carries the unique set of types for custom json translation
     pub types_for_custom_json_translation: HashSet<String>,
 }
@@ -101,68 +116,71 @@ impl Language for Python {
         &self.type_mappings
     }
 
-    fn generate_types(
-        &mut self,
-        w: &mut dyn Write,
-        _imports: &CrateTypes,
-        data: ParsedData,
-    ) -> std::io::Result<()> {
-        self.begin_file(w, &data)?;
-
-        let ParsedData {
-            structs,
-            enums,
-            aliases,
-            consts,
-            ..
-        } = data;
-
-        let mut items = aliases
-            .into_iter()
-            .map(RustItem::Alias)
-            .chain(structs.into_iter().map(RustItem::Struct))
-            .chain(enums.into_iter().map(RustItem::Enum))
-            .chain(consts.into_iter().map(RustItem::Const))
-            .collect::<Vec<_>>();
-
-        topsort(&mut items);
-
-        let mut body: Vec<u8> = Vec::new();
-        for thing in items {
-            match thing {
-                RustItem::Enum(e) => self.write_enum(&mut body, &e)?,
-                RustItem::Struct(rs) => self.write_struct(&mut body, &rs)?,
-                RustItem::Alias(t) => self.write_type_alias(&mut body, &t)?,
-                RustItem::Const(c) => self.write_const(&mut body, &c)?,
-            };
-        }
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
 
-        self.write_all_imports(w)?;
+    fn emit(&mut self, imports: &CrateTypes, data: ParsedData) -> std::io::Result<FileOutput> {
+        self.imports.clear();
+        self.type_variables.clear();
 
-        self.types_for_custom_json_translation
-            .iter()
-            .sorted()
-            .filter_map(|py_type| json_translation_for_type(py_type))
-            .map(|custom_translation_functions| {
-                format!(
-                    r#"{}
+        let header = written(|w| self.begin_file(w, &data))?;
+        let mut file_imports = if data.multi_file {
+            written(|w| self.write_imports(w, super::used_imports(&data, imports)))?
+        } else {
+            String::new()
+        };
 
-{}"#,
-                    custom_translation_functions.serialization_content,
-                    custom_translation_functions.deserialization_content
-                )
+        let items = sorted_items(data)
+            .iter()
+            .map(|thing| {
+                ItemOutput::new(thing, |w| {
+                    match thing {
+                        RustItem::Enum(e) => self.write_enum(w, e),
+                        RustItem::Struct(rs) => self.write_struct(w, rs),
+                        RustItem::Alias(t) => self.write_type_alias(w, t),
+                        RustItem::Const(c) => self.write_const(w, c),
+                    }?;
+                    super::write_item_schema_hash(self, w, thing)
+                })
+                .map_err(super::item_context(super::item_name(thing)))
             })
-            .try_for_each(|custom_translation_function| -> std::io::Result<()> {
-                writeln!(w, "{custom_translation_function}")?;
-                writeln!(w)
-            })?;
+            .collect::<std::io::Result<_>>()?;
 
-        w.write_all(&body)
+        // The imports and JSON translations are known once the items are generated.
+        file_imports.push_str(&written(|w| self.write_all_imports(w))?);
+        let definitions = written(|w| {
+            self.types_for_custom_json_translation
+                .iter()
+                .sorted()
+                .filter_map(|py_type| json_translation_for_type(py_type))
+                .map(|custom_translation_functions| {
+                    format!(
+                        r#"{}
+
+{}"#,
+                        custom_translation_functions.serialization_content,
+                        custom_translation_functions.deserialization_content
+                    )
+                })
+                .try_for_each(|custom_translation_function| -> std::io::Result<()> {
+                    writeln!(w, "{custom_translation_function}")?;
+                    writeln!(w)
+                })
+        })?;
+
+        Ok(FileOutput {
+            header,
+            imports: file_imports,
+            definitions,
+            items,
+            footer: String::new(),
+        })
     }
 
     fn format_generic_type(
         &mut self,
-        base: &String,
+        base: &str,
         parameters: &[RustType],
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
@@ -189,7 +207,7 @@ impl Language for Python {
 
     fn format_simple_type(
         &mut self,
-        base: &String,
+        base: &str,
         _generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         self.add_imports(base);
@@ -232,8 +250,10 @@ impl Language for Python {
                 Ok(format!(
                     "Dict[{}, {}]",
                     match rtype1.as_ref() {
-                        RustType::Simple { id } if generic_types.contains(id) => {
-                            return Err(RustTypeFormatError::GenericKeyForbiddenInTS(id.clone()));
+                        RustType::Simple { id } if generic_types.iter().any(|g| g == id) => {
+                            return Err(RustTypeFormatError::GenericKeyForbiddenInTS(
+                                id.to_string(),
+                            ));
                         }
                         _ => self.format_type(rtype1, generic_types)?,
                     },
@@ -245,6 +265,7 @@ impl Language for Python {
                 Ok("datetime".into())
             }
             SpecialRustType::Unit => Ok("None".into()),
+            SpecialRustType::JsonValue => self.format_simple_type(special_ty.id(), generic_types),
             SpecialRustType::String | SpecialRustType::Char => Ok("str".into()),
             SpecialRustType::I8
             | SpecialRustType::U8
@@ -263,8 +284,10 @@ impl Language for Python {
         }
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> std::io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> std::io::Result<()> {
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "\"\"\"", " ", "\"\"\"")?;
+        } else if !self.no_version_header {
             writeln!(w, "\"\"\"")?;
             writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
             writeln!(w, "\"\"\"")?;
@@ -311,6 +334,19 @@ impl Language for Python {
         }
     }
 
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        hash: &str,
+    ) -> std::io::Result<()> {
+        writeln!(w, "{name}: str = \"{hash}\"\n")
+    }
+
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
         self.add_import("pydantic".to_string(), "BaseModel".to_string());
         {
@@ -397,14 +433,28 @@ impl Language for Python {
         Ok(())
     }
 
+    /// Python imports are written together, after the body is generated, so this
+    /// only records the modules of the types to import.
     fn write_imports(
         &mut self,
         _writer: &mut dyn Write,
-        _imports: super::ScopedCrateTypes<'_>,
+        imports: super::ScopedCrateTypes<'_>,
     ) -> std::io::Result<()> {
-        // TODO: to implement when adding suport for outputting to multiple files.
+        for (crate_name, types) in imports {
+            for ty in types {
+                self.add_import(format!(".{}", crate_name.file_stem()), ty.to_string());
+            }
+        }
         Ok(())
     }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        unmapped_json_values(data, "Python", &self.type_mappings)
+    }
+
+    fn field_identifier(&self, field: &RustField) -> String {
+        python_property_aware_rename(&field.id.original, &self.reserved_words)
+    }
 }
 
 impl Python {
@@ -451,7 +501,8 @@ impl Python {
         let python_type = self
             .format_type(&field.ty, generic_types)
             .map_err(std::io::Error::other)?;
-        let python_field_name = python_property_aware_rename(&field.id.original);
+        let python_field_name =
+            python_property_aware_rename(&field.id.original, &self.reserved_words);
         let is_aliased = python_field_name != field.id.renamed;
         let custom_translations = json_translation_for_type(&python_type);
         // Adds all the required imports needed based off whether its optional ,aliased, or needs a byte translation
@@ -476,7 +527,11 @@ impl Python {
             decorators.push(format!("alias=\"{}\"", field.id.renamed));
         }
 
-        if is_optional || not_optional_but_default {
+        // Without a default, the field is required even though it's nullable
+        let required = field.ty.is_optional()
+            && !field.has_default
+            && self.optional_fields == Some(OptionalFields::Null);
+        if (is_optional || not_optional_but_default) && !required {
             decorators.push("default=None".to_string());
         }
 
@@ -721,35 +776,32 @@ impl Python {
     }
 }
 
-static PYTHON_KEYWORDS: OnceLock<HashSet<String>> = OnceLock::new();
-
-fn get_python_keywords() -> &'static HashSet<String> {
-    PYTHON_KEYWORDS.get_or_init(|| {
-        HashSet::from_iter(
-            vec![
-                "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
-                "continue", "def", "del", "elif", "else", "except", "finally", "for", "from",
-                "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass",
-                "raise", "return", "try", "while", "with", "yield",
-            ]
-            .iter()
-            .map(|v| v.to_string()),
-        )
-    })
-}
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
 
-fn python_property_aware_rename(name: &str) -> String {
+const IDENTIFIERS: IdentifierPolicy = IdentifierPolicy {
+    keywords: PYTHON_KEYWORDS,
+    escape: Escape::Suffix("_"),
+};
+
+fn python_property_aware_rename(name: &str, reserved_words: &HashSet<String>) -> String {
     let snake_name = name.to_case(Case::Snake);
-    match get_python_keywords().contains(&snake_name) {
-        true => format!("{name}_"),
-        false => snake_name,
+    if IDENTIFIERS.is_reserved(&snake_name, reserved_words) {
+        IDENTIFIERS.escaped(name)
+    } else {
+        snake_name
     }
 }
 
 // If at least one field from within a class is changed when the serde rename is used (a.k.a the field has 2 words) then we must use aliasing and we must also use a config dict at the top level of the class.
 fn handle_model_config(w: &mut dyn Write, python_module: &mut Python, fields: &[RustField]) {
     let visibly_renamed_field = fields.iter().find(|f| {
-        let python_field_name = python_property_aware_rename(&f.id.original);
+        let python_field_name =
+            python_property_aware_rename(&f.id.original, &python_module.reserved_words);
         python_field_name != f.id.renamed
     });
     if visibly_renamed_field.is_some() {
@@ -819,8 +871,65 @@ mod test {
     use super::*;
     #[test]
     fn test_python_property_aware_rename() {
-        assert_eq!(python_property_aware_rename("class"), "class_");
-        assert_eq!(python_property_aware_rename("snake_case"), "snake_case");
+        let reserved_words = HashSet::from(["model_config".to_string()]);
+        assert_eq!(
+            python_property_aware_rename("class", &reserved_words),
+            "class_"
+        );
+        assert_eq!(
+            python_property_aware_rename("snake_case", &reserved_words),
+            "snake_case"
+        );
+        assert_eq!(
+            python_property_aware_rename("modelConfig", &reserved_words),
+            "modelConfig_"
+        );
+    }
+
+    #[test]
+    fn multi_file_imports_modules() {
+        use crate::context::{ParseContext, ParseFileContext};
+        use crate::language::CrateName;
+        use std::collections::HashSet;
+
+        let parsed_data = crate::parser::parse(
+            &ParseContext {
+                multi_file: true,
+                ..Default::default()
+            },
+            ParseFileContext {
+                source_code: r#"
+                    use foo::net::Address;
+
+                    #[typeshare]
+                    pub struct Bar {
+                        pub address: Address,
+                    }
+                "#
+                .into(),
+                crate_name: "bar".into(),
+                file_name: "bar.py".into(),
+                file_path: "bar/src/lib.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let imports = HashMap::from([(
+            CrateName::from("foo.net"),
+            HashSet::from(["Address".to_string()]),
+        )]);
+
+        let mut output = Vec::new();
+        Python::default()
+            .generate_types(&mut output, &imports, parsed_data)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(
+            output.contains("from .foo_net import Address\n"),
+            "{output}"
+        );
     }
 
     #[test]
@@ -834,11 +943,13 @@ mod test {
                 serde_rename: false,
             },
             ty: RustType::Special(SpecialRustType::Option(Box::new(RustType::Simple {
-                id: "str".to_string(),
+                id: "str".into(),
             }))),
             has_default: true,
             comments: Default::default(),
+            deprecated: None,
             decorators: Default::default(),
+            span: None,
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -858,11 +969,13 @@ mod test {
                 serde_rename: false,
             },
             ty: RustType::Special(SpecialRustType::Option(Box::new(RustType::Simple {
-                id: "str".to_string(),
+                id: "str".into(),
             }))),
             has_default: false,
             comments: Default::default(),
+            deprecated: None,
             decorators: Default::default(),
+            span: None,
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -883,12 +996,12 @@ mod test {
                 renamed: "field".to_string(),
                 serde_rename: false,
             },
-            ty: RustType::Simple {
-                id: "str".to_string(),
-            },
+            ty: RustType::Simple { id: "str".into() },
             has_default: true,
             comments: Default::default(),
+            deprecated: None,
             decorators: Default::default(),
+            span: None,
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(
@@ -907,12 +1020,12 @@ mod test {
                 renamed: "field".to_string(),
                 serde_rename: false,
             },
-            ty: RustType::Simple {
-                id: "str".to_string(),
-            },
+            ty: RustType::Simple { id: "str".into() },
             has_default: false,
             comments: Default::default(),
+            deprecated: None,
             decorators: Default::default(),
+            span: None,
         };
         python.write_field(mock_writer, &rust_field, &[]).unwrap();
         assert_eq!(String::from_utf8_lossy(mock_writer), "    field: str\n");
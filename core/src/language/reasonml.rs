@@ -1,14 +1,15 @@
 use crate::RenameExt;
 use crate::{
-    language::{Language, SupportedLanguage},
+    language::{CodeStyle, Escape, FileHeader, IdentifierPolicy, Language, SupportedLanguage},
+    lint::{lossy_mappings, unmapped_json_values, Lint, Warning},
     parser::ParsedData,
     rust_types::{
-        RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustStruct,
-        RustTypeAlias, RustTypeFormatError, SpecialRustType,
+        Id, RustConst, RustConstExpr, RustEnum, RustEnumShared, RustEnumVariant, RustField,
+        RustStruct, RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
     },
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io::{self, Write},
 };
 
@@ -16,14 +17,63 @@ use super::ScopedCrateTypes;
 
 // ReasonML keywords
 const REASONML_KEYWORDS: &[&str] = &[
-    "and", "as", "assert", "begin", "class", "constraint", "do", "done", "downto",
-    "else", "end", "exception", "external", "false", "for", "fun", "function",
-    "functor", "if", "in", "include", "inherit", "initializer", "lazy", "let",
-    "match", "method", "module", "mutable", "new", "nonrec", "object", "of",
-    "open", "or", "private", "rec", "sig", "struct", "switch", "then", "to",
-    "true", "try", "type", "val", "virtual", "when", "while", "with",
+    "and",
+    "as",
+    "assert",
+    "begin",
+    "class",
+    "constraint",
+    "do",
+    "done",
+    "downto",
+    "else",
+    "end",
+    "exception",
+    "external",
+    "false",
+    "for",
+    "fun",
+    "function",
+    "functor",
+    "if",
+    "in",
+    "include",
+    "inherit",
+    "initializer",
+    "lazy",
+    "let",
+    "match",
+    "method",
+    "module",
+    "mutable",
+    "new",
+    "nonrec",
+    "object",
+    "of",
+    "open",
+    "or",
+    "private",
+    "rec",
+    "sig",
+    "struct",
+    "switch",
+    "then",
+    "to",
+    "true",
+    "try",
+    "type",
+    "val",
+    "virtual",
+    "when",
+    "while",
+    "with",
 ];
 
+const IDENTIFIERS: IdentifierPolicy = IdentifierPolicy {
+    keywords: REASONML_KEYWORDS,
+    escape: Escape::Quote("\""),
+};
+
 /// All information needed to generate ReasonML type-code
 #[derive(Default)]
 pub struct ReasonML {
@@ -32,24 +82,43 @@ pub struct ReasonML {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `let userSchemaHash = "…";`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// Identifiers which are quoted like ReasonML keywords, besides the keywords.
+    pub reserved_words: HashSet<String>,
+    /// Whether to write a `decode<Type>` and `encode<Type>` function after every type,
+    /// which convert between it and its serialized form, a `Js.Json.t`.
+    pub codecs: bool,
 }
 
 impl Language for ReasonML {
     fn type_map(&mut self) -> &HashMap<String, String> {
         &self.type_mappings
     }
-    
-    #[allow(clippy::ptr_arg)]
+
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
+
+    fn indent_width(&self) -> usize {
+        2
+    }
+
     fn format_simple_type(
         &mut self,
-        base: &String,
+        base: &str,
         _generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         Ok(if let Some(mapped) = self.type_map().get(base) {
             mapped.into()
         } else {
             // For ReasonML, ensure type references are in camelCase
-            base.to_camel_case()
+            base.to_owned().to_camel_case()
         })
     }
 
@@ -62,25 +131,29 @@ impl Language for ReasonML {
             return Ok(mapped.to_owned());
         }
         match special_ty {
-            SpecialRustType::Vec(rtype) => {
-                Ok(format!("array({0})", self.format_type(rtype, generic_types)?))
-            }
+            SpecialRustType::Vec(rtype) => Ok(format!(
+                "array({0})",
+                self.format_type(rtype, generic_types)?
+            )),
             SpecialRustType::Array(rtype, _len) => {
                 let formatted_type = self.format_type(rtype, generic_types)?;
                 Ok(format!("array({0})", formatted_type))
             }
-            SpecialRustType::Slice(rtype) => {
-                Ok(format!("array({0})", self.format_type(rtype, generic_types)?))
-            }
-            SpecialRustType::Option(rtype) => {
-                Ok(format!("option({0})", self.format_type(rtype, generic_types)?))
-            }
+            SpecialRustType::Slice(rtype) => Ok(format!(
+                "array({0})",
+                self.format_type(rtype, generic_types)?
+            )),
+            SpecialRustType::Option(rtype) => Ok(format!(
+                "option({0})",
+                self.format_type(rtype, generic_types)?
+            )),
             SpecialRustType::HashMap(_rtype1, rtype2) => Ok(format!(
                 "Js.Dict.t({0})",
                 self.format_type(rtype2, generic_types)?
             )),
             SpecialRustType::Unit => Ok("unit".into()),
             SpecialRustType::DateTime => Ok("Js.Date.t".into()),
+            SpecialRustType::JsonValue => self.format_simple_type(special_ty.id(), generic_types),
             SpecialRustType::String => Ok("string".into()),
             SpecialRustType::Char => Ok("string".into()),
             SpecialRustType::I8
@@ -103,8 +176,11 @@ impl Language for ReasonML {
         }
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "/*", " * ", " */")?;
+            writeln!(w)?;
+        } else if !self.no_version_header {
             writeln!(w, "/*")?;
             writeln!(w, " * Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
             writeln!(w, " */")?;
@@ -118,7 +194,7 @@ impl Language for ReasonML {
 
         let r#type = self
             .format_type(&ty.r#type, ty.generic_types.as_slice())
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            .map_err(io::Error::other)?;
 
         let generic_params = if !ty.generic_types.is_empty() {
             format!("('{})", ty.generic_types.join(", '"))
@@ -134,6 +210,13 @@ impl Language for ReasonML {
             r#type,
         )?;
 
+        if self.codecs {
+            let decode = self.decode_expr(&ty.r#type, "json", 0);
+            let encode = self.encode_expr(&ty.r#type, "value", 0);
+            let recursive = references(&ty.r#type, &ty.id);
+            self.write_codecs(w, &ty.id, &ty.generic_types, recursive, &decode, &encode)?;
+        }
+
         Ok(())
     }
 
@@ -142,7 +225,7 @@ impl Language for ReasonML {
             RustConstExpr::Int(val) => {
                 let const_type = self
                     .format_type(&c.r#type, &[])
-                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                    .map_err(std::io::Error::other)?;
                 writeln!(
                     w,
                     "let {} = ({}: {});",
@@ -154,6 +237,14 @@ impl Language for ReasonML {
         }
     }
 
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(&mut self, w: &mut dyn Write, name: &str, hash: &str) -> io::Result<()> {
+        writeln!(w, "let {name} = \"{hash}\";\n")
+    }
+
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
         self.write_comments(w, 0, &rs.comments)?;
 
@@ -164,24 +255,29 @@ impl Language for ReasonML {
         };
 
         let type_name = rs.id.renamed.to_camel_case();
-        
+
         // Handle empty structs as opaque types
         if rs.fields.is_empty() {
-            return writeln!(w, "type {};", type_name);
+            writeln!(w, "type {};", type_name)?;
+            if self.codecs {
+                writeln!(w)?;
+                self.write_opaque_codecs(w, &rs.id)?;
+            }
+            return Ok(());
         }
-        
-        writeln!(
-            w,
-            "type {}{} = {{",
-            type_name,
-            generic_params
-        )?;
+
+        writeln!(w, "type {}{} = {{", type_name, generic_params)?;
 
         rs.fields
             .iter()
             .try_for_each(|f| self.write_field(w, f, rs.generic_types.as_slice()))?;
 
-        writeln!(w, "}};\n")
+        writeln!(w, "}};\n")?;
+
+        if self.codecs {
+            self.write_struct_codecs(w, rs)?;
+        }
+        Ok(())
     }
 
     fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
@@ -204,13 +300,23 @@ impl Language for ReasonML {
 
                 self.write_enum_variants(w, e)?;
 
-                writeln!(w, ";\n")
+                writeln!(w, ";\n")?;
+
+                if self.codecs {
+                    self.write_unit_enum_codecs(w, shared)?;
+                }
+                Ok(())
             }
             RustEnum::Algebraic { shared, .. } => {
                 // ReasonML doesn't support serde(tag, content, or rename) style enums
                 // Replace the enum comment with our unsupported message
                 writeln!(w, "/* Unsupported Serde Serialisation */")?;
-                writeln!(w, "type {};\n", shared.id.renamed.to_camel_case())
+                writeln!(w, "type {};\n", shared.id.renamed.to_camel_case())?;
+
+                if self.codecs {
+                    self.write_opaque_codecs(w, &shared.id)?;
+                }
+                Ok(())
             }
         }
     }
@@ -220,18 +326,298 @@ impl Language for ReasonML {
         w: &mut dyn Write,
         imports: ScopedCrateTypes<'_>,
     ) -> std::io::Result<()> {
+        if imports.is_empty() {
+            return Ok(());
+        }
         for (path, _) in imports {
-            writeln!(w, "open {};\n", path)?;
+            // Modules are named after their files, with the first letter capitalized
+            let module = path.file_stem();
+            let mut chars = module.chars();
+            let module = chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default();
+            writeln!(w, "open {module};")?;
         }
-        Ok(())
+        writeln!(w)
     }
 
     fn ignored_reference_types(&self) -> Vec<&str> {
         self.type_mappings.keys().map(|s| s.as_str()).collect()
     }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        let mut warnings = data
+            .enums
+            .iter()
+            .filter(|e| matches!(e, RustEnum::Algebraic { .. }))
+            .map(|e| Warning {
+                lint: Lint::UnsupportedItem,
+                item: e.shared().id.original.clone(),
+                message: "algebraic enums are generated as opaque types in ReasonML".to_string(),
+                location: e.shared().location.clone(),
+            })
+            .collect::<Vec<_>>();
+        // Every number is a `float`, which also accepts fractions.
+        warnings.extend(lossy_mappings(data, "ReasonML", |ty| match ty {
+            SpecialRustType::I8
+            | SpecialRustType::U8
+            | SpecialRustType::I16
+            | SpecialRustType::U16
+            | SpecialRustType::I32
+            | SpecialRustType::U32
+            | SpecialRustType::I54
+            | SpecialRustType::U53 => Some("float"),
+            _ => None,
+        }));
+        warnings.extend(unmapped_json_values(data, "ReasonML", &self.type_mappings));
+        warnings
+    }
+
+    fn type_identifier(&self, id: &Id) -> String {
+        id.renamed.to_camel_case()
+    }
+
+    fn field_identifier(&self, field: &RustField) -> String {
+        self.property_aware_rename(&field.id.renamed)
+    }
 }
 
 impl ReasonML {
+    /// Quotes keywords, the reserved words and names with hyphens.
+    fn property_aware_rename(&self, name: &str) -> String {
+        if name.contains('-') {
+            return IDENTIFIERS.escaped(name);
+        }
+        IDENTIFIERS.escape(name, &self.reserved_words).into_owned()
+    }
+
+    /// Write the `decode<Type>` and `encode<Type>` functions of a type, which convert
+    /// between it and `Js.Json.t`, with the bodies of the functions of the `json` and
+    /// the `value`. Generic types take the functions of their type parameters as extra
+    /// arguments.
+    fn write_codecs(
+        &mut self,
+        w: &mut dyn Write,
+        id: &Id,
+        generic_types: &[String],
+        recursive: bool,
+        decode: &str,
+        encode: &str,
+    ) -> io::Result<()> {
+        let name = id.renamed.to_camel_case();
+        let function = id.renamed.to_pascal_case();
+        let generic_params = if !generic_types.is_empty() {
+            format!("('{})", generic_types.join(", '"))
+        } else {
+            String::new()
+        };
+        let decoders = generic_types
+            .iter()
+            .map(|t| format!(", decode{t}: Js.Json.t => '{t}"))
+            .collect::<String>();
+        let encoders = generic_types
+            .iter()
+            .map(|t| format!(", encode{t}: '{t} => Js.Json.t"))
+            .collect::<String>();
+        let rec = if recursive { "rec " } else { "" };
+        writeln!(
+            w,
+            "let {rec}decode{function} = (json: Js.Json.t{decoders}): {name}{generic_params} =>\n  {decode};\n"
+        )?;
+        writeln!(
+            w,
+            "let {rec}encode{function} = (value: {name}{generic_params}{encoders}): Js.Json.t =>\n  {encode};\n"
+        )
+    }
+
+    /// The codecs of opaque types, which keep the JSON as it is.
+    fn write_opaque_codecs(&mut self, w: &mut dyn Write, id: &Id) -> io::Result<()> {
+        self.write_codecs(w, id, &[], false, "Obj.magic(json)", "Obj.magic(value)")
+    }
+
+    fn write_struct_codecs(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let mut decoded = String::new();
+        let mut encoded = String::new();
+        for field in &rs.fields {
+            let identifier = self.property_aware_rename(&field.id.renamed);
+            let key = serde_json::Value::from(field.id.renamed.as_str());
+            let (decode, encode) = match field.type_override(SupportedLanguage::TypeScript) {
+                Some(_) => (
+                    format!("Obj.magic(field({key}))"),
+                    format!("Obj.magic(value.{identifier})"),
+                ),
+                None => (
+                    self.decode_expr(&field.ty, &format!("field({key})"), 0),
+                    self.encode_expr(&field.ty, &format!("value.{identifier}"), 0),
+                ),
+            };
+            decoded.push_str(&format!("      {identifier}: {decode},\n"));
+            encoded.push_str(&format!("        ({key}, {encode}),\n"));
+        }
+        let decode = format!(
+            "{{
+    let fields = Js.Json.decodeObject(json)->Belt.Option.getExn;
+    let field = name =>
+      Js.Dict.get(fields, name)->Belt.Option.getWithDefault(Js.Json.null);
+    {{
+{decoded}    }};
+  }}"
+        );
+        let encode = format!(
+            "Js.Json.object_(
+    Js.Dict.fromArray(
+      [|
+{encoded}      |],
+    ),
+  )"
+        );
+        let recursive = rs.fields.iter().any(|f| references(&f.ty, &rs.id));
+        self.write_codecs(w, &rs.id, &rs.generic_types, recursive, &decode, &encode)
+    }
+
+    fn write_unit_enum_codecs(
+        &mut self,
+        w: &mut dyn Write,
+        shared: &RustEnumShared,
+    ) -> io::Result<()> {
+        let mut decoded = String::new();
+        let mut encoded = String::new();
+        for variant in &shared.variants {
+            let name = &variant.shared().id.renamed;
+            let value = serde_json::Value::from(name.as_str());
+            decoded.push_str(&format!("  | Some({value}) => {name}\n"));
+            encoded.push_str(&format!("  | {name} => Js.Json.string({value})\n"));
+        }
+        let decode = format!(
+            "switch (Js.Json.decodeString(json)) {{\n{decoded}  | _ => failwith(\"Unexpected {}: \" ++ Js.Json.stringify(json))\n  }}",
+            shared.id.renamed.to_camel_case()
+        );
+        let encode = format!("switch (value) {{\n{encoded}  }}");
+        self.write_codecs(w, &shared.id, &[], false, &decode, &encode)
+    }
+
+    /// Whether the type is mapped to a type typeshare doesn't know, whose values are
+    /// kept as their JSON.
+    fn is_mapped(&self, ty: &RustType) -> bool {
+        match ty {
+            RustType::Simple { id } | RustType::Generic { id, .. } => {
+                self.type_mappings.contains_key(id.as_str())
+            }
+            RustType::Special(special) => self.type_mappings.contains_key(&special.to_string()),
+        }
+    }
+
+    /// An expression decoding the `Js.Json.t` of `value` into the type.
+    fn decode_expr(&self, ty: &RustType, value: &str, depth: usize) -> String {
+        let element = format!("v{depth}");
+        if self.is_mapped(ty) {
+            return format!("Obj.magic({value})");
+        }
+        match ty {
+            RustType::Simple { id } => {
+                format!("decode{}({value})", id.to_string().to_pascal_case())
+            }
+            RustType::Generic { id, parameters } => {
+                let decoders = parameters
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            ", {element} => {}",
+                            self.decode_expr(p, &element, depth + 1)
+                        )
+                    })
+                    .collect::<String>();
+                format!(
+                    "decode{}({value}{decoders})",
+                    id.to_string().to_pascal_case()
+                )
+            }
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(inner)
+                | SpecialRustType::Slice(inner)
+                | SpecialRustType::Array(inner, _) => format!(
+                    "Js.Json.decodeArray({value})->Belt.Option.getExn->Belt.Array.map({element} => {})",
+                    self.decode_expr(inner, &element, depth + 1)
+                ),
+                SpecialRustType::Option(inner) => format!(
+                    "Js.Json.decodeNull({value})->Belt.Option.isSome ? None : Some({})",
+                    self.decode_expr(inner, value, depth)
+                ),
+                SpecialRustType::HashMap(_, inner) => format!(
+                    "Js.Dict.map((. {element}) => {}, Js.Json.decodeObject({value})->Belt.Option.getExn)",
+                    self.decode_expr(inner, &element, depth + 1)
+                ),
+                SpecialRustType::Unit => "()".to_owned(),
+                SpecialRustType::JsonValue => format!("decode{}({value})", special.id()),
+                SpecialRustType::DateTime => format!(
+                    "Js.Date.fromString(Js.Json.decodeString({value})->Belt.Option.getExn)"
+                ),
+                SpecialRustType::String | SpecialRustType::Char => {
+                    format!("Js.Json.decodeString({value})->Belt.Option.getExn")
+                }
+                SpecialRustType::Bool => {
+                    format!("Js.Json.decodeBoolean({value})->Belt.Option.getExn")
+                }
+                _ => format!("Js.Json.decodeNumber({value})->Belt.Option.getExn"),
+            },
+        }
+    }
+
+    /// An expression encoding `value` of the type into a `Js.Json.t`.
+    fn encode_expr(&self, ty: &RustType, value: &str, depth: usize) -> String {
+        let element = format!("v{depth}");
+        if self.is_mapped(ty) {
+            return format!("Obj.magic({value})");
+        }
+        match ty {
+            RustType::Simple { id } => {
+                format!("encode{}({value})", id.to_string().to_pascal_case())
+            }
+            RustType::Generic { id, parameters } => {
+                let encoders = parameters
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            ", {element} => {}",
+                            self.encode_expr(p, &element, depth + 1)
+                        )
+                    })
+                    .collect::<String>();
+                format!(
+                    "encode{}({value}{encoders})",
+                    id.to_string().to_pascal_case()
+                )
+            }
+            RustType::Special(special) => match special {
+                SpecialRustType::Vec(inner)
+                | SpecialRustType::Slice(inner)
+                | SpecialRustType::Array(inner, _) => format!(
+                    "Js.Json.array(Belt.Array.map({value}, {element} => {}))",
+                    self.encode_expr(inner, &element, depth + 1)
+                ),
+                SpecialRustType::Option(inner) => format!(
+                    "switch ({value}) {{ | Some({element}) => {} | None => Js.Json.null }}",
+                    self.encode_expr(inner, &element, depth + 1)
+                ),
+                SpecialRustType::HashMap(_, inner) => format!(
+                    "Js.Json.object_(Js.Dict.map((. {element}) => {}, {value}))",
+                    self.encode_expr(inner, &element, depth + 1)
+                ),
+                SpecialRustType::Unit => "Js.Json.null".to_owned(),
+                SpecialRustType::JsonValue => format!("encode{}({value})", special.id()),
+                SpecialRustType::DateTime => {
+                    format!("Js.Json.string(Js.Date.toISOString({value}))")
+                }
+                SpecialRustType::String | SpecialRustType::Char => {
+                    format!("Js.Json.string({value})")
+                }
+                SpecialRustType::Bool => format!("Js.Json.boolean({value})"),
+                _ => format!("Js.Json.number({value})"),
+            },
+        }
+    }
+
     fn write_enum_variants(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
         match e {
             RustEnum::Unit(shared) => {
@@ -257,25 +643,17 @@ impl ReasonML {
                     match variant {
                         RustEnumVariant::Unit(shared) => {
                             self.write_comments(w, 1, &shared.comments)?;
-                            writeln!(
-                                w,
-                                "  | {}({}: string)", 
-                                shared.id.renamed,
-                                tag_key
-                            )?;
+                            writeln!(w, "  | {}({}: string)", shared.id.renamed, tag_key)?;
                         }
                         RustEnumVariant::Tuple { ty, shared } => {
                             self.write_comments(w, 1, &shared.comments)?;
                             let r#type = self
                                 .format_type(ty, e.shared().generic_types.as_slice())
-                                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                                .map_err(io::Error::other)?;
                             writeln!(
                                 w,
-                                "  | {}({}: string, {}: {})", 
-                                shared.id.renamed,
-                                tag_key,
-                                content_key,
-                                r#type
+                                "  | {}({}: string, {}: {})",
+                                shared.id.renamed, tag_key, content_key, r#type
                             )?;
                         }
                         RustEnumVariant::AnonymousStruct { fields, shared } => {
@@ -285,11 +663,11 @@ impl ReasonML {
                                 "  | {}({}: string, {}: {{",
                                 shared.id.renamed, tag_key, content_key
                             )?;
-                            
+
                             for field in fields {
                                 self.write_field(w, field, e.shared().generic_types.as_slice())?;
                             }
-                            
+
                             writeln!(w, "  }})")?;
                         }
                     }
@@ -310,16 +688,16 @@ impl ReasonML {
             Some(type_override) => type_override.to_owned(),
             None => self
                 .format_type(&field.ty, generic_types)
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?,
+                .map_err(io::Error::other)?,
         };
-        
+
         // If the type itself is already optional (from Option<T>), don't double-wrap it
         let type_str = reasonml_ty;
-        
+
         writeln!(
             w,
             "    {}: {},",
-            reasonml_property_aware_rename(&field.id.renamed),
+            self.property_aware_rename(&field.id.renamed),
             type_str
         )?;
 
@@ -356,11 +734,8 @@ impl ReasonML {
     }
 }
 
-fn reasonml_property_aware_rename(name: &str) -> String {
-    // Check if the name contains hyphens or is a keyword
-    if name.contains('-') || REASONML_KEYWORDS.contains(&name) {
-        return format!("\"{0}\"", name);
-    }
-    name.to_string()
+/// Whether the type refers to the type of the identifier, so that its codecs are
+/// recursive.
+fn references(ty: &RustType, id: &Id) -> bool {
+    ty.contains_type(&id.original) || ty.contains_type(&id.renamed)
 }
-
@@ -1,15 +1,65 @@
-use super::{CrateTypes, Language};
+use super::{
+    item_context, item_name, used_imports, write_item_schema_hash, written, CodeStyle, CrateTypes,
+    Escape, FileHeader, FileOutput, IdentifierPolicy, ItemOutput, Language, OptionalFields,
+};
 use crate::language::SupportedLanguage;
+use crate::lint::{lossy_mappings, unmapped_json_values, Warning};
 use crate::parser::{remove_dash_from_identifier, ParsedData};
 use crate::rust_types::{
-    RustConst, RustEnum, RustEnumVariant, RustField, RustStruct, RustType, RustTypeAlias,
-    RustTypeFormatError, SpecialRustType,
+    RustConst, RustEnum, RustEnumVariant, RustEnumVariantShared, RustField, RustItem, RustStruct,
+    RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
 };
 use itertools::Itertools;
 use joinery::JoinableIterator;
 use lazy_format::lazy_format;
 use std::ops::Deref;
-use std::{collections::HashMap, io::Write};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+};
+
+/// Scala keywords.
+const SCALA_KEYWORDS: &[&str] = &[
+    "case",
+    "class",
+    "def",
+    "do",
+    "else",
+    "extends",
+    "false",
+    "final",
+    "for",
+    "if",
+    "import",
+    "lazy",
+    "match",
+    "new",
+    "null",
+    "object",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "return",
+    "sealed",
+    "super",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "val",
+    "var",
+    "while",
+    "with",
+    "yield",
+];
+
+const IDENTIFIERS: IdentifierPolicy = IdentifierPolicy {
+    keywords: SCALA_KEYWORDS,
+    escape: Escape::Quote("`"),
+};
 
 /// All information needed for Scala type-code
 #[derive(Default)]
@@ -23,44 +73,54 @@ pub struct Scala {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `val UserSchemaHash: String = "…"`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// Identifiers which are escaped with backticks like Scala keywords, besides the
+    /// keywords.
+    pub reserved_words: HashSet<String>,
+    /// How fields of `Option` types are represented, instead of the default of the
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
+    /// Whether to generate circe `Encoder` and `Decoder` instances for every type,
+    /// matching the JSON layout of serde (including `tag`/`content` enum layouts).
+    pub circe: bool,
+    /// Whether to generate Scala 3 code, with top-level definitions, `enum`s,
+    /// braceless syntax and `derives` clauses, instead of Scala 2 code.
+    pub scala3: bool,
+    /// Whether newtype structs, like `struct Id(String)`, should be generated as
+    /// `opaque type`s instead of type aliases. Only applies to Scala 3 code.
+    pub opaque_newtypes: bool,
+    /// Whether to generate Scala.js facades, `@js.native` traits extending `js.Object`,
+    /// for the JSON values of every type instead of case classes. Can't be combined
+    /// with `circe`.
+    pub scala_js: bool,
 }
 
 impl Language for Scala {
-    fn generate_types(
-        &mut self,
-        writable: &mut dyn Write,
-        _imports: &CrateTypes,
-        data: ParsedData,
-    ) -> std::io::Result<()> {
-        self.begin_file(writable, &data)?;
-
-        // Package object to hold type aliases: aliases must be in class or object in Scala 2)
-        let unsigned_used = self.unsigned_integer_used(&data);
-        if unsigned_used || !data.aliases.is_empty() {
-            self.begin_package_object(writable)?;
-            if unsigned_used {
-                self.write_unsigned_aliases(writable)?;
-            }
-            for a in data.aliases.iter() {
-                self.write_type_alias(writable, a)?;
-            }
-            self.end_package_object(writable)?;
+    fn emit(&mut self, all_types: &CrateTypes, data: ParsedData) -> std::io::Result<FileOutput> {
+        // Every file gets its own package, as package objects can only be defined once
+        if !data.multi_file {
+            return self.emit_file(String::new(), data);
         }
+        // Imports are relative to the root package
+        let imports = written(|w| self.write_imports(w, used_imports(&data, all_types)))?;
+        let package = match self.package.as_str() {
+            "" => data.crate_name.to_string(),
+            root => format!("{root}.{}", data.crate_name),
+        };
+        let root_package = std::mem::replace(&mut self.package, package);
+        let output = self.emit_file(imports, data);
+        self.package = root_package;
+        output
+    }
 
-        if !data.structs.is_empty() || !data.enums.is_empty() {
-            self.begin_package(writable)?;
-            for s in data.structs.iter() {
-                self.write_struct(writable, s)?;
-            }
-            for e in data.enums.iter() {
-                self.write_enum(writable, e)?;
-            }
-            self.end_package(writable)?;
-        }
-
-        self.end_file(writable)?;
-
-        Ok(())
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
     }
 
     fn type_map(&mut self) -> &HashMap<String, String> {
@@ -76,6 +136,9 @@ impl Language for Scala {
         special_ty: &SpecialRustType,
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
+        if self.scala_js {
+            return self.format_js_special_type(special_ty, generic_types);
+        }
         Ok(match special_ty {
             SpecialRustType::Vec(rtype) => {
                 format!("Vector[{}]", self.format_type(rtype, generic_types)?)
@@ -112,6 +175,9 @@ impl Language for Scala {
             SpecialRustType::Bool => "Boolean".into(),
             SpecialRustType::F32 => "Float".into(),
             SpecialRustType::F64 => "Double".into(),
+            SpecialRustType::JsonValue => {
+                self.format_simple_type(special_ty.id(), generic_types)?
+            }
             // TODO: https://github.com/1Password/typeshare/issues/237
             SpecialRustType::DateTime => {
                 return Err(RustTypeFormatError::UnsupportedSpecialType(
@@ -121,28 +187,61 @@ impl Language for Scala {
         })
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> std::io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> std::io::Result<()> {
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "/**", " * ", " */")?;
+        } else if !self.no_version_header {
             writeln!(w, "/**")?;
             writeln!(w, " * Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
             writeln!(w, " */")?;
         }
         if self.package.is_empty() {
-            panic!("package name must be provided")
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "package name must be provided",
+            ));
+        }
+        if self.scala3 {
+            writeln!(w, "package {}", self.package)?;
+            writeln!(w)?;
+        } else if let Some((parent, _last)) = self.package.rsplit_once('.') {
+            writeln!(w, "package {parent}")?;
+            writeln!(w)?;
+        }
+        if self.scala_js {
+            writeln!(w, "import scala.scalajs.js")?;
+            writeln!(w, "import scala.scalajs.js.|")?;
+            writeln!(w)?;
+        }
+        if self.circe && self.scala3 {
+            writeln!(
+                w,
+                "import io.circe.{{Codec, Decoder, DecodingFailure, Encoder, Json}}"
+            )?;
+            writeln!(w, "import io.circe.syntax.*")?;
+            writeln!(w)?;
+        } else if self.circe {
+            writeln!(
+                w,
+                "import io.circe.{{Decoder, DecodingFailure, Encoder, Json}}"
+            )?;
+            writeln!(
+                w,
+                "import io.circe.generic.semiauto.{{deriveDecoder, deriveEncoder}}"
+            )?;
+            writeln!(w, "import io.circe.syntax._")?;
+            writeln!(w)?;
         }
-        match self.package.rsplit_once('.') {
-            None => {}
-            Some((parent, _last)) => {
-                writeln!(w, "package {parent}")?;
-                writeln!(w)?;
-            }
-        };
         Ok(())
     }
 
     fn write_type_alias(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> std::io::Result<()> {
         self.write_comments(w, 0, &ty.comments)?;
 
+        if ty.is_newtype && self.opaque_newtypes && self.scala3 {
+            return self.write_opaque_type(w, ty);
+        }
+
         writeln!(
             w,
             "type {}{} = {}\n",
@@ -160,12 +259,35 @@ impl Language for Scala {
     }
 
     fn write_const(&mut self, _w: &mut dyn Write, _c: &RustConst) -> std::io::Result<()> {
-        todo!()
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "typeshare doesn't generate constants in Scala",
+        ))
+    }
+
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        hash: &str,
+    ) -> std::io::Result<()> {
+        writeln!(w, "val {name}: String = \"{hash}\"\n")
     }
 
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
         self.write_comments(w, 0, &rs.comments)?;
 
+        if self.scala_js {
+            return self.write_js_struct(w, rs);
+        }
+        if self.scala3 {
+            return self.write_scala3_struct(w, rs);
+        }
+
         if !rs.fields.is_empty() {
             writeln!(
                 w,
@@ -190,6 +312,9 @@ impl Language for Scala {
         } else {
             writeln!(w, "class {} extends Serializable\n", rs.id.renamed)?;
         }
+        if self.circe {
+            self.write_circe_struct_codecs(w, rs)?;
+        }
         Ok(())
     }
 
@@ -201,6 +326,13 @@ impl Language for Scala {
 
         self.write_comments(w, 0, &e.shared().comments)?;
 
+        if self.scala_js {
+            return self.write_js_enum(w, e);
+        }
+        if self.scala3 {
+            return self.write_scala3_enum(w, e);
+        }
+
         let generic_parameters = if !e.shared().generic_types.is_empty() {
             format!("[{}]", e.shared().generic_types.join(", "))
         } else {
@@ -228,19 +360,169 @@ impl Language for Scala {
 
         writeln!(w, "object {} {{", &e.shared().id.renamed)?;
         self.write_enum_variants(w, e)?;
+        if self.circe {
+            self.write_circe_enum_codecs(w, e)?;
+        }
         writeln!(w, "}}\n")
     }
 
     fn write_imports(
         &mut self,
-        _writer: &mut dyn Write,
-        _imports: super::ScopedCrateTypes<'_>,
+        w: &mut dyn Write,
+        imports: super::ScopedCrateTypes<'_>,
     ) -> std::io::Result<()> {
-        unimplemented!()
+        if imports.is_empty() {
+            return Ok(());
+        }
+        let wildcard = if self.scala3 { "*" } else { "_" };
+        for path in imports.keys() {
+            match self.package.as_str() {
+                "" => writeln!(w, "import {path}.{wildcard}")?,
+                root => writeln!(w, "import {root}.{path}.{wildcard}")?,
+            }
+        }
+        writeln!(w)
+    }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        let scala_js = self.scala_js;
+        let mut warnings = lossy_mappings(data, "Scala", |ty| match ty {
+            SpecialRustType::ISize => Some("Int"),
+            SpecialRustType::USize if !scala_js => Some("UInt"),
+            // Doubles only hold integers up to 2^53 exactly
+            SpecialRustType::USize | SpecialRustType::I64 | SpecialRustType::U64 if scala_js => {
+                Some("Double")
+            }
+            _ => None,
+        });
+        warnings.extend(unmapped_json_values(data, "Scala", &self.type_mappings));
+        warnings
     }
 }
 
 impl Scala {
+    /// The identifier of a serialized name, escaped with backticks if it's a Scala
+    /// keyword, such as `type`, which are common JSON property names, or a reserved word.
+    fn identifier(&self, name: &str) -> String {
+        IDENTIFIERS.identifier(name, &self.reserved_words)
+    }
+
+    /// Generates the types of a file in the current package, after its imports.
+    /// The parts of the file of the parsed data, in the package of the language.
+    ///
+    /// Scala 3 allows top-level definitions, so every type is an item. In Scala 2, type
+    /// aliases and values have to be defined in an object, so the aliases and the schema
+    /// hashes are defined in the package object, which is written with the definitions,
+    /// and the structs and enums are the items of the package after it.
+    fn emit_file(&mut self, imports: String, data: ParsedData) -> std::io::Result<FileOutput> {
+        let header = written(|w| self.begin_file(w, &data))?;
+        let unsigned_used = self.unsigned_integer_used(&data);
+        let ParsedData {
+            structs,
+            enums,
+            aliases,
+            ..
+        } = data;
+
+        if self.scala3 {
+            let definitions = written(|w| {
+                if unsigned_used {
+                    self.write_unsigned_aliases(w)?;
+                }
+                Ok(())
+            })?;
+            let items = aliases
+                .into_iter()
+                .map(RustItem::Alias)
+                .chain(structs.into_iter().map(RustItem::Struct))
+                .chain(enums.into_iter().map(RustItem::Enum))
+                .map(|item| self.emit_item(&item))
+                .collect::<std::io::Result<_>>()?;
+            return Ok(FileOutput {
+                header,
+                imports,
+                definitions,
+                items,
+                footer: written(|w| self.end_file(w))?,
+            });
+        }
+
+        // Package object to hold type aliases: aliases must be in class or object in Scala 2)
+        // Package objects also hold the schema hashes: values can't be top-level either
+        let hashes_used =
+            self.schema_hashes && (!structs.is_empty() || !enums.is_empty() || !aliases.is_empty());
+        let in_package = !structs.is_empty() || !enums.is_empty();
+        let definitions = written(|w| {
+            if unsigned_used || !aliases.is_empty() || hashes_used {
+                self.begin_package_object(w)?;
+                if unsigned_used {
+                    self.write_unsigned_aliases(w)?;
+                }
+                for a in aliases.iter() {
+                    self.write_type_alias(w, a)
+                        .and_then(|()| self.write_schema_hash_of(w, RustItem::Alias, a))
+                        .map_err(item_context(&a.id.original))?;
+                }
+                for s in structs.iter() {
+                    self.write_schema_hash_of(w, RustItem::Struct, s)
+                        .map_err(item_context(&s.id.original))?;
+                }
+                for e in enums.iter() {
+                    self.write_schema_hash_of(w, RustItem::Enum, e)
+                        .map_err(item_context(&e.shared().id.original))?;
+                }
+                self.end_package_object(w)?;
+            }
+            if in_package {
+                self.begin_package(w)?;
+            }
+            Ok(())
+        })?;
+
+        let items = structs
+            .into_iter()
+            .map(RustItem::Struct)
+            .chain(enums.into_iter().map(RustItem::Enum))
+            .map(|item| {
+                let name = item_name(&item).to_owned();
+                ItemOutput::new(&item, |w| match &item {
+                    RustItem::Struct(s) => self.write_struct(w, s),
+                    RustItem::Enum(e) => self.write_enum(w, e),
+                    RustItem::Alias(_) | RustItem::Const(_) => Ok(()),
+                })
+                .map_err(item_context(&name))
+            })
+            .collect::<std::io::Result<_>>()?;
+
+        let footer = written(|w| {
+            if in_package {
+                self.end_package(w)?;
+            }
+            self.end_file(w)
+        })?;
+        Ok(FileOutput {
+            header,
+            imports,
+            definitions,
+            items,
+            footer,
+        })
+    }
+
+    /// The generated source of a top-level item of Scala 3, followed by its schema hash.
+    fn emit_item(&mut self, item: &RustItem) -> std::io::Result<ItemOutput> {
+        ItemOutput::new(item, |w| {
+            match item {
+                RustItem::Alias(a) => self.write_type_alias(w, a),
+                RustItem::Struct(s) => self.write_struct(w, s),
+                RustItem::Enum(e) => self.write_enum(w, e),
+                RustItem::Const(_) => Ok(()),
+            }?;
+            write_item_schema_hash(self, w, item)
+        })
+        .map_err(item_context(item_name(item)))
+    }
+
     fn write_enum_variants(&mut self, w: &mut dyn Write, e: &RustEnum) -> std::io::Result<()> {
         match e {
             RustEnum::Unit(shared) => {
@@ -269,22 +551,7 @@ impl Scala {
                     let printed_value = format!(r##"{:?}"##, &v.shared().id.renamed);
                     self.write_comments(w, 1, &v.shared().comments)?;
 
-                    let variant_name = {
-                        let mut variant_name = v.shared().id.original.to_string();
-
-                        if variant_name
-                            .chars()
-                            .next()
-                            .map(|c| c.is_ascii_digit())
-                            .unwrap_or(false)
-                        {
-                            // If the name starts with a digit just add an underscore
-                            // to the front and make it valid
-                            variant_name = format!("_{variant_name}");
-                        }
-
-                        variant_name
-                    };
+                    let variant_name = variant_name(v);
 
                     match v {
                         RustEnumVariant::Unit(_) => {
@@ -320,33 +587,11 @@ impl Scala {
                                 }
                             )?;
 
-                            // Builds the list of generic types (e.g [T, U, V]), by digging
-                            // through the fields recursively and comparing against the
-                            // enclosing enum's list of generic parameters.
-                            let generics = fields
-                                .iter()
-                                .flat_map(|field| {
-                                    e.shared()
-                                        .generic_types
-                                        .iter()
-                                        .filter(|g| field.ty.contains_type(g))
-                                })
-                                .unique()
-                                .collect_vec();
-
-                            // Sadly the parenthesis are required because of macro limitations
-                            let generics = lazy_format!(match (generics.is_empty()) {
-                                false => ("[{}]", generics.iter().join_with(", ")),
-                                true => (""),
-                            });
-
                             write!(
                                 w,
-                                "{}: {}{}Inner{}",
+                                "{}: {}",
                                 content_key,
-                                e.shared().id.original,
-                                shared.id.original,
-                                generics,
+                                anonymous_struct_type(e, shared, fields),
                             )?;
                             write!(w, ")")?;
                         }
@@ -371,6 +616,483 @@ impl Scala {
         Ok(())
     }
 
+    /// Writes an opaque type for a newtype, with a companion object to wrap
+    /// and unwrap values and, when enabled, codecs for the underlying type.
+    fn write_opaque_type(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> std::io::Result<()> {
+        let name = &ty.id.original;
+        let generics = generic_parameters(&ty.generic_types);
+        let inner = self
+            .format_type(&ty.r#type, ty.generic_types.as_slice())
+            .map_err(std::io::Error::other)?;
+
+        writeln!(w, "opaque type {name}{generics} = {inner}\n")?;
+        writeln!(w, "object {name}:")?;
+        writeln!(
+            w,
+            "\tdef apply{generics}(value: {inner}): {name}{generics} = value"
+        )?;
+        writeln!(
+            w,
+            "\textension {}(self: {name}{generics}) def value: {inner} = self",
+            if generics.is_empty() {
+                String::new()
+            } else {
+                format!("{generics} ")
+            }
+        )?;
+        if self.circe {
+            let ty_name = format!("{name}{generics}");
+            for codec in ["Encoder", "Decoder"] {
+                self.write_circe_instance(
+                    w,
+                    codec,
+                    &ty_name,
+                    &ty.generic_types,
+                    &format!("{codec}[{inner}]"),
+                )?;
+            }
+        }
+        writeln!(w)
+    }
+
+    /// Formats a special type as the type of a JSON value in a Scala.js facade.
+    fn format_js_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype)
+            | SpecialRustType::Array(rtype, _)
+            | SpecialRustType::Slice(rtype) => {
+                format!("js.Array[{}]", self.format_type(rtype, generic_types)?)
+            }
+            // Missing fields are `undefined`, and `None` is serialized as `null`
+            SpecialRustType::Option(rtype) => {
+                format!(
+                    "js.UndefOr[{} | Null]",
+                    self.format_type(rtype, generic_types)?
+                )
+            }
+            // JSON object keys are always strings
+            SpecialRustType::HashMap(_, rtype) => {
+                format!("js.Dictionary[{}]", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::Unit => "Unit".into(),
+            SpecialRustType::String | SpecialRustType::Char => "String".into(),
+            SpecialRustType::I8 => "Byte".into(),
+            SpecialRustType::I16 | SpecialRustType::U8 => "Short".into(),
+            SpecialRustType::I32 | SpecialRustType::ISize | SpecialRustType::U16 => "Int".into(),
+            // JavaScript numbers are doubles, which hold all of these exactly
+            SpecialRustType::U32
+            | SpecialRustType::USize
+            | SpecialRustType::I54
+            | SpecialRustType::U53
+            | SpecialRustType::I64
+            | SpecialRustType::U64
+            | SpecialRustType::F64 => "Double".into(),
+            SpecialRustType::F32 => "Float".into(),
+            SpecialRustType::Bool => "Boolean".into(),
+            SpecialRustType::JsonValue => {
+                self.format_simple_type(special_ty.id(), generic_types)?
+            }
+            SpecialRustType::DateTime => {
+                return Err(RustTypeFormatError::UnsupportedSpecialType(
+                    special_ty.to_string(),
+                ))
+            }
+        })
+    }
+
+    /// Writes a Scala.js facade trait for the JSON object of a struct.
+    fn write_js_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
+        writeln!(w, "@js.native")?;
+        write!(
+            w,
+            "trait {}{} extends js.Object",
+            rs.id.renamed,
+            generic_parameters(&rs.generic_types)
+        )?;
+        self.write_js_members(w, &rs.fields, &rs.generic_types, 0)?;
+        writeln!(w)
+    }
+
+    /// Writes the members of a Scala.js facade trait for the given fields.
+    fn write_js_members(
+        &mut self,
+        w: &mut dyn Write,
+        fields: &[RustField],
+        generic_types: &[String],
+        indent: usize,
+    ) -> std::io::Result<()> {
+        if fields.is_empty() {
+            return writeln!(w);
+        }
+        writeln!(w, " {{")?;
+        for f in fields {
+            self.write_comments(w, indent + 1, &f.comments)?;
+            let ty = match f.type_override(SupportedLanguage::Scala) {
+                Some(type_override) => type_override.to_owned(),
+                None => self
+                    .format_type(&f.ty, generic_types)
+                    .map_err(std::io::Error::other)?,
+            };
+            // Fields that are left out when serialized with their default are `undefined`
+            let ty = if f.has_default && !f.ty.is_optional() {
+                format!("js.UndefOr[{ty}]")
+            } else {
+                ty
+            };
+            self.write_js_member(w, &f.id.renamed, &ty, indent + 1)?;
+        }
+        writeln!(w, "{}}}", "\t".repeat(indent))
+    }
+
+    /// Writes a member of a Scala.js facade trait for the JSON property `name`.
+    fn write_js_member(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        ty: &str,
+        indent: usize,
+    ) -> std::io::Result<()> {
+        let identifier = remove_dash_from_identifier(name);
+        let indent = "\t".repeat(indent);
+        if identifier != name {
+            writeln!(w, "{indent}@js.annotation.JSName({name:?})")?;
+        }
+        writeln!(w, "{indent}val {}: {ty} = js.native", self.identifier(name))
+    }
+
+    /// Writes Scala.js facades for the JSON values of an enum. The values of unit
+    /// enums are strings, which are typed as an opaque `js.Any` with a constant for
+    /// every variant. Algebraic enums get a trait for every variant.
+    fn write_js_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> std::io::Result<()> {
+        let shared = e.shared();
+        let generics = generic_parameters(&shared.generic_types);
+        match e {
+            RustEnum::Unit(shared) => {
+                writeln!(w, "@js.native")?;
+                writeln!(w, "sealed trait {} extends js.Any", shared.id.renamed)?;
+                writeln!(w, "object {} {{", shared.id.renamed)?;
+                for v in &shared.variants {
+                    self.write_comments(w, 1, &v.shared().comments)?;
+                    writeln!(
+                        w,
+                        "\tval {}: {} = {:?}.asInstanceOf[{}]",
+                        variant_name(v),
+                        shared.id.renamed,
+                        v.shared().id.renamed,
+                        shared.id.renamed
+                    )?;
+                }
+                writeln!(w, "}}\n")
+            }
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                writeln!(w, "@js.native")?;
+                write!(
+                    w,
+                    "sealed trait {}{generics} extends js.Object",
+                    shared.id.renamed
+                )?;
+                writeln!(w, " {{")?;
+                self.write_js_member(w, tag_key, "String", 1)?;
+                writeln!(w, "}}")?;
+                writeln!(w, "object {} {{", shared.id.renamed)?;
+                for v in &shared.variants {
+                    let content_type = match v {
+                        RustEnumVariant::Unit(_) => None,
+                        RustEnumVariant::Tuple { ty, .. } => Some(
+                            self.format_type(ty, &shared.generic_types)
+                                .map_err(std::io::Error::other)?,
+                        ),
+                        RustEnumVariant::AnonymousStruct {
+                            shared: variant_shared,
+                            fields,
+                        } => Some(anonymous_struct_type(e, variant_shared, fields)),
+                    };
+                    self.write_comments(w, 1, &v.shared().comments)?;
+                    writeln!(w, "\t@js.native")?;
+                    write!(
+                        w,
+                        "\tsealed trait {}{generics} extends {}{generics}",
+                        variant_name(v),
+                        shared.id.renamed
+                    )?;
+                    match content_type {
+                        Some(content_type) => {
+                            writeln!(w, " {{")?;
+                            self.write_js_member(w, content_key, &content_type, 2)?;
+                            writeln!(w, "\t}}")?;
+                        }
+                        None => writeln!(w)?,
+                    }
+                }
+                writeln!(w, "}}\n")
+            }
+        }
+    }
+
+    fn write_scala3_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> std::io::Result<()> {
+        write!(
+            w,
+            "case class {}{}(",
+            rs.id.renamed,
+            generic_parameters(&rs.generic_types)
+        )?;
+        if let Some((last, elements)) = rs.fields.split_last() {
+            writeln!(w)?;
+            for f in elements.iter() {
+                self.write_element(w, f, rs.generic_types.as_slice())?;
+                writeln!(w, ",")?;
+            }
+            self.write_element(w, last, rs.generic_types.as_slice())?;
+            writeln!(w)?;
+        }
+        write!(w, ")")?;
+        if self.circe {
+            write!(w, " derives Codec.AsObject")?;
+        }
+        writeln!(w, "\n")
+    }
+
+    fn write_scala3_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> std::io::Result<()> {
+        let shared = e.shared();
+        let enum_type = format!(
+            "{}{}",
+            shared.id.renamed,
+            generic_parameters(&shared.generic_types)
+        );
+        writeln!(w, "enum {enum_type}(val serialName: String):")?;
+
+        for v in &shared.variants {
+            self.write_comments(w, 1, &v.shared().comments)?;
+            let variant_name = variant_name(v);
+            let content = match (v, e) {
+                (RustEnumVariant::Unit(_), _) => None,
+                (RustEnumVariant::Tuple { ty, .. }, RustEnum::Algebraic { content_key, .. }) => {
+                    Some(format!(
+                        "{content_key}: {}",
+                        self.format_type(ty, &shared.generic_types)
+                            .map_err(std::io::Error::other)?
+                    ))
+                }
+                (
+                    RustEnumVariant::AnonymousStruct {
+                        shared: variant_shared,
+                        fields,
+                    },
+                    RustEnum::Algebraic { content_key, .. },
+                ) => Some(format!(
+                    "{content_key}: {}",
+                    anonymous_struct_type(e, variant_shared, fields)
+                )),
+                _ => unreachable!("unit enums only have unit variants"),
+            };
+
+            match content {
+                Some(content) => writeln!(
+                    w,
+                    "\tcase {variant_name}{}({content}) extends {enum_type}({:?})",
+                    generic_parameters(&shared.generic_types),
+                    v.shared().id.renamed
+                )?,
+                None => writeln!(
+                    w,
+                    "\tcase {variant_name} extends {enum_type}({:?})",
+                    v.shared().id.renamed
+                )?,
+            }
+        }
+        writeln!(w)?;
+
+        if self.circe {
+            writeln!(w, "object {}:", shared.id.renamed)?;
+            self.write_circe_enum_codecs(w, e)?;
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_circe_struct_codecs(
+        &mut self,
+        w: &mut dyn Write,
+        rs: &RustStruct,
+    ) -> std::io::Result<()> {
+        let name = &rs.id.renamed;
+        let (encoder, decoder) = if rs.fields.is_empty() {
+            (
+                "Encoder.instance(_ => Json.obj())".to_owned(),
+                format!("Decoder.const(new {name})"),
+            )
+        } else {
+            ("deriveEncoder".to_owned(), "deriveDecoder".to_owned())
+        };
+
+        writeln!(w, "object {name} {{")?;
+        self.write_circe_instance(
+            w,
+            "Encoder",
+            &format!("{name}{}", generic_parameters(&rs.generic_types)),
+            &rs.generic_types,
+            &encoder,
+        )?;
+        self.write_circe_instance(
+            w,
+            "Decoder",
+            &format!("{name}{}", generic_parameters(&rs.generic_types)),
+            &rs.generic_types,
+            &decoder,
+        )?;
+        writeln!(w, "}}\n")
+    }
+
+    fn write_circe_enum_codecs(&mut self, w: &mut dyn Write, e: &RustEnum) -> std::io::Result<()> {
+        let shared = e.shared();
+        let name = &shared.id.renamed;
+        let ty = format!("{name}{}", generic_parameters(&shared.generic_types));
+        // `String` may be shadowed by a variant, so the string codecs are used explicitly.
+        let unknown = format!("s\"Unknown {name}: $other\"");
+
+        match e {
+            RustEnum::Unit(shared) => {
+                let cases = shared
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        format!(
+                            "\t\tcase {:?} => Right({})\n",
+                            v.shared().id.renamed,
+                            variant_name(v)
+                        )
+                    })
+                    .join("");
+
+                self.write_circe_instance(
+                    w,
+                    "Encoder",
+                    &ty,
+                    &[],
+                    "Encoder.encodeString.contramap(_.serialName)",
+                )?;
+                self.write_circe_instance(
+                    w,
+                    "Decoder",
+                    &ty,
+                    &[],
+                    &format!(
+                        "Decoder.decodeString.emap {{\n{cases}\t\tcase other => Left({unknown})\n\t}}"
+                    ),
+                )
+            }
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                shared,
+            } => {
+                let generics = generic_parameters(&shared.generic_types);
+                let mut encode_cases = String::new();
+                let mut decode_cases = String::new();
+
+                for v in &shared.variants {
+                    let variant_name = variant_name(v);
+                    let tag = format!(
+                        "{tag_key:?} -> Json.fromString({:?})",
+                        v.shared().id.renamed
+                    );
+                    let content_type = match v {
+                        RustEnumVariant::Unit(_) => None,
+                        RustEnumVariant::Tuple { ty, .. } => Some(
+                            self.format_type(ty, &shared.generic_types)
+                                .map_err(std::io::Error::other)?,
+                        ),
+                        RustEnumVariant::AnonymousStruct {
+                            shared: variant_shared,
+                            fields,
+                        } => Some(anonymous_struct_type(e, variant_shared, fields)),
+                    };
+
+                    match content_type {
+                        None => {
+                            encode_cases
+                                .push_str(&format!("\t\tcase {variant_name} => Json.obj({tag})\n"));
+                            decode_cases.push_str(&format!(
+                                "\t\t\tcase {:?} => Right({variant_name})\n",
+                                v.shared().id.renamed
+                            ));
+                        }
+                        Some(content_type) => {
+                            encode_cases.push_str(&format!(
+                                "\t\tcase {variant_name}(content) => Json.obj({tag}, {content_key:?} -> content.asJson)\n"
+                            ));
+                            decode_cases.push_str(&format!(
+                                "\t\t\tcase {:?} => c.downField({content_key:?}).as[{content_type}].map({variant_name}{generics}(_))\n",
+                                v.shared().id.renamed
+                            ));
+                        }
+                    }
+                }
+
+                self.write_circe_instance(
+                    w,
+                    "Encoder",
+                    &ty,
+                    &shared.generic_types,
+                    &format!("Encoder.instance {{\n{encode_cases}\t}}"),
+                )?;
+                self.write_circe_instance(
+                    w,
+                    "Decoder",
+                    &ty,
+                    &shared.generic_types,
+                    &format!(
+                        "Decoder.instance {{ c =>\n\t\tc.downField({tag_key:?}).as(Decoder.decodeString).flatMap {{\n{decode_cases}\t\t\tcase other => Left(DecodingFailure({unknown}, c.history))\n\t\t}}\n\t}}"
+                    ),
+                )
+            }
+        }
+    }
+
+    /// Writes an implicit circe `Encoder` or `Decoder` (the `codec`) for a type,
+    /// which requires instances for its generic parameters.
+    fn write_circe_instance(
+        &mut self,
+        w: &mut dyn Write,
+        codec: &str,
+        ty: &str,
+        generic_types: &[String],
+        body: &str,
+    ) -> std::io::Result<()> {
+        let name = codec.to_lowercase();
+        if self.scala3 && generic_types.is_empty() {
+            writeln!(w, "\tgiven {codec}[{ty}] = {body}")
+        } else if self.scala3 {
+            writeln!(
+                w,
+                "\tgiven [{}]: {codec}[{ty}] = {body}",
+                generic_types
+                    .iter()
+                    .map(|g| format!("{g}: {codec}"))
+                    .join(", ")
+            )
+        } else if generic_types.is_empty() {
+            writeln!(w, "\timplicit val {name}: {codec}[{ty}] = {body}")
+        } else {
+            writeln!(
+                w,
+                "\timplicit def {name}[{}]: {codec}[{ty}] = {body}",
+                generic_types
+                    .iter()
+                    .map(|g| format!("{g}: {codec}"))
+                    .join(", ")
+            )
+        }
+    }
+
     fn write_element(
         &mut self,
         w: &mut dyn Write,
@@ -389,11 +1111,15 @@ impl Scala {
         write!(
             w,
             "\t{}: {}{}",
-            remove_dash_from_identifier(&f.id.renamed),
+            self.identifier(&f.id.renamed),
             ty,
             (f.has_default && !f.ty.is_optional())
                 .then_some(" = _")
-                .or_else(|| f.ty.is_optional().then_some(" = None"))
+                .or_else(|| {
+                    (f.ty.is_optional()
+                        && (f.has_default || self.optional_fields != Some(OptionalFields::Null)))
+                    .then_some(" = None")
+                })
                 .unwrap_or_default()
         )
     }
@@ -419,6 +1145,19 @@ impl Scala {
             .try_for_each(|comment| self.write_comment(w, indent, comment))
     }
 
+    /// Write the constant with the schema hash of a type, if they're written.
+    fn write_schema_hash_of<T: Clone>(
+        &mut self,
+        w: &mut dyn Write,
+        item: fn(T) -> RustItem,
+        ty: &T,
+    ) -> std::io::Result<()> {
+        if !self.schema_hashes {
+            return Ok(());
+        }
+        write_item_schema_hash(self, w, &item(ty.clone()))
+    }
+
     fn begin_package_object(&mut self, w: &mut dyn Write) -> std::io::Result<()> {
         match self.package.rsplit_once('.') {
             None => {}
@@ -461,6 +1200,10 @@ impl Scala {
     }
 
     fn unsigned_integer_used(&mut self, data: &ParsedData) -> bool {
+        // Scala.js facades map unsigned integers to wider types instead
+        if self.scala_js {
+            return false;
+        }
         let types_in_aliases = data.aliases.iter().map(|f| f.r#type.clone()).collect_vec();
         let types_in_structs = data
             .structs
@@ -509,3 +1252,62 @@ impl Scala {
             })
     }
 }
+
+/// The name of an enum variant, which is made a valid identifier if it
+/// starts with a digit by adding an underscore to the front.
+fn variant_name(v: &RustEnumVariant) -> String {
+    let variant_name = v.shared().id.original.to_string();
+
+    if variant_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+    {
+        format!("_{variant_name}")
+    } else {
+        variant_name
+    }
+}
+
+/// The type generated for an anonymous struct variant of an enum, with its generic parameters.
+fn anonymous_struct_type(
+    e: &RustEnum,
+    shared: &RustEnumVariantShared,
+    fields: &[RustField],
+) -> String {
+    // Builds the list of generic types (e.g [T, U, V]), by digging
+    // through the fields recursively and comparing against the
+    // enclosing enum's list of generic parameters.
+    let generics = fields
+        .iter()
+        .flat_map(|field| {
+            e.shared()
+                .generic_types
+                .iter()
+                .filter(|g| field.ty.contains_type(g))
+        })
+        .unique()
+        .collect_vec();
+
+    // Sadly the parenthesis are required because of macro limitations
+    let generics = lazy_format!(match (generics.is_empty()) {
+        false => ("[{}]", generics.iter().join_with(", ")),
+        true => (""),
+    });
+
+    format!(
+        "{}{}Inner{}",
+        e.shared().id.original,
+        shared.id.original,
+        generics
+    )
+}
+
+fn generic_parameters(generic_types: &[String]) -> String {
+    if generic_types.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", generic_types.join(", "))
+    }
+}
@@ -0,0 +1,259 @@
+//! The code style of generated files, like their indentation.
+/// What every level of indentation is written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// A tab.
+    Tabs,
+    /// A number of spaces.
+    Spaces(usize),
+}
+
+/// How generated code is indented and wrapped, instead of the default of the language.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CodeStyle {
+    /// The indentation of every level, instead of the one the language writes.
+    pub indent: Option<Indent>,
+    /// The maximum width of comment lines, which longer lines are wrapped at. Code
+    /// isn't wrapped.
+    pub max_line_width: Option<usize>,
+}
+
+/// What the comment lines which are wrapped at the maximum line width start with, after
+/// their indentation.
+const LINE_COMMENTS: &[&str] = &["/// ", "// ", "* ", "# "];
+
+/// What the comment lines which are read by tools start with, after their indentation,
+/// which are never wrapped, like Go's build constraints and generated-file marker.
+const DIRECTIVE_COMMENTS: &[&str] = &[
+    "//go:",
+    "// +build",
+    "//+build",
+    "// Code generated ",
+    "//nolint",
+    "//export ",
+    "//line ",
+    "// swiftlint:",
+    "// eslint-",
+    "/* eslint-",
+    "// @ts-",
+    "// prettier-ignore",
+    "# type:",
+    "# noqa",
+    "# pyright:",
+    "#!",
+];
+
+/// Delimits the Python docstrings, whose lines are wrapped too.
+const DOCSTRING: &str = "\"\"\"";
+
+impl CodeStyle {
+    /// The source in this code style. A language writes every level of indentation as
+    /// a tab or `native_width` spaces.
+    pub(crate) fn restyled(&self, source: String, native_width: usize) -> String {
+        if *self == CodeStyle::default() {
+            return source;
+        }
+        self.restyle(&source, native_width)
+    }
+
+    fn restyle(&self, contents: &str, native_width: usize) -> String {
+        let mut output = String::with_capacity(contents.len());
+        let mut in_docstring = false;
+        for line in contents.split_inclusive('\n') {
+            let (line, newline) = match line.strip_suffix('\n') {
+                Some(line) => (line, "\n"),
+                None => (line, ""),
+            };
+            let (indent, rest) = self.reindent(line, native_width);
+            output.push_str(&indent);
+            match self.max_line_width {
+                Some(max_width) if rest != DOCSTRING => {
+                    let indent_width = indent
+                        .chars()
+                        .map(|c| if c == '\t' { self.tab_width() } else { 1 })
+                        .sum::<usize>();
+                    let max_width = max_width.saturating_sub(indent_width);
+                    wrap_comment(&mut output, &indent, rest, max_width, in_docstring);
+                }
+                _ => output.push_str(rest),
+            }
+            if rest == DOCSTRING {
+                in_docstring = !in_docstring;
+            }
+            output.push_str(newline);
+        }
+        output
+    }
+
+    /// The indentation of the line in this style, and the rest of the line. Spaces
+    /// which don't make up a level, like those aligning comments, are kept.
+    fn reindent<'a>(&self, line: &'a str, native_width: usize) -> (String, &'a str) {
+        let rest = line.trim_start_matches([' ', '\t']);
+        let whitespace = &line[..line.len() - rest.len()];
+        let Some(indent) = self.indent else {
+            return (whitespace.to_owned(), rest);
+        };
+        let mut levels = 0;
+        let mut spaces = 0;
+        for c in whitespace.chars() {
+            if c == '\t' {
+                levels += 1;
+                spaces = 0;
+            } else {
+                spaces += 1;
+                if spaces == native_width {
+                    levels += 1;
+                    spaces = 0;
+                }
+            }
+        }
+        let level = match indent {
+            Indent::Tabs => "\t".to_owned(),
+            Indent::Spaces(width) => " ".repeat(width),
+        };
+        (level.repeat(levels) + &" ".repeat(spaces), rest)
+    }
+
+    /// The width tabs are counted as.
+    fn tab_width(&self) -> usize {
+        match self.indent {
+            Some(Indent::Spaces(width)) => width,
+            Some(Indent::Tabs) | None => 4,
+        }
+    }
+}
+
+/// Write the line after its indentation, wrapping a comment longer than `max_width`
+/// between words into lines of the same indentation. Line comments are continued with
+/// the same prefix, and block comments of a single line are written over multiple lines.
+/// Directive comments are never wrapped.
+fn wrap_comment(
+    output: &mut String,
+    indent: &str,
+    line: &str,
+    max_width: usize,
+    in_docstring: bool,
+) {
+    if line.chars().count() <= max_width
+        || DIRECTIVE_COMMENTS
+            .iter()
+            .any(|directive| line.starts_with(directive))
+    {
+        output.push_str(line);
+        return;
+    }
+    if in_docstring {
+        return wrap_words(output, indent, "", line, max_width);
+    }
+    if let Some(prefix) = LINE_COMMENTS
+        .iter()
+        .find(|prefix| line.starts_with(**prefix))
+    {
+        output.push_str(prefix);
+        return wrap_words(output, indent, prefix, &line[prefix.len()..], max_width);
+    }
+    let block = ["/**", "/*"].into_iter().find_map(|start| {
+        let text = line.strip_prefix(start)?.strip_suffix("*/")?;
+        Some((start, text.trim()))
+    });
+    match block {
+        Some((start, text)) if !text.is_empty() => {
+            output.push_str(start);
+            output.push('\n');
+            output.push_str(indent);
+            output.push_str(" * ");
+            wrap_words(output, indent, " * ", text, max_width);
+            output.push('\n');
+            output.push_str(indent);
+            output.push_str(" */");
+        }
+        _ => output.push_str(line),
+    }
+}
+
+/// Write the words of the text after `prefix`, which is already written, starting new
+/// lines of the indentation and prefix before words which would be past `max_width`.
+fn wrap_words(output: &mut String, indent: &str, prefix: &str, text: &str, max_width: usize) {
+    let start_width = prefix.chars().count();
+    let mut width = start_width;
+    for (index, word) in text.split(' ').enumerate() {
+        let word_width = word.chars().count();
+        if index > 0 {
+            if width > start_width && width + 1 + word_width > max_width {
+                output.push('\n');
+                output.push_str(indent);
+                output.push_str(prefix);
+                width = start_width;
+            } else {
+                output.push(' ');
+                width += 1;
+            }
+        }
+        output.push_str(word);
+        width += word_width;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CodeStyle, Indent};
+
+    #[test]
+    fn reindents_levels() {
+        let style = CodeStyle {
+            indent: Some(Indent::Spaces(2)),
+            max_line_width: None,
+        };
+        assert_eq!(
+            style.restyle(
+                "struct A {\n\tfunc a() {\n\t\treturn\n    }\n}\n/**\n * Doc\n */",
+                4
+            ),
+            "struct A {\n  func a() {\n    return\n  }\n}\n/**\n * Doc\n */"
+        );
+        let style = CodeStyle {
+            indent: Some(Indent::Tabs),
+            max_line_width: None,
+        };
+        assert_eq!(
+            style.restyle("type t =\n  | A\n    | B\n", 2),
+            "type t =\n\t| A\n\t\t| B\n"
+        );
+    }
+
+    #[test]
+    fn wraps_comments() {
+        let style = CodeStyle {
+            indent: None,
+            max_line_width: Some(24),
+        };
+        assert_eq!(
+            style.restyle(
+                "\t/// The identifier of the user, which never changes\n\tlet id: String = \"a long string literal\"\n",
+                4
+            ),
+            "\t/// The identifier\n\t/// of the user,\n\t/// which never\n\t/// changes\n\tlet id: String = \"a long string literal\"\n"
+        );
+        assert_eq!(
+            style.restyle("/** The identifier of the user */\nid: string;\n", 4),
+            "/**\n * The identifier of the\n * user\n */\nid: string;\n"
+        );
+        assert_eq!(
+            style.restyle(
+                "    \"\"\"\n    The identifier of the user\n    \"\"\"\n",
+                4
+            ),
+            "    \"\"\"\n    The identifier of\n    the user\n    \"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn keeps_directive_comments() {
+        let style = CodeStyle {
+            indent: None,
+            max_line_width: Some(24),
+        };
+        let source = "// Code generated by typeshare 1.13.3. DO NOT EDIT.\n//go:generate stringer -type=State\n\t// +build linux,amd64 darwin,arm64\n";
+        assert_eq!(style.restyle(source, 4), source);
+    }
+}
@@ -1,11 +1,14 @@
 use crate::{
     error::GenerationError,
-    language::{Language, SupportedLanguage},
+    language::{
+        CodeStyle, CrateName, Escape, FileHeader, IdentifierPolicy, Language, SupportedLanguage,
+    },
+    lint::{unmapped_json_values, Warning},
     parser::{remove_dash_from_identifier, DecoratorKind, ParsedData},
     rename::RenameExt,
     rust_types::{
-        DecoratorMap, RustConst, RustEnum, RustEnumVariant, RustStruct, RustTypeAlias,
-        RustTypeFormatError, SpecialRustType,
+        DecoratorMap, Id, RustConst, RustEnum, RustEnumShared, RustEnumVariant, RustField,
+        RustStruct, RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
     },
 };
 use itertools::{Either, Itertools};
@@ -13,7 +16,7 @@ use joinery::JoinableIterator;
 use lazy_format::lazy_format;
 use std::{
     borrow::Cow,
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs,
     io::{self, Write},
     path::Path,
@@ -78,6 +81,11 @@ const SWIFT_KEYWORDS: &[&str] = &[
     "Type",
 ];
 
+const IDENTIFIERS: IdentifierPolicy = IdentifierPolicy {
+    keywords: SWIFT_KEYWORDS,
+    escape: Escape::Quote("`"),
+};
+
 const CODABLE: &str = "Codable";
 
 /// Information on serialization/deserialization coding keys.
@@ -132,6 +140,21 @@ impl Default for GenericConstraints {
     }
 }
 
+/// How `CodingKeys` are generated for structs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodingKeyStrategy {
+    /// Only emit `CodingKeys` when a key can't be used as a property name as-is.
+    #[default]
+    Default,
+    /// Name properties after the camel cased keys, relying on the decoder using
+    /// `keyDecodingStrategy = .convertFromSnakeCase` (and the encoder using
+    /// `keyEncodingStrategy = .convertToSnakeCase`), so `CodingKeys` are only
+    /// emitted when that transformation isn't enough.
+    ConvertFromSnakeCase,
+    /// Always emit `CodingKeys`.
+    Explicit,
+}
+
 /// All information needed to generate Swift type-code
 #[derive(Default)]
 pub struct Swift {
@@ -150,10 +173,50 @@ pub struct Swift {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `public let UserSchemaHash = "…"`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// Identifiers which are escaped like Swift keywords, besides the keywords.
+    pub reserved_words: HashSet<String>,
     /// Are we generating multiple modules?
     pub multi_file: bool,
     /// The constraints to apply to `CodableVoid`.
     pub codablevoid_constraints: Vec<String>,
+    /// Whether optional parameters of the generated `public init` should default to `nil`.
+    pub init_optional_defaults: bool,
+    /// Whether fields with `#[serde(default)]` should be emitted as non-optional properties
+    /// with a Swift default value, decoded with `decodeIfPresent` and a fallback.
+    /// Only applies to fields whose type has a well-known default.
+    pub serde_default_values: bool,
+    /// The name of the SwiftPM package to generate in multi-file mode.
+    /// When set, the output folder is the root of the package, which a `Package.swift`
+    /// manifest is written to, and every Rust crate (or module) is a target of the package,
+    /// whose file is expected in `Sources/<target>`, named by [`Swift::package_target`].
+    pub package_name: Option<String>,
+    /// How `CodingKeys` are generated for structs.
+    pub coding_keys: CodingKeyStrategy,
+    /// The name of a fallback case, such as `unknown`, to add to every enum.
+    /// Values that don't match any known variant are decoded into this case
+    /// with their raw value (or tag, for algebraic enums) instead of failing.
+    pub unknown_case: Option<String>,
+    /// Whether to generate a `static func mock(...)` factory for every struct, with
+    /// placeholder defaults for its fields, for use in unit tests and previews.
+    pub mocks: bool,
+    /// Whether to conform every struct and enum to SwiftCheck's `Arbitrary`, generating
+    /// values of it for property-based tests.
+    pub arbitraries: bool,
+    /// Placeholder values of the typeshared types in the current file, such as `.mock()`,
+    /// used as defaults of `mock` parameters. This is filled in when a file is generated.
+    pub mock_values: HashMap<String, String>,
+    /// The targets of the package, with the targets they import, of the files generated
+    /// so far. This is filled in when a file is generated.
+    pub package_targets: BTreeMap<String, BTreeSet<String>>,
+    /// The target of the file being generated, when generating a package.
+    pub current_target: Option<String>,
 }
 
 impl Language for Swift {
@@ -161,14 +224,18 @@ impl Language for Swift {
         &self.type_mappings
     }
 
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
+
     fn format_simple_type(
         &mut self,
-        base: &String,
+        base: &str,
         generic_types: &[String],
     ) -> Result<String, RustTypeFormatError> {
         Ok(if let Some(mapped) = self.type_map().get(base) {
             mapped.into()
-        } else if generic_types.contains(base) {
+        } else if generic_types.iter().any(|g| g == base) {
             base.into()
         } else {
             format!("{}{}", self.prefix, base)
@@ -215,6 +282,9 @@ impl Language for Swift {
             SpecialRustType::Bool => "Bool".into(),
             SpecialRustType::F32 => "Float".into(),
             SpecialRustType::F64 => "Double".into(),
+            SpecialRustType::JsonValue => {
+                self.format_simple_type(special_ty.id(), generic_types)?
+            }
             // TODO: https://github.com/1Password/typeshare/issues/237
             SpecialRustType::DateTime => {
                 return Err(RustTypeFormatError::UnsupportedSpecialType(
@@ -224,19 +294,39 @@ impl Language for Swift {
         })
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        if self.generates_package() {
+            let target = Self::package_target(&parsed_data.crate_name);
+            self.package_targets.entry(target.clone()).or_default();
+            self.current_target = Some(target);
+        }
+        if self.mocks {
+            self.collect_mock_values(parsed_data);
+        }
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "/*", " ", " */")?;
+            writeln!(w)?;
+        } else if !self.no_version_header {
             writeln!(w, "/*")?;
             writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
             writeln!(w, " */")?;
             writeln!(w)?;
         }
         writeln!(w, "import Foundation")?;
+        if self.arbitraries {
+            writeln!(w, "import SwiftCheck")?;
+        }
         Ok(())
     }
 
     fn end_file(&mut self, w: &mut dyn Write) -> io::Result<()> {
-        if self.should_emit_codable_void.load(Ordering::SeqCst) && !self.multi_file {
+        // The targets of a package each define `CodableVoid`, which shadows those of
+        // the targets they import.
+        if self.generates_package() {
+            if self.should_emit_codable_void.swap(false, Ordering::SeqCst) {
+                self.write_codable(w, &self.get_codable_contents())?;
+            }
+        } else if self.should_emit_codable_void.load(Ordering::SeqCst) && !self.multi_file {
             self.write_codable(w, &self.get_codable_contents())?;
         }
 
@@ -248,7 +338,7 @@ impl Language for Swift {
         self.write_comments(w, 0, &ty.comments)?;
 
         let swift_prefix = &self.prefix;
-        let type_name = swift_keyword_aware_rename(format!("{}{}", swift_prefix, ty.id.renamed));
+        let type_name = self.keyword_aware_rename(format!("{}{}", swift_prefix, ty.id.renamed));
 
         writeln!(
             w,
@@ -267,7 +357,23 @@ impl Language for Swift {
     }
 
     fn write_const(&mut self, _w: &mut dyn Write, _c: &RustConst) -> std::io::Result<()> {
-        todo!()
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "typeshare doesn't generate constants in Swift",
+        ))
+    }
+
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(
+        &mut self,
+        w: &mut dyn Write,
+        name: &str,
+        hash: &str,
+    ) -> std::io::Result<()> {
+        writeln!(w, "\npublic let {name} = \"{hash}\"")
     }
 
     fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
@@ -277,7 +383,7 @@ impl Language for Swift {
         writeln!(w)?;
         self.write_comments(w, 0, &rs.comments)?;
 
-        let type_name = swift_keyword_aware_rename(format!("{}{}", self.prefix, rs.id.renamed));
+        let type_name = self.keyword_aware_rename(format!("{}{}", self.prefix, rs.id.renamed));
 
         // If there are no decorators found for this struct, still write `Codable` and default decorators for structs
         // Check if this struct's decorators contains swift in the hashmap
@@ -315,15 +421,17 @@ impl Language for Swift {
         for f in &rs.fields {
             self.write_comments(w, 1, &f.comments)?;
 
+            let key = self.coding_key(f);
+
             // Create coding keys for serialization / deserialization
             //
             // As of right now this was only written to handle fields
             // that get renamed to an ident with - in it
-            if f.id.renamed.chars().any(|c| c == '-') {
+            if key.chars().any(|c| c == '-') {
                 coding_keys.push(format!(
                     r##"{} = "{}""##,
-                    remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref()),
-                    &f.id.renamed
+                    remove_dash_from_identifier(self.keyword_aware_rename(key.as_ref()).as_ref()),
+                    key
                 ));
 
                 // We only need to write out coding keys if we encounter a
@@ -331,7 +439,7 @@ impl Language for Swift {
                 should_write_coding_keys = true;
             } else {
                 coding_keys.push(remove_dash_from_identifier(
-                    swift_keyword_aware_rename(&f.id.renamed).as_ref(),
+                    self.keyword_aware_rename(key.as_ref()).as_ref(),
                 ));
             }
 
@@ -345,9 +453,9 @@ impl Language for Swift {
             writeln!(
                 w,
                 "\tpublic let {}: {}{}",
-                remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref()),
+                remove_dash_from_identifier(self.keyword_aware_rename(key.as_ref()).as_ref()),
                 case_type,
-                if f.has_default && !f.ty.is_optional() {
+                if f.has_default && !f.ty.is_optional() && self.default_value(f).is_none() {
                     "?"
                 } else {
                     Default::default()
@@ -355,7 +463,14 @@ impl Language for Swift {
             )?;
         }
 
-        if should_write_coding_keys {
+        // A custom `init(from:)` is needed to fall back to the default value
+        // when a key is missing, which in turn needs the coding keys.
+        let has_default_values = rs.fields.iter().any(|f| self.default_value(f).is_some());
+
+        if should_write_coding_keys
+            || has_default_values
+            || (self.coding_keys == CodingKeyStrategy::Explicit && !rs.fields.is_empty())
+        {
             writeln!(
                 w,
                 r#"
@@ -379,33 +494,73 @@ impl Language for Swift {
                     .map_err(io::Error::other)?,
             };
 
+            let default_value = self.default_value(f);
             init_params.push(format!(
-                "{}: {}{}",
-                remove_dash_from_identifier(&f.id.renamed),
+                "{}: {}{}{}",
+                remove_dash_from_identifier(&self.coding_key(f)),
                 swift_ty,
-                if f.has_default && !f.ty.is_optional() {
+                if f.has_default && !f.ty.is_optional() && default_value.is_none() {
                     "?"
                 } else {
                     Default::default()
+                },
+                match default_value {
+                    Some(value) => format!(" = {value}"),
+                    None if self.init_optional_defaults
+                        && (f.has_default || f.ty.is_optional()) =>
+                    {
+                        " = nil".to_owned()
+                    }
+                    None => Default::default(),
                 }
             ));
         }
 
         write!(w, "\tpublic init({}) {{", init_params.join(", "))?;
         for f in &rs.fields {
+            let key = self.coding_key(f);
             write!(
                 w,
                 "\n\t\tself.{} = {}",
-                remove_dash_from_identifier(&f.id.renamed),
-                remove_dash_from_identifier(swift_keyword_aware_rename(&f.id.renamed).as_ref())
+                remove_dash_from_identifier(&key),
+                remove_dash_from_identifier(self.keyword_aware_rename(key.as_ref()).as_ref())
             )?;
         }
         if !rs.fields.is_empty() {
             write!(w, "\n\t")?;
         }
         writeln!(w, "}}")?;
+
+        if has_default_values {
+            self.write_decoding_init(w, rs)?;
+        }
+
         writeln!(w, "}}")?;
 
+        if self.mocks {
+            self.write_mock(w, rs, &type_name)?;
+        }
+
+        if self.arbitraries {
+            let generator = if rs.fields.is_empty() {
+                format!("Gen.pure({type_name}())")
+            } else {
+                let arguments = rs
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{}: {}",
+                            remove_dash_from_identifier(&self.coding_key(f)),
+                            arbitrary_value(&f.ty)
+                        )
+                    })
+                    .join(", ");
+                format!("Gen.compose {{ c in\n\t\t\t{type_name}({arguments})\n\t\t}}")
+            };
+            self.write_arbitrary(w, &type_name, &rs.generic_types, &generator)?;
+        }
+
         Ok(())
     }
 
@@ -434,8 +589,16 @@ impl Language for Swift {
         }
 
         let shared = e.shared();
-        let enum_name = swift_keyword_aware_rename(format!("{}{}", self.prefix, shared.id.renamed));
+        let enum_name = self.keyword_aware_rename(format!("{}{}", self.prefix, shared.id.renamed));
+        let unknown_case = self
+            .unknown_case
+            .as_deref()
+            .map(|c| self.keyword_aware_rename(c).into_owned());
         let always_present = match e {
+            // A case with an associated value rules out a `String` raw type
+            RustEnum::Unit(_) if unknown_case.is_some() => {
+                self.get_default_decorators().collect::<Vec<_>>()
+            }
             RustEnum::Unit(_) => ["String"]
                 .into_iter()
                 .chain(self.get_default_decorators())
@@ -472,6 +635,14 @@ impl Language for Swift {
 
         let coding_keys_info = self.write_enum_variants(w, e, make_anonymous_struct_name)?;
 
+        if let Some(unknown_case) = &unknown_case {
+            writeln!(w, "\tcase {unknown_case}(String)")?;
+
+            if let RustEnum::Unit(shared) = e {
+                self.write_unit_enum_coding(w, shared, unknown_case)?;
+            }
+        }
+
         if !coding_keys_info.coding_keys.is_empty() {
             writeln!(
                 w,
@@ -501,13 +672,13 @@ impl Language for Swift {
 		if let type = try? container.decode(CodingKeys.self, forKey: .{tag_key}) {{
 			switch type {{{decoding_switch}
 			}}
-		}}
+		}}{unknown_decoding}
 		throw DecodingError.typeMismatch({type_name}.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Wrong type for {type_name}"))
 	}}
 
 	public func encode(to encoder: Encoder) throws {{
 		var container = encoder.container(keyedBy: ContainerCodingKeys.self)
-		switch self {{{encoding_switch}
+		switch self {{{encoding_switch}{unknown_encoding}
 		}}
 	}}"#,
                 tag_key = tag_key,
@@ -515,41 +686,94 @@ impl Language for Swift {
                 type_name = enum_name,
                 decoding_switch = coding_keys_info.decoding_cases.join(""),
                 encoding_switch = coding_keys_info.encoding_cases.join(""),
+                unknown_decoding = unknown_case
+                    .as_ref()
+                    .map(|unknown_case| format!(
+                        " else if let type = try? container.decode(String.self, forKey: .{tag_key}) {{
+			self = .{unknown_case}(type)
+			return
+		}}"
+                    ))
+                    .unwrap_or_default(),
+                unknown_encoding = unknown_case
+                    .as_ref()
+                    .map(|unknown_case| format!(
+                        "
+		case .{unknown_case}(let type):
+			try container.encode(type, forKey: .{tag_key})"
+                    ))
+                    .unwrap_or_default(),
             )?;
         }
 
-        writeln!(w, "}}")
-    }
+        writeln!(w, "}}")?;
 
-    // TODO: This will be added in the future.
-    // fn write_imports(
-    //     &mut self,
-    //     w: &mut dyn Write,
-    //     imports: super::ScopedCrateTypes<'_>,
-    // ) -> std::io::Result<()> {
-    //     for module in imports.keys() {
-    //         writeln!(w, "import {}", module.0.to_pascal_case())?;
-    //     }
-    //     writeln!(w)
-    // }
+        if self.arbitraries {
+            let generator = self.arbitrary_enum_generator(e);
+            self.write_arbitrary(w, &enum_name, &shared.generic_types, &generator)?;
+        }
+
+        Ok(())
+    }
 
     fn write_imports(
         &mut self,
-        _writer: &mut dyn Write,
-        _imports: super::ScopedCrateTypes<'_>,
+        w: &mut dyn Write,
+        imports: super::ScopedCrateTypes<'_>,
     ) -> std::io::Result<()> {
-        // This will be added to Foundation for now.
+        // Without a package, every file is added to the same module for now.
+        let Some(current_target) = self.current_target.clone() else {
+            return Ok(());
+        };
+        let targets = imports
+            .keys()
+            .map(|crate_name| Self::package_target(crate_name))
+            .filter(|target| *target != current_target)
+            .collect::<BTreeSet<_>>();
+        for target in &targets {
+            writeln!(w, "import {target}")?;
+        }
+        self.package_targets
+            .entry(current_target)
+            .or_default()
+            .extend(targets);
         Ok(())
     }
 
     fn post_generation(&self, output_folder: &str) -> Result<(), GenerationError> {
-        //
-        if self.should_emit_codable_void.load(Ordering::SeqCst) && self.multi_file {
+        if self.should_emit_codable_void.load(Ordering::SeqCst)
+            && self.multi_file
+            && !self.generates_package()
+        {
             self.write_codable_file(output_folder)
                 .map_err(|e| GenerationError::PostGeneration(e.to_string()))?;
         }
+        if let Some(package_name) = self.package_name.as_ref().filter(|_| self.multi_file) {
+            self.write_package_manifest(output_folder, package_name)
+                .map_err(|e| GenerationError::PostGeneration(e.to_string()))?;
+        }
         Ok(())
     }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        unmapped_json_values(data, "Swift", &self.type_mappings)
+    }
+
+    fn type_identifier(&self, id: &Id) -> String {
+        format!("{}{}", self.prefix, id.renamed)
+    }
+
+    fn field_identifier(&self, field: &RustField) -> String {
+        remove_dash_from_identifier(&self.coding_key(field))
+    }
+
+    fn variant_identifier(&self, variant: &RustEnumVariant) -> String {
+        case_name(variant)
+    }
+
+    fn contains_referenced_structs(&self) -> bool {
+        true
+    }
 }
 
 impl Swift {
@@ -565,19 +789,27 @@ impl Swift {
 
         match e {
             RustEnum::Unit(shared) => {
+                // Once any case is renamed, every case spells out its raw value, so
+                // that the wire strings don't depend on the names of the cases.
+                let explicit_raw_values = self.unknown_case.is_none()
+                    && shared
+                        .variants
+                        .iter()
+                        .any(|v| v.shared().id.renamed != v.shared().id.original.to_camel_case());
+
                 for v in &shared.variants {
                     let variant_name = v.shared().id.original.to_camel_case();
 
                     self.write_comments(w, 1, &v.shared().comments)?;
-                    if v.shared().id.renamed == variant_name {
+                    if !explicit_raw_values {
                         // We don't need to handle any renaming
-                        writeln!(w, "\tcase {}", &swift_keyword_aware_rename(&variant_name))?;
+                        writeln!(w, "\tcase {}", &self.keyword_aware_rename(&variant_name))?;
                     } else {
                         // We do need to handle renaming
                         writeln!(
                             w,
                             "\tcase {} = {:?}",
-                            swift_keyword_aware_rename(&variant_name),
+                            self.keyword_aware_rename(&variant_name),
                             &v.shared().id.renamed
                         )?;
                     }
@@ -592,34 +824,19 @@ impl Swift {
                 for v in &shared.variants {
                     self.write_comments(w, 1, &v.shared().comments)?;
 
-                    let variant_name = {
-                        let mut variant_name = v.shared().id.original.to_camel_case();
-
-                        if variant_name
-                            .chars()
-                            .next()
-                            .map(|c| c.is_ascii_digit())
-                            .unwrap_or(false)
-                        {
-                            // If the name starts with a digit just add an underscore
-                            // to the front and make it valid
-                            variant_name = format!("_{variant_name}");
-                        }
-
-                        variant_name
-                    };
+                    let variant_name = case_name(v);
 
                     coding_keys.push(if variant_name == v.shared().id.renamed {
-                        swift_keyword_aware_rename(&variant_name).into_owned()
+                        self.keyword_aware_rename(&variant_name).into_owned()
                     } else {
                         format!(
                             r##"{} = "{}""##,
-                            swift_keyword_aware_rename(&variant_name),
+                            self.keyword_aware_rename(&variant_name),
                             &v.shared().id.renamed
                         )
                     });
 
-                    write!(w, "\tcase {}", swift_keyword_aware_rename(&variant_name))?;
+                    write!(w, "\tcase {}", self.keyword_aware_rename(&variant_name))?;
 
                     match v {
                         RustEnumVariant::Unit(_) => {
@@ -636,7 +853,7 @@ impl Swift {
 		case .{case_name}:
 			try container.encode(CodingKeys.{case_name}, forKey: .{tag_key})",
                                 tag_key = tag_key,
-                                case_name = swift_keyword_aware_rename(&variant_name),
+                                case_name = self.keyword_aware_rename(&variant_name),
                             ));
                         }
                         RustEnumVariant::Tuple { ty, .. } => {
@@ -644,7 +861,7 @@ impl Swift {
                             let case_type = self
                                 .format_type(ty, e.shared().generic_types.as_slice())
                                 .map_err(io::Error::other)?;
-                            write!(w, "({})", swift_keyword_aware_rename(&case_type))?;
+                            write!(w, "({})", self.keyword_aware_rename(&case_type))?;
 
                             if content_optional {
                                 decoding_cases.push(format!(
@@ -659,7 +876,7 @@ impl Swift {
 					return
 				}}",
                                     content_key = content_key,
-                                    case_type = swift_keyword_aware_rename(&case_type),
+                                    case_type = self.keyword_aware_rename(&case_type),
                                     case_name = &variant_name
                                 ))
                             } else {
@@ -671,7 +888,7 @@ impl Swift {
 					return
 				}}",
                                     content_key = content_key,
-                                    case_type = swift_keyword_aware_rename(&case_type),
+                                    case_type = self.keyword_aware_rename(&case_type),
                                     case_name = &variant_name,
                                 ));
                             }
@@ -749,6 +966,298 @@ impl Swift {
         })
     }
 
+    /// Write the coding for a unit enum with a fallback case, which can't rely
+    /// on a `String` raw value.
+    fn write_unit_enum_coding(
+        &mut self,
+        w: &mut dyn Write,
+        shared: &RustEnumShared,
+        unknown_case: &str,
+    ) -> io::Result<()> {
+        let cases = shared
+            .variants
+            .iter()
+            .map(|v| {
+                (
+                    self.keyword_aware_rename(v.shared().id.original.to_camel_case()),
+                    &v.shared().id.renamed,
+                )
+            })
+            .collect_vec();
+
+        writeln!(w)?;
+        writeln!(w, "\tpublic init(from decoder: Decoder) throws {{")?;
+        writeln!(
+            w,
+            "\t\tlet value = try decoder.singleValueContainer().decode(String.self)"
+        )?;
+        writeln!(w, "\t\tswitch value {{")?;
+        for (case_name, value) in &cases {
+            writeln!(w, "\t\tcase {value:?}: self = .{case_name}")?;
+        }
+        writeln!(w, "\t\tdefault: self = .{unknown_case}(value)")?;
+        writeln!(w, "\t\t}}")?;
+        writeln!(w, "\t}}")?;
+        writeln!(w)?;
+        writeln!(w, "\tpublic func encode(to encoder: Encoder) throws {{")?;
+        writeln!(w, "\t\tvar container = encoder.singleValueContainer()")?;
+        writeln!(w, "\t\tswitch self {{")?;
+        for (case_name, value) in &cases {
+            writeln!(w, "\t\tcase .{case_name}: try container.encode({value:?})")?;
+        }
+        writeln!(
+            w,
+            "\t\tcase .{unknown_case}(let value): try container.encode(value)"
+        )?;
+        writeln!(w, "\t\t}}")?;
+        writeln!(w, "\t}}")
+    }
+
+    /// The key of a field as seen by the Swift decoder, which is also used to
+    /// name the field's property.
+    fn coding_key<'a>(&self, f: &'a RustField) -> Cow<'a, str> {
+        match self.coding_keys {
+            CodingKeyStrategy::ConvertFromSnakeCase => convert_from_snake_case(&f.id.renamed),
+            CodingKeyStrategy::Default | CodingKeyStrategy::Explicit => {
+                Cow::Borrowed(&f.id.renamed)
+            }
+        }
+    }
+
+    /// The Swift default value for a field with `#[serde(default)]`, if
+    /// `serde_default_values` is enabled and the field's type has a known default.
+    /// The default of a type in the type mappings is that of the type it's mapped to.
+    fn default_value(&self, f: &RustField) -> Option<&'static str> {
+        if !self.serde_default_values
+            || !f.has_default
+            || f.type_override(SupportedLanguage::Swift).is_some()
+        {
+            return None;
+        }
+
+        match &f.ty {
+            RustType::Special(SpecialRustType::JsonValue)
+            | RustType::Generic { .. }
+            | RustType::Simple { .. } => self
+                .type_mappings
+                .get(f.ty.id())
+                .and_then(|mapped| mapped_empty_value(mapped)),
+            RustType::Special(special_ty) => empty_value(special_ty),
+        }
+    }
+
+    /// Finds the placeholder values of the types in a file that can be created without
+    /// arguments: structs whose fields all have placeholders, and unit enums.
+    fn collect_mock_values(&mut self, parsed_data: &ParsedData) {
+        self.mock_values.clear();
+
+        for e in &parsed_data.enums {
+            if let RustEnum::Unit(shared) = e {
+                if let Some(first) = shared.variants.first() {
+                    let case = first.shared().id.original.to_camel_case();
+                    self.mock_values.insert(
+                        shared.id.original.clone(),
+                        format!(".{}", self.keyword_aware_rename(&case)),
+                    );
+                }
+            }
+        }
+
+        // Structs can be made of other structs, so repeat until no more are found.
+        loop {
+            let mockable = parsed_data
+                .structs
+                .iter()
+                .filter(|rs| {
+                    rs.generic_types.is_empty() && !self.mock_values.contains_key(&rs.id.original)
+                })
+                .filter(|rs| rs.fields.iter().all(|f| self.mock_value(f).is_some()))
+                .map(|rs| rs.id.original.clone())
+                .collect::<Vec<_>>();
+            if mockable.is_empty() {
+                break;
+            }
+            for name in mockable {
+                self.mock_values.insert(name, ".mock()".into());
+            }
+        }
+    }
+
+    /// The placeholder value of a field in a `mock` factory, if it has one.
+    fn mock_value(&self, f: &RustField) -> Option<String> {
+        if (f.has_default || f.ty.is_optional()) && self.default_value(f).is_none() {
+            return Some("nil".into());
+        }
+        if f.type_override(SupportedLanguage::Swift).is_some() {
+            return None;
+        }
+        self.mock_type_value(&f.ty)
+    }
+
+    fn mock_type_value(&self, ty: &RustType) -> Option<String> {
+        match ty {
+            RustType::Special(special_ty) => empty_value(special_ty).map(ToOwned::to_owned),
+            RustType::Simple { id } if !self.type_mappings.contains_key(id.as_str()) => {
+                self.mock_values.get(id.as_str()).cloned()
+            }
+            RustType::Simple { .. } | RustType::Generic { .. } => None,
+        }
+    }
+
+    /// Write an extension conforming a type to SwiftCheck's `Arbitrary`, with `generator`.
+    /// Generic types conform when their type parameters do.
+    fn write_arbitrary(
+        &self,
+        w: &mut dyn Write,
+        type_name: &str,
+        generic_types: &[String],
+        generator: &str,
+    ) -> io::Result<()> {
+        let constraints = if generic_types.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " where {}",
+                generic_types
+                    .iter()
+                    .map(|t| format!("{t}: Arbitrary"))
+                    .join(", ")
+            )
+        };
+        writeln!(w)?;
+        writeln!(w, "extension {type_name}: Arbitrary{constraints} {{")?;
+        writeln!(w, "\tpublic static var arbitrary: Gen<Self> {{")?;
+        writeln!(w, "\t\t{generator}")?;
+        writeln!(w, "\t}}")?;
+        writeln!(w, "}}")
+    }
+
+    /// The generator of an enum, one of its cases. The values of cases are generated
+    /// with the `Arbitrary` conformances of their types.
+    fn arbitrary_enum_generator(&self, e: &RustEnum) -> String {
+        match e {
+            RustEnum::Unit(shared) => {
+                let cases = shared
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let case = v.shared().id.original.to_camel_case();
+                        format!(".{}", self.keyword_aware_rename(&case))
+                    })
+                    .join(", ");
+                format!("Gen.fromElements(of: [{cases}])")
+            }
+            RustEnum::Algebraic { shared, .. } => {
+                let cases = shared
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let case = self.keyword_aware_rename(case_name(v)).into_owned();
+                        let generator = match v {
+                            RustEnumVariant::Unit(_) => format!("Gen<Self>.pure(.{case})"),
+                            RustEnumVariant::Tuple { ty, .. } => format!(
+                                "Gen<Self>.compose {{ c in .{case}({}) }}",
+                                arbitrary_value(ty)
+                            ),
+                            RustEnumVariant::AnonymousStruct { .. } => {
+                                format!("Gen<Self>.compose {{ c in .{case}(c.generate()) }}")
+                            }
+                        };
+                        format!("\t\t\t{generator},\n")
+                    })
+                    .join("");
+                format!("Gen.one(of: [\n{cases}\t\t])")
+            }
+        }
+    }
+
+    /// Write a `static func mock(...)` factory for a struct, with a parameter per field.
+    fn write_mock(
+        &mut self,
+        w: &mut dyn Write,
+        rs: &RustStruct,
+        type_name: &str,
+    ) -> io::Result<()> {
+        let mut params = Vec::new();
+        let mut arguments = Vec::new();
+        for f in &rs.fields {
+            let swift_ty = match f.type_override(SupportedLanguage::Swift) {
+                Some(type_override) => type_override.to_owned(),
+                None => self
+                    .format_type(&f.ty, rs.generic_types.as_slice())
+                    .map_err(io::Error::other)?,
+            };
+            let label = remove_dash_from_identifier(&self.coding_key(f));
+            let optional = f.has_default && !f.ty.is_optional() && self.default_value(f).is_none();
+
+            params.push(format!(
+                "\t\t{label}: {swift_ty}{}{}",
+                if optional { "?" } else { "" },
+                self.mock_value(f)
+                    .map(|value| format!(" = {value}"))
+                    .unwrap_or_default()
+            ));
+            arguments.push(format!("{label}: {}", self.keyword_aware_rename(&label)));
+        }
+
+        writeln!(w)?;
+        writeln!(w, "public extension {type_name} {{")?;
+        if params.is_empty() {
+            writeln!(w, "\tstatic func mock() -> {type_name} {{")?;
+        } else {
+            writeln!(w, "\tstatic func mock(")?;
+            writeln!(w, "{}", params.join(",\n"))?;
+            writeln!(w, "\t) -> {type_name} {{")?;
+        }
+        writeln!(w, "\t\t{type_name}({})", arguments.join(", "))?;
+        writeln!(w, "\t}}")?;
+        writeln!(w, "}}")
+    }
+
+    /// Write an `init(from:)` that falls back to the default values of
+    /// fields with `#[serde(default)]` when their key is missing.
+    fn write_decoding_init(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        writeln!(w)?;
+        writeln!(w, "\tpublic init(from decoder: Decoder) throws {{")?;
+        writeln!(
+            w,
+            "\t\tlet container = try decoder.container(keyedBy: CodingKeys.self)"
+        )?;
+        for f in &rs.fields {
+            let field_name = remove_dash_from_identifier(
+                self.keyword_aware_rename(self.coding_key(f).as_ref())
+                    .as_ref(),
+            );
+            let swift_ty = match f.type_override(SupportedLanguage::Swift) {
+                Some(type_override) => type_override.to_owned(),
+                None => self
+                    .format_type(&f.ty, rs.generic_types.as_slice())
+                    .map_err(io::Error::other)?,
+            };
+
+            match self.default_value(f) {
+                Some(value) => writeln!(
+                    w,
+                    "\t\tself.{field_name} = try container.decodeIfPresent({swift_ty}.self, forKey: .{field_name}) ?? {value}"
+                )?,
+                None if f.ty.is_optional() => writeln!(
+                    w,
+                    "\t\tself.{field_name} = try container.decodeIfPresent({}.self, forKey: .{field_name})",
+                    swift_ty.strip_suffix('?').unwrap_or(&swift_ty)
+                )?,
+                None if f.has_default => writeln!(
+                    w,
+                    "\t\tself.{field_name} = try container.decodeIfPresent({swift_ty}.self, forKey: .{field_name})"
+                )?,
+                None => writeln!(
+                    w,
+                    "\t\tself.{field_name} = try container.decode({swift_ty}.self, forKey: .{field_name})"
+                )?,
+            }
+        }
+        writeln!(w, "\t}}")
+    }
+
     fn write_comment(&mut self, w: &mut dyn Write, indent: usize, comment: &str) -> io::Result<()> {
         writeln!(w, "{}/// {}", "\t".repeat(indent), comment.trim_end())?;
         Ok(())
@@ -767,6 +1276,14 @@ impl Swift {
 }
 
 impl Swift {
+    /// Escape Swift keywords and the reserved words with backticks.
+    fn keyword_aware_rename<'a, T>(&self, name: T) -> Cow<'a, str>
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        IDENTIFIERS.escape(name, &self.reserved_words)
+    }
+
     fn get_default_decorators(&self) -> impl Iterator<Item = &str> {
         [CODABLE]
             .into_iter()
@@ -789,6 +1306,66 @@ impl Swift {
         self.write_codable(&mut w, &output_string)
     }
 
+    /// Whether the files are generated as the targets of a SwiftPM package.
+    fn generates_package(&self) -> bool {
+        self.multi_file && self.package_name.is_some()
+    }
+
+    /// The target of a package the types of a Rust crate, or module like
+    /// `my_crate.models`, are generated in, like `MyCrateModels`. Its file goes in
+    /// `Sources/<target>` of the package.
+    pub fn package_target(crate_name: &CrateName) -> String {
+        crate_name.as_str().replace('.', "_").to_pascal_case()
+    }
+
+    /// Write the `Package.swift` manifest to the root of the package, the output folder.
+    fn write_package_manifest(&self, output_folder: &str, package_name: &str) -> io::Result<()> {
+        let output_path = Path::new(output_folder).join("Package.swift");
+        let output_string = self.get_package_manifest_contents(package_name);
+
+        if let Ok(buf) = fs::read(&output_path) {
+            if buf == output_string.as_bytes() {
+                return Ok(());
+            }
+        }
+
+        fs::write(output_path, output_string)
+    }
+
+    /// The manifest of a package with a library of every target.
+    fn get_package_manifest_contents(&self, package_name: &str) -> String {
+        let quoted = |target: &String| format!("{target:?}");
+        let library_targets = self.package_targets.keys().map(quoted).join(", ");
+        let targets = self
+            .package_targets
+            .iter()
+            .map(|(target, dependencies)| {
+                if dependencies.is_empty() {
+                    format!("\t\t.target(name: {target:?}),\n")
+                } else {
+                    format!(
+                        "\t\t.target(name: {target:?}, dependencies: [{}]),\n",
+                        dependencies.iter().map(quoted).join(", ")
+                    )
+                }
+            })
+            .collect::<String>();
+        format!(
+            r#"// swift-tools-version:5.5
+import PackageDescription
+
+let package = Package(
+	name: "{package_name}",
+	products: [
+		.library(name: "{package_name}", targets: [{library_targets}]),
+	],
+	targets: [
+{targets}	]
+)
+"#
+        )
+    }
+
     fn get_codable_contents(&self) -> String {
         let mut decs = self
             .get_default_decorators()
@@ -856,14 +1433,95 @@ impl Swift {
     }
 }
 
-fn swift_keyword_aware_rename<'a, T>(name: T) -> Cow<'a, str>
-where
-    T: Into<Cow<'a, str>>,
-{
-    let name = name.into();
-    if SWIFT_KEYWORDS.contains(&name.as_ref()) {
-        Cow::Owned(format!("`{name}`"))
-    } else {
-        name
+/// Convert a key the same way Foundation's `.convertFromSnakeCase` key
+/// decoding strategy does: underscore separated words are camel cased,
+/// while leading and trailing underscores are preserved.
+/// The empty value of a special type, such as `[]` for an array.
+/// The name of the case of a variant of an algebraic enum.
+fn case_name(v: &RustEnumVariant) -> String {
+    let case_name = v.shared().id.original.to_camel_case();
+
+    if case_name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+    {
+        // If the name starts with a digit just add an underscore
+        // to the front and make it valid
+        return format!("_{case_name}");
+    }
+
+    case_name
+}
+
+/// A generated value of type `ty`, in the `Gen.compose` of a generator.
+/// `CodableVoid` has a single value, and isn't `Arbitrary`.
+fn arbitrary_value(ty: &RustType) -> &'static str {
+    match ty {
+        RustType::Special(SpecialRustType::Unit) => "CodableVoid()",
+        _ => "c.generate()",
+    }
+}
+
+fn empty_value(special_ty: &SpecialRustType) -> Option<&'static str> {
+    match special_ty {
+        SpecialRustType::Vec(_) | SpecialRustType::Array(_, _) | SpecialRustType::Slice(_) => {
+            Some("[]")
+        }
+        SpecialRustType::HashMap(_, _) => Some("[:]"),
+        SpecialRustType::String => Some(r#""""#),
+        SpecialRustType::Bool => Some("false"),
+        SpecialRustType::I8
+        | SpecialRustType::U8
+        | SpecialRustType::I16
+        | SpecialRustType::U16
+        | SpecialRustType::USize
+        | SpecialRustType::ISize
+        | SpecialRustType::I32
+        | SpecialRustType::U32
+        | SpecialRustType::I54
+        | SpecialRustType::I64
+        | SpecialRustType::U53
+        | SpecialRustType::U64
+        | SpecialRustType::F32
+        | SpecialRustType::F64 => Some("0"),
+        SpecialRustType::Option(_)
+        | SpecialRustType::Unit
+        | SpecialRustType::Char
+        | SpecialRustType::DateTime
+        | SpecialRustType::JsonValue => None,
     }
 }
+
+/// The default value of a Swift type a Rust type is mapped to, if it's one of the
+/// types [`empty_value`] has a default for.
+fn mapped_empty_value(swift_ty: &str) -> Option<&'static str> {
+    match swift_ty {
+        "String" => Some(r#""""#),
+        "Bool" => Some("false"),
+        "Int" | "Int8" | "Int16" | "Int32" | "Int64" | "UInt" | "UInt8" | "UInt16" | "UInt32"
+        | "UInt64" | "Float" | "Double" => Some("0"),
+        _ if swift_ty.starts_with('[') && swift_ty.ends_with(']') => {
+            Some(if swift_ty.contains(':') { "[:]" } else { "[]" })
+        }
+        _ => None,
+    }
+}
+
+/// The property Foundation's `.convertFromSnakeCase` decodes a key into: the first
+/// component is kept as it is and the others are capitalized, so `URL_value` becomes
+/// `URLValue`. Leading and trailing underscores are kept, and keys without
+/// underscores between their words aren't changed.
+fn convert_from_snake_case(key: &str) -> Cow<'_, str> {
+    let words = key.trim_matches('_');
+    let Some((first, rest)) = words.split_once('_') else {
+        return Cow::Borrowed(key);
+    };
+    let leading = &key[..key.len() - key.trim_start_matches('_').len()];
+    let trailing = &key[key.trim_end_matches('_').len()..];
+    Cow::Owned(format!(
+        "{leading}{first}{}{trailing}",
+        rest.to_lowercase().to_pascal_case()
+    ))
+}
@@ -0,0 +1,338 @@
+use crate::{
+    language::{emit_items, output::written, CrateTypes, FileOutput, Language},
+    parser::ParsedData,
+    rust_types::{
+        RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustStruct, RustType,
+        RustTypeAlias, RustTypeFormatError, SpecialRustType,
+    },
+};
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use super::ScopedCrateTypes;
+
+/// A language whose types are rendered with [Handlebars](https://handlebarsjs.com/)
+/// templates, for output formats typeshare has no language for.
+///
+/// The templates are parsed once, when the language is created. Values aren't escaped,
+/// missing values are written as nothing, and lines with only a block tag, like
+/// `{{#each fields}}`, aren't written.
+pub struct Template {
+    templates: Handlebars<'static>,
+    /// Mappings from Rust type names to the names of types in the templates.
+    pub type_mappings: HashMap<String, String>,
+}
+
+/// The sources of the templates of a [`Template`]. The items whose template is empty
+/// aren't written.
+#[derive(Default)]
+pub struct TemplateSources {
+    /// Written at the start of every file, with the `crate` of the file, which is empty
+    /// when generating a single file, and the typeshare `version`.
+    pub header: String,
+    /// Written for every struct.
+    pub struct_template: String,
+    /// Written for every enum.
+    pub enum_template: String,
+    /// Written for every type alias, and newtype struct.
+    pub alias_template: String,
+    /// Written for every constant.
+    pub const_template: String,
+    /// Written at the end of every file, like the header.
+    pub footer: String,
+}
+
+impl Language for Template {
+    fn emit(&mut self, all_types: &CrateTypes, data: ParsedData) -> io::Result<FileOutput> {
+        let file = json!({
+            "crate": data.crate_name.as_str(),
+            "version": env!("CARGO_PKG_VERSION"),
+        });
+        Ok(FileOutput {
+            header: written(|w| self.render("header", &file, w))?,
+            footer: written(|w| self.render("footer", &file, w))?,
+            ..emit_items(self, all_types, data)?
+        })
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_mappings
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        let name = |language: &mut Self, id: &str| {
+            language
+                .type_mappings
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| id.to_owned())
+        };
+        Ok(match special_ty {
+            SpecialRustType::Vec(rtype) | SpecialRustType::Option(rtype) => format!(
+                "{}<{}>",
+                name(self, special_ty.id()),
+                self.format_type(rtype, generic_types)?
+            ),
+            SpecialRustType::HashMap(key, value) => format!(
+                "{}<{}, {}>",
+                name(self, special_ty.id()),
+                self.format_type(key, generic_types)?,
+                self.format_type(value, generic_types)?
+            ),
+            SpecialRustType::Array(rtype, len) => {
+                format!("[{}; {len}]", self.format_type(rtype, generic_types)?)
+            }
+            SpecialRustType::Slice(rtype) => {
+                format!("[{}]", self.format_type(rtype, generic_types)?)
+            }
+            _ => name(self, special_ty.id()),
+        })
+    }
+
+    fn write_imports(
+        &mut self,
+        _writer: &mut dyn Write,
+        _imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_type_alias(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> io::Result<()> {
+        let context = json!({
+            "name": ty.id.renamed,
+            "rust_name": ty.id.original,
+            "generic_types": ty.generic_types,
+            "comments": ty.comments,
+            "type": self.type_name(&ty.r#type, &ty.generic_types)?,
+            "newtype": ty.is_newtype,
+            "item": ty,
+        });
+        self.render("alias", &context, w)
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        let RustConstExpr::Int(value) = c.expr;
+        let context = json!({
+            "name": c.id.renamed,
+            "rust_name": c.id.original,
+            "type": self.type_name(&c.r#type, &[])?,
+            "value": value.to_string(),
+            "item": c,
+        });
+        self.render("const", &context, w)
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        let context = json!({
+            "name": rs.id.renamed,
+            "rust_name": rs.id.original,
+            "generic_types": rs.generic_types,
+            "comments": rs.comments,
+            "fields": self.fields(&rs.fields, &rs.generic_types)?,
+            "item": rs,
+        });
+        self.render("struct", &context, w)
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        let shared = e.shared();
+        let variants = shared
+            .variants
+            .iter()
+            .map(|variant| {
+                let (ty, fields) = match variant {
+                    RustEnumVariant::Unit(_) => (None, None),
+                    RustEnumVariant::Tuple { ty, .. } => {
+                        (Some(self.type_name(ty, &shared.generic_types)?), None)
+                    }
+                    RustEnumVariant::AnonymousStruct { fields, .. } => {
+                        (None, Some(self.fields(fields, &shared.generic_types)?))
+                    }
+                };
+                let variant_shared = variant.shared();
+                Ok(json!({
+                    "name": variant_shared.id.renamed,
+                    "rust_name": variant_shared.id.original,
+                    "comments": variant_shared.comments,
+                    "unit": matches!(variant, RustEnumVariant::Unit(_)),
+                    "type": ty,
+                    "fields": fields,
+                }))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let (tag_key, content_key) = match e {
+            RustEnum::Unit(_) => (None, None),
+            RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                ..
+            } => (Some(tag_key), Some(content_key)),
+        };
+        let context = json!({
+            "name": shared.id.renamed,
+            "rust_name": shared.id.original,
+            "generic_types": shared.generic_types,
+            "comments": shared.comments,
+            "algebraic": matches!(e, RustEnum::Algebraic { .. }),
+            "tag_key": tag_key,
+            "content_key": content_key,
+            "variants": variants,
+            "item": e,
+        });
+        self.render("enum", &context, w)
+    }
+}
+
+impl Template {
+    /// Parse the templates, failing on the first invalid one, like one with an
+    /// unclosed block.
+    pub fn new(
+        sources: TemplateSources,
+        type_mappings: HashMap<String, String>,
+    ) -> io::Result<Self> {
+        let mut templates = Handlebars::new();
+        templates.register_escape_fn(handlebars::no_escape);
+        for (name, source) in [
+            ("header", sources.header),
+            ("struct", sources.struct_template),
+            ("enum", sources.enum_template),
+            ("alias", sources.alias_template),
+            ("const", sources.const_template),
+            ("footer", sources.footer),
+        ] {
+            templates
+                .register_template_string(name, source)
+                .map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Invalid {name} template: {err}"),
+                    )
+                })?;
+        }
+        Ok(Self {
+            templates,
+            type_mappings,
+        })
+    }
+
+    /// Render the template of the name with the values of the context.
+    fn render(&self, name: &str, context: &Value, w: &mut dyn Write) -> io::Result<()> {
+        self.templates
+            .render_to_write(name, context, w)
+            .map_err(|err| io::Error::other(format!("Failed to render the {name} template: {err}")))
+    }
+
+    /// The name of the type in the templates: its Rust name, with the names of the
+    /// types in it mapped.
+    fn type_name(&mut self, ty: &RustType, generic_types: &[String]) -> io::Result<String> {
+        self.format_type(ty, generic_types)
+            .map_err(|err| io::Error::other(err.to_string()))
+    }
+
+    fn fields(&mut self, fields: &[RustField], generic_types: &[String]) -> io::Result<Value> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(json!({
+                    "name": field.id.renamed,
+                    "rust_name": field.id.original,
+                    "comments": field.comments,
+                    "type": self.type_name(&field.ty, generic_types)?,
+                    "optional": field.ty.is_optional() || field.has_default,
+                }))
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(Value::Array)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Template, TemplateSources};
+    use serde_json::{json, Value};
+
+    fn rendered(template: &str, context: Value) -> String {
+        let template = Template::new(
+            TemplateSources {
+                struct_template: template.to_owned(),
+                ..Default::default()
+            },
+            Default::default(),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        template.render("struct", &context, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn renders_values_and_blocks() {
+        let context = json!({
+            "name": "User<T>",
+            "fields": [{ "name": "id", "optional": false }, { "name": "email", "optional": true }],
+        });
+        assert_eq!(
+            rendered(
+                "type {{name}} = {\n  {{#each fields}}\n  {{name}}{{#if optional}}?{{/if}}{{#unless @last}},{{/unless}}\n  {{/each}}\n}\n",
+                context
+            ),
+            "type User<T> = {\n  id,\n  email?\n}\n"
+        );
+    }
+
+    #[test]
+    fn renders_inverted_blocks() {
+        let context = json!({ "name": "User", "comments": [], "newtype": false });
+        assert_eq!(
+            rendered(
+                "{{#each comments}}\n// {{this}}\n{{else}}\n// {{name}} has no comments\n{{/each}}\n{{#unless newtype}}\nstruct\n{{/unless}}\n",
+                context
+            ),
+            "// User has no comments\nstruct\n"
+        );
+    }
+
+    #[test]
+    fn renders_missing_values_as_nothing() {
+        let context = json!({ "name": "User" });
+        assert_eq!(
+            rendered(
+                "{{name}}[{{missing}}{{item.id.original}}]{{#if missing}}!{{/if}}",
+                context
+            ),
+            "User[]"
+        );
+    }
+
+    #[test]
+    fn fails_on_malformed_tags() {
+        for template in [
+            "{{#each fields}}",
+            "{{/each}}",
+            "{{#if a}}{{/each}}",
+            "{{name",
+        ] {
+            let error = Template::new(
+                TemplateSources {
+                    enum_template: template.to_owned(),
+                    ..Default::default()
+                },
+                Default::default(),
+            )
+            .err()
+            .unwrap_or_else(|| panic!("{template} is valid"));
+            assert!(
+                error.to_string().starts_with("Invalid enum template"),
+                "{error}"
+            );
+        }
+    }
+}
@@ -1,15 +1,19 @@
 use crate::RenameExt;
 use crate::{
-    language::{Language, SupportedLanguage},
+    language::{
+        emit_items_with, output::written, write_item_schema_hash, CodeStyle, CrateTypes,
+        FileHeader, FileOutput, ItemOutput, Language, OptionalFields, SupportedLanguage,
+    },
+    lint::{unmapped_json_values, Warning},
     parser::ParsedData,
     rust_types::{
-        RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustStruct, RustType,
-        RustTypeAlias, RustTypeFormatError, SpecialRustType,
+        Id, RustConst, RustConstExpr, RustEnum, RustEnumVariant, RustField, RustItem, RustStruct,
+        RustType, RustTypeAlias, RustTypeFormatError, SpecialRustType,
     },
 };
 use itertools::Itertools;
 use joinery::JoinableIterator;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::{
     collections::HashMap,
     io::{self, Write},
@@ -17,6 +21,34 @@ use std::{
 
 use super::ScopedCrateTypes;
 
+/// The module system that generated Typescript files are consumed with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TypeScriptModuleFormat {
+    /// Extensionless `import` specifiers, as resolved by bundlers and
+    /// `moduleResolution: bundler`.
+    #[default]
+    Bundler,
+    /// Native ES modules, as resolved by `moduleResolution: nodenext` in a
+    /// `"type": "module"` package. Import specifiers end in `.js` and types are
+    /// imported with `import type`.
+    Esm,
+    /// CommonJS modules, as resolved by `moduleResolution: nodenext` in a CommonJS
+    /// package. Import specifiers are extensionless and types are imported with
+    /// `import type`, so that no `require` of the imported file is emitted.
+    CommonJs,
+}
+
+/// How the properties of generated Typescript types are named.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TypeScriptFieldNaming {
+    /// Properties are named after the serialized fields.
+    #[default]
+    Wire,
+    /// Properties are named in camelCase. Every type gets a `decode<Type>` and
+    /// `encode<Type>` function, which convert between it and its serialized form.
+    CamelCase,
+}
+
 /// All information needed to generate Typescript type-code
 #[derive(Default)]
 pub struct TypeScript {
@@ -25,8 +57,32 @@ pub struct TypeScript {
     /// Whether or not to exclude the version header that normally appears at the top of generated code.
     /// If you aren't generating a snapshot test, this setting can just be left as a default (false)
     pub no_version_header: bool,
+    /// A custom header replacing the version header.
+    pub header: Option<FileHeader>,
+    /// Whether to write a constant with the schema hash of every type after it, like
+    /// `export const UserSchemaHash = "…";`.
+    pub schema_hashes: bool,
+    /// How the generated code is indented and wrapped.
+    pub code_style: CodeStyle,
+    /// How fields of `Option` types are represented, instead of the default of the
+    /// language.
+    pub optional_fields: Option<OptionalFields>,
     /// Carries the unique set of types for custom json translation
     pub types_for_custom_json_translation: BTreeMap<String, BTreeSet<String>>,
+    /// The module system the generated files are consumed with, which decides the
+    /// form of the imports between files in multi-file mode.
+    pub module_format: TypeScriptModuleFormat,
+    /// The extension of the import specifiers between files in multi-file mode,
+    /// such as `.js`, instead of the extension implied by `module_format`.
+    pub import_extension: Option<String>,
+    /// How the properties of generated types are named.
+    pub field_naming: TypeScriptFieldNaming,
+    /// Whether to generate a fast-check arbitrary, `arb<Type>`, for every type, which
+    /// generates values of it for property-based tests.
+    pub arbitraries: bool,
+    /// The types of the current file whose arbitraries aren't written yet, which are
+    /// referenced lazily. This is filled in when a file is generated.
+    pub pending_arbitraries: HashSet<String>,
 }
 
 #[derive(Clone)]
@@ -40,6 +96,10 @@ impl Language for TypeScript {
         &self.type_mappings
     }
 
+    fn code_style(&self) -> Option<&CodeStyle> {
+        Some(&self.code_style)
+    }
+
     fn end_file(&mut self, w: &mut dyn Write) -> std::io::Result<()> {
         if !self.types_for_custom_json_translation.is_empty() {
             let custom_translation_content = self
@@ -106,8 +166,8 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
             SpecialRustType::HashMap(rtype1, rtype2) => Ok(format!(
                 "Record<{}, {}>",
                 match rtype1.as_ref() {
-                    RustType::Simple { id } if generic_types.contains(id) => {
-                        return Err(RustTypeFormatError::GenericKeyForbiddenInTS(id.clone()));
+                    RustType::Simple { id } if generic_types.iter().any(|g| g == id) => {
+                        return Err(RustTypeFormatError::GenericKeyForbiddenInTS(id.to_string()));
                     }
                     _ => self.format_type(rtype1, generic_types)?,
                 },
@@ -115,6 +175,7 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
             )),
             SpecialRustType::Unit => Ok("undefined".into()),
             SpecialRustType::DateTime => Ok("Date".into()),
+            SpecialRustType::JsonValue => self.format_simple_type(special_ty.id(), generic_types),
             SpecialRustType::String => Ok("string".into()),
             SpecialRustType::Char => Ok("string".into()),
             SpecialRustType::I8
@@ -137,19 +198,163 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
         }
     }
 
-    fn begin_file(&mut self, w: &mut dyn Write, _parsed_data: &ParsedData) -> io::Result<()> {
-        if !self.no_version_header {
+    fn begin_file(&mut self, w: &mut dyn Write, parsed_data: &ParsedData) -> io::Result<()> {
+        if let Some(header) = &self.header {
+            header.write(w, &parsed_data.crate_name, "/*", " ", "*/")?;
+            writeln!(w)?;
+        } else if !self.no_version_header {
             writeln!(w, "/*")?;
             writeln!(w, " Generated by typeshare {}", env!("CARGO_PKG_VERSION"))?;
             writeln!(w, "*/")?;
             writeln!(w)?;
         }
+        if self.arbitraries {
+            self.pending_arbitraries = parsed_data
+                .structs
+                .iter()
+                .map(|s| &s.id)
+                .chain(parsed_data.enums.iter().map(|e| &e.shared().id))
+                .chain(parsed_data.aliases.iter().map(|a| &a.id))
+                .map(|id| id.original.clone())
+                .collect();
+            writeln!(w, "import * as fc from \"fast-check\";")?;
+            writeln!(w)?;
+        }
         Ok(())
     }
 
     fn write_type_alias(&mut self, w: &mut dyn Write, ty: &RustTypeAlias) -> io::Result<()> {
         self.write_comments(w, 0, &ty.comments)?;
+        self.write_type_alias_declaration(w, ty)?;
+        self.write_type_alias_companions(w, ty)
+    }
+
+    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
+        match c.expr {
+            RustConstExpr::Int(val) => {
+                let const_type = self
+                    .format_type(&c.r#type, &[])
+                    .map_err(std::io::Error::other)?;
+                writeln!(
+                    w,
+                    "export const {}: {} = {};",
+                    c.id.renamed.to_snake_case().to_uppercase(),
+                    const_type,
+                    val
+                )
+            }
+        }
+    }
+
+    fn schema_hashes(&self) -> bool {
+        self.schema_hashes
+    }
+
+    fn write_schema_hash(&mut self, w: &mut dyn Write, name: &str, hash: &str) -> io::Result<()> {
+        writeln!(w, "export const {name} = \"{hash}\";\n")
+    }
+
+    fn emit(&mut self, all_types: &CrateTypes, data: ParsedData) -> io::Result<FileOutput> {
+        emit_items_with(self, all_types, data, Self::emit_item)
+    }
+
+    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        self.write_comments(w, 0, &rs.comments)?;
+        self.write_struct_declaration(w, rs)?;
+        self.write_struct_companions(w, rs)
+    }
+
+    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        self.write_comments(w, 0, &e.shared().comments)?;
+        self.write_enum_declaration(w, e)?;
+        self.write_enum_companions(w, e)
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> std::io::Result<()> {
+        let (import, default_extension) = match self.module_format {
+            TypeScriptModuleFormat::Bundler => ("import", ""),
+            TypeScriptModuleFormat::Esm => ("import type", ".js"),
+            TypeScriptModuleFormat::CommonJs => ("import type", ""),
+        };
+        let extension = self
+            .import_extension
+            .as_deref()
+            .unwrap_or(default_extension);
+        for (path, ty) in imports {
+            write!(w, "{import} {{ ")?;
+            let ty_list = ty.iter().join(", ");
+            write!(w, "{ty_list}")?;
+            writeln!(w, " }} from \"./{path}{extension}\";")?;
+            if self.field_naming == TypeScriptFieldNaming::CamelCase {
+                let codec_list = ty
+                    .iter()
+                    .flat_map(|ty| [format!("decode{ty}"), format!("encode{ty}")])
+                    .join(", ");
+                writeln!(w, "import {{ {codec_list} }} from \"./{path}{extension}\";")?;
+            }
+            if self.arbitraries {
+                let arbitrary_list = ty.iter().map(|ty| format!("arb{ty}")).join(", ");
+                writeln!(
+                    w,
+                    "import {{ {arbitrary_list} }} from \"./{path}{extension}\";"
+                )?;
+            }
+        }
+        writeln!(w)
+    }
+
+    fn ignored_reference_types(&self) -> Vec<&str> {
+        self.type_mappings.keys().map(|s| s.as_str()).collect()
+    }
+
+    fn warnings(&self, data: &ParsedData) -> Vec<Warning> {
+        unmapped_json_values(data, "TypeScript", &self.type_mappings)
+    }
 
+    fn field_identifier(&self, field: &RustField) -> String {
+        self.property_name(field)
+    }
+}
+
+impl TypeScript {
+    /// The generated source of the item, with its doc comment, its declaration, and its
+    /// codecs, arbitrary and schema hash apart.
+    fn emit_item(&mut self, item: &RustItem) -> io::Result<ItemOutput> {
+        let comments = match item {
+            RustItem::Struct(s) => &s.comments,
+            RustItem::Enum(e) => &e.shared().comments,
+            RustItem::Alias(a) => &a.comments,
+            RustItem::Const(_) => &Vec::new(),
+        };
+        let docs = written(|w| self.write_comments(w, 0, comments))?;
+        let body = written(|w| match item {
+            RustItem::Struct(s) => self.write_struct_declaration(w, s),
+            RustItem::Enum(e) => self.write_enum_declaration(w, e),
+            RustItem::Alias(a) => self.write_type_alias_declaration(w, a),
+            RustItem::Const(c) => self.write_const(w, c),
+        })?;
+        let companions = written(|w| {
+            match item {
+                RustItem::Struct(s) => self.write_struct_companions(w, s),
+                RustItem::Enum(e) => self.write_enum_companions(w, e),
+                RustItem::Alias(a) => self.write_type_alias_companions(w, a),
+                RustItem::Const(_) => Ok(()),
+            }?;
+            write_item_schema_hash(self, w, item)
+        })?;
+        Ok(ItemOutput::from_parts(item, docs, body, companions))
+    }
+
+    /// Write the type of the alias.
+    fn write_type_alias_declaration(
+        &mut self,
+        w: &mut dyn Write,
+        ty: &RustTypeAlias,
+    ) -> io::Result<()> {
         let r#type = self
             .format_type(&ty.r#type, ty.generic_types.as_slice())
             .map_err(io::Error::other)?;
@@ -169,30 +374,39 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
             } else {
                 Default::default()
             },
-        )?;
-
-        Ok(())
+        )
     }
 
-    fn write_const(&mut self, w: &mut dyn Write, c: &RustConst) -> io::Result<()> {
-        match c.expr {
-            RustConstExpr::Int(val) => {
-                let const_type = self
-                    .format_type(&c.r#type, &[])
-                    .map_err(std::io::Error::other)?;
-                writeln!(
-                    w,
-                    "export const {}: {} = {};",
-                    c.id.renamed.to_snake_case().to_uppercase(),
-                    const_type,
-                    val
-                )
+    /// Write the codecs and the arbitrary of the alias, when they're generated.
+    fn write_type_alias_companions(
+        &mut self,
+        w: &mut dyn Write,
+        ty: &RustTypeAlias,
+    ) -> io::Result<()> {
+        if self.field_naming == TypeScriptFieldNaming::CamelCase {
+            for decode in [true, false] {
+                let body = self
+                    .codec_expr(&ty.r#type, codec_argument(decode), decode, 0)
+                    .unwrap_or_else(|| codec_argument(decode).to_owned());
+                self.write_codec(w, decode, &ty.id.renamed, &ty.generic_types, |w| {
+                    writeln!(w, "\treturn {body};")
+                })?;
+            }
+        }
+
+        if self.arbitraries {
+            let mut arbitrary = self.arbitrary_expr(&ty.r#type);
+            if ty.r#type.is_optional() {
+                arbitrary = format!("fc.option({arbitrary}, {{ nil: undefined }})");
             }
+            self.write_arbitrary(w, &ty.id, &ty.generic_types, &arbitrary)?;
         }
+
+        Ok(())
     }
 
-    fn write_struct(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
-        self.write_comments(w, 0, &rs.comments)?;
+    /// Write the interface of the struct.
+    fn write_struct_declaration(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
         writeln!(
             w,
             "export interface {}{} {{",
@@ -211,9 +425,27 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
         writeln!(w, "}}\n")
     }
 
-    fn write_enum(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
-        self.write_comments(w, 0, &e.shared().comments)?;
+    /// Write the codecs and the arbitrary of the struct, when they're generated.
+    fn write_struct_companions(&mut self, w: &mut dyn Write, rs: &RustStruct) -> io::Result<()> {
+        if self.field_naming == TypeScriptFieldNaming::CamelCase {
+            for decode in [true, false] {
+                let object = self.codec_object(&rs.fields, codec_argument(decode), decode, 1);
+                self.write_codec(w, decode, &rs.id.renamed, &rs.generic_types, |w| {
+                    writeln!(w, "\treturn {object};")
+                })?;
+            }
+        }
+
+        if self.arbitraries {
+            let arbitrary = self.arbitrary_record(&rs.fields, 0);
+            self.write_arbitrary(w, &rs.id, &rs.generic_types, &arbitrary)?;
+        }
 
+        Ok(())
+    }
+
+    /// Write the enum, or the union type of an algebraic enum.
+    fn write_enum_declaration(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
         let generic_parameters = if !e.shared().generic_types.is_empty() {
             format!("<{}>", e.shared().generic_types.join(", "))
         } else {
@@ -248,26 +480,44 @@ export const ReplacerFunc = (key: string, value: unknown): unknown => {{
         }
     }
 
-    fn write_imports(
-        &mut self,
-        w: &mut dyn Write,
-        imports: ScopedCrateTypes<'_>,
-    ) -> std::io::Result<()> {
-        for (path, ty) in imports {
-            write!(w, "import {{ ")?;
-            let ty_list = ty.iter().join(", ");
-            write!(w, "{ty_list}")?;
-            writeln!(w, " }} from \"./{path}\";")?;
-        }
-        writeln!(w)
-    }
+    /// Write the codecs and the arbitrary of the enum, when they're generated.
+    fn write_enum_companions(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
+        match e {
+            RustEnum::Unit(shared) => {
+                if self.field_naming == TypeScriptFieldNaming::CamelCase {
+                    for decode in [true, false] {
+                        self.write_codec(w, decode, &shared.id.renamed, &[], |w| {
+                            writeln!(w, "\treturn {};", codec_argument(decode))
+                        })?;
+                    }
+                }
 
-    fn ignored_reference_types(&self) -> Vec<&str> {
-        self.type_mappings.keys().map(|s| s.as_str()).collect()
+                if self.arbitraries {
+                    let members = shared
+                        .variants
+                        .iter()
+                        .map(|v| format!("{}.{}", shared.id.renamed, v.shared().id.original))
+                        .join(", ");
+                    let arbitrary = format!("fc.constantFrom({members})");
+                    self.write_arbitrary(w, &shared.id, &[], &arbitrary)?;
+                }
+            }
+            RustEnum::Algebraic { shared, .. } => {
+                if self.field_naming == TypeScriptFieldNaming::CamelCase {
+                    for decode in [true, false] {
+                        self.write_algebraic_enum_codec(w, e, decode)?;
+                    }
+                }
+
+                if self.arbitraries {
+                    let arbitrary = self.arbitrary_algebraic_enum(e);
+                    self.write_arbitrary(w, &shared.id, &shared.generic_types, &arbitrary)?;
+                }
+            }
+        }
+        Ok(())
     }
-}
 
-impl TypeScript {
     fn write_enum_variants(&mut self, w: &mut dyn Write, e: &RustEnum) -> io::Result<()> {
         match e {
             // Write all the unit variants out (there can only be unit variants in
@@ -355,8 +605,7 @@ impl TypeScript {
                 .or_default()
                 .insert(field.id.renamed.clone());
         }
-        let optional = field.ty.is_optional() || field.has_default;
-        let double_optional = field.ty.is_double_optional();
+        let (optional, nullable) = self.optionality(field);
         let is_readonly = field
             .decorators
             .get(&SupportedLanguage::TypeScript)
@@ -370,10 +619,10 @@ impl TypeScript {
             } else {
                 Default::default()
             },
-            typescript_property_aware_rename(&field.id.renamed),
+            typescript_property_aware_rename(&self.property_name(field)),
             if optional { "?" } else { Default::default() },
             ts_ty,
-            if double_optional {
+            if nullable {
                 " | null"
             } else {
                 Default::default()
@@ -383,6 +632,409 @@ impl TypeScript {
         Ok(())
     }
 
+    /// Whether the property of a field is optional, and whether it's nullable.
+    fn optionality(&self, field: &RustField) -> (bool, bool) {
+        let policy = self.optional_fields.filter(|_| field.ty.is_optional());
+        let optional = match policy {
+            Some(OptionalFields::Null) => field.has_default,
+            _ => field.ty.is_optional() || field.has_default,
+        };
+        let nullable = field.ty.is_double_optional()
+            || matches!(policy, Some(OptionalFields::Null | OptionalFields::Both));
+        (optional, nullable)
+    }
+
+    /// The name of the property of a field.
+    fn property_name(&self, field: &RustField) -> String {
+        match self.field_naming {
+            TypeScriptFieldNaming::Wire => field.id.renamed.clone(),
+            TypeScriptFieldNaming::CamelCase => field.id.renamed.to_camel_case(),
+        }
+    }
+
+    /// Write the `decode<Type>` or `encode<Type>` function of a type, which converts
+    /// between it and its serialized form. Generic types take the functions of their
+    /// type parameters as extra arguments.
+    fn write_codec(
+        &mut self,
+        w: &mut dyn Write,
+        decode: bool,
+        name: &str,
+        generic_types: &[String],
+        write_body: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let generic_parameters = if !generic_types.is_empty() {
+            format!("<{}>", generic_types.join(", "))
+        } else {
+            Default::default()
+        };
+        let codec_parameters = generic_types
+            .iter()
+            .map(|t| {
+                if decode {
+                    format!(", decode{t}: (json: any) => {t}")
+                } else {
+                    format!(", encode{t}: (value: {t}) => any")
+                }
+            })
+            .join("");
+        if decode {
+            writeln!(
+                w,
+                "export function decode{name}{generic_parameters}(json: any{codec_parameters}): {name}{generic_parameters} {{"
+            )?;
+        } else {
+            writeln!(
+                w,
+                "export function encode{name}{generic_parameters}(value: {name}{generic_parameters}{codec_parameters}): any {{"
+            )?;
+        }
+        write_body(w)?;
+        writeln!(w, "}}\n")
+    }
+
+    fn write_algebraic_enum_codec(
+        &mut self,
+        w: &mut dyn Write,
+        e: &RustEnum,
+        decode: bool,
+    ) -> io::Result<()> {
+        let RustEnum::Algebraic {
+            tag_key,
+            content_key,
+            shared,
+        } = e
+        else {
+            unreachable!()
+        };
+        let argument = codec_argument(decode);
+        let content = property_access(argument, content_key);
+        let cases = shared
+            .variants
+            .iter()
+            .filter_map(|v| {
+                let converted = match v {
+                    RustEnumVariant::Unit(_) => None,
+                    RustEnumVariant::Tuple { ty, .. } => self.codec_expr(ty, &content, decode, 0),
+                    RustEnumVariant::AnonymousStruct { fields, .. } => {
+                        Some(self.codec_object(fields, &content, decode, 3))
+                    }
+                }?;
+                Some(format!(
+                    "\t\tcase {:?}:\n\t\t\treturn {{ {}: {}, {}: {converted} }};\n",
+                    v.shared().id.renamed,
+                    typescript_property_aware_rename(tag_key),
+                    property_access(argument, tag_key),
+                    typescript_property_aware_rename(content_key),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        self.write_codec(w, decode, &shared.id.renamed, &shared.generic_types, |w| {
+            if cases.is_empty() {
+                return writeln!(w, "\treturn {argument};");
+            }
+            writeln!(w, "\tswitch ({}) {{", property_access(argument, tag_key))?;
+            for case in &cases {
+                write!(w, "{case}")?;
+            }
+            writeln!(w, "\t\tdefault:\n\t\t\treturn {argument};")?;
+            writeln!(w, "\t}}")
+        })
+    }
+
+    /// An object literal converting the fields of `value`, from their serialized form
+    /// when decoding and to it when encoding.
+    fn codec_object(
+        &mut self,
+        fields: &[RustField],
+        value: &str,
+        decode: bool,
+        indent: usize,
+    ) -> String {
+        if fields.is_empty() {
+            return "{}".to_string();
+        }
+        let properties = fields
+            .iter()
+            .map(|f| {
+                let property = self.property_name(f);
+                let (from, to) = if decode {
+                    (&f.id.renamed, &property)
+                } else {
+                    (&property, &f.id.renamed)
+                };
+                let access = property_access(value, from);
+                let converted = f
+                    .type_override(SupportedLanguage::TypeScript)
+                    .is_none()
+                    .then(|| self.codec_expr(&f.ty, &access, decode, 0))
+                    .flatten()
+                    .map(|converted| {
+                        if f.has_default && !f.ty.is_optional() {
+                            format!("{access} == null ? {access} : {converted}")
+                        } else {
+                            converted
+                        }
+                    })
+                    .unwrap_or_else(|| access.clone());
+                format!(
+                    "{}{}: {converted},\n",
+                    "\t".repeat(indent + 1),
+                    typescript_property_aware_rename(to)
+                )
+            })
+            .join("");
+        format!("{{\n{properties}{}}}", "\t".repeat(indent))
+    }
+
+    /// An expression converting `value` of type `ty`, from its serialized form when
+    /// decoding and to it when encoding, or `None` if it is the same in both forms.
+    fn codec_expr(
+        &mut self,
+        ty: &RustType,
+        value: &str,
+        decode: bool,
+        depth: usize,
+    ) -> Option<String> {
+        let direction = if decode { "decode" } else { "encode" };
+        let element = format!("v{depth}");
+        match ty {
+            RustType::Simple { id } | RustType::Generic { id, .. }
+                if self.type_mappings.contains_key(id.as_str()) =>
+            {
+                None
+            }
+            RustType::Simple { id } => Some(format!("{direction}{id}({value})")),
+            RustType::Generic { id, parameters } => {
+                let codecs = parameters
+                    .iter()
+                    .map(|p| {
+                        let converted = self
+                            .codec_expr(p, &element, decode, depth + 1)
+                            .unwrap_or_else(|| element.clone());
+                        format!(", ({element}: any) => {converted}")
+                    })
+                    .join("");
+                Some(format!("{direction}{id}({value}{codecs})"))
+            }
+            RustType::Special(
+                SpecialRustType::Vec(inner)
+                | SpecialRustType::Slice(inner)
+                | SpecialRustType::Array(inner, _),
+            ) => self
+                .codec_expr(inner, &element, decode, depth + 1)
+                .map(|converted| format!("{value}.map(({element}: any) => {converted})")),
+            RustType::Special(SpecialRustType::Option(inner)) => self
+                .codec_expr(inner, value, decode, depth)
+                .map(|converted| format!("{value} == null ? {value} : {converted}")),
+            RustType::Special(SpecialRustType::HashMap(_, inner)) => self
+                .codec_expr(inner, &element, decode, depth + 1)
+                .map(|converted| {
+                    format!(
+                        "Object.fromEntries(Object.entries({value}).map(([k{depth}, {element}]: [string, any]) => [k{depth}, {converted}]))"
+                    )
+                }),
+            RustType::Special(_) => None,
+        }
+    }
+
+    /// Write the fast-check arbitrary of a type, `arb<Type>`. Generic types get a
+    /// function taking the arbitraries of their type parameters.
+    fn write_arbitrary(
+        &mut self,
+        w: &mut dyn Write,
+        id: &Id,
+        generic_types: &[String],
+        arbitrary: &str,
+    ) -> io::Result<()> {
+        let name = &id.renamed;
+        if generic_types.is_empty() {
+            writeln!(
+                w,
+                "export const arb{name}: fc.Arbitrary<{name}> = {arbitrary};\n"
+            )?;
+        } else {
+            let generic_parameters = generic_types.join(", ");
+            let arbitrary_parameters = generic_types
+                .iter()
+                .map(|t| format!("arb{t}: fc.Arbitrary<{t}>"))
+                .join(", ");
+            writeln!(
+                w,
+                "export const arb{name} = <{generic_parameters}>({arbitrary_parameters}): fc.Arbitrary<{name}<{generic_parameters}>> => {arbitrary};\n"
+            )?;
+        }
+        self.pending_arbitraries.remove(&id.original);
+        Ok(())
+    }
+
+    /// The arbitrary of an algebraic enum, one of the arbitraries of its variants.
+    fn arbitrary_algebraic_enum(&self, e: &RustEnum) -> String {
+        let RustEnum::Algebraic {
+            tag_key,
+            content_key,
+            shared,
+        } = e
+        else {
+            unreachable!()
+        };
+        let tag_key = typescript_property_aware_rename(tag_key);
+        let content_key = typescript_property_aware_rename(content_key);
+        let variants = shared
+            .variants
+            .iter()
+            .map(|v| {
+                let tag = format!(
+                    "{tag_key}: fc.constant({:?} as const)",
+                    v.shared().id.renamed
+                );
+                match v {
+                    RustEnumVariant::Unit(_) => format!("\tfc.record({{ {tag} }}),\n"),
+                    RustEnumVariant::Tuple { ty, .. } => {
+                        let mut content = self.arbitrary_expr(ty);
+                        if ty.is_optional() {
+                            content = format!("fc.option({content}, {{ nil: undefined }})");
+                        }
+                        format!("\tfc.record({{ {tag}, {content_key}: {content} }}),\n")
+                    }
+                    RustEnumVariant::AnonymousStruct { fields, .. } => format!(
+                        "\tfc.record({{ {tag}, {content_key}: {} }}),\n",
+                        self.arbitrary_record(fields, 1)
+                    ),
+                }
+            })
+            .join("");
+        format!("fc.oneof(\n{variants})")
+    }
+
+    /// The arbitrary of an object with the properties of the fields, leaving out the
+    /// optional ones at times.
+    fn arbitrary_record(&self, fields: &[RustField], indent: usize) -> String {
+        if fields.is_empty() {
+            return "fc.record({})".to_owned();
+        }
+        let mut required_keys = Vec::new();
+        let properties = fields
+            .iter()
+            .map(|f| {
+                let property = self.property_name(f);
+                let (optional, nullable) = self.optionality(f);
+                let mut arbitrary = match f.type_override(SupportedLanguage::TypeScript) {
+                    Some(_) => ANY_ARBITRARY.to_owned(),
+                    None => self.arbitrary_expr(&f.ty),
+                };
+                if nullable {
+                    arbitrary = format!("fc.option({arbitrary}, {{ nil: null }})");
+                }
+                if !optional {
+                    required_keys.push(format!("{property:?}"));
+                }
+                format!(
+                    "{}{}: {arbitrary},\n",
+                    "\t".repeat(indent + 1),
+                    typescript_property_aware_rename(&property)
+                )
+            })
+            .join("");
+        let record = format!("fc.record({{\n{properties}{}}}", "\t".repeat(indent));
+        if required_keys.len() == fields.len() {
+            format!("{record})")
+        } else {
+            format!(
+                "{record}, {{ requiredKeys: [{}] }})",
+                required_keys.join(", ")
+            )
+        }
+    }
+
+    /// The fast-check arbitrary generating values of type `ty`. Optional values are
+    /// generated by the properties and variants they're in.
+    fn arbitrary_expr(&self, ty: &RustType) -> String {
+        match ty {
+            RustType::Simple { id } | RustType::Generic { id, .. }
+                if self.type_mappings.contains_key(id.as_str()) =>
+            {
+                ANY_ARBITRARY.to_owned()
+            }
+            RustType::Simple { id } => self.lazy_arbitrary(id, format!("arb{id}")),
+            RustType::Generic { id, parameters } => {
+                let arbitraries = parameters.iter().map(|p| self.arbitrary_expr(p)).join(", ");
+                self.lazy_arbitrary(id, format!("arb{id}({arbitraries})"))
+            }
+            RustType::Special(special_ty)
+                if self.type_mappings.contains_key(&special_ty.to_string()) =>
+            {
+                ANY_ARBITRARY.to_owned()
+            }
+            RustType::Special(special_ty) => match special_ty {
+                SpecialRustType::Vec(inner) | SpecialRustType::Slice(inner) => {
+                    format!("fc.array({})", self.arbitrary_expr(inner))
+                }
+                SpecialRustType::Array(inner, len) => format!(
+                    "fc.tuple({})",
+                    std::iter::repeat_n(self.arbitrary_expr(inner), *len).join(", ")
+                ),
+                SpecialRustType::Option(inner) => self.arbitrary_expr(inner),
+                SpecialRustType::HashMap(key, value) => {
+                    // Keys of objects are strings, whatever the type of the keys is
+                    let key_arbitrary = match key.as_ref() {
+                        RustType::Special(SpecialRustType::String | SpecialRustType::Char) => {
+                            self.arbitrary_expr(key)
+                        }
+                        RustType::Special(_) => {
+                            format!("{}.map(String)", self.arbitrary_expr(key))
+                        }
+                        _ => self.arbitrary_expr(key),
+                    };
+                    format!(
+                        "fc.dictionary({key_arbitrary}, {})",
+                        self.arbitrary_expr(value)
+                    )
+                }
+                SpecialRustType::Unit => "fc.constant(undefined)".to_owned(),
+                SpecialRustType::DateTime => "fc.date({ noInvalidDate: true })".to_owned(),
+                SpecialRustType::JsonValue => {
+                    self.lazy_arbitrary(special_ty.id(), format!("arb{}", special_ty.id()))
+                }
+                SpecialRustType::String => "fc.string()".to_owned(),
+                SpecialRustType::Char => "fc.string({ minLength: 1, maxLength: 1 })".to_owned(),
+                SpecialRustType::I8 => "fc.integer({ min: -128, max: 127 })".to_owned(),
+                SpecialRustType::U8 => "fc.integer({ min: 0, max: 255 })".to_owned(),
+                SpecialRustType::I16 => "fc.integer({ min: -32768, max: 32767 })".to_owned(),
+                SpecialRustType::U16 => "fc.integer({ min: 0, max: 65535 })".to_owned(),
+                SpecialRustType::I32 => "fc.integer()".to_owned(),
+                SpecialRustType::U32 => {
+                    "fc.bigInt({ min: 0n, max: 4294967295n }).map(Number)".to_owned()
+                }
+                SpecialRustType::I54 | SpecialRustType::I64 | SpecialRustType::ISize => {
+                    "fc.maxSafeInteger()".to_owned()
+                }
+                SpecialRustType::U53 | SpecialRustType::U64 | SpecialRustType::USize => {
+                    "fc.maxSafeNat()".to_owned()
+                }
+                SpecialRustType::Bool => "fc.boolean()".to_owned(),
+                // JSON has no NaN or infinities
+                SpecialRustType::F32 => {
+                    "fc.float({ noNaN: true, noDefaultInfinity: true })".to_owned()
+                }
+                SpecialRustType::F64 => {
+                    "fc.double({ noNaN: true, noDefaultInfinity: true })".to_owned()
+                }
+            },
+        }
+    }
+
+    /// The arbitrary of a type of the file, deferred until values are generated if it
+    /// isn't written yet, like that of a recursive type.
+    fn lazy_arbitrary(&self, id: &str, arbitrary: String) -> String {
+        if self.pending_arbitraries.contains(id) {
+            format!("fc.constant(null).chain(() => {arbitrary})")
+        } else {
+            arbitrary
+        }
+    }
+
     fn write_comments(
         &mut self,
         w: &mut dyn Write,
@@ -452,9 +1104,99 @@ impl TypeScript {
     }
 }
 
+/// The arbitrary of values of types typeshare doesn't know, like those of type mappings.
+const ANY_ARBITRARY: &str = "fc.anything() as fc.Arbitrary<any>";
+
+/// The name of the argument of `decode<Type>` and `encode<Type>` functions.
+fn codec_argument(decode: bool) -> &'static str {
+    if decode {
+        "json"
+    } else {
+        "value"
+    }
+}
+
+/// Access the property `name` of `object`.
+fn property_access(object: &str, name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if is_identifier {
+        format!("{object}.{name}")
+    } else {
+        format!("{object}[{name:?}]")
+    }
+}
+
 fn typescript_property_aware_rename(name: &str) -> String {
     if name.chars().any(|c| c == '-') {
         return format!("{name:?}");
     }
     name.to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::{TypeScript, TypeScriptFieldNaming, TypeScriptModuleFormat};
+    use crate::language::{CrateName, Language};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn imports(ts: &mut TypeScript) -> String {
+        let foo = CrateName::from("foo");
+        let bar = CrateName::from("bar");
+        let imports = BTreeMap::from([
+            (&foo, BTreeSet::from(["Foo"])),
+            (&bar, BTreeSet::from(["Bar", "Baz"])),
+        ]);
+
+        let mut output = Vec::new();
+        ts.write_imports(&mut output, imports).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn module_format_imports() {
+        let mut ts = TypeScript::default();
+        assert_eq!(
+            imports(&mut ts),
+            "import { Bar, Baz } from \"./bar\";\nimport { Foo } from \"./foo\";\n\n"
+        );
+
+        ts.module_format = TypeScriptModuleFormat::Esm;
+        assert_eq!(
+            imports(&mut ts),
+            "import type { Bar, Baz } from \"./bar.js\";\nimport type { Foo } from \"./foo.js\";\n\n"
+        );
+
+        ts.module_format = TypeScriptModuleFormat::CommonJs;
+        assert_eq!(
+            imports(&mut ts),
+            "import type { Bar, Baz } from \"./bar\";\nimport type { Foo } from \"./foo\";\n\n"
+        );
+
+        ts.import_extension = Some(".mjs".into());
+        assert_eq!(
+            imports(&mut ts),
+            "import type { Bar, Baz } from \"./bar.mjs\";\nimport type { Foo } from \"./foo.mjs\";\n\n"
+        );
+    }
+
+    #[test]
+    fn camel_case_fields_import_codecs() {
+        let mut ts = TypeScript {
+            module_format: TypeScriptModuleFormat::Esm,
+            field_naming: TypeScriptFieldNaming::CamelCase,
+            ..Default::default()
+        };
+        assert_eq!(
+            imports(&mut ts),
+            concat!(
+                "import type { Bar, Baz } from \"./bar.js\";\n",
+                "import { decodeBar, encodeBar, decodeBaz, encodeBaz } from \"./bar.js\";\n",
+                "import type { Foo } from \"./foo.js\";\n",
+                "import { decodeFoo, encodeFoo } from \"./foo.js\";\n\n",
+            )
+        );
+    }
+}
@@ -1,17 +1,95 @@
 //! The core library for typeshare.
 //! Contains the parser and language converters.
+//!
+//! # Embedding
+//!
+//! The items re-exported at the root of the crate are its API for embedding typeshare,
+//! like in build scripts, build systems or code generation servers:
+//!
+//! - [`generate`] generates the types of Rust files and directories into a single file,
+//!   and [`generate_source`] those of Rust source code.
+//! - [`parse`] parses the source code of a Rust file into [`ParsedData`], which every
+//!   [`Language`] generates types from with [`Language::generate_types`], or emits as
+//!   the parts of a [`FileOutput`] with [`Language::emit`].
+//! - [`Ir`] serializes [`ParsedData`] to JSON, in a format described in the [`ir`]
+//!   module.
+//! - [`docs::generate`] writes reference documentation of the types, as Markdown or
+//!   HTML.
+//! - [`schema_hash`] fingerprints the serialized shape of a type, like the constants
+//!   languages write with their `schema_hashes` option.
+//! - [`Plugin`] runs a language backend outside of typeshare on the [`ParsedData`] of
+//!   every crate, as described in the [`plugin`] module.
+//! - [`conformance::generate`] generates round-trip tests of the generated types, which
+//!   decode and encode samples serialized by serde, which [`samples::generate`] can
+//!   generate.
+//! - The languages, like [`TypeScript`] or [`Swift`], whose options are their public
+//!   fields. New options are added as fields with defaults, so construct languages
+//!   with `..Default::default()`.
+//!
+//! ```
+//! use typeshare_core::{generate_source, TypeScript};
+//!
+//! let source = generate_source(
+//!     &mut TypeScript {
+//!         no_version_header: true,
+//!         ..Default::default()
+//!     },
+//!     "#[typeshare] pub struct User { pub name: String }",
+//! )
+//! .unwrap();
+//! assert_eq!(source, "export interface User {\n\tname: string;\n}\n\n");
+//! ```
+//!
+//! The modules have more of typeshare, for its command line tool, and may be
+//! reorganized, so import from the root of the crate. Neither is stable yet: between
+//! minor versions, the types of [`ParsedData`] may gain fields, and the methods of
+//! [`Language`] may change their parameters.
+pub mod collision;
+pub mod conformance;
 pub mod context;
+pub mod docs;
 pub mod error;
+pub mod generate;
+pub mod ir;
 /// Implementations for each language converter
 pub mod language;
+pub mod lint;
 /// Parsing Rust code into a format the `language` modules can understand
 pub mod parser;
+#[cfg(feature = "fs")]
+pub mod plugin;
 pub mod reconcile;
 mod rename;
 /// Codifying Rust types and how they convert to various languages.
 pub mod rust_types;
+pub mod samples;
+pub mod schema_hash;
 mod target_os_check;
 mod topsort;
+pub mod validate;
 mod visitors;
 
+pub use collision::{name_collisions, NameCollision};
+pub use context::{ParseContext, ParseFileContext};
+pub use error::{
+    ConformanceError, GenerateError, GenerationError, ParseError, ParseErrorWithSpan, PluginError,
+    SourceLocation, SourceSpan,
+};
+pub use generate::generate_source;
+#[cfg(feature = "fs")]
+pub use generate::{generate, GenerateConfig, Outputs};
+pub use ir::Ir;
+#[doc(inline)]
+pub use language::{
+    CodeStyle, CrateName, CrateTypes, Escape, FileHeader, FileOutput, Go, IdentifierPolicy, Indent,
+    ItemKind, ItemOutput, Kotlin, Language, OptionalFields, Python, ReasonML, Scala,
+    SupportedLanguage, Swift, TypeScript,
+};
+#[cfg(feature = "template")]
+#[doc(inline)]
+pub use language::{Template, TemplateSources};
+pub use parser::{parse, ParsedData};
+#[cfg(feature = "fs")]
+pub use plugin::{Plugin, PluginFile};
 pub use rename::RenameExt;
+pub use schema_hash::schema_hash;
@@ -0,0 +1,383 @@
+//! Warnings about typeshared items which aren't generated exactly like they're
+//! serialized, grouped by the lint they belong to.
+use crate::{
+    error::{SourceLocation, SourceSpan},
+    parser::{get_meta_items, ParsedData, SERDE, TYPESHARE},
+    rust_types::{RustEnumVariant, RustField, RustType, SpecialRustType},
+};
+use proc_macro2::Span;
+use std::{collections::HashMap, fmt, path::Path, str::FromStr};
+use syn::{spanned::Spanned as _, Attribute, Fields, Ident, ItemEnum, ItemStruct, ItemType, Meta};
+
+/// The serde attributes typeshare understands, or which don't change how an item
+/// is serialized.
+const SUPPORTED_SERDE_ATTRIBUTES: &[&str] = &[
+    "alias",
+    "borrow",
+    "bound",
+    "content",
+    "crate",
+    "default",
+    "deny_unknown_fields",
+    "expecting",
+    "flatten",
+    "rename",
+    "rename_all",
+    "skip",
+    "skip_serializing_if",
+    "tag",
+];
+
+/// A kind of warning, which can be allowed, warned about or denied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Lint {
+    /// An item the language can't represent, which is generated as a fallback like a
+    /// comment or an opaque type.
+    UnsupportedItem,
+    /// A serde attribute typeshare doesn't understand, which can change how the item
+    /// is serialized.
+    UnsupportedAttribute,
+    /// A field or variant skipped with `#[serde(skip)]` or `#[typeshare(skip)]`.
+    SkippedField,
+    /// A Rust type generated as a type which doesn't represent it exactly, like
+    /// an integer generated as a floating point number.
+    LossyMapping,
+    /// A reference to a type which isn't typeshared, or mapped to a type of the
+    /// language, which is generated as a name nothing defines.
+    UnknownType,
+    /// Structs which contain each other in a cycle, which the structs of the language
+    /// can't, like Swift's.
+    RecursiveStruct,
+}
+
+impl Lint {
+    /// Every lint.
+    pub const ALL: [Lint; 6] = [
+        Lint::UnsupportedItem,
+        Lint::UnsupportedAttribute,
+        Lint::SkippedField,
+        Lint::LossyMapping,
+        Lint::UnknownType,
+        Lint::RecursiveStruct,
+    ];
+
+    /// The name of the lint, like `lossy_mapping`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnsupportedItem => "unsupported_item",
+            Lint::UnsupportedAttribute => "unsupported_attribute",
+            Lint::SkippedField => "skipped_field",
+            Lint::LossyMapping => "lossy_mapping",
+            Lint::UnknownType => "unknown_type",
+            Lint::RecursiveStruct => "recursive_struct",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .into_iter()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| format!("Unknown lint {s:?}"))
+    }
+}
+
+/// A warning about a typeshared item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The lint the warning belongs to.
+    pub lint: Lint,
+    /// The Rust name of the item.
+    pub item: String,
+    /// What is wrong with the item.
+    pub message: String,
+    /// Where the item, or what's wrong with it, is in the Rust source, if known.
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.item, self.message, self.lint)
+    }
+}
+
+/// Warnings about the attributes and skipped fields of a typeshared struct.
+pub(crate) fn struct_warnings(s: &ItemStruct, file_path: &Path) -> Vec<Warning> {
+    item_warnings(&s.ident, &s.attrs, field_attrs(&s.fields), file_path)
+}
+
+/// Warnings about the attributes and skipped variants or fields of a typeshared enum.
+pub(crate) fn enum_warnings(e: &ItemEnum, file_path: &Path) -> Vec<Warning> {
+    let variants = e
+        .variants
+        .iter()
+        .flat_map(|v| {
+            let variant = (
+                format!("variant `{}`", v.ident),
+                v.attrs.as_slice(),
+                v.ident.span(),
+            );
+            std::iter::once(variant).chain(field_attrs(&v.fields))
+        })
+        .collect();
+    item_warnings(&e.ident, &e.attrs, variants, file_path)
+}
+
+/// Warnings about the attributes of a typeshared type alias.
+pub(crate) fn alias_warnings(t: &ItemType, file_path: &Path) -> Vec<Warning> {
+    item_warnings(&t.ident, &t.attrs, Vec::new(), file_path)
+}
+
+fn item_warnings(
+    ident: &Ident,
+    attrs: &[Attribute],
+    fields: Vec<(String, &[Attribute], Span)>,
+    file_path: &Path,
+) -> Vec<Warning> {
+    let item = ident.to_string();
+    let warning = |lint, message, span: Span| Warning {
+        lint,
+        item: item.clone(),
+        message,
+        location: Some(SourceLocation::new(file_path, span)),
+    };
+
+    let mut warnings = unsupported_attributes(attrs)
+        .map(|(attribute, span)| warning(Lint::UnsupportedAttribute, attribute, span))
+        .collect::<Vec<_>>();
+    for (name, attrs, span) in fields {
+        warnings.extend(unsupported_attributes(attrs).map(|(attribute, span)| {
+            warning(
+                Lint::UnsupportedAttribute,
+                format!("{attribute} on {name}"),
+                span,
+            )
+        }));
+        let skipped = attrs.iter().any(|attr| {
+            get_meta_items(attr, SERDE)
+                .chain(get_meta_items(attr, TYPESHARE))
+                .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident("skip")))
+        });
+        if skipped {
+            warnings.push(warning(
+                Lint::SkippedField,
+                format!("{name} is skipped"),
+                span,
+            ));
+        }
+    }
+    warnings
+}
+
+fn field_attrs(fields: &Fields) -> Vec<(String, &[Attribute], Span)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let name = f
+                .ident
+                .as_ref()
+                .map_or_else(|| i.to_string(), ToString::to_string);
+            let span = f.ident.as_ref().map_or_else(|| f.ty.span(), Ident::span);
+            (format!("field `{name}`"), f.attrs.as_slice(), span)
+        })
+        .collect()
+}
+
+/// The serde attributes typeshare doesn't understand, like `serde(untagged)`, with
+/// where they are.
+fn unsupported_attributes(attrs: &[Attribute]) -> impl Iterator<Item = (String, Span)> + '_ {
+    attrs
+        .iter()
+        .flat_map(|attr| get_meta_items(attr, SERDE))
+        .filter_map(|meta| {
+            let name = meta.path().get_ident()?.to_string();
+            (!SUPPORTED_SERDE_ATTRIBUTES.contains(&name.as_str())).then(|| {
+                (
+                    format!("`serde({name})` isn't supported"),
+                    meta.path().span(),
+                )
+            })
+        })
+}
+
+/// Warnings for the special types of the parsed data which `lossy` maps to a type of
+/// the language that doesn't represent them exactly, like `float` for `u32`.
+pub fn lossy_mappings(
+    data: &ParsedData,
+    language: &str,
+    lossy: impl Fn(&SpecialRustType) -> Option<&'static str>,
+) -> Vec<Warning> {
+    special_type_warnings(data, Lint::LossyMapping, |ty| {
+        lossy(ty)
+            .map(|generated| format!("`{}` is generated as `{generated}` in {language}", ty.id()))
+    })
+}
+
+/// Warnings for the special types of the parsed data which the language has no type
+/// for, and generates as a fallback, described by `unsupported`.
+pub fn unsupported_types(
+    data: &ParsedData,
+    unsupported: impl Fn(&SpecialRustType) -> Option<String>,
+) -> Vec<Warning> {
+    special_type_warnings(data, Lint::UnsupportedItem, unsupported)
+}
+
+/// Warnings for the `serde_json::Value`s of the parsed data, which languages without a
+/// type for any JSON value generate as a type named `Value`, unless it's mapped.
+pub fn unmapped_json_values(
+    data: &ParsedData,
+    language: &str,
+    type_mappings: &HashMap<String, String>,
+) -> Vec<Warning> {
+    if type_mappings.contains_key(SpecialRustType::JsonValue.id()) {
+        return Vec::new();
+    }
+    unsupported_types(data, |ty| {
+        matches!(ty, SpecialRustType::JsonValue).then(|| {
+            format!("`serde_json::Value` has no type in {language}, and is generated as `Value`")
+        })
+    })
+}
+
+/// Warnings for the special types `describe` has a message for, once per item.
+fn special_type_warnings(
+    data: &ParsedData,
+    lint: Lint,
+    describe: impl Fn(&SpecialRustType) -> Option<String>,
+) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    for (item, location, types) in item_types(data) {
+        let mut found = Vec::new();
+        for (ty, span) in types {
+            let mut messages = Vec::new();
+            collect_messages(ty, &describe, &mut messages);
+            found.extend(messages.into_iter().map(|message| (message, span)));
+        }
+        // The first field a type is referenced in is where it's reported
+        found.sort_by(|(a, _), (b, _)| a.cmp(b));
+        found.dedup_by(|(a, _), (b, _)| a == b);
+        warnings.extend(found.into_iter().map(|(message, span)| Warning {
+            lint,
+            item: item.clone(),
+            message,
+            location: location.map(|l| l.at(span)),
+        }));
+    }
+    warnings
+}
+
+/// The types the structs, enums and type aliases of the parsed data reference,
+/// with where they're referenced, by the Rust name and location of the item.
+#[allow(clippy::type_complexity)]
+pub(crate) fn item_types(
+    data: &ParsedData,
+) -> impl Iterator<
+    Item = (
+        &String,
+        Option<&SourceLocation>,
+        Vec<(&RustType, Option<SourceSpan>)>,
+    ),
+> {
+    let structs = data
+        .structs
+        .iter()
+        .map(|s| (&s.id.original, s.location.as_ref(), field_types(&s.fields)));
+    let enums = data.enums.iter().map(|e| {
+        let shared = e.shared();
+        let types = shared
+            .variants
+            .iter()
+            .flat_map(|v| match v {
+                RustEnumVariant::Unit(_) => Vec::new(),
+                RustEnumVariant::Tuple { ty, shared } => vec![(ty, shared.span)],
+                RustEnumVariant::AnonymousStruct { fields, .. } => field_types(fields),
+            })
+            .collect();
+        (&shared.id.original, shared.location.as_ref(), types)
+    });
+    let aliases = data
+        .aliases
+        .iter()
+        .map(|a| (&a.id.original, a.location.as_ref(), vec![(&a.r#type, None)]));
+    structs.chain(enums).chain(aliases)
+}
+
+fn field_types(fields: &[RustField]) -> Vec<(&RustType, Option<SourceSpan>)> {
+    fields.iter().map(|f| (&f.ty, f.span)).collect()
+}
+
+fn collect_messages(
+    ty: &RustType,
+    describe: &impl Fn(&SpecialRustType) -> Option<String>,
+    found: &mut Vec<String>,
+) {
+    if let RustType::Special(special) = ty {
+        found.extend(describe(special));
+    }
+    for parameter in ty.parameters() {
+        collect_messages(parameter, describe, found);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{struct_warnings, Lint};
+    use std::path::Path;
+
+    #[test]
+    fn warns_about_attributes_and_skipped_fields() {
+        let item = syn::parse_str(
+            r#"
+            #[typeshare]
+            #[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+            pub struct Foo {
+                #[serde(skip)]
+                pub a: String,
+                #[serde(with = "time", default)]
+                pub b: String,
+            }
+            "#,
+        )
+        .unwrap();
+        let warnings = struct_warnings(&item, Path::new("src/lib.rs"))
+            .into_iter()
+            .map(|warning| {
+                let location = warning.location.unwrap().to_string();
+                (warning.lint, warning.message, location)
+            })
+            .collect::<Vec<_>>();
+        let warning =
+            |lint, message: &str, location: &str| (lint, message.to_string(), location.to_string());
+        assert_eq!(
+            warnings,
+            [
+                warning(
+                    Lint::UnsupportedAttribute,
+                    "`serde(rename_all_fields)` isn't supported",
+                    "src/lib.rs:3:46"
+                ),
+                warning(
+                    Lint::SkippedField,
+                    "field `a` is skipped",
+                    "src/lib.rs:6:20"
+                ),
+                warning(
+                    Lint::UnsupportedAttribute,
+                    "`serde(with)` isn't supported on field `b`",
+                    "src/lib.rs:7:24"
+                ),
+            ]
+        );
+        assert_eq!("lossy_mapping".parse(), Ok(Lint::LossyMapping));
+    }
+}
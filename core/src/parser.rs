@@ -1,7 +1,8 @@
 use crate::{
     context::{ParseContext, ParseFileContext},
-    error::{ParseError, ParseErrorWithSpan, WithSpan as _},
+    error::{ParseError, ParseErrorWithSpan, SourceLocation, SourceSpan, WithSpan as _},
     language::{CrateName, SupportedLanguage},
+    lint::Warning,
     rename::RenameExt,
     rust_types::{
         DecoratorMap, FieldDecorator, Id, RustConst, RustConstExpr, RustEnum, RustEnumShared,
@@ -14,22 +15,26 @@ use crate::{
 use itertools::Either;
 use log::debug;
 use proc_macro2::Ident;
+use quote::ToTokens;
+use serde::Serialize;
 use std::{
     collections::{BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     ops::AddAssign,
+    path::Path,
 };
 use syn::{
     ext::IdentExt, parse::ParseBuffer, punctuated::Punctuated, spanned::Spanned as _, visit::Visit,
-    Attribute, Expr, ExprLit, Fields, GenericParam, ItemConst, ItemEnum, ItemStruct, ItemType, Lit,
-    LitStr, Meta, MetaList, MetaNameValue, Token,
+    visit_mut::VisitMut, Attribute, Expr, ExprLit, Fields, GenericParam, ItemConst, ItemEnum,
+    ItemStruct, ItemType, Lit, LitStr, Meta, MetaList, MetaNameValue, Token,
 };
 
-const TYPESHARE: &str = "typeshare";
-const SERDE: &str = "serde";
+pub(crate) const TYPESHARE: &str = "typeshare";
+pub(crate) const SERDE: &str = "serde";
 
 /// Supported typeshare type level decorator attributes.
-#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Copy, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum DecoratorKind {
     /// The typeshare attribute for swift type constraints "swift"
     Swift,
@@ -51,16 +56,40 @@ impl DecoratorKind {
 }
 
 /// Error with it's related data.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ErrorInfo {
     /// The file name being parsed.
     pub file_name: String,
     /// The parse error.
     pub error: String,
+    /// Where the error is in the file, if known.
+    pub span: Option<SourceSpan>,
+    /// Whether the error is about Rust typeshare doesn't support, rather than
+    /// Rust which couldn't be parsed.
+    pub unsupported: bool,
 }
 
-/// The results of parsing Rust source input.
-#[derive(Default, Debug)]
+/// Warning with it's related data.
+#[derive(Debug, Clone)]
+pub struct WarningInfo {
+    /// The file name being parsed.
+    pub file_name: String,
+    /// The warning.
+    pub warning: Warning,
+}
+
+/// A typeshared item which isn't generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedItem {
+    /// The Rust name of the item.
+    pub name: String,
+    /// Why the item isn't generated.
+    pub reason: String,
+}
+
+/// The results of parsing Rust source input. Serialized, it has the typeshared items and
+/// the crate they're in.
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct ParsedData {
     /// Structs defined in the source
     pub structs: Vec<RustStruct>,
@@ -71,16 +100,27 @@ pub struct ParsedData {
     /// Constant variables defined in the source
     pub consts: Vec<RustConst>,
     /// Imports used by this file
+    #[serde(skip)]
     pub import_types: HashSet<ImportedType>,
     /// Crate this belongs to.
     pub crate_name: CrateName,
     /// File name to write to for generated type.
+    #[serde(skip)]
     pub file_name: String,
     /// All type names
+    #[serde(skip)]
     pub type_names: HashSet<String>,
     /// Failures during parsing.
+    #[serde(skip)]
     pub errors: Vec<ErrorInfo>,
+    /// Typeshared items which aren't generated.
+    #[serde(skip)]
+    pub skipped: Vec<SkippedItem>,
+    /// Warnings about the typeshared items.
+    #[serde(skip)]
+    pub warnings: Vec<WarningInfo>,
     /// Using multi file support.
+    #[serde(skip)]
     pub multi_file: bool,
 }
 
@@ -95,6 +135,8 @@ impl AddAssign<ParsedData> for ParsedData {
         self.import_types.extend(rhs.import_types);
         self.type_names.extend(rhs.type_names);
         self.errors.append(&mut rhs.errors);
+        self.skipped.append(&mut rhs.skipped);
+        self.warnings.append(&mut rhs.warnings);
 
         self.file_name = rhs.file_name;
         self.crate_name = rhs.crate_name;
@@ -134,6 +176,27 @@ impl ParsedData {
         }
     }
 
+    /// Sort the items of each kind by their Rust names, so they're generated in the same
+    /// order however they're spread over files and modules. The items other items depend
+    /// on are still generated before them.
+    pub fn sort_by_name(&mut self) {
+        self.structs
+            .sort_by(|a, b| a.id.original.cmp(&b.id.original));
+        self.enums
+            .sort_by(|a, b| a.shared().id.original.cmp(&b.shared().id.original));
+        self.aliases
+            .sort_by(|a, b| a.id.original.cmp(&b.id.original));
+        self.consts
+            .sort_by(|a, b| a.id.original.cmp(&b.id.original));
+    }
+
+    /// Whether a struct, enum or type alias with the Rust name was parsed.
+    pub fn has_type(&self, name: &str) -> bool {
+        self.structs.iter().any(|s| s.id.original == name)
+            || self.enums.iter().any(|e| e.shared().id.original == name)
+            || self.aliases.iter().any(|a| a.id.original == name)
+    }
+
     /// If this file was skipped by the visitor.
     pub fn is_empty(&self) -> bool {
         self.structs.is_empty()
@@ -163,17 +226,97 @@ pub fn parse(
     } = parse_file_context;
 
     debug!("parsing {file_path:?}");
+    let mut file = syn::parse_file(&source_code).map_err(|err| {
+        let span = err.span();
+        ParseError::from(err).with_span(span)
+    })?;
+    qualify_json_values(&mut file);
     // Parse and process the input, ensuring we parse only items marked with
     // `#[typeshare]`
     let mut import_visitor = TypeShareVisitor::new(parse_context, crate_name, file_name, file_path);
-    import_visitor.visit_file(&syn::parse_file(&source_code).map_err(|err| {
-        let span = err.span();
-        ParseError::from(err).with_span(span)
-    })?);
+    import_visitor.visit_file(&file);
 
     Ok(import_visitor.parsed_data())
 }
 
+/// Replace the names `serde_json::Value` is imported as in the file, like `Value` after
+/// `use serde_json::Value;`, with its path, so it's parsed as
+/// [`SpecialRustType::JsonValue`] rather than as a type named `Value`.
+fn qualify_json_values(file: &mut syn::File) {
+    /// The names `serde_json::Value` is imported as.
+    #[derive(Default)]
+    struct JsonValueImports(HashSet<Ident>);
+
+    impl JsonValueImports {
+        fn collect(&mut self, tree: &syn::UseTree, in_serde_json: bool) {
+            match tree {
+                syn::UseTree::Path(path) => self.collect(&path.tree, path.ident == "serde_json"),
+                syn::UseTree::Name(name) if in_serde_json && name.ident == "Value" => {
+                    self.0.insert(name.ident.clone());
+                }
+                syn::UseTree::Rename(rename) if in_serde_json && rename.ident == "Value" => {
+                    self.0.insert(rename.rename.clone());
+                }
+                syn::UseTree::Group(group) => {
+                    for tree in &group.items {
+                        self.collect(tree, in_serde_json);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    impl Visit<'_> for JsonValueImports {
+        fn visit_item_use(&mut self, i: &syn::ItemUse) {
+            self.collect(&i.tree, false);
+        }
+    }
+
+    impl VisitMut for JsonValueImports {
+        fn visit_type_path_mut(&mut self, i: &mut syn::TypePath) {
+            match i.path.get_ident() {
+                Some(ident) if i.qself.is_none() && self.0.contains(ident) => {
+                    i.path = syn::parse_quote_spanned!(ident.span()=> serde_json::Value);
+                }
+                _ => syn::visit_mut::visit_type_path_mut(self, i),
+            }
+        }
+    }
+
+    let mut imports = JsonValueImports::default();
+    imports.visit_file(file);
+    if !imports.0.is_empty() {
+        imports.visit_file_mut(file);
+    }
+}
+
+/// The Rust name of the struct, enum, type alias or constant of the source code whose
+/// definition, with its attributes and doc comments, spans the line, which is 1-based,
+/// like the item under the cursor of an editor. Items in inline modules are found too.
+pub fn item_at_line(source_code: &str, line: usize) -> Result<Option<String>, ParseError> {
+    fn find(items: &[syn::Item], line: usize) -> Option<String> {
+        let item = items.iter().find(|item| {
+            let mut tokens = item.to_token_stream().into_iter();
+            let first = tokens.next().map(|token| token.span().start().line);
+            let last = tokens.last().map(|token| token.span().end().line).or(first);
+            first
+                .zip(last)
+                .is_some_and(|(first, last)| (first..=last).contains(&line))
+        })?;
+        match item {
+            syn::Item::Struct(s) => Some(s.ident.to_string()),
+            syn::Item::Enum(e) => Some(e.ident.to_string()),
+            syn::Item::Type(t) => Some(t.ident.to_string()),
+            syn::Item::Const(c) => Some(c.ident.to_string()),
+            syn::Item::Mod(m) => find(&m.content.as_ref()?.1, line),
+            _ => None,
+        }
+    }
+
+    Ok(find(&syn::parse_file(source_code)?.items, line))
+}
+
 /// Parses a struct into a definition that more succinctly represents what
 /// typeshare needs to generate code for other languages.
 ///
@@ -181,8 +324,10 @@ pub fn parse(
 /// hack.
 pub(crate) fn parse_struct(
     s: &ItemStruct,
+    file_path: &Path,
     target_os: &[String],
 ) -> Result<RustItem, ParseErrorWithSpan> {
+    let location = SourceLocation::new(file_path, s.ident.span());
     let serde_rename_all = serde_rename_all(&s.attrs);
 
     let generic_types = s
@@ -203,9 +348,12 @@ pub(crate) fn parse_struct(
             id: get_ident(Some(&s.ident), &s.attrs, &None),
             r#type: ty.parse()?,
             comments: parse_comment_attrs(&s.attrs),
+            deprecated: parse_deprecation(&s.attrs),
             generic_types,
             decorators: get_decorators(&s.attrs),
             is_redacted: is_redacted(&s.attrs),
+            is_newtype: false,
+            location: Some(location),
         }));
     }
 
@@ -235,8 +383,10 @@ pub(crate) fn parse_struct(
                         id: get_ident(f.ident.as_ref(), &f.attrs, &serde_rename_all),
                         ty,
                         comments: parse_comment_attrs(&f.attrs),
+                        deprecated: parse_deprecation(&f.attrs),
                         has_default,
                         decorators,
+                        span: Some(field_span(f)),
                     })
                 })
                 .collect::<Result<_, ParseErrorWithSpan>>()?;
@@ -246,8 +396,11 @@ pub(crate) fn parse_struct(
                 generic_types,
                 fields,
                 comments: parse_comment_attrs(&s.attrs),
+                deprecated: parse_deprecation(&s.attrs),
                 decorators: get_decorators(&s.attrs),
                 is_redacted: is_redacted(&s.attrs),
+                deny_unknown_fields: serde_deny_unknown_fields(&s.attrs),
+                location: Some(location),
             })
         }
         // Tuple structs
@@ -267,9 +420,12 @@ pub(crate) fn parse_struct(
                 id: get_ident(Some(&s.ident), &s.attrs, &None),
                 r#type: ty,
                 comments: parse_comment_attrs(&s.attrs),
+                deprecated: parse_deprecation(&s.attrs),
                 generic_types,
                 decorators: get_decorators(&s.attrs),
                 is_redacted: is_redacted(&s.attrs),
+                is_newtype: true,
+                location: Some(location),
             })
         }
         // Unit structs or `None`
@@ -278,8 +434,11 @@ pub(crate) fn parse_struct(
             generic_types,
             fields: vec![],
             comments: parse_comment_attrs(&s.attrs),
+            deprecated: parse_deprecation(&s.attrs),
             decorators: get_decorators(&s.attrs),
             is_redacted: is_redacted(&s.attrs),
+            deny_unknown_fields: serde_deny_unknown_fields(&s.attrs),
+            location: Some(location),
         }),
     })
 }
@@ -291,8 +450,10 @@ pub(crate) fn parse_struct(
 /// hack.
 pub(crate) fn parse_enum(
     e: &ItemEnum,
+    file_path: &Path,
     target_os: &[String],
 ) -> Result<RustItem, ParseErrorWithSpan> {
+    let location = SourceLocation::new(file_path, e.ident.span());
     let generic_types = e
         .generics
         .params
@@ -312,9 +473,12 @@ pub(crate) fn parse_enum(
             id: get_ident(Some(&e.ident), &e.attrs, &None),
             r#type: ty.parse()?,
             comments: parse_comment_attrs(&e.attrs),
+            deprecated: parse_deprecation(&e.attrs),
             generic_types,
             decorators: get_decorators(&e.attrs),
             is_redacted: is_redacted(&e.attrs),
+            is_newtype: false,
+            location: Some(location),
         }));
     }
 
@@ -347,11 +511,13 @@ pub(crate) fn parse_enum(
     let shared = RustEnumShared {
         id: get_ident(Some(&e.ident), &e.attrs, &None),
         comments: parse_comment_attrs(&e.attrs),
+        deprecated: parse_deprecation(&e.attrs),
         variants,
         decorators: get_decorators(&e.attrs),
         generic_types,
         is_recursive,
         is_redacted: is_redacted(&e.attrs),
+        location: Some(location),
     };
 
     // Figure out if we're dealing with a unit enum or an algebraic enum
@@ -408,6 +574,8 @@ fn parse_enum_variant(
     let shared = RustEnumVariantShared {
         id: get_ident(Some(&v.ident), &v.attrs, enum_serde_rename_all),
         comments: parse_comment_attrs(&v.attrs),
+        deprecated: parse_deprecation(&v.attrs),
+        span: Some(v.ident.span().into()),
     };
 
     // Get the value of `#[serde(rename_all)]` for this specific variant rather
@@ -455,8 +623,10 @@ fn parse_enum_variant(
                         id: get_ident(f.ident.as_ref(), &f.attrs, &variant_serde_rename_all),
                         ty: field_type,
                         comments: parse_comment_attrs(&f.attrs),
+                        deprecated: parse_deprecation(&f.attrs),
                         has_default,
                         decorators,
+                        span: Some(field_span(f)),
                     })
                 })
                 .collect::<Result<Vec<_>, ParseErrorWithSpan>>()?,
@@ -465,9 +635,20 @@ fn parse_enum_variant(
     }
 }
 
+/// Where a field is in its file: at its name, or its type if it's unnamed.
+fn field_span(f: &syn::Field) -> SourceSpan {
+    f.ident
+        .as_ref()
+        .map_or_else(|| f.ty.span(), Ident::span)
+        .into()
+}
+
 /// Parses a type alias into a definition that more succinctly represents what
 /// typeshare needs to generate code for other languages.
-pub(crate) fn parse_type_alias(t: &ItemType) -> Result<RustItem, ParseErrorWithSpan> {
+pub(crate) fn parse_type_alias(
+    t: &ItemType,
+    file_path: &Path,
+) -> Result<RustItem, ParseErrorWithSpan> {
     let ty = if let Some(ty) = get_serialized_as_type(&t.attrs) {
         ty.parse()?
     } else {
@@ -488,14 +669,17 @@ pub(crate) fn parse_type_alias(t: &ItemType) -> Result<RustItem, ParseErrorWithS
         id: get_ident(Some(&t.ident), &t.attrs, &None),
         r#type: ty,
         comments: parse_comment_attrs(&t.attrs),
+        deprecated: parse_deprecation(&t.attrs),
         generic_types,
         decorators: get_decorators(&t.attrs),
         is_redacted: is_redacted(&t.attrs),
+        is_newtype: false,
+        location: Some(SourceLocation::new(file_path, t.ident.span())),
     }))
 }
 
 /// Parses a const variant.
-pub(crate) fn parse_const(c: &ItemConst) -> Result<RustItem, ParseErrorWithSpan> {
+pub(crate) fn parse_const(c: &ItemConst, file_path: &Path) -> Result<RustItem, ParseErrorWithSpan> {
     let expr = parse_const_expr(&c.expr)?;
 
     // serialized_as needs to be supported in case the user wants to use a different type
@@ -523,6 +707,7 @@ pub(crate) fn parse_const(c: &ItemConst) -> Result<RustItem, ParseErrorWithSpan>
         id: get_ident(Some(&c.ident), &c.attrs, &None),
         r#type: ty,
         expr,
+        location: Some(SourceLocation::new(file_path, c.ident.span())),
     }))
 }
 
@@ -668,6 +853,27 @@ fn parse_comment_attrs(attrs: &[Attribute]) -> Vec<String> {
         .collect()
 }
 
+/// The note of `#[deprecated = "..."]` or `#[deprecated(note = "...")]`, or an empty
+/// note for `#[deprecated]`, if the attributes deprecate the item.
+fn parse_deprecation(attrs: &[Attribute]) -> Option<String> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("deprecated"))?;
+    let note = match &attr.meta {
+        Meta::NameValue(name_value) => expr_to_string(&name_value.value),
+        Meta::List(list) => list
+            .parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)
+            .ok()
+            .and_then(|args| {
+                args.iter()
+                    .find(|arg| arg.path.is_ident("note"))
+                    .and_then(|arg| expr_to_string(&arg.value))
+            }),
+        Meta::Path(_) => None,
+    };
+    Some(note.unwrap_or_default())
+}
+
 // `#[typeshare(skip)]` or `#[serde(skip)]`
 fn is_skipped(attrs: &[syn::Attribute], target_os: &[String]) -> bool {
     let typeshare_skip = attrs.iter().any(|attr| {
@@ -702,6 +908,10 @@ fn serde_flatten(attrs: &[syn::Attribute]) -> bool {
     serde_attr(attrs, "flatten")
 }
 
+fn serde_deny_unknown_fields(attrs: &[syn::Attribute]) -> bool {
+    serde_attr(attrs, "deny_unknown_fields")
+}
+
 /// Checks the struct or enum for decorators like `#[typeshare(typescript(readonly)]`
 /// Takes a slice of `syn::Attribute`, returns a `HashMap<language, BTreeSet<decorator>>`, where `language` is `SupportedLanguage`
 /// and `decorator` is `FieldDecorator`. Field decorators are ordered in a `BTreeSet` for consistent code generation.
@@ -867,3 +1077,28 @@ fn test_rename_all_to_case() {
         );
     }
 }
+
+#[test]
+fn test_qualify_json_values() {
+    let mut file: syn::File = syn::parse_quote! {
+        use serde_json::{Map, Value as Json};
+
+        pub struct Event {
+            pub payload: Json,
+            pub attributes: Map<String, Json>,
+            pub total: Value,
+        }
+    };
+    qualify_json_values(&mut file);
+
+    let expected: syn::File = syn::parse_quote! {
+        use serde_json::{Map, Value as Json};
+
+        pub struct Event {
+            pub payload: serde_json::Value,
+            pub attributes: Map<String, serde_json::Value>,
+            pub total: Value,
+        }
+    };
+    assert_eq!(file, expected);
+}
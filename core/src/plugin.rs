@@ -0,0 +1,172 @@
+//! Language backends outside of typeshare, which are run as a subprocess.
+//!
+//! A plugin is a command which reads a [`PluginRequest`] as JSON from its standard
+//! input, with the parsed types of every crate, and writes a [`PluginResponse`] as JSON
+//! to its standard output, with the files it generated from them. What it writes to
+//! its standard error is shown to the user, and it fails by exiting with an error.
+use crate::{error::PluginError, language::CrateName, parser::ParsedData};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Component, PathBuf},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// The version of the protocol, which changes when requests or responses change in a way
+/// plugins can't ignore.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What a plugin is given to generate files from.
+#[derive(Debug, Serialize)]
+pub struct PluginRequest<'a> {
+    /// The version of the protocol, [`PROTOCOL_VERSION`].
+    pub protocol: u32,
+    /// The version of typeshare.
+    pub typeshare_version: &'a str,
+    /// The parsed types of every crate, by its name.
+    pub crates: &'a BTreeMap<CrateName, ParsedData>,
+    /// The options of the plugin in the configuration file.
+    pub options: &'a serde_json::Value,
+}
+
+/// The files a plugin generated.
+#[derive(Debug, Deserialize)]
+pub struct PluginResponse {
+    /// The generated files.
+    pub files: Vec<PluginFile>,
+}
+
+/// A file a plugin generated.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct PluginFile {
+    /// The path of the file, relative to the output folder.
+    pub path: PathBuf,
+    /// The contents of the file.
+    pub contents: String,
+}
+
+/// A plugin, by the command running it.
+#[derive(Debug, Clone, Default)]
+pub struct Plugin {
+    /// The program and its arguments.
+    pub command: Vec<String>,
+    /// The options of the plugin, which are passed on to it.
+    pub options: serde_json::Value,
+}
+
+impl Plugin {
+    /// Run the plugin on the parsed types of the crates, and return the files it
+    /// generated from them.
+    pub fn generate(
+        &self,
+        crates: &BTreeMap<CrateName, ParsedData>,
+    ) -> Result<Vec<PluginFile>, PluginError> {
+        let (program, args) = self.command.split_first().ok_or(PluginError::NoCommand)?;
+        let request = serde_json::to_vec(&PluginRequest {
+            protocol: PROTOCOL_VERSION,
+            typeshare_version: env!("CARGO_PKG_VERSION"),
+            crates,
+            options: &self.options,
+        })
+        .map_err(PluginError::Serialize)?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| PluginError::Spawn {
+                program: program.clone(),
+                error,
+            })?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        // The request is written while the response is read, so neither pipe fills up
+        // with a plugin which reads its input as it writes its output.
+        let output = thread::scope(|scope| {
+            scope.spawn(move || stdin.write_all(&request));
+            child.wait_with_output()
+        })
+        .map_err(|error| PluginError::Spawn {
+            program: program.clone(),
+            error,
+        })?;
+        if !output.status.success() {
+            return Err(PluginError::Failed {
+                program: program.clone(),
+                status: output.status.to_string(),
+            });
+        }
+
+        let response: PluginResponse =
+            serde_json::from_slice(&output.stdout).map_err(PluginError::Response)?;
+        for file in &response.files {
+            let relative = file
+                .path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            if !relative {
+                return Err(PluginError::Path(file.path.clone()));
+            }
+        }
+        Ok(response.files)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::{Plugin, PluginFile};
+    use crate::{error::PluginError, language::CrateName, parser::ParsedData};
+    use std::collections::BTreeMap;
+
+    /// A plugin which ignores its request and writes the response.
+    fn responding(response: &str) -> Plugin {
+        Plugin {
+            command: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                format!("cat > /dev/null; printf '%s' '{response}'"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn returns_the_generated_files() {
+        let crates = BTreeMap::from([(CrateName::from("app"), ParsedData::default())]);
+        let files = responding(r#"{"files": [{"path": "app/types.txt", "contents": "app"}]}"#)
+            .generate(&crates)
+            .unwrap();
+        assert_eq!(
+            files,
+            [PluginFile {
+                path: "app/types.txt".into(),
+                contents: "app".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_responses() {
+        let crates = BTreeMap::new();
+        assert!(matches!(
+            responding(r#"{"files": [{"path": "../types.txt", "contents": ""}]}"#)
+                .generate(&crates),
+            Err(PluginError::Path(_))
+        ));
+        assert!(matches!(
+            responding("types").generate(&crates),
+            Err(PluginError::Response(_))
+        ));
+        let failing = Plugin {
+            command: vec!["sh".to_owned(), "-c".to_owned(), "exit 3".to_owned()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            failing.generate(&crates),
+            Err(PluginError::Failed { .. })
+        ));
+    }
+}
@@ -6,7 +6,7 @@
 use crate::{
     language::CrateName,
     parser::ParsedData,
-    rust_types::{RustEnum, RustEnumVariant, RustType, SpecialRustType},
+    rust_types::{RustEnum, RustEnumVariant, RustType, SpecialRustType, TypeName},
     visitors::ImportedType,
 };
 use log::{debug, info};
@@ -16,12 +16,20 @@ use std::{
 };
 
 /// A mapping of original type names to a mapping of crate name to new name.
-type RenamedTypes = HashMap<String, HashMap<CrateName, String>>;
+pub type RenamedTypes = HashMap<String, HashMap<CrateName, String>>;
 
 /// Update any type references that have the refenced type renamed via `serde(rename)`.
 pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>) {
     let serde_renamed = collect_serde_renames(crate_parsed_data);
+    reconcile_aliases_with(crate_parsed_data, &serde_renamed);
+}
 
+/// Update any type references that have the refenced type renamed via `serde(rename)`,
+/// using the renamed types of all crates, including crates that aren't in `crate_parsed_data`.
+pub fn reconcile_aliases_with(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    serde_renamed: &RenamedTypes,
+) {
     for (crate_name, parsed_data) in crate_parsed_data {
         let import_types = mem::take(&mut parsed_data.import_types);
 
@@ -29,7 +37,7 @@ pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>
         for s in &mut parsed_data.structs {
             debug!("struct: {}", s.id.original);
             for f in &mut s.fields {
-                check_type(crate_name, &serde_renamed, &import_types, &mut f.ty);
+                check_type(crate_name, serde_renamed, &import_types, &mut f.ty);
             }
         }
 
@@ -39,13 +47,13 @@ pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>
             match e {
                 RustEnum::Unit(shared) => check_variant(
                     crate_name,
-                    &serde_renamed,
+                    serde_renamed,
                     &import_types,
                     &mut shared.variants,
                 ),
                 RustEnum::Algebraic { shared, .. } => check_variant(
                     crate_name,
-                    &serde_renamed,
+                    serde_renamed,
                     &import_types,
                     &mut shared.variants,
                 ),
@@ -54,7 +62,7 @@ pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>
 
         // update references to renamed ids in aliases.
         for a in &mut parsed_data.aliases {
-            check_type(crate_name, &serde_renamed, &import_types, &mut a.r#type);
+            check_type(crate_name, serde_renamed, &import_types, &mut a.r#type);
         }
 
         // Apply sorting to types for deterministic output.
@@ -62,14 +70,26 @@ pub fn reconcile_aliases(crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>
         parsed_data.enums.sort();
         parsed_data.aliases.sort();
 
-        // put back our import types for file generation.
-        parsed_data.import_types = import_types;
+        // put back our import types for file generation, by the renamed names that
+        // the types of their crates are known by.
+        parsed_data.import_types = import_types
+            .into_iter()
+            .map(|mut import| {
+                if let Some(renamed) = serde_renamed
+                    .get(&import.type_name)
+                    .and_then(|name_map| name_map.get(&import.base_crate))
+                {
+                    import.type_name = renamed.clone();
+                }
+                import
+            })
+            .collect();
     }
 }
 
 /// Traverse all the parsed typeshare data and collect all types that have been renamed
 /// via `serde(rename)` into a mapping of original name to renamed name.
-fn collect_serde_renames(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) -> RenamedTypes {
+pub fn collect_serde_renames(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) -> RenamedTypes {
     crate_parsed_data
         .iter()
         .flat_map(|(crate_name, parsed_data)| {
@@ -102,6 +122,122 @@ fn collect_serde_renames(crate_parsed_data: &BTreeMap<CrateName, ParsedData>) ->
         )
 }
 
+/// Move the type with the Rust name `name` from the output of crate `from` into the
+/// output of crate `to`, which is created with `file_name` if there's none, and import
+/// it where it's referenced from.
+pub fn move_type(
+    crate_parsed_data: &mut BTreeMap<CrateName, ParsedData>,
+    name: &str,
+    from: &CrateName,
+    to: &CrateName,
+    file_name: String,
+) {
+    if from == to {
+        return;
+    }
+    let Some(source) = crate_parsed_data.get_mut(from) else {
+        return;
+    };
+    let mut moved = ParsedData::new(to.clone(), file_name, source.multi_file);
+    if let Some(index) = source.structs.iter().position(|s| s.id.original == name) {
+        moved.structs.push(source.structs.remove(index));
+    } else if let Some(index) = source
+        .enums
+        .iter()
+        .position(|e| e.shared().id.original == name)
+    {
+        moved.enums.push(source.enums.remove(index));
+    } else if let Some(index) = source.aliases.iter().position(|a| a.id.original == name) {
+        moved.aliases.push(source.aliases.remove(index));
+    } else {
+        return;
+    }
+    let renamed = moved
+        .structs
+        .iter()
+        .map(|s| &s.id)
+        .chain(moved.enums.iter().map(|e| &e.shared().id))
+        .chain(moved.aliases.iter().map(|a| &a.id))
+        .map(|id| id.renamed.clone())
+        .collect::<HashSet<_>>();
+    source
+        .type_names
+        .retain(|type_name| !renamed.contains(type_name));
+    moved.type_names.extend(renamed);
+
+    // The moved type keeps the imports of the types it references, and imports the
+    // types of its old crate.
+    for reference in referenced_types(&moved) {
+        if let Some(import) = source
+            .import_types
+            .iter()
+            .find(|import| import.type_name == reference)
+        {
+            moved.import_types.insert(import.clone());
+        } else if source.has_type(&reference) {
+            moved.import_types.insert(ImportedType {
+                base_crate: from.clone(),
+                type_name: reference,
+            });
+        }
+    }
+
+    for (crate_name, parsed_data) in crate_parsed_data.iter_mut() {
+        let imported = parsed_data.import_types.iter().any(|import| {
+            import.base_crate == *from && [name, "*"].contains(&import.type_name.as_str())
+        });
+        if crate_name != to
+            && (crate_name == from || imported)
+            && referenced_types(parsed_data).contains(name)
+        {
+            parsed_data.import_types.insert(ImportedType {
+                base_crate: to.clone(),
+                type_name: name.to_owned(),
+            });
+        }
+        parsed_data
+            .import_types
+            .retain(|import| !(import.base_crate == *from && import.type_name == name));
+    }
+
+    match crate_parsed_data.get_mut(to) {
+        Some(target) => {
+            moved.file_name = target.file_name.clone();
+            *target += moved;
+        }
+        None => {
+            crate_parsed_data.insert(to.clone(), moved);
+        }
+    }
+}
+
+/// The names of the types referenced from the types of the parsed data.
+fn referenced_types(parsed_data: &ParsedData) -> HashSet<String> {
+    let fields = parsed_data
+        .structs
+        .iter()
+        .flat_map(|s| &s.fields)
+        .map(|f| &f.ty);
+    let variants = parsed_data
+        .enums
+        .iter()
+        .flat_map(|e| &e.shared().variants)
+        .flat_map(|v| match v {
+            RustEnumVariant::Unit(_) => Vec::new(),
+            RustEnumVariant::Tuple { ty, .. } => vec![ty],
+            RustEnumVariant::AnonymousStruct { fields, .. } => {
+                fields.iter().map(|f| &f.ty).collect()
+            }
+        });
+    let aliases = parsed_data.aliases.iter().map(|a| &a.r#type);
+    fields
+        .chain(variants)
+        .chain(aliases)
+        .flat_map(RustType::all_reference_type_names)
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
 fn check_variant(
     crate_name: &CrateName,
     serde_renamed: &RenamedTypes,
@@ -160,7 +296,7 @@ fn check_type(
 
             if let Some(renamed) = resolve_renamed(crate_name, serde_renamed, import_types, id) {
                 info!("renaming type from {id} to {renamed}");
-                *id = renamed.to_owned();
+                *id = TypeName::new(&renamed);
             }
         }
     }
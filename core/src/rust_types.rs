@@ -1,13 +1,21 @@
+use itertools::Itertools as _;
 use quote::ToTokens;
-use std::collections::BTreeSet;
+use serde::{Serialize, Serializer};
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Display;
+use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::{collections::HashMap, convert::TryFrom};
 use syn::spanned::Spanned;
 use syn::{Expr, ExprLit, Lit, TypeArray, TypeSlice};
 use thiserror::Error;
 
-use crate::error::{ParseErrorWithSpan, RustTypeParseError, WithSpan as _};
+use crate::error::{
+    ParseErrorWithSpan, RustTypeParseError, SourceLocation, SourceSpan, WithSpan as _,
+};
 use crate::language::SupportedLanguage;
 use crate::parser::DecoratorKind;
 use crate::visitors::accept_type;
@@ -16,7 +24,7 @@ use crate::visitors::accept_type;
 pub type DecoratorMap = HashMap<DecoratorKind, BTreeSet<String>>;
 
 /// Identifier used in Rust structs, enums, and fields. It includes the `original` name and the `renamed` value after the transformation based on `serde` attributes.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Id {
     /// The original identifier name
     pub original: String,
@@ -39,7 +47,7 @@ impl std::fmt::Display for Id {
 }
 
 /// Rust struct.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RustStruct {
     /// The identifier for the struct.
     pub id: Id,
@@ -51,10 +59,20 @@ pub struct RustStruct {
     /// We copy comments over to the typeshared files,
     /// so we need to collect them here.
     pub comments: Vec<String>,
+    /// The note of the `#[deprecated]` attribute of the struct, empty without one, if
+    /// it's deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
     /// Attributes that exist for this struct.
+    #[serde(serialize_with = "sorted")]
     pub decorators: DecoratorMap,
     /// True if this struct contains data that needs to be redacted
     pub is_redacted: bool,
+    /// True if this struct is annotated with `#[serde(deny_unknown_fields)]`.
+    pub deny_unknown_fields: bool,
+    /// Where the struct is in the Rust source, if it was parsed from it.
+    #[serde(skip)]
+    pub location: Option<SourceLocation>,
 }
 
 impl PartialEq for RustStruct {
@@ -83,7 +101,7 @@ impl Ord for RustStruct {
 /// ```
 /// pub const MY_CONST: &str = "constant value";
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RustConst {
     /// The identifier for the constant.
     pub id: Id,
@@ -91,6 +109,9 @@ pub struct RustConst {
     pub r#type: RustType,
     /// The expression that the constant contains.
     pub expr: RustConstExpr,
+    /// Where the constant is in the Rust source, if it was parsed from it.
+    #[serde(skip)]
+    pub location: Option<SourceLocation>,
 }
 
 impl PartialEq for RustConst {
@@ -101,7 +122,8 @@ impl PartialEq for RustConst {
 
 /// A constant expression that can be shared via a constant variable across the typeshare
 /// boundary.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RustConstExpr {
     /// Expression represents an integer.
     Int(i128),
@@ -111,7 +133,7 @@ pub enum RustConstExpr {
 /// ```
 /// pub struct MasterPassword(String);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RustTypeAlias {
     /// The identifier for the alias.
     pub id: Id,
@@ -121,10 +143,20 @@ pub struct RustTypeAlias {
     pub r#type: RustType,
     /// Comments that were in the type alias source.
     pub comments: Vec<String>,
+    /// The note of the `#[deprecated]` attribute of the type alias, empty without one, if
+    /// it's deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
     /// Attributes that exist for this struct.
+    #[serde(serialize_with = "sorted")]
     pub decorators: DecoratorMap,
     /// True if this type alias contains data that needs to be redacted
     pub is_redacted: bool,
+    /// True if this type alias was parsed from a newtype struct, like `struct Id(String)`
+    pub is_newtype: bool,
+    /// Where the type alias is in the Rust source, if it was parsed from it.
+    #[serde(skip)]
+    pub location: Option<SourceLocation>,
 }
 
 impl PartialEq for RustTypeAlias {
@@ -148,7 +180,7 @@ impl PartialOrd for RustTypeAlias {
 }
 
 /// Rust field definition.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RustField {
     /// Identifier for the field.
     pub id: Id,
@@ -156,17 +188,37 @@ pub struct RustField {
     pub ty: RustType,
     /// Comments that were in the original source.
     pub comments: Vec<String>,
+    /// The note of the `#[deprecated]` attribute of the field, empty without one, if
+    /// it's deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
     /// This will be true if the field has a `serde(default)` decorator.
     /// Even if the field's type is not optional, we need to make it optional
     /// for the languages we generate code for.
     pub has_default: bool,
     /// Language-specific decorators assigned to a given field.
     /// The keys are language names (e.g. SupportedLanguage::TypeScript), the values are field decorators (e.g. readonly)
+    #[serde(serialize_with = "sorted")]
     pub decorators: HashMap<SupportedLanguage, BTreeSet<FieldDecorator>>,
+    /// Where the field is in the Rust file of its item, if it was parsed from it.
+    #[serde(skip)]
+    pub span: Option<SourceSpan>,
+}
+
+/// Serialize the map in the order of its keys, so serialized types don't change between
+/// runs.
+fn sorted<S, K, V>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().sorted_by_key(|(key, _)| *key))
 }
 
 /// A single decorator on a field in Rust code.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FieldDecorator {
     /// A boolean flag enabled by its existence as a decorator: for example, `readonly`.
     Word(String),
@@ -183,8 +235,146 @@ impl FieldDecorator {
     }
 }
 
+/// The name of a type a [`RustType`] refers to, like `User`. Names are interned, so
+/// the many references to a type share its name, and copying them doesn't allocate.
+///
+/// The interner is per thread and outlives a parse, so that long-running processes like
+/// `typeshare --serve`, build scripts and the WASM module reuse the names of earlier
+/// parses. It holds on to names no [`TypeName`] refers to anymore only until it doubles
+/// in size, when they are dropped, so it never grows beyond twice the names in use.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeName(Arc<str>);
+
+/// The size of the interner below which unused names aren't dropped.
+const MIN_INTERNED_NAMES: usize = 1024;
+
+/// The names of types parsed on a thread.
+struct TypeNames {
+    names: HashSet<Arc<str>>,
+    /// The size at which the unused names are dropped.
+    prune_at: usize,
+}
+
+thread_local! {
+    static TYPE_NAMES: RefCell<TypeNames> = RefCell::new(TypeNames {
+        names: HashSet::new(),
+        prune_at: MIN_INTERNED_NAMES,
+    });
+}
+
+impl TypeName {
+    /// The interned name.
+    pub fn new(name: &str) -> Self {
+        TYPE_NAMES.with_borrow_mut(|interner| {
+            if let Some(interned) = interner.names.get(name) {
+                return Self(interned.clone());
+            }
+            if interner.names.len() >= interner.prune_at {
+                // Only the interner refers to the names with a count of one.
+                interner.names.retain(|name| Arc::strong_count(name) > 1);
+                interner.prune_at = (interner.names.len() * 2).max(MIN_INTERNED_NAMES);
+            }
+            let interned = Arc::<str>::from(name);
+            interner.names.insert(interned.clone());
+            Self(interned)
+        })
+    }
+
+    /// The name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for TypeName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for TypeName {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for TypeName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for TypeName {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for TypeName {
+    fn from(name: String) -> Self {
+        Self::new(&name)
+    }
+}
+
+impl From<&String> for TypeName {
+    fn from(name: &String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl PartialEq<str> for TypeName {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for TypeName {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for TypeName {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<TypeName> for String {
+    fn eq(&self, other: &TypeName) -> bool {
+        **self == *other.0
+    }
+}
+
+impl PartialEq<TypeName> for str {
+    fn eq(&self, other: &TypeName) -> bool {
+        *self == *other.0
+    }
+}
+
+impl Display for TypeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Debug for TypeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl Serialize for TypeName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
 /// A Rust type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RustType {
     /// A type with generic parameters. Consists of a type ID + parameters that come
     /// after in angled brackets. Examples include:
@@ -198,7 +388,7 @@ pub enum RustType {
     /// If a generic type is type-mapped via `typeshare.toml`, the generic parameters will be dropped automatically.
     Generic {
         #[allow(missing_docs)]
-        id: String,
+        id: TypeName,
         #[allow(missing_docs)]
         parameters: Vec<RustType>,
     },
@@ -210,17 +400,17 @@ pub enum RustType {
     /// However, these types can still be transformed as part of the type-map in `typeshare.toml`.
     Simple {
         #[allow(missing_docs)]
-        id: String,
+        id: TypeName,
     },
 }
 
 impl Display for RustType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let rust_type = match &self {
-            RustType::Simple { id } => id,
+            RustType::Simple { id } => id.as_str(),
             RustType::Generic { id, parameters } => {
                 if parameters.is_empty() {
-                    id
+                    id.as_str()
                 } else {
                     &format!(
                         "{id}<{}>",
@@ -239,7 +429,8 @@ impl Display for RustType {
 }
 
 /// A special rust type that needs a manual type conversion
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SpecialRustType {
     /// Represents `Vec<T>` from the standard library
     Vec(Box<RustType>),
@@ -293,6 +484,10 @@ pub enum SpecialRustType {
     I54,
     /// Represents `U53` from `typeshare::U53`
     U53,
+    /// Represents `serde_json::Value`, referenced by its path or by the name it's
+    /// imported as from `serde_json`. Languages without a type for any JSON value
+    /// generate it like a type named `Value`.
+    JsonValue,
 }
 
 impl Display for SpecialRustType {
@@ -323,6 +518,19 @@ impl FromStr for RustType {
     }
 }
 
+/// Whether the path is that of `serde_json::Value`, like `serde_json::Value` or
+/// `::serde_json::Value`.
+fn is_json_value_path(path: &syn::Path) -> bool {
+    let mut segments = path.segments.iter().rev();
+    segments
+        .next()
+        .is_some_and(|segment| segment.ident == "Value")
+        && segments
+            .next()
+            .is_some_and(|segment| segment.ident == "serde_json")
+        && segments.next().is_none()
+}
+
 impl TryFrom<&syn::Type> for RustType {
     type Error = ParseErrorWithSpan;
 
@@ -389,7 +597,11 @@ impl TryFrom<&syn::Type> for RustType {
                     "I54" => Self::Special(SpecialRustType::I54),
                     "f32" => Self::Special(SpecialRustType::F32),
                     "f64" => Self::Special(SpecialRustType::F64),
+                    "Value" if is_json_value_path(&path.path) => {
+                        Self::Special(SpecialRustType::JsonValue)
+                    }
                     _ => {
+                        let id = TypeName::new(&id);
                         if parameters.is_empty() {
                             Self::Simple { id }
                         } else {
@@ -568,7 +780,8 @@ impl SpecialRustType {
             | Self::F32
             | Self::F64
             | Self::I54
-            | Self::U53 => ty == self.id(),
+            | Self::U53
+            | Self::JsonValue => ty == self.id(),
         }
     }
 
@@ -599,6 +812,7 @@ impl SpecialRustType {
             Self::USize => "usize",
             Self::U53 => "U53",
             Self::I54 => "I54",
+            Self::JsonValue => "Value",
         }
     }
 
@@ -630,13 +844,15 @@ impl SpecialRustType {
             | Self::F32
             | Self::F64
             | Self::I54
-            | Self::U53 => Box::new(std::iter::empty()),
+            | Self::U53
+            | Self::JsonValue => Box::new(std::iter::empty()),
         }
     }
 }
 
 /// Parsed information about a Rust enum definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RustEnum {
     /// A unit enum
     ///
@@ -703,10 +919,17 @@ impl RustEnum {
             Self::Unit(shared) | Self::Algebraic { shared, .. } => shared,
         }
     }
+
+    /// Get a mutable reference to the inner shared content
+    pub fn shared_mut(&mut self) -> &mut RustEnumShared {
+        match self {
+            Self::Unit(shared) | Self::Algebraic { shared, .. } => shared,
+        }
+    }
 }
 
 /// Enum information shared among different enum types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RustEnumShared {
     /// The enum's ident
     pub id: Id,
@@ -714,21 +937,30 @@ pub struct RustEnumShared {
     pub generic_types: Vec<String>,
     /// Comments on the enum definition itself
     pub comments: Vec<String>,
+    /// The note of the `#[deprecated]` attribute of the enum, empty without one, if
+    /// it's deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
     /// The enum's variants
     pub variants: Vec<RustEnumVariant>,
     /// Decorators applied to the enum for generation in other languages
     ///
     /// Example: `#[typeshare(swift = "Equatable, Comparable, Hashable")]`.
+    #[serde(serialize_with = "sorted")]
     pub decorators: DecoratorMap,
     /// True if this enum references itself in any field of any variant
     /// Swift needs the special keyword `indirect` for this case
     pub is_recursive: bool,
     /// True if this enum contains data that needs to be redacted
     pub is_redacted: bool,
+    /// Where the enum is in the Rust source, if it was parsed from it.
+    #[serde(skip)]
+    pub location: Option<SourceLocation>,
 }
 
 /// Parsed information about a Rust enum variant
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RustEnumVariant {
     /// A unit variant
     Unit(RustEnumVariantShared),
@@ -760,12 +992,19 @@ impl RustEnumVariant {
 }
 
 /// Variant information shared among different variant types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RustEnumVariantShared {
     /// The variant's ident
     pub id: Id,
     /// Comments applied to the variant
     pub comments: Vec<String>,
+    /// The note of the `#[deprecated]` attribute of the variant, empty without one, if
+    /// it's deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Where the variant is in the Rust file of its enum, if it was parsed from it.
+    #[serde(skip)]
+    pub span: Option<SourceSpan>,
 }
 
 /// An enum that encapsulates units of code generation for Typeshare.
@@ -782,3 +1021,40 @@ pub enum RustItem {
     /// A `const` definition
     Const(RustConst),
 }
+
+#[cfg(test)]
+mod test {
+    use super::{RustType, TypeName, MIN_INTERNED_NAMES, TYPE_NAMES};
+    use std::sync::Arc;
+
+    #[test]
+    fn type_names_are_interned() {
+        let user = TypeName::new("User");
+        assert!(Arc::ptr_eq(&user.0, &TypeName::from("User".to_owned()).0));
+        assert!(!Arc::ptr_eq(&user.0, &TypeName::new("Group").0));
+        assert_eq!(user, "User");
+    }
+
+    #[test]
+    fn parsed_references_share_names() {
+        let parsed = RustType::try_from(&syn::parse_str::<syn::Type>("Vec<User>").unwrap());
+        let RustType::Special(super::SpecialRustType::Vec(inner)) = parsed.unwrap() else {
+            panic!("expected a Vec");
+        };
+        let RustType::Simple { id } = *inner else {
+            panic!("expected a simple type");
+        };
+        assert!(Arc::ptr_eq(&id.0, &TypeName::new("User").0));
+    }
+
+    #[test]
+    fn unused_type_names_are_dropped() {
+        let interned = || TYPE_NAMES.with_borrow(|interner| interner.names.len());
+        let kept = TypeName::new("Kept");
+        for index in 0..MIN_INTERNED_NAMES * 4 {
+            TypeName::new(&format!("Dropped{index}"));
+        }
+        assert!(interned() <= MIN_INTERNED_NAMES);
+        assert!(Arc::ptr_eq(&kept.0, &TypeName::new("Kept").0));
+    }
+}
@@ -0,0 +1,401 @@
+//! Example JSON values of the typeshared types, like serde serializes them, for
+//! documentation and as the samples of the round-trip tests of
+//! [`conformance`](crate::conformance).
+//!
+//! The samples honor the serialized names of types, fields and variants, and the tags of
+//! algebraic enums. A struct has a sample with every field, and one without the fields
+//! which are optional or have a default, if it has any. An enum has a sample of every
+//! variant. Generic types have no samples of their own, but are sampled with their
+//! parameters where they're referenced.
+//!
+//! ```
+//! let parsed_data = typeshare_core::parse(
+//!     &Default::default(),
+//!     typeshare_core::ParseFileContext {
+//!         source_code: r#"
+//!             #[typeshare]
+//!             #[serde(rename_all = "camelCase")]
+//!             pub struct User { pub user_name: String, pub age: Option<u32> }
+//!         "#
+//!         .to_owned(),
+//!         crate_name: Default::default(),
+//!         file_name: "user.rs".to_owned(),
+//!         file_path: "user.rs".into(),
+//!     },
+//! )
+//! .unwrap()
+//! .unwrap();
+//! let samples = typeshare_core::samples::generate(&parsed_data);
+//! assert_eq!(
+//!     samples["User"],
+//!     [
+//!         serde_json::json!({ "userName": "Alice", "age": 42 }),
+//!         serde_json::json!({ "userName": "Alice" }),
+//!     ]
+//! );
+//! ```
+use crate::{
+    conformance::Samples,
+    parser::ParsedData,
+    rust_types::{RustEnum, RustEnumVariant, RustField, RustType, SpecialRustType},
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Generate samples of every typeshared type of the parsed data which isn't generic, by
+/// its Rust name.
+pub fn generate(parsed_data: &ParsedData) -> Samples {
+    let mut generator = Generator {
+        parsed_data,
+        sampling: Vec::new(),
+    };
+    let structs = parsed_data
+        .structs
+        .iter()
+        .filter(|s| s.generic_types.is_empty())
+        .map(|s| {
+            generator.sampling.push(&s.id.original);
+            let mut samples = vec![generator.fields(&s.fields, &HashMap::new(), true)];
+            let required = generator.fields(&s.fields, &HashMap::new(), false);
+            if samples[0] != required {
+                samples.push(required);
+            }
+            generator.sampling.pop();
+            (s.id.original.clone(), samples)
+        })
+        .collect::<Vec<_>>();
+    let enums = parsed_data
+        .enums
+        .iter()
+        .filter(|e| e.shared().generic_types.is_empty())
+        .map(|e| {
+            generator.sampling.push(&e.shared().id.original);
+            let samples = (0..e.shared().variants.len())
+                .map(|variant| generator.variant(e, variant, &HashMap::new()))
+                .collect();
+            generator.sampling.pop();
+            (e.shared().id.original.clone(), samples)
+        })
+        .collect::<Vec<_>>();
+    let aliases = parsed_data
+        .aliases
+        .iter()
+        .filter(|a| a.generic_types.is_empty())
+        .map(|a| {
+            generator.sampling.push(&a.id.original);
+            let sample = generator.value(&a.r#type, &HashMap::new(), "");
+            generator.sampling.pop();
+            (a.id.original.clone(), vec![sample.unwrap_or(Value::Null)])
+        })
+        .collect::<Vec<_>>();
+    structs.into_iter().chain(enums).chain(aliases).collect()
+}
+
+/// Whether serde deserializes the field when it's left out.
+fn is_optional(field: &RustField) -> bool {
+    field.has_default || matches!(field.ty, RustType::Special(SpecialRustType::Option(_)))
+}
+
+/// The generic parameters of the type being sampled, by their names.
+type Parameters<'a> = HashMap<&'a str, &'a RustType>;
+
+struct Generator<'a> {
+    parsed_data: &'a ParsedData,
+    /// The types being sampled, whose references are left out, to not sample recursive
+    /// types forever.
+    sampling: Vec<&'a str>,
+}
+
+impl<'a> Generator<'a> {
+    /// An object of the fields, with or without those which are optional.
+    fn fields(
+        &mut self,
+        fields: &'a [RustField],
+        parameters: &Parameters<'a>,
+        optional: bool,
+    ) -> Value {
+        let fields = fields
+            .iter()
+            .filter(|field| optional || !is_optional(field))
+            .filter_map(|field| {
+                let value = self.value(&field.ty, parameters, &field.id.renamed);
+                match value {
+                    Some(value) => Some((field.id.renamed.clone(), value)),
+                    // A recursive reference which can be left out.
+                    None if is_optional(field) => None,
+                    None => Some((field.id.renamed.clone(), Value::Null)),
+                }
+            });
+        Value::Object(Map::from_iter(fields))
+    }
+
+    /// A variant of the enum, serialized like serde does: the name of a unit enum, or
+    /// an object with the tag and content of an algebraic enum.
+    fn variant(&mut self, e: &'a RustEnum, index: usize, parameters: &Parameters<'a>) -> Value {
+        let variant = &e.shared().variants[index];
+        let name = &variant.shared().id.renamed;
+        let RustEnum::Algebraic {
+            tag_key,
+            content_key,
+            ..
+        } = e
+        else {
+            return Value::from(name.as_str());
+        };
+        let content = match variant {
+            RustEnumVariant::Unit(_) => None,
+            RustEnumVariant::Tuple { ty, .. } => {
+                Some(self.value(ty, parameters, name).unwrap_or(Value::Null))
+            }
+            RustEnumVariant::AnonymousStruct { fields, .. } => {
+                Some(self.fields(fields, parameters, true))
+            }
+        };
+        let mut sample = Map::from_iter([(tag_key.clone(), Value::from(name.as_str()))]);
+        if let Some(content) = content {
+            sample.insert(content_key.clone(), content);
+        }
+        Value::Object(sample)
+    }
+
+    /// A plausible value of the type, for a field or variant of the name. It's `None`
+    /// for a recursive reference.
+    fn value(
+        &mut self,
+        ty: &'a RustType,
+        parameters: &Parameters<'a>,
+        name: &str,
+    ) -> Option<Value> {
+        match ty {
+            RustType::Simple { id } => match parameters.get(id.as_str()) {
+                Some(parameter) => self.value(parameter, &HashMap::new(), name),
+                None => self.reference(id, &[], parameters, name),
+            },
+            RustType::Generic {
+                id,
+                parameters: arguments,
+            } => self.reference(id, arguments, parameters, name),
+            RustType::Special(special) => self.special(special, parameters, name),
+        }
+    }
+
+    /// A value of a typeshared type, with its generic parameters, or of a type which
+    /// isn't typeshared, like `Uuid`.
+    fn reference(
+        &mut self,
+        id: &str,
+        arguments: &'a [RustType],
+        parameters: &Parameters<'a>,
+        name: &str,
+    ) -> Option<Value> {
+        if self.sampling.contains(&id) {
+            return None;
+        }
+        // The arguments refer to the parameters of the type being sampled.
+        let arguments = arguments
+            .iter()
+            .map(|argument| match argument {
+                RustType::Simple { id } => parameters.get(id.as_str()).copied().unwrap_or(argument),
+                _ => argument,
+            })
+            .collect::<Vec<_>>();
+        let parsed_data = self.parsed_data;
+        let with_arguments = |generic_types: &'a [String]| -> Parameters<'a> {
+            generic_types
+                .iter()
+                .map(String::as_str)
+                .zip(arguments.iter().copied())
+                .collect()
+        };
+
+        let sample = if let Some(s) = parsed_data.structs.iter().find(|s| s.id.original == id) {
+            let parameters = with_arguments(&s.generic_types);
+            self.sampling.push(&s.id.original);
+            Some(self.fields(&s.fields, &parameters, true))
+        } else if let Some(e) = parsed_data
+            .enums
+            .iter()
+            .find(|e| e.shared().id.original == id)
+        {
+            let parameters = with_arguments(&e.shared().generic_types);
+            self.sampling.push(&e.shared().id.original);
+            Some(match e.shared().variants.is_empty() {
+                true => Value::Null,
+                false => self.variant(e, 0, &parameters),
+            })
+        } else if let Some(a) = parsed_data.aliases.iter().find(|a| a.id.original == id) {
+            let parameters = with_arguments(&a.generic_types);
+            self.sampling.push(&a.id.original);
+            Some(
+                self.value(&a.r#type, &parameters, name)
+                    .unwrap_or(Value::Null),
+            )
+        } else {
+            return Some(external(id, name));
+        };
+        self.sampling.pop();
+        sample
+    }
+
+    fn special(
+        &mut self,
+        special: &'a SpecialRustType,
+        parameters: &Parameters<'a>,
+        name: &str,
+    ) -> Option<Value> {
+        Some(match special {
+            SpecialRustType::Vec(ty) | SpecialRustType::Slice(ty) => {
+                Value::Array(self.value(ty, parameters, name).into_iter().collect())
+            }
+            SpecialRustType::Array(ty, length) => match self.value(ty, parameters, name) {
+                Some(value) => Value::Array(vec![value; *length]),
+                None => return None,
+            },
+            SpecialRustType::HashMap(key, value) => {
+                let key = match self.value(key, parameters, name) {
+                    Some(Value::String(key)) => key,
+                    Some(key) => key.to_string(),
+                    None => return Some(json!({})),
+                };
+                let value = self.value(value, parameters, name);
+                Value::Object(Map::from_iter(value.map(|value| (key, value))))
+            }
+            SpecialRustType::Option(ty) => return self.value(ty, parameters, name),
+            SpecialRustType::DateTime => json!("2024-01-01T12:00:00Z"),
+            SpecialRustType::Unit => Value::Null,
+            SpecialRustType::JsonValue => external(special.id(), name),
+            SpecialRustType::String => Value::from(string(name)),
+            SpecialRustType::Char => json!("a"),
+            SpecialRustType::Bool => json!(true),
+            SpecialRustType::F32 | SpecialRustType::F64 => json!(1.5),
+            SpecialRustType::I8
+            | SpecialRustType::I16
+            | SpecialRustType::I32
+            | SpecialRustType::I64
+            | SpecialRustType::U8
+            | SpecialRustType::U16
+            | SpecialRustType::U32
+            | SpecialRustType::U64
+            | SpecialRustType::ISize
+            | SpecialRustType::USize
+            | SpecialRustType::I54
+            | SpecialRustType::U53 => json!(42),
+        })
+    }
+}
+
+/// A plausible string for a field or variant of the name.
+fn string(name: &str) -> &'static str {
+    let is_id = name.ends_with("Id");
+    let name = name.to_lowercase();
+    if name.contains("email") {
+        "alice@example.com"
+    } else if ["url", "uri", "link", "href"]
+        .iter()
+        .any(|url| name.contains(url))
+    {
+        "https://example.com"
+    } else if name.contains("name") {
+        "Alice"
+    } else if is_id || name == "id" || name.ends_with("_id") {
+        "1a2b3c"
+    } else {
+        "example"
+    }
+}
+
+/// A value of a type which isn't typeshared, like those of well-known crates.
+fn external(id: &str, name: &str) -> Value {
+    match id {
+        "Uuid" => json!("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+        "Url" => json!("https://example.com"),
+        "Value" => json!({}),
+        _ => Value::from(string(name)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate;
+    use crate::{parser::parse, ParseFileContext};
+    use serde_json::json;
+
+    fn samples(source_code: &str) -> crate::conformance::Samples {
+        let parsed_data = parse(
+            &Default::default(),
+            ParseFileContext {
+                source_code: source_code.to_owned(),
+                crate_name: Default::default(),
+                file_name: "input.rs".to_owned(),
+                file_path: "input.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        generate(&parsed_data)
+    }
+
+    #[test]
+    fn samples_enums_by_their_tags() {
+        let samples = samples(
+            r#"
+            #[typeshare]
+            #[serde(rename_all = "lowercase")]
+            pub enum Role { Admin, Member }
+
+            #[typeshare]
+            #[serde(tag = "kind", content = "data")]
+            pub enum Event {
+                Joined(Vec<Role>),
+                #[serde(rename = "renamed")]
+                Renamed { display_name: String },
+                Left,
+            }
+            "#,
+        );
+        assert_eq!(samples["Role"], [json!("admin"), json!("member")]);
+        assert_eq!(
+            samples["Event"],
+            [
+                json!({ "kind": "Joined", "data": ["admin"] }),
+                json!({ "kind": "renamed", "data": { "display_name": "Alice" } }),
+                json!({ "kind": "Left" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn samples_generic_and_recursive_references() {
+        let samples = samples(
+            r#"
+            #[typeshare]
+            pub struct Page<T> { pub items: Vec<T>, pub next: Option<String> }
+
+            #[typeshare]
+            pub struct Tree { pub label: String, pub children: Vec<Tree>, pub parent: Option<Box<Tree>> }
+
+            #[typeshare]
+            pub struct Listing { pub trees: Page<Tree>, pub owner_id: Uuid }
+
+            #[typeshare]
+            pub struct Email(String);
+            "#,
+        );
+        assert!(!samples.contains_key("Page"));
+        assert_eq!(
+            samples["Tree"],
+            [json!({ "label": "example", "children": [] })]
+        );
+        assert_eq!(
+            samples["Listing"][0],
+            json!({
+                "trees": {
+                    "items": [{ "label": "example", "children": [] }],
+                    "next": "example",
+                },
+                "owner_id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            })
+        );
+        assert_eq!(samples["Email"], [json!("example")]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Stable fingerprints of the serialized shapes of typeshared types, which generated code
+//! can carry as constants, so that clients and servers can compare them at runtime and
+//! detect that they were generated from different versions of a type.
+//!
+//! The hash of a type covers what decides how it's serialized: its serde name, its
+//! generic parameters, the names, types and defaults of its fields, and the variants,
+//! tag and content of enums. Comments, decorators and the Rust names of renamed items
+//! don't change it. Types referenced by a type contribute their names; each has its
+//! own hash. The hash is the same in every language, and between versions of typeshare.
+use crate::{
+    language::CrateName,
+    parser::ParsedData,
+    rust_types::{RustEnum, RustEnumVariant, RustField, RustItem, RustType, SpecialRustType},
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// The schema hash of a typeshared type, as 16 hexadecimal digits, or `None` for
+/// constants, which aren't types.
+pub fn schema_hash(item: &RustItem) -> Option<String> {
+    shape(item).map(|shape| format!("{:016x}", fnv1a(shape.as_bytes())))
+}
+
+/// The schema hashes of the types of every crate, by their Rust names.
+pub fn schema_hashes(
+    crates: &BTreeMap<CrateName, ParsedData>,
+) -> BTreeMap<CrateName, BTreeMap<String, String>> {
+    crates
+        .iter()
+        .map(|(crate_name, data)| {
+            let items = data
+                .structs
+                .iter()
+                .cloned()
+                .map(RustItem::Struct)
+                .chain(data.enums.iter().cloned().map(RustItem::Enum))
+                .chain(data.aliases.iter().cloned().map(RustItem::Alias));
+            let hashes = items
+                .filter_map(|item| Some((item_name(&item).to_owned(), schema_hash(&item)?)))
+                .collect();
+            (crate_name.clone(), hashes)
+        })
+        .collect()
+}
+
+fn item_name(item: &RustItem) -> &str {
+    match item {
+        RustItem::Struct(s) => &s.id.original,
+        RustItem::Enum(e) => &e.shared().id.original,
+        RustItem::Alias(a) => &a.id.original,
+        RustItem::Const(c) => &c.id.original,
+    }
+}
+
+/// The canonical description of the serialized shape of the item which is hashed.
+fn shape(item: &RustItem) -> Option<String> {
+    let mut shape = String::new();
+    match item {
+        RustItem::Struct(s) => {
+            shape.push_str("struct ");
+            write_name(&mut shape, &s.id.renamed, &s.generic_types);
+            if s.deny_unknown_fields {
+                shape.push_str(" deny_unknown_fields");
+            }
+            write_fields(&mut shape, &s.fields);
+        }
+        RustItem::Alias(a) => {
+            shape.push_str("type ");
+            write_name(&mut shape, &a.id.renamed, &a.generic_types);
+            shape.push_str(" = ");
+            write_type(&mut shape, &a.r#type);
+        }
+        RustItem::Enum(e) => {
+            let shared = e.shared();
+            shape.push_str("enum ");
+            write_name(&mut shape, &shared.id.renamed, &shared.generic_types);
+            if let RustEnum::Algebraic {
+                tag_key,
+                content_key,
+                ..
+            } = e
+            {
+                let _ = write!(shape, " tag {tag_key:?} content {content_key:?}");
+            }
+            shape.push_str(" {");
+            for variant in &shared.variants {
+                let _ = write!(shape, " {:?}", variant.shared().id.renamed);
+                match variant {
+                    RustEnumVariant::Unit(_) => {}
+                    RustEnumVariant::Tuple { ty, .. } => {
+                        shape.push('(');
+                        write_type(&mut shape, ty);
+                        shape.push(')');
+                    }
+                    RustEnumVariant::AnonymousStruct { fields, .. } => {
+                        write_fields(&mut shape, fields);
+                    }
+                }
+                shape.push(';');
+            }
+            shape.push_str(" }");
+        }
+        RustItem::Const(_) => return None,
+    }
+    Some(shape)
+}
+
+fn write_name(shape: &mut String, name: &str, generic_types: &[String]) {
+    let _ = write!(shape, "{name:?}");
+    if !generic_types.is_empty() {
+        let _ = write!(shape, "<{}>", generic_types.join(", "));
+    }
+}
+
+fn write_fields(shape: &mut String, fields: &[RustField]) {
+    shape.push_str(" {");
+    for field in fields {
+        let _ = write!(shape, " {:?}: ", field.id.renamed);
+        write_type(shape, &field.ty);
+        if field.has_default {
+            shape.push_str(" = default");
+        }
+        shape.push(';');
+    }
+    shape.push_str(" }");
+}
+
+/// Write the type with all of its parameters, unlike its `Display`, which leaves out
+/// those of the type in an `Option`.
+fn write_type(shape: &mut String, ty: &RustType) {
+    let (name, parameters, end): (&str, Vec<&RustType>, String) = match ty {
+        RustType::Simple { id } => return shape.push_str(id),
+        RustType::Generic { id, parameters } => (id, parameters.iter().collect(), ">".into()),
+        RustType::Special(special) => match special {
+            SpecialRustType::Vec(ty) => ("Vec", vec![ty], ">".into()),
+            SpecialRustType::Array(ty, len) => ("", vec![ty], format!("; {len}]")),
+            SpecialRustType::Slice(ty) => ("", vec![ty], "]".into()),
+            SpecialRustType::Option(ty) => ("Option", vec![ty], ">".into()),
+            SpecialRustType::HashMap(key, value) => ("HashMap", vec![key, value], ">".into()),
+            _ => return shape.push_str(special.id()),
+        },
+    };
+    shape.push_str(name);
+    if parameters.is_empty() {
+        return;
+    }
+    shape.push(if name.is_empty() { '[' } else { '<' });
+    for (i, parameter) in parameters.into_iter().enumerate() {
+        if i > 0 {
+            shape.push_str(", ");
+        }
+        write_type(shape, parameter);
+    }
+    shape.push_str(&end);
+}
+
+/// The 64-bit FNV-1a hash of the bytes, which unlike the hashers of the standard
+/// library is the same on every platform and in every version of Rust.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fnv1a, schema_hash, shape};
+    use crate::{parser::parse_struct, rust_types::RustItem};
+    use std::path::Path;
+
+    fn item(source: &str) -> RustItem {
+        parse_struct(&syn::parse_str(source).unwrap(), Path::new(""), &[]).unwrap()
+    }
+
+    #[test]
+    fn hashes_the_serialized_shape_of_types() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let user = item("pub struct User { name: String, #[serde(default)] age: Option<u32> }");
+        assert_eq!(
+            shape(&user).unwrap(),
+            r#"struct "User" { "name": String; "age": Option<u32> = default; }"#
+        );
+
+        let documented = item(
+            "/// A user.\n#[typeshare(swift = \"Equatable\")]\npub struct User {\n\
+             /// The name.\nname: String, #[serde(default)] age: Option<u32> }",
+        );
+        assert_eq!(schema_hash(&user), schema_hash(&documented));
+        assert_eq!(schema_hash(&user).unwrap().len(), 16);
+
+        let renamed = item(
+            "#[serde(rename_all = \"camelCase\")]\n\
+             pub struct User { user_name: String, #[serde(default)] age: Option<u32> }",
+        );
+        assert_ne!(schema_hash(&user), schema_hash(&renamed));
+
+        let pages =
+            item("pub struct Pages { users: Option<Vec<User>>, ids: HashMap<String, [u8; 4]> }");
+        assert_eq!(
+            shape(&pages).unwrap(),
+            r#"struct "Pages" { "users": Option<Vec<User>>; "ids": HashMap<String, [u8; 4]>; }"#
+        );
+    }
+}
@@ -13,9 +13,9 @@ fn get_dependencies_from_type(
 ) {
     match tp {
         RustType::Generic { id, parameters } => {
-            if let Some(tp) = types.get(id) {
-                if seen.insert(id.clone()) {
-                    res.push(id.clone());
+            if let Some(tp) = types.get(id.as_str()) {
+                if seen.insert(id.to_string()) {
+                    res.push(id.to_string());
                     get_dependencies(tp, types, res, seen);
                     for parameter in parameters {
                         let id = parameter.id().to_string();
@@ -27,16 +27,16 @@ fn get_dependencies_from_type(
                             }
                         }
                     }
-                    seen.remove(&id.clone());
+                    seen.remove(id.as_str());
                 }
             }
         }
         RustType::Simple { id } => {
-            if let Some(tp) = types.get(id) {
-                if seen.insert(id.clone()) {
-                    res.push(id.clone());
+            if let Some(tp) = types.get(id.as_str()) {
+                if seen.insert(id.to_string()) {
+                    res.push(id.to_string());
                     get_dependencies(tp, types, res, seen);
-                    seen.remove(&id.clone());
+                    seen.remove(id.as_str());
                 }
             }
         }
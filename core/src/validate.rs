@@ -0,0 +1,422 @@
+//! Validating the references between typeshared types, so references to types nothing
+//! generates, and structs the language can't generate, are reported instead of being
+//! generated as source which doesn't compile.
+use crate::{
+    error::SourceSpan,
+    error::{ParseError, ParseErrorWithSpan, WithSpan as _},
+    language::{CrateName, Language, SupportedLanguage},
+    lint::{Lint, Warning},
+    parser::ParsedData,
+    parser::{parse_const, parse_enum, parse_struct, parse_type_alias, TYPESHARE},
+    rust_types::{RustEnumVariant, RustField, RustStruct, RustType, SpecialRustType},
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
+use syn::{spanned::Spanned as _, Attribute, Fields, Item};
+
+/// Check that typeshare can parse a typeshared item, so the errors parsing it, like
+/// those of the 64-bit integers typeshare rejects or of algebraic enums without
+/// `#[serde(tag, content)]`, can be reported when the item is compiled, like
+/// `typeshare-macros` does, rather than when types are generated.
+pub fn check_item(item: &Item) -> Result<(), ParseErrorWithSpan> {
+    // Every field is checked, whatever the target OS types are generated for.
+    let file_path = Path::new("");
+    match item {
+        Item::Struct(s) => parse_struct(s, file_path, &[]).map(drop),
+        Item::Enum(e) => parse_enum(e, file_path, &[]).map(drop),
+        Item::Type(t) => parse_type_alias(t, file_path).map(drop),
+        Item::Const(c) => parse_const(c, file_path).map(drop),
+        _ => Err(ParseError::UnsupportedItem.with_span(item.span())),
+    }
+}
+
+/// Remove the `#[typeshare]` attributes of the fields and variants of an item, which
+/// would fail to compile, since `#[typeshare]` only marks items, like the `#[typeshare]`
+/// attributes of `typeshare-annotation` and `typeshare-macros` do before emitting it.
+pub fn strip_configuration_attribute(item: &mut Item) {
+    fn remove_configuration_from_attributes(attributes: &mut Vec<Attribute>) {
+        attributes.retain(|x| !x.path().is_ident(TYPESHARE));
+    }
+
+    fn remove_configuration_from_fields(fields: &mut Fields) {
+        for field in fields.iter_mut() {
+            remove_configuration_from_attributes(&mut field.attrs);
+        }
+    }
+
+    match item {
+        Item::Enum(item_enum) => {
+            for variant in item_enum.variants.iter_mut() {
+                remove_configuration_from_attributes(&mut variant.attrs);
+                remove_configuration_from_fields(&mut variant.fields);
+            }
+        }
+        Item::Struct(item_struct) => {
+            remove_configuration_from_fields(&mut item_struct.fields);
+        }
+        Item::Union(item_union) => {
+            for field in item_union.fields.named.iter_mut() {
+                remove_configuration_from_attributes(&mut field.attrs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The warnings about the references of the typeshared types of every crate, by crate:
+/// references to types which aren't typeshared in any crate, mapped to a type of the
+/// language or in `other_types`, like the types of crates which weren't parsed, and
+/// structs which contain each other in a cycle, if the structs of the language can't.
+pub fn reference_warnings<'a>(
+    lang: &mut dyn Language,
+    language: SupportedLanguage,
+    crate_parsed_data: &BTreeMap<CrateName, ParsedData>,
+    other_types: impl IntoIterator<Item = &'a str>,
+) -> BTreeMap<CrateName, Vec<Warning>> {
+    let mut known = lang.type_map().keys().cloned().collect::<HashSet<_>>();
+    known.extend(other_types.into_iter().map(ToOwned::to_owned));
+    for data in crate_parsed_data.values() {
+        let structs = data.structs.iter().map(|s| &s.id);
+        let enums = data.enums.iter().map(|e| &e.shared().id);
+        let aliases = data.aliases.iter().map(|a| &a.id);
+        for id in structs.chain(enums).chain(aliases) {
+            known.insert(id.original.clone());
+            known.insert(id.renamed.clone());
+        }
+    }
+
+    let mut warnings = crate_parsed_data
+        .iter()
+        .map(|(crate_name, data)| (crate_name.clone(), unknown_types(data, language, &known)))
+        .collect::<BTreeMap<_, _>>();
+    if lang.contains_referenced_structs() {
+        for (crate_name, warning) in recursive_structs(crate_parsed_data, language) {
+            warnings.entry(crate_name).or_default().push(warning);
+        }
+    }
+    warnings
+}
+
+/// The warnings about the references of the items of the parsed data to unknown types,
+/// at the first field referencing each of them.
+fn unknown_types(
+    data: &ParsedData,
+    language: SupportedLanguage,
+    known: &HashSet<String>,
+) -> Vec<Warning> {
+    fn types(
+        fields: &[RustField],
+        language: SupportedLanguage,
+    ) -> Vec<(&RustType, Option<SourceSpan>)> {
+        fields
+            .iter()
+            .filter(|f| f.type_override(language).is_none())
+            .map(|f| (&f.ty, f.span))
+            .collect()
+    }
+    let structs = data.structs.iter().map(|s| {
+        let types = types(&s.fields, language);
+        (&s.id.original, s.location.as_ref(), &s.generic_types, types)
+    });
+    let enums = data.enums.iter().map(|e| {
+        let shared = e.shared();
+        let types = shared
+            .variants
+            .iter()
+            .flat_map(|v| match v {
+                RustEnumVariant::Unit(_) => Vec::new(),
+                RustEnumVariant::Tuple { ty, shared } => vec![(ty, shared.span)],
+                RustEnumVariant::AnonymousStruct { fields, .. } => types(fields, language),
+            })
+            .collect();
+        let location = shared.location.as_ref();
+        (&shared.id.original, location, &shared.generic_types, types)
+    });
+    let aliases = data.aliases.iter().map(|a| {
+        let types = vec![(&a.r#type, None)];
+        (&a.id.original, a.location.as_ref(), &a.generic_types, types)
+    });
+
+    let mut warnings = Vec::new();
+    for (item, location, generic_types, types) in structs.chain(enums).chain(aliases) {
+        let mut unknown = BTreeMap::new();
+        for (ty, span) in types {
+            collect_unknown(ty, span, generic_types, known, &mut unknown);
+        }
+        warnings.extend(unknown.into_iter().map(|(name, span)| Warning {
+            lint: Lint::UnknownType,
+            item: item.clone(),
+            message: format!(
+                "references {name}, which isn't typeshared or in the type mappings of the \
+                 language; typeshare it, map it with `type_mappings`, or skip the field with \
+                 #[typeshare(skip)]"
+            ),
+            location: location.map(|l| l.at(span)),
+        }));
+    }
+    warnings
+}
+
+/// The names of the types the type references which aren't known or generic
+/// parameters, with the span of the first reference to each of them.
+fn collect_unknown<'a>(
+    ty: &'a RustType,
+    span: Option<SourceSpan>,
+    generic_types: &[String],
+    known: &HashSet<String>,
+    unknown: &mut BTreeMap<&'a str, Option<SourceSpan>>,
+) {
+    match ty {
+        RustType::Simple { id } | RustType::Generic { id, .. } => {
+            if !known.contains(id.as_str()) && !generic_types.iter().any(|g| g == id) {
+                unknown.entry(id.as_str()).or_insert(span);
+            }
+        }
+        RustType::Special(_) => {}
+    }
+    for parameter in ty.parameters() {
+        collect_unknown(parameter, span, generic_types, known, unknown);
+    }
+}
+
+/// The warnings about the cycles of structs which contain each other, by value or in
+/// an `Option`, by the crate of the first struct of every cycle.
+fn recursive_structs(
+    crate_parsed_data: &BTreeMap<CrateName, ParsedData>,
+    language: SupportedLanguage,
+) -> Vec<(CrateName, Warning)> {
+    let mut structs = HashMap::new();
+    for (crate_name, data) in crate_parsed_data {
+        for s in &data.structs {
+            structs.insert(s.id.original.as_str(), (crate_name, s));
+            structs.insert(s.id.renamed.as_str(), (crate_name, s));
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let mut visited = HashSet::new();
+    for data in crate_parsed_data.values() {
+        for s in &data.structs {
+            let mut chain = Vec::new();
+            find_cycles(
+                s,
+                language,
+                &structs,
+                &mut chain,
+                &mut visited,
+                &mut |cycle| {
+                    let (crate_name, first) = structs[cycle[0]];
+                    warnings.push((
+                        crate_name.clone(),
+                        Warning {
+                            lint: Lint::RecursiveStruct,
+                            item: first.id.original.clone(),
+                            location: first.location.clone(),
+                            message: format!(
+                                "contains itself through {}, which the structs of the language \
+                             can't; put one of the references in a Vec, or skip its field \
+                             with #[typeshare(skip)]",
+                                cycle
+                                    .iter()
+                                    .chain(&cycle[..1])
+                                    .copied()
+                                    .collect::<Vec<_>>()
+                                    .join(" -> ")
+                            ),
+                        },
+                    ));
+                },
+            );
+        }
+    }
+    warnings
+}
+
+/// Walk the structs the struct contains, depth first, calling `found` with the chain of
+/// every cycle back to a struct of the chain.
+fn find_cycles<'a>(
+    s: &'a RustStruct,
+    language: SupportedLanguage,
+    structs: &HashMap<&str, (&CrateName, &'a RustStruct)>,
+    chain: &mut Vec<&'a str>,
+    visited: &mut HashSet<&'a str>,
+    found: &mut dyn FnMut(&[&'a str]),
+) {
+    let name = s.id.original.as_str();
+    if let Some(start) = chain.iter().position(|&n| n == name) {
+        found(&chain[start..]);
+        return;
+    }
+    if !visited.insert(name) {
+        return;
+    }
+    chain.push(name);
+    for f in s
+        .fields
+        .iter()
+        .filter(|f| f.type_override(language).is_none())
+    {
+        let contained = match &f.ty {
+            RustType::Special(SpecialRustType::Option(inner)) => inner.as_ref(),
+            ty => ty,
+        };
+        if let RustType::Simple { id } | RustType::Generic { id, .. } = contained {
+            if let Some((_, contained)) = structs.get(id.as_str()) {
+                find_cycles(contained, language, structs, chain, visited, found);
+            }
+        }
+    }
+    chain.pop();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_item, reference_warnings};
+    use crate::{
+        language::{SupportedLanguage, Swift, TypeScript},
+        parser::{parse, ParsedData},
+        ParseFileContext,
+    };
+    use std::collections::BTreeMap;
+
+    fn parsed(source_code: &str) -> BTreeMap<crate::language::CrateName, ParsedData> {
+        let data = parse(
+            &Default::default(),
+            ParseFileContext {
+                source_code: source_code.to_owned(),
+                crate_name: Default::default(),
+                file_name: "input.rs".to_owned(),
+                file_path: "input.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        BTreeMap::from([(Default::default(), data)])
+    }
+
+    fn messages(
+        warnings: BTreeMap<crate::language::CrateName, Vec<crate::lint::Warning>>,
+    ) -> Vec<String> {
+        warnings
+            .into_values()
+            .flatten()
+            .map(|w| format!("{} {}: {}", w.lint.name(), w.item, w.message))
+            .collect()
+    }
+
+    #[test]
+    fn reports_references_to_unknown_types() {
+        let data = parsed(
+            r#"
+            #[typeshare]
+            pub struct User<T> {
+                id: Uuid,
+                groups: Vec<Group>,
+                extra: Option<T>,
+                #[typeshare(typescript(type = "Date"))]
+                created_at: DateTime,
+                session: Session,
+            }
+
+            #[typeshare]
+            pub struct Group {
+                name: String,
+            }
+            "#,
+        );
+
+        let warnings = reference_warnings(
+            &mut TypeScript::default(),
+            SupportedLanguage::TypeScript,
+            &data,
+            ["Session"],
+        );
+        assert_eq!(
+            messages(warnings),
+            [
+                "unknown_type User: references Uuid, which isn't typeshared or in the type \
+              mappings of the language; typeshare it, map it with `type_mappings`, or skip \
+              the field with #[typeshare(skip)]"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_cycles_of_structs_only_for_languages_which_contain_them() {
+        let data = parsed(
+            r#"
+            #[typeshare]
+            pub struct Node {
+                next: Option<Box<Node>>,
+            }
+
+            #[typeshare]
+            pub struct Parent {
+                child: Child,
+            }
+
+            #[typeshare]
+            pub struct Child {
+                parent: Option<Parent>,
+            }
+
+            #[typeshare]
+            pub struct Tree {
+                children: Vec<Tree>,
+            }
+            "#,
+        );
+
+        let warnings =
+            reference_warnings(&mut Swift::default(), SupportedLanguage::Swift, &data, []);
+        assert_eq!(
+            messages(warnings),
+            [
+                "recursive_struct Node: contains itself through Node -> Node, which the \
+                 structs of the language can't; put one of the references in a Vec, or skip \
+                 its field with #[typeshare(skip)]",
+                "recursive_struct Parent: contains itself through Parent -> Child -> Parent, \
+                 which the structs of the language can't; put one of the references in a Vec, \
+                 or skip its field with #[typeshare(skip)]",
+            ]
+        );
+
+        let warnings = reference_warnings(
+            &mut TypeScript::default(),
+            SupportedLanguage::TypeScript,
+            &data,
+            [],
+        );
+        assert!(messages(warnings).is_empty());
+    }
+
+    #[test]
+    fn checks_items_like_they_are_parsed() {
+        let check = |source: &str| {
+            check_item(&syn::parse_str(source).unwrap()).map_err(|error| {
+                let span = error.span();
+                (error.to_string(), span.line, span.column)
+            })
+        };
+        assert!(
+            check("#[typeshare] pub struct User { id: u32, #[typeshare(skip)] n: u64 }").is_ok()
+        );
+        assert!(check(
+            r#"#[typeshare] pub struct User { #[typeshare(serialized_as = "String")] id: u64 }"#
+        )
+        .is_ok());
+        assert_eq!(
+            check("#[typeshare]\npub struct User {\n    id: u64,\n}"),
+            Err((
+                "Failed to parse a Rust type: Unsupported type: \"u64\", on line 3 and column 8"
+                    .to_owned(),
+                3,
+                8
+            ))
+        );
+        assert!(check("#[typeshare] pub enum Event { Sent(String) }").is_err());
+        assert!(check("#[typeshare] pub fn user() {}").is_err());
+    }
+}
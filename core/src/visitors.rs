@@ -3,9 +3,10 @@ use crate::{
     context::ParseContext,
     error::ParseErrorWithSpan,
     language::CrateName,
+    lint::{alias_warnings, enum_warnings, struct_warnings, Warning},
     parser::{
         has_typeshare_annotation, parse_const, parse_enum, parse_struct, parse_type_alias,
-        ErrorInfo, ParsedData,
+        ErrorInfo, ParsedData, SkippedItem, WarningInfo,
     },
     rust_types::{RustEnumVariant, RustItem},
     target_os_check::accept_target_os,
@@ -90,10 +91,22 @@ impl<'a> TypeShareVisitor<'a> {
             Err(error) => self.parsed_data.errors.push(ErrorInfo {
                 file_name: self.file_path.to_string_lossy().into_owned(),
                 error: error.to_string(),
+                span: Some(error.span()),
+                unsupported: error.error().is_unsupported(),
             }),
         }
     }
 
+    fn collect_warnings(&mut self, warnings: Vec<Warning>) {
+        let file_name = self.file_path.to_string_lossy().into_owned();
+        self.parsed_data
+            .warnings
+            .extend(warnings.into_iter().map(|warning| WarningInfo {
+                file_name: file_name.clone(),
+                warning,
+            }));
+    }
+
     /// After collecting all imports we now want to retain only those
     /// that are referenced by the typeshared types.
     fn reconcile_referenced_types(&mut self) {
@@ -200,6 +213,14 @@ impl<'a> TypeShareVisitor<'a> {
     fn target_os_accepted(&self, attrs: &[Attribute]) -> bool {
         accept_target_os(attrs, &self.parse_context.target_os)
     }
+
+    /// Record a typeshared item that isn't generated because of its `target_os`.
+    fn skip_target_os(&mut self, ident: &syn::Ident) {
+        self.parsed_data.skipped.push(SkippedItem {
+            name: ident.to_string(),
+            reason: "its target_os doesn't match --target-os".into(),
+        });
+    }
 }
 
 impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
@@ -276,9 +297,18 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust structs.
     fn visit_item_struct(&mut self, i: &'ast syn::ItemStruct) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_struct(i, &self.parse_context.target_os));
+        if has_typeshare_annotation(&i.attrs) {
+            if self.target_os_accepted(&i.attrs) {
+                debug!("\tParsing {}", i.ident);
+                self.collect_result(parse_struct(
+                    i,
+                    &self.file_path,
+                    &self.parse_context.target_os,
+                ));
+                self.collect_warnings(struct_warnings(i, &self.file_path));
+            } else {
+                self.skip_target_os(&i.ident);
+            }
         }
 
         syn::visit::visit_item_struct(self, i);
@@ -287,9 +317,18 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust enums.
     fn visit_item_enum(&mut self, i: &'ast syn::ItemEnum) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_enum(i, &self.parse_context.target_os));
+        if has_typeshare_annotation(&i.attrs) {
+            if self.target_os_accepted(&i.attrs) {
+                debug!("\tParsing {}", i.ident);
+                self.collect_result(parse_enum(
+                    i,
+                    &self.file_path,
+                    &self.parse_context.target_os,
+                ));
+                self.collect_warnings(enum_warnings(i, &self.file_path));
+            } else {
+                self.skip_target_os(&i.ident);
+            }
         }
 
         syn::visit::visit_item_enum(self, i);
@@ -298,9 +337,14 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     /// Collect rust type aliases.
     fn visit_item_type(&mut self, i: &'ast syn::ItemType) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_type_alias(i));
+        if has_typeshare_annotation(&i.attrs) {
+            if self.target_os_accepted(&i.attrs) {
+                debug!("\tParsing {}", i.ident);
+                self.collect_result(parse_type_alias(i, &self.file_path));
+                self.collect_warnings(alias_warnings(i, &self.file_path));
+            } else {
+                self.skip_target_os(&i.ident);
+            }
         }
 
         syn::visit::visit_item_type(self, i);
@@ -309,9 +353,13 @@ impl<'ast> Visit<'ast> for TypeShareVisitor<'_> {
     // Collect rust consts.
     fn visit_item_const(&mut self, i: &'ast syn::ItemConst) {
         debug!("Visiting {}", i.ident);
-        if has_typeshare_annotation(&i.attrs) && self.target_os_accepted(&i.attrs) {
-            debug!("\tParsing {}", i.ident);
-            self.collect_result(parse_const(i));
+        if has_typeshare_annotation(&i.attrs) {
+            if self.target_os_accepted(&i.attrs) {
+                debug!("\tParsing {}", i.ident);
+                self.collect_result(parse_const(i, &self.file_path));
+            } else {
+                self.skip_target_os(&i.ident);
+            }
         }
 
         syn::visit::visit_item_const(self, i);
@@ -204,3 +204,49 @@ mod serde_attributes_on_enums {
         );
     }
 }
+
+mod sorted_output {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn sorts_by_name_within_dependency_order() {
+        let source = r##"
+    #[typeshare]
+    pub struct Beta {
+        pub gamma: Gamma,
+    }
+
+    #[typeshare]
+    pub struct Alpha {
+        pub value: String,
+    }
+
+    #[typeshare]
+    pub struct Gamma {
+        pub value: String,
+    }
+    "##;
+        let mut parsed_data = parser::parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: source.to_string(),
+                crate_name: "default_name".into(),
+                file_name: "file_name".into(),
+                file_path: "file_path".into(),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        parsed_data.sort_by_name();
+
+        let mut out: Vec<u8> = Vec::new();
+        TypeScript::default()
+            .generate_types(&mut out, &HashMap::new(), parsed_data)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let position = |name: &str| out.find(&format!("interface {name} ")).unwrap();
+        assert!(position("Alpha") < position("Gamma"));
+        assert!(position("Gamma") < position("Beta"));
+    }
+}
@@ -0,0 +1,557 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::PathBuf,
+};
+use typeshare_core::{
+    docs::{self, DocsFormat},
+    language::{CrateName, CrateTypes, FileOutput, ScopedCrateTypes},
+    parse,
+    parser::ParsedData,
+    rust_types::{RustStruct, RustTypeFormatError, SpecialRustType},
+    CodeStyle, GenerateConfig, GenerateError, Indent, Ir, ItemKind, Language, ParseContext,
+    ParseFileContext, Python, ReasonML, Scala, Swift, Template, TemplateSources, TypeScript,
+};
+
+fn test_folder(test_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("data/tests")
+        .join(test_name)
+}
+
+#[test]
+fn generates_the_types_of_a_directory() {
+    let folder = test_folder("can_generate_generic_struct");
+    let outputs = typeshare_core::generate(GenerateConfig {
+        language: &mut TypeScript {
+            no_version_header: true,
+            ..Default::default()
+        },
+        inputs: vec![folder.clone()],
+        target_os: Vec::new(),
+    })
+    .unwrap();
+
+    assert_eq!(
+        outputs.source,
+        fs::read_to_string(folder.join("output.ts")).unwrap()
+    );
+    assert_eq!(outputs.files, [folder.join("input.rs")]);
+    assert_eq!(outputs.inputs, [folder]);
+}
+
+#[test]
+fn reports_errors_with_their_file() {
+    let folder = test_folder("can_generate_generic_struct");
+    let error = typeshare_core::generate(GenerateConfig {
+        language: &mut TypeScript::default(),
+        inputs: vec![folder.join("missing.rs")],
+        target_os: Vec::new(),
+    })
+    .unwrap_err();
+    assert!(matches!(error, GenerateError::Read { path, .. } if path == folder.join("missing.rs")));
+}
+
+#[test]
+fn generates_the_types_of_source_code() {
+    let folder = test_folder("can_generate_generic_struct");
+    let source = typeshare_core::generate_source(
+        &mut TypeScript {
+            no_version_header: true,
+            ..Default::default()
+        },
+        &fs::read_to_string(folder.join("input.rs")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        source,
+        fs::read_to_string(folder.join("output.ts")).unwrap()
+    );
+
+    let error = typeshare_core::generate_source(
+        &mut TypeScript::default(),
+        "#[typeshare] pub struct User {",
+    )
+    .unwrap_err();
+    assert!(matches!(error, GenerateError::Parse { span: Some(_), .. }));
+}
+
+#[test]
+fn fails_on_identifiers_generated_for_more_than_one_item() {
+    let source = r#"
+        #[typeshare]
+        pub struct User {
+            pub user_id: String,
+            pub userId: String,
+        }
+
+        #[typeshare]
+        pub struct User_Profile {}
+
+        #[typeshare]
+        pub struct userProfile {}
+    "#;
+    // TypeScript generates the fields as they're serialized, so they don't collide.
+    typeshare_core::generate_source(&mut TypeScript::default(), source).unwrap();
+
+    let error = typeshare_core::generate_source(&mut ReasonML::default(), source).unwrap_err();
+    let GenerateError::NameCollisions(collisions) = error else {
+        panic!("expected name collisions, got {error}");
+    };
+    assert_eq!(
+        collisions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["User_Profile and userProfile are both generated as userProfile, at line 12, column 19"]
+    );
+
+    let error = typeshare_core::generate_source(&mut Python::default(), source).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Failed to generate types: User::user_id and User::userId are both generated as \
+         user_id, at line 5, column 16"
+    );
+}
+
+#[test]
+fn generates_parsed_data_in_any_language() {
+    let folder = test_folder("can_generate_generic_struct");
+    let parsed_data = parse(
+        &ParseContext::default(),
+        ParseFileContext {
+            source_code: fs::read_to_string(folder.join("input.rs")).unwrap(),
+            crate_name: "default".into(),
+            file_name: "input.rs".to_owned(),
+            file_path: folder.join("input.rs"),
+        },
+    )
+    .unwrap()
+    .unwrap();
+
+    let mut swift = Swift {
+        no_version_header: true,
+        prefix: "Core".to_owned(),
+        codablevoid_constraints: vec!["Equatable".to_owned()],
+        ..Default::default()
+    };
+    let mut source = Vec::new();
+    swift
+        .generate_types(&mut source, &Default::default(), parsed_data)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(source).unwrap(),
+        fs::read_to_string(folder.join("output.swift")).unwrap()
+    );
+}
+
+#[test]
+fn generates_types_with_templates() {
+    let source = typeshare_core::generate_source(
+        &mut Template::new(
+            TemplateSources {
+                header: "-- Types{{#if crate}} of {{crate}}{{/if}}\n".to_owned(),
+                struct_template: "record {{name}}\n{{#each fields}}\n  {{name}}: {{type}}{{#if optional}} (optional){{/if}}\n{{/each}}\n".to_owned(),
+                enum_template: "{{#if algebraic}}union{{else}}enum{{/if}} {{name}} = {{#each variants}}{{name}}{{#if type}}({{type}}){{/if}}{{#unless @last}} | {{/unless}}{{/each}}\n".to_owned(),
+                ..Default::default()
+            },
+            [("String".to_owned(), "text".to_owned())].into(),
+        )
+        .unwrap(),
+        r#"
+        #[typeshare]
+        pub struct User {
+            pub name: String,
+            pub emails: Vec<String>,
+            pub age: Option<u32>,
+        }
+
+        #[typeshare]
+        #[serde(tag = "type", content = "content")]
+        pub enum Event {
+            Joined(User),
+            Left,
+        }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        source,
+        "-- Types\nrecord User\n  name: text\n  emails: Vec<text>\n  age: Option<u32> (optional)\nunion Event = Joined(User) | Left\n"
+    );
+}
+
+#[test]
+fn emits_the_items_of_a_file() {
+    let parsed_data = parse(
+        &ParseContext::default(),
+        ParseFileContext {
+            source_code: r#"
+            #[typeshare]
+            pub struct Group { pub users: Vec<User> }
+
+            /// A user.
+            #[typeshare]
+            pub struct User { pub name: String }
+
+            #[typeshare]
+            pub type UserName = String;
+            "#
+            .to_owned(),
+            crate_name: CrateName::default(),
+            file_name: "input.rs".to_owned(),
+            file_path: "input.rs".into(),
+        },
+    )
+    .unwrap()
+    .unwrap();
+
+    let output = TypeScript {
+        no_version_header: true,
+        schema_hashes: true,
+        ..Default::default()
+    }
+    .emit(&Default::default(), parsed_data)
+    .unwrap();
+    let items = output
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        [
+            ("UserName", ItemKind::Alias),
+            ("User", ItemKind::Struct),
+            ("Group", ItemKind::Struct),
+        ]
+    );
+    assert_eq!(output.items[1].comments, ["A user."]);
+    assert_eq!(output.items[1].docs, "/** A user. */\n");
+    assert_eq!(
+        output.items[1].body,
+        "export interface User {\n\tname: string;\n}\n\n"
+    );
+    assert!(output.items[1]
+        .companions
+        .starts_with("export const UserSchemaHash = "));
+}
+
+#[test]
+fn emits_the_scala_2_package_object_with_the_definitions() {
+    let parsed_data = || {
+        parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: r#"
+                #[typeshare]
+                pub struct User { pub name: UserName }
+
+                #[typeshare]
+                pub type UserName = String;
+                "#
+                .to_owned(),
+                crate_name: CrateName::default(),
+                file_name: "input.rs".to_owned(),
+                file_path: "input.rs".into(),
+            },
+        )
+        .unwrap()
+        .unwrap()
+    };
+
+    let mut scala = Scala {
+        package: "com.example".to_owned(),
+        no_version_header: true,
+        ..Default::default()
+    };
+    let output = scala.emit(&Default::default(), parsed_data()).unwrap();
+    assert_eq!(output.header, "package com\n\n");
+    assert_eq!(
+        output.definitions,
+        "package object example {\n\ntype UserName = String\n\n}\npackage example {\n\n"
+    );
+    let items = output
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(items, [("User", ItemKind::Struct)]);
+    assert_eq!(output.footer, "}\n");
+
+    // Scala 3 allows top-level type aliases
+    let output = Scala {
+        scala3: true,
+        ..scala
+    }
+    .emit(&Default::default(), parsed_data())
+    .unwrap();
+    let items = output
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        [("UserName", ItemKind::Alias), ("User", ItemKind::Struct)]
+    );
+}
+
+/// A language writing the names of structs, which post-processes its output by adding an
+/// import line.
+#[derive(Default)]
+struct Importing {
+    type_map: HashMap<String, String>,
+}
+
+impl Language for Importing {
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        &self.type_map
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        _generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        Ok(special_ty.id().to_owned())
+    }
+
+    fn write_imports(
+        &mut self,
+        _w: &mut dyn io::Write,
+        _imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_struct(&mut self, w: &mut dyn io::Write, rs: &RustStruct) -> io::Result<()> {
+        writeln!(w, "struct {}", rs.id.renamed)
+    }
+
+    fn post_process(&mut self, output: &mut String) {
+        output.insert_str(0, "import base\n");
+    }
+}
+
+#[test]
+fn post_processes_the_generated_types() {
+    let source = typeshare_core::generate_source(
+        &mut Importing::default(),
+        "#[typeshare] pub struct User { pub name: String }",
+    )
+    .unwrap();
+    assert_eq!(source, "import base\nstruct User\n");
+}
+
+/// Scala, post-processing its output by adding an import line.
+struct ImportingScala(Scala);
+
+impl Language for ImportingScala {
+    fn emit(&mut self, all_types: &CrateTypes, data: ParsedData) -> io::Result<FileOutput> {
+        self.0.emit(all_types, data)
+    }
+
+    fn code_style(&self) -> Option<&CodeStyle> {
+        self.0.code_style()
+    }
+
+    fn type_map(&mut self) -> &HashMap<String, String> {
+        self.0.type_map()
+    }
+
+    fn format_special_type(
+        &mut self,
+        special_ty: &SpecialRustType,
+        generic_types: &[String],
+    ) -> Result<String, RustTypeFormatError> {
+        self.0.format_special_type(special_ty, generic_types)
+    }
+
+    fn write_imports(
+        &mut self,
+        w: &mut dyn io::Write,
+        imports: ScopedCrateTypes<'_>,
+    ) -> io::Result<()> {
+        self.0.write_imports(w, imports)
+    }
+
+    fn post_process(&mut self, output: &mut String) {
+        *output = output.replacen("package com\n", "package com\n\nimport base._\n", 1);
+    }
+}
+
+#[test]
+fn post_processes_the_generated_scala_types() {
+    let source = typeshare_core::generate_source(
+        &mut ImportingScala(Scala {
+            package: "com.example".to_owned(),
+            no_version_header: true,
+            ..Default::default()
+        }),
+        "#[typeshare] pub struct User { pub name: String }",
+    )
+    .unwrap();
+    assert!(
+        source.starts_with("package com\n\nimport base._\n\npackage example {\n"),
+        "{source}"
+    );
+}
+
+/// A writer counting the writes of the generated source.
+#[derive(Default)]
+struct CountingWriter {
+    writes: usize,
+    written: Vec<u8>,
+}
+
+impl io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writes_every_file_with_a_single_write() {
+    let folder = test_folder("can_generate_generic_struct");
+    let languages: [Box<dyn Language>; 3] = [
+        Box::new(TypeScript::default()),
+        Box::new(Scala {
+            package: "com.example".to_owned(),
+            ..Default::default()
+        }),
+        Box::new(Swift {
+            code_style: CodeStyle {
+                indent: Some(Indent::Spaces(2)),
+                max_line_width: None,
+            },
+            ..Default::default()
+        }),
+    ];
+    for mut language in languages {
+        let parsed_data = parse(
+            &ParseContext::default(),
+            ParseFileContext {
+                source_code: fs::read_to_string(folder.join("input.rs")).unwrap(),
+                crate_name: "default".into(),
+                file_name: "input.rs".to_owned(),
+                file_path: folder.join("input.rs"),
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut writer = CountingWriter::default();
+        language
+            .generate_types(&mut writer, &Default::default(), parsed_data)
+            .unwrap();
+        assert!(!writer.written.is_empty());
+        assert_eq!(writer.writes, 1);
+    }
+}
+
+#[test]
+fn serializes_the_parsed_types() {
+    let folder = test_folder("can_serialize_the_parsed_types");
+    let parsed_data = parse(
+        &ParseContext::default(),
+        ParseFileContext {
+            source_code: fs::read_to_string(folder.join("input.rs")).unwrap(),
+            crate_name: "users".into(),
+            file_name: "users.json".to_owned(),
+            file_path: folder.join("input.rs"),
+        },
+    )
+    .unwrap()
+    .unwrap();
+
+    let crates = BTreeMap::from([(CrateName::from("users"), parsed_data)]);
+    let json = Ir::new(&crates).to_json().unwrap();
+    // The version of typeshare changes with every release.
+    let json = json.replace(env!("CARGO_PKG_VERSION"), "<version>");
+    expect_test::expect_file!["../data/tests/can_serialize_the_parsed_types/output.json"]
+        .assert_eq(&json);
+}
+
+#[test]
+fn generates_documentation_of_the_types() {
+    let folder = test_folder("can_generate_docs");
+    let parsed_data = parse(
+        &ParseContext::default(),
+        ParseFileContext {
+            source_code: fs::read_to_string(folder.join("input.rs")).unwrap(),
+            crate_name: "users".into(),
+            file_name: "users.md".to_owned(),
+            file_path: folder.join("input.rs"),
+        },
+    )
+    .unwrap()
+    .unwrap();
+
+    let crates = BTreeMap::from([(CrateName::from("users"), parsed_data)]);
+    expect_test::expect_file!["../data/tests/can_generate_docs/output.md"]
+        .assert_eq(&docs::generate(&crates, DocsFormat::Markdown));
+    expect_test::expect_file!["../data/tests/can_generate_docs/output.html"]
+        .assert_eq(&docs::generate(&crates, DocsFormat::Html));
+}
+
+#[test]
+fn generates_a_swift_package() {
+    let folder = test_folder("can_generate_swift_package");
+    let mut swift = Swift {
+        no_version_header: true,
+        multi_file: true,
+        package_name: Some("Shop".to_owned()),
+        ..Default::default()
+    };
+    let all_types = HashMap::from([
+        (CrateName::from("accounts"), ["Account".to_owned()].into()),
+        (CrateName::from("orders"), ["Order".to_owned()].into()),
+    ]);
+    let mut generate = |crate_name: &str| {
+        let target = Swift::package_target(&crate_name.into());
+        let parsed_data = parse(
+            &ParseContext {
+                multi_file: true,
+                ..Default::default()
+            },
+            ParseFileContext {
+                source_code: fs::read_to_string(folder.join(format!("{crate_name}.rs"))).unwrap(),
+                crate_name: crate_name.into(),
+                file_name: format!("Sources/{target}/{target}.swift"),
+                file_path: folder.join(crate_name).join("src/lib.rs"),
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let mut output = Vec::new();
+        swift
+            .generate_types(&mut output, &all_types, parsed_data)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    expect_test::expect_file!["../data/tests/can_generate_swift_package/Accounts.swift"]
+        .assert_eq(&generate("accounts"));
+    expect_test::expect_file!["../data/tests/can_generate_swift_package/Orders.swift"]
+        .assert_eq(&generate("orders"));
+
+    let package_root =
+        std::env::temp_dir().join(format!("typeshare-swift-package-{}", std::process::id()));
+    fs::create_dir_all(&package_root).unwrap();
+    swift
+        .post_generation(package_root.to_str().unwrap())
+        .unwrap();
+    let manifest = fs::read_to_string(package_root.join("Package.swift"));
+    // `CodableVoid` is defined in the target using it, not in a file of its own.
+    let codable_file = package_root.join("Codable.swift").exists();
+    fs::remove_dir_all(&package_root).unwrap();
+    expect_test::expect_file!["../data/tests/can_generate_swift_package/Package.swift"]
+        .assert_eq(&manifest.unwrap());
+    assert!(!codable_file);
+}
@@ -376,7 +376,7 @@ macro_rules! target_os {
 ///
 /// This macro outputs the following structure:
 ///
-/// ```
+/// ```text
 /// mod $test {
 ///     #[test]
 ///     fn kotlin() {
@@ -522,7 +522,8 @@ tests! {
         },
         kotlin,
         scala,
-        typescript
+        typescript,
+        go
     ];
     can_generate_generic_struct: [
         swift {
@@ -532,6 +533,7 @@ tests! {
         kotlin,
         scala,
         typescript,
+        go,
     ];
     can_generate_generic_type_alias: [
         swift {
@@ -539,7 +541,8 @@ tests! {
         },
         kotlin,
         scala,
-        typescript
+        typescript,
+        go
     ];
     can_generate_const: [typescript, reasonml, go, python];
     can_generate_slice_of_user_type: [swift, kotlin, scala, typescript, go, python];
@@ -637,6 +640,9 @@ tests! {
         },
         typescript {
             type_mappings: super::TYPESCRIPT_MAPPINGS.clone(),
+        },
+        reasonml {
+            type_mappings: super::REASONML_MAPPINGS.clone(),
         },
          go {
             type_mappings: super::GO_MAPPINGS.clone(),
@@ -698,6 +704,7 @@ tests! {
     use_correct_integer_types: [swift, kotlin, scala,  typescript, go, python];
     // Only swift supports generating types with keywords
     generate_types_with_keywords: [swift];
+    can_escape_keywords: [swift, kotlin, scala, reasonml];
     // TODO: how is this different from generates_empty_structs_and_initializers?
     use_correct_decoded_variable_name: [swift, kotlin, scala,  typescript, go, python];
     can_handle_unit_type: [swift { codablevoid_constraints: vec!["Equatable".into()]} , kotlin, scala,  typescript, go, python];
@@ -746,4 +753,155 @@ tests! {
     }
     ];
     no_mangle: [swift, kotlin, scala, typescript, go];
+    can_generate_swift_init_optional_defaults: [swift { init_optional_defaults: true }];
+    can_generate_swift_serde_default_values: [swift {
+        serde_default_values: true,
+        type_mappings: [
+            ("ExternalId".to_string(), "String".to_string()),
+            ("Revision".to_string(), "UInt64".to_string()),
+            ("Timestamp".to_string(), "Date".to_string()),
+        ].into(),
+    }];
+    can_generate_swift_convert_from_snake_case_coding_keys: [swift {
+        coding_keys: typeshare_core::language::CodingKeyStrategy::ConvertFromSnakeCase,
+    }];
+    can_generate_swift_unknown_enum_case: [swift { unknown_case: Some("unknown".into()) }];
+    can_generate_kotlin_moshi: [kotlin {
+        package: "com.agilebits.onepassword".to_string(),
+        serializer: typeshare_core::language::KotlinSerializer::Moshi,
+    }];
+    can_generate_kotlin_sealed_interfaces: [kotlin { sealed_interfaces: true }];
+    can_generate_kotlin_serde_default_values: [kotlin { serde_default_values: true }];
+    can_generate_kotlin_parcelize: [kotlin {
+        package: "com.agilebits.onepassword".to_string(),
+        parcelize: true,
+    }];
+    can_generate_kotlin_newtype_value_classes: [kotlin { newtype_value_classes: true }];
+    can_generate_typescript_camel_case_fields: [typescript {
+        field_naming: typeshare_core::language::TypeScriptFieldNaming::CamelCase,
+    }];
+    can_generate_reasonml_codecs: [reasonml { codecs: true }];
+    can_generate_kotlin_kdoc_links: [kotlin];
+    can_generate_kotlin_multiplatform: [kotlin {
+        package: "com.agilebits.onepassword".to_string(),
+        newtype_value_classes: true,
+        multiplatform: true,
+    }];
+    can_generate_go_no_omitempty: [go { no_omitempty: true }];
+    can_generate_go_generic_options: [go { option_style: typeshare_core::language::GoOptionStyle::Generic }];
+    can_generate_go_null_options: [go {
+        option_style: typeshare_core::language::GoOptionStyle::Null,
+        type_mappings: [("ExternalId".to_string(), "uint64".to_string())].into(),
+    }];
+    can_generate_go_unix_millis_datetime: [go {
+        datetime_format: typeshare_core::language::GoDateTimeFormat::UnixMillis
+    }];
+    can_generate_go_layout_datetime: [go {
+        datetime_format: typeshare_core::language::GoDateTimeFormat::Layout("2006-01-02".into())
+    }];
+    can_generate_null_optional_fields: [
+        typescript { optional_fields: Some(typeshare_core::language::OptionalFields::Null) },
+        kotlin { optional_fields: Some(typeshare_core::language::OptionalFields::Null) },
+        scala {
+            package: "com.agilebits.onepassword".to_string(),
+            optional_fields: Some(typeshare_core::language::OptionalFields::Null),
+        },
+        go { optional_fields: Some(typeshare_core::language::OptionalFields::Null) },
+        python { optional_fields: Some(typeshare_core::language::OptionalFields::Null) },
+    ];
+    can_generate_absent_or_null_optional_fields: [
+        typescript { optional_fields: Some(typeshare_core::language::OptionalFields::Both) },
+        go { optional_fields: Some(typeshare_core::language::OptionalFields::Both) },
+    ];
+    can_generate_go_json_value: [go];
+    can_generate_go_declared_value: [go];
+    can_generate_go_strict_decoding: [go];
+    can_generate_go_json_value_any: [go { json_value_any: true }];
+    can_generate_go_enum_methods: [go { enum_methods: true }];
+    can_generate_scala_js_facades: [scala {
+        package: "com.agilebits.onepassword".to_string(),
+        scala_js: true,
+    }];
+    can_generate_scala_circe_codecs: [scala {
+        package: "com.agilebits.onepassword".to_string(),
+        circe: true,
+    }];
+    can_generate_scala3: [scala {
+        package: "com.agilebits.onepassword".to_string(),
+        scala3: true,
+    }];
+    can_generate_scala3_circe_codecs: [scala {
+        package: "com.agilebits.onepassword".to_string(),
+        scala3: true,
+        circe: true,
+    }];
+    can_generate_scala_opaque_newtypes: [scala {
+        package: "com.agilebits.onepassword".to_string(),
+        scala3: true,
+        circe: true,
+        opaque_newtypes: true,
+    }];
+    can_generate_swift_mocks: [swift { mocks: true }];
+    can_generate_arbitraries: [
+        typescript { arbitraries: true },
+        swift { arbitraries: true },
+        kotlin { arbitraries: true },
+    ];
+    can_generate_reserved_words: [
+        swift { reserved_words: std::collections::HashSet::from(["description".to_string(), "hash".to_string()]) },
+        kotlin { reserved_words: std::collections::HashSet::from(["description".to_string(), "hash".to_string()]) },
+        scala {
+            package: "com.agilebits.onepassword".to_string(),
+            reserved_words: std::collections::HashSet::from(["description".to_string(), "hash".to_string()]),
+        },
+        reasonml { reserved_words: std::collections::HashSet::from(["description".to_string(), "hash".to_string()]) },
+        python { reserved_words: std::collections::HashSet::from(["description".to_string(), "hash".to_string()]) },
+    ];
+    can_generate_with_code_style: [
+        swift {
+            code_style: typeshare_core::language::CodeStyle {
+                indent: Some(typeshare_core::language::Indent::Spaces(2)),
+                max_line_width: Some(60),
+            },
+        },
+        typescript {
+            code_style: typeshare_core::language::CodeStyle {
+                indent: Some(typeshare_core::language::Indent::Spaces(2)),
+                max_line_width: Some(60),
+            },
+        },
+        reasonml {
+            code_style: typeshare_core::language::CodeStyle {
+                indent: Some(typeshare_core::language::Indent::Tabs),
+                max_line_width: None,
+            },
+        },
+        python {
+            code_style: typeshare_core::language::CodeStyle {
+                indent: Some(typeshare_core::language::Indent::Spaces(2)),
+                max_line_width: Some(60),
+            },
+        },
+    ];
+    can_keep_directive_comments_with_code_style: [go {
+        code_style: typeshare_core::language::CodeStyle {
+            indent: None,
+            max_line_width: Some(40),
+        },
+    }];
+    can_generate_swift_explicit_coding_keys: [swift {
+        coding_keys: typeshare_core::language::CodingKeyStrategy::Explicit,
+    }];
+    can_generate_schema_hashes: [
+        typescript { schema_hashes: true },
+        swift { schema_hashes: true },
+        kotlin { schema_hashes: true },
+        scala {
+            package: "com.agilebits.onepassword".to_string(),
+            schema_hashes: true,
+        },
+        go { schema_hashes: true },
+        python { schema_hashes: true },
+        reasonml { schema_hashes: true },
+    ];
 }
@@ -1,7 +1,10 @@
 //! Types and functions for code that defines and/or uses `#[typeshare]`
 //! types.
 
+#[cfg(not(feature = "validate"))]
 pub use typeshare_annotation::typeshare;
+#[cfg(feature = "validate")]
+pub use typeshare_macros::typeshare;
 
 mod integer;
 pub use integer::{usize_from_u53_saturated, I54, U53};
@@ -0,0 +1,166 @@
+//! Defines the `#[typeshare]` attribute, which checks the types it marks when they're
+//! compiled.
+
+extern crate proc_macro;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
+use syn::{parse_quote, Attribute, Item};
+use typeshare_core::validate::strip_configuration_attribute;
+
+/// Marks a type as a type shared across the FFI boundary using typeshare, like the
+/// attribute of `typeshare-annotation`, and checks that typeshare can generate it.
+///
+/// Types typeshare can't parse fail to compile, with the error typeshare would fail
+/// with pointing at the Rust it's about, like fields with 64-bit integers, which
+/// JavaScript can't represent, or algebraic enums without `#[serde(tag, content)]`:
+///
+/// ```compile_fail
+/// use typeshare_macros::typeshare;
+///
+/// #[typeshare]
+/// pub struct Person {
+///     name: String,
+///     id: u64,
+/// }
+/// ```
+///
+/// Fields skipped with `#[typeshare(skip)]`, or serialized as another type with
+/// `#[typeshare(serialized_as = "...")]`, are checked like the CLI parses them:
+///
+/// ```
+/// use typeshare_macros::typeshare;
+///
+/// #[typeshare]
+/// pub struct Person {
+///     name: String,
+///     #[typeshare(serialized_as = "String")]
+///     id: u64,
+/// }
+/// ```
+///
+/// So are the arguments of the attribute itself:
+///
+/// ```
+/// use typeshare_macros::typeshare;
+///
+/// #[typeshare(serialized_as = "String")]
+/// pub struct ItemId(i64);
+/// ```
+#[proc_macro_attribute]
+pub fn typeshare(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(attr.into(), item.into()).into()
+}
+
+/// The item without the `#[typeshare]` attributes of its fields and variants, and the
+/// error typeshare fails to parse it with, if any.
+fn expand(attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
+    let Ok(mut parsed) = syn::parse2::<Item>(item.clone()) else {
+        return item;
+    };
+
+    // The arguments of the attribute, like `serialized_as`, are parsed by typeshare from
+    // the attributes of the item, which the compiler removed this attribute from.
+    let mut checked = parsed.clone();
+    if !attr.is_empty() {
+        if let Some(attrs) = item_attributes(&mut checked) {
+            attrs.push(parse_quote!(#[typeshare(#attr)]));
+        }
+    }
+    let error = typeshare_core::validate::check_item(&checked)
+        .err()
+        .map(|error| syn::Error::new(error.token_span(), error.error()).to_compile_error());
+
+    // The #[typeshare] attributes of fields and variants would fail to compile.
+    strip_configuration_attribute(&mut parsed);
+    let mut output = parsed.into_token_stream();
+    output.extend(error);
+    output
+}
+
+fn item_attributes(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Struct(item_struct) => Some(&mut item_struct.attrs),
+        Item::Enum(item_enum) => Some(&mut item_enum.attrs),
+        Item::Type(item_type) => Some(&mut item_type.attrs),
+        Item::Const(item_const) => Some(&mut item_const.attrs),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::expand;
+    use proc_macro2::TokenStream as TokenStream2;
+    use quote::quote;
+
+    #[test]
+    fn reports_the_errors_of_items_typeshare_cannot_parse() {
+        let supported = expand(
+            TokenStream2::new(),
+            quote! {
+                pub struct Person {
+                    name: String,
+                    #[typeshare(serialized_as = "String")]
+                    id: u64,
+                }
+            },
+        );
+        assert_eq!(
+            supported.to_string(),
+            quote! {
+                pub struct Person {
+                    name: String,
+                    id: u64,
+                }
+            }
+            .to_string()
+        );
+
+        let unsupported = expand(
+            TokenStream2::new(),
+            quote! {
+                pub struct Person {
+                    id: u64,
+                }
+            },
+        );
+        assert_eq!(
+            unsupported.to_string(),
+            quote! {
+                pub struct Person {
+                    id: u64,
+                }
+                ::core::compile_error! {
+                    "Failed to parse a Rust type: Unsupported type: \"u64\""
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn checks_items_with_the_arguments_of_the_attribute() {
+        let serialized_as = expand(
+            quote!(serialized_as = "String"),
+            quote! {
+                pub struct ItemId(i64);
+            },
+        );
+        assert_eq!(
+            serialized_as.to_string(),
+            quote! {
+                pub struct ItemId(i64);
+            }
+            .to_string()
+        );
+
+        let unsupported = expand(
+            TokenStream2::new(),
+            quote! {
+                pub struct ItemId(i64);
+            },
+        );
+        assert!(unsupported.to_string().contains("compile_error"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Typeshare's code generation as a WebAssembly module, for generating types from Rust
+//! source in a browser, like in a playground. Built for `wasm32-unknown-unknown`:
+//!
+//! ```text
+//! cargo build -p typeshare-wasm --release --target wasm32-unknown-unknown
+//! ```
+//!
+//! The module exports the functions of this crate which take and return strings as
+//! pointers and lengths in its memory, which `typeshare.js`, in the directory of this
+//! crate, wraps as `generate(source, lang, config)`:
+//!
+//! ```js
+//! import { load } from "./typeshare.js";
+//!
+//! const typeshare = await load("typeshare_wasm.wasm");
+//! typeshare.generate("#[typeshare] pub struct User { name: String }", "typescript", {});
+//! ```
+//!
+//! Nothing is read from or written to files: the types are generated from the source
+//! given as a single file.
+use serde::Deserialize;
+use std::{cell::RefCell, collections::HashMap, slice, str};
+use typeshare_core::{
+    Go, Kotlin, Language, Python, ReasonML, Scala, SupportedLanguage, Swift, TypeScript,
+};
+
+/// The options of the language types are generated in, deserialized from JSON like
+/// `{"type_mappings": {"Uuid": "string"}, "prefix": "TS"}`. Options a language doesn't
+/// have are ignored by it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Mappings from Rust type names to type names of the language.
+    pub type_mappings: HashMap<String, String>,
+    /// The prefix of the generated type names, in Swift and Kotlin.
+    pub prefix: String,
+    /// The package of the generated types, in Kotlin, Scala and Go.
+    pub package: String,
+    /// Whether to leave out the header the generated code starts with.
+    pub no_version_header: bool,
+    /// Whether to write a constant with the schema hash of every type after it.
+    pub schema_hashes: bool,
+}
+
+/// Generate the types of Rust source code, like the contents of a single file, in a
+/// language, like `typescript`, with the options of the JSON `config`. An empty
+/// configuration uses the defaults. The error is the message to show, like that of
+/// the typeshared item which couldn't be parsed.
+pub fn generate(source: &str, lang: &str, config: &str) -> Result<String, String> {
+    let language = lang
+        .parse::<SupportedLanguage>()
+        .map_err(|error| error.to_string())?;
+    let config = if config.trim().is_empty() {
+        Config::default()
+    } else {
+        serde_json::from_str(config).map_err(|error| format!("Invalid configuration: {error}"))?
+    };
+    let mut language = language_with(language, config);
+    typeshare_core::generate_source(language.as_mut(), source).map_err(|error| error.to_string())
+}
+
+/// The language with the options of the configuration.
+fn language_with(language: SupportedLanguage, config: Config) -> Box<dyn Language> {
+    let Config {
+        type_mappings,
+        prefix,
+        package,
+        no_version_header,
+        schema_hashes,
+    } = config;
+    match language {
+        SupportedLanguage::Go => Box::new(Go {
+            package,
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::Kotlin => Box::new(Kotlin {
+            package,
+            prefix,
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::ReasonML => Box::new(ReasonML {
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::Scala => Box::new(Scala {
+            package,
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::Swift => Box::new(Swift {
+            prefix,
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::TypeScript => Box::new(TypeScript {
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+        SupportedLanguage::Python => Box::new(Python {
+            type_mappings,
+            no_version_header,
+            schema_hashes,
+            ..Default::default()
+        }),
+    }
+}
+
+thread_local! {
+    /// The generated types, or the error, of the last call to `typeshare_generate`.
+    static OUTPUT: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Allocate `len` bytes of the module's memory, for the strings passed to
+/// `typeshare_generate`.
+#[no_mangle]
+pub extern "C" fn typeshare_alloc(len: usize) -> *mut u8 {
+    let mut buffer = Vec::<u8>::with_capacity(len);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr
+}
+
+/// Free the bytes allocated with `typeshare_alloc`.
+///
+/// # Safety
+///
+/// `ptr` must have been returned by `typeshare_alloc(len)`, and not be freed already.
+#[no_mangle]
+pub unsafe extern "C" fn typeshare_free(ptr: *mut u8, len: usize) {
+    drop(Vec::from_raw_parts(ptr, 0, len));
+}
+
+/// Generate the types of the source, in the language, with the configuration, like
+/// `generate`, from the UTF-8 strings at the pointers. Returns whether they were
+/// generated, and the types, or the error, are the output until the next call.
+///
+/// # Safety
+///
+/// Every pointer must point at as many initialized bytes as its length.
+#[no_mangle]
+pub unsafe extern "C" fn typeshare_generate(
+    source: *const u8,
+    source_len: usize,
+    lang: *const u8,
+    lang_len: usize,
+    config: *const u8,
+    config_len: usize,
+) -> bool {
+    let string = |ptr, len| {
+        str::from_utf8(slice::from_raw_parts(ptr, len))
+            .map_err(|error| format!("Invalid UTF-8: {error}"))
+    };
+    let result = string(source, source_len)
+        .and_then(|source| generate(source, string(lang, lang_len)?, string(config, config_len)?));
+    let generated = result.is_ok();
+    OUTPUT.with_borrow_mut(|output| *output = result.unwrap_or_else(|error| error));
+    generated
+}
+
+/// Where the output of the last call to `typeshare_generate` is.
+#[no_mangle]
+pub extern "C" fn typeshare_output_ptr() -> *const u8 {
+    OUTPUT.with_borrow(|output| output.as_ptr())
+}
+
+/// The length of the output of the last call to `typeshare_generate`, in bytes.
+#[no_mangle]
+pub extern "C" fn typeshare_output_len() -> usize {
+    OUTPUT.with_borrow(|output| output.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        generate, typeshare_alloc, typeshare_free, typeshare_generate, typeshare_output_len,
+        typeshare_output_ptr,
+    };
+    use std::{ptr, slice};
+
+    const SOURCE: &str = "#[typeshare] pub struct User { id: Uuid, name: String }";
+
+    #[test]
+    fn generates_types_with_the_configuration() {
+        let config = r#"{"type_mappings": {"Uuid": "string"}, "no_version_header": true}"#;
+        assert_eq!(
+            generate(SOURCE, "typescript", config).unwrap(),
+            "export interface User {\n\tid: string;\n\tname: string;\n}\n\n"
+        );
+        assert!(generate(SOURCE, "swift", "")
+            .unwrap()
+            .contains("public struct User"));
+        assert_eq!(
+            generate(SOURCE, "cobol", "").unwrap_err(),
+            "Unsupported language encountered: cobol"
+        );
+        assert!(generate(SOURCE, "typescript", r#"{"types": {}}"#)
+            .unwrap_err()
+            .starts_with("Invalid configuration: unknown field `types`"));
+    }
+
+    #[test]
+    fn reports_the_inputs_languages_cannot_generate() {
+        let constant = "#[typeshare] pub const LIMIT: u32 = 10;";
+        for (lang, name) in [("kotlin", "Kotlin"), ("swift", "Swift")] {
+            assert!(generate(constant, lang, "")
+                .unwrap_err()
+                .ends_with(&format!("typeshare doesn't generate constants in {name}")));
+        }
+        assert!(generate(SOURCE, "scala", "")
+            .unwrap_err()
+            .ends_with("package name must be provided"));
+        assert!(generate(SOURCE, "scala", r#"{"package": "com.example"}"#).is_ok());
+    }
+
+    #[test]
+    fn generates_types_from_strings_in_memory() {
+        let pass = |text: &str| {
+            let ptr = typeshare_alloc(text.len());
+            unsafe { ptr::copy_nonoverlapping(text.as_ptr(), ptr, text.len()) };
+            (ptr, text.len())
+        };
+        let output = || unsafe {
+            let bytes = slice::from_raw_parts(typeshare_output_ptr(), typeshare_output_len());
+            String::from_utf8(bytes.to_vec()).unwrap()
+        };
+
+        let (source, source_len) = pass(SOURCE);
+        let (lang, lang_len) = pass("kotlin");
+        let (config, config_len) = pass(r#"{"prefix": "K", "no_version_header": true}"#);
+        let generated =
+            unsafe { typeshare_generate(source, source_len, lang, lang_len, config, config_len) };
+        assert!(generated);
+        assert!(output().contains("data class KUser"));
+
+        let generated =
+            unsafe { typeshare_generate(source, source_len, lang, 0, config, config_len) };
+        assert!(!generated);
+        assert_eq!(output(), "Unsupported language encountered: ");
+
+        for (ptr, len) in [(source, source_len), (lang, lang_len), (config, config_len)] {
+            unsafe { typeshare_free(ptr, len) };
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Generating types with typeshare's WebAssembly module, built with
+//
+//     cargo build -p typeshare-wasm --release --target wasm32-unknown-unknown
+//
+// into target/wasm32-unknown-unknown/release/typeshare_wasm.wasm.
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+// Load the module from its URL, or a `Response` of it, as an object with the
+// `generate` function.
+export async function load(module) {
+  const response = typeof module === "string" ? fetch(module) : module;
+  const { instance } = await WebAssembly.instantiateStreaming(response);
+  const exports = instance.exports;
+
+  // Copy a string into the memory of the module.
+  const pass = (string) => {
+    const bytes = encoder.encode(string);
+    const ptr = exports.typeshare_alloc(bytes.length);
+    new Uint8Array(exports.memory.buffer, ptr, bytes.length).set(bytes);
+    return [ptr, bytes.length];
+  };
+
+  return {
+    // Generate the types of Rust source code in a language, like "typescript", with
+    // the options of the configuration, like `{ type_mappings: { Uuid: "string" } }`.
+    // Throws the error of the source typeshare couldn't generate types for.
+    generate(source, lang, config = {}) {
+      const strings = [source, lang, JSON.stringify(config)].map(pass);
+      try {
+        const generated = exports.typeshare_generate(...strings.flat());
+        const output = decoder.decode(
+          new Uint8Array(
+            exports.memory.buffer,
+            exports.typeshare_output_ptr(),
+            exports.typeshare_output_len(),
+          ),
+        );
+        if (!generated) {
+          throw new Error(output);
+        }
+        return output;
+      } finally {
+        for (const [ptr, len] of strings) {
+          exports.typeshare_free(ptr, len);
+        }
+      }
+    },
+  };
+}